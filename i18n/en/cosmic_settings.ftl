@@ -2,6 +2,151 @@ app = COSMIC Settings
 
 unknown = Unknown
 
+copy-row = Copy
+    .copied = Copied to clipboard
+
+date-picker = Date
+    .header = { $month } { $year }
+    .january = January
+    .february = February
+    .march = March
+    .april = April
+    .may = May
+    .june = June
+    .july = July
+    .august = August
+    .september = September
+    .october = October
+    .november = November
+    .december = December
+    .mon = Mo
+    .tue = Tu
+    .wed = We
+    .thu = Th
+    .fri = Fr
+    .sat = Sa
+    .sun = Su
+
+dialog = Dialog
+    .cancel = Cancel
+    .dont-ask-again = Don't ask again
+
+editable-list = List
+    .add = Add
+    .cancel = Cancel
+    .save = Save
+
+info-popover = More Information
+    .learn-more = Learn more
+
+keybind-capture = Shortcut
+    .disabled = Disabled
+    .prompt = Press a key combination…
+
+search-input = Search settings…
+
+searchable-dropdown = Dropdown
+    .none = None
+    .filter = Search…
+    .empty = No matches
+
+secure-input = Password
+    .caps-lock = Caps Lock is on
+    .very-weak = Very weak
+    .weak = Weak
+    .fair = Fair
+    .strong = Strong
+    .very-strong = Very strong
+
+link-row = Link
+    .failed = Could not open { $url }
+
+operation-progress = Progress
+    .cancel = Cancel
+    .cancelling = Cancelling…
+    .details = Details
+
+settings-file = Settings File
+    .export = Export Settings…
+    .import = Import Settings…
+    .export-title = Export Settings
+    .import-title = Import Settings
+    .filter = COSMIC Settings exports
+    .exported = Exported { $count } settings
+    .imported = Imported { $applied } settings, skipped { $skipped }
+    .skipped = Skipped: { $keys }
+    .not-an-export = The file is not an export of COSMIC Settings
+
+reset-defaults = Reset to Defaults
+    .title = Reset { $page } to defaults?
+    .desc = Every setting on this page will be returned to its default value.
+    .confirm = Reset
+
+section-state = Section
+    .loading = Loading…
+    .retry = Retry
+
+time-picker = Time
+    .am = AM
+    .pm = PM
+
+unsaved-changes = Discard unsaved changes?
+    .desc = Changes made on this page have not been applied yet.
+    .apply = Apply
+    .cancel = Cancel
+    .discard = Discard
+
+## Accessibility
+
+accessibility = Accessibility
+    .desc = Screen reader and typing aids
+
+screen-reader = Screen Reader
+    .enable = Screen reader
+    .desc = Reads the contents of the screen aloud with Orca.
+    .preferences = Open Screen Reader Preferences
+    .not-installed = Orca is not installed. Install the orca package with your package manager to use the screen reader.
+
+keyboard-a11y = Typing
+    .sticky-keys = Sticky keys
+    .sticky-keys-desc = Modifier keys, such as Shift and Ctrl, stay pressed until the next key is pressed.
+    .sticky-keys-timeout = Release modifier keys after
+    .sticky-keys-shortcut = Turn on or off by pressing Shift five times
+    .slow-keys = Slow keys
+    .slow-keys-desc = Keys are only typed once they are held down for a moment.
+    .slow-keys-delay = Acceptance delay
+    .bounce-keys = Bounce keys
+    .bounce-keys-desc = Quickly repeated presses of the same key are ignored.
+    .bounce-keys-delay = Rejection delay
+    .ms = { $value } ms
+
+## Applications
+
+applications = Applications
+
+## Applications: Default Applications
+
+default-apps = Default Applications
+    .desc = Default web browser, mail client, file browser, and other applications.
+    .web-browser = Web Browser
+    .email = Email
+    .file-manager = File Manager
+    .music = Music
+    .video = Video
+    .image-viewer = Image Viewer
+    .default = Default application
+    .none = None
+    .save-failed = The default application could not be changed: { $why }
+
+startup-apps = Startup Applications
+    .desc = Applications which are started when you log in.
+    .none = No applications are started when you log in.
+    .add = Add application
+    .remove = Remove
+    .application = Application
+    .command = Command
+    .cancel = Cancel
+
 ## Desktop
 
 desktop = Desktop
@@ -11,11 +156,65 @@ desktop = Desktop
 appearance = Appearance
     .desc = Accent colors and COSMIC theming.
 
+accent-color = Accent Color
+    .desc = The color of highlighted and selected elements.
+
+color-picker = Color
+    .hue = Hue
+    .alpha = Opacity
+    .invalid = Enter a color such as #62a0ea.
+    .recent = Recent colors
+
+cursor = Cursor
+    .theme = Cursor theme
+    .size = Cursor size
+    .px = { $value } px
+    .relogin = Windows opened from now on use the new cursor. Log out and back in for it to apply everywhere, including older applications.
+
+fonts = Fonts
+    .interface = Interface font
+    .document = Document font
+    .monospace = Monospace font
+    .sample = The quick brown fox jumps over the lazy dog.
+
+font-rendering = Font Rendering
+    .desc = Applies to applications at once, except for those which must be opened again.
+    .hinting = Hinting
+    .antialiasing = Antialiasing
+    .none = None
+    .slight = Slight
+    .medium = Medium
+    .full = Full
+    .grayscale = Grayscale
+    .subpixel = Subpixel (for LCD screens)
+
 ## Desktop: Dock & Panel
 
 dock = Dock & Top Panel
     .desc = Customize size, positions, and more for Dock and Top Panel.
 
+panel = Panel
+    .top = Top Panel
+    .dock = Dock
+    .position = Position
+    .top-edge = Top
+    .bottom-edge = Bottom
+    .left-edge = Left
+    .right-edge = Right
+    .size = Size
+    .extra-small = Extra small
+    .small = Small
+    .medium = Medium
+    .large = Large
+    .extra-large = Extra large
+    .autohide = Automatically hide
+    .autohide-desc = Hidden until the pointer is moved to its edge of the screen.
+    .applets = Applets
+    .add-applet = Add applet
+    .start = Start
+    .center = Center
+    .end = End
+
 ## Desktop: Notifications
 
 notifications = Notifications
@@ -25,7 +224,7 @@ notifications = Notifications
 ## Desktop: Options
 
 desktop-options = Desktop Options
-    .desc = Super Key action, hot corners, window control options.
+    .desc = Super Key action, hot corners, window control and management options.
 
 super-key-action = Super Key Action
     .launcher = Launcher
@@ -43,6 +242,16 @@ window-controls = Window Controls
     .minimize = Show Minimize Button
     .maximize = Show Maximize Button
 
+window-management = Window Management
+    .focus-follows-mouse = Focus follows mouse
+    .focus-follows-mouse-desc = Windows are focused as the pointer moves over them.
+    .auto-raise = Delay before raising the focused window
+    .ms = { $value } ms
+    .tiling = New windows
+    .tiling-desc = Windows which are already open keep their place.
+    .floating = Floating
+    .auto-tile = Tiled
+
 ## Desktop: Wallpaper
 
 wallpaper = Wallpaper
@@ -60,17 +269,277 @@ workspaces = Workspaces
 
 workspaces-behavior = Workspace Behavior
     .dynamic = Dynamic workspaces
+    .dynamic-desc = Empty workspaces are removed, and a new one is added after the last.
     .fixed = Fixed Number of Workspaces
+    .fixed-desc = Workspaces are kept, whether or not they have windows.
+    .count = Number of workspaces
 
 workspaces-multi-behavior = Multi-monitor Behavior
-    .span = Workspaces Span Displays
-    .separate = Displays Have Separate Workspaces
+    .primary = Workspaces on Primary Display Only
+    .primary-desc = Other displays keep their windows while switching workspaces.
+    .all = Workspaces on All Displays
+    .all-desc = Every display switches workspaces.
+
+workspaces-layout = Workspace Layout
+    .orientation = Workspace orientation
+    .horizontal = Horizontal
+    .vertical = Vertical
+
+## Display
+
+display = Displays
+    .desc = Arrangement, resolution, and scaling of displays.
+
+display-arrangement = Display Arrangement
+    .desc = Drag displays to arrange them.
+    .primary = Primary display
+
+display-resolution = Resolution
+    .display = Display
+    .desc = The number of pixels shown by the display.
+    .recommended = { $mode } (Recommended)
+    .rejected = The display did not accept the resolution: { $why }
+
+display-mirror = Mirror displays
+    .desc = Shows the same content on every display.
+    .no-common-mode = The displays have no resolution in common, so they can't be mirrored.
+
+display-orientation = Orientation
+    .landscape = Landscape
+    .portrait-right = Portrait Right
+    .portrait-left = Portrait Left
+    .flipped = Landscape (Flipped)
+
+display-refresh-rate = Refresh Rate
+    .changed = The resolution does not support the previous refresh rate, so { $rate } is used.
+    .unsupported = The display does not support this refresh rate at this resolution.
+
+display-revert = Keep These Display Settings?
+    .body = The previous settings are restored in { $seconds } seconds.
+    .keep = Keep Changes
+    .revert = Revert
+
+display-scale = Scale
+    .desc = Size of text and interface elements
+    .fractional = ⚠ Applications which do not support fractional scaling may appear blurry.
+    .fractional-info = Applications for X11, which is used by older applications, are drawn at 100% and then enlarged at fractional scales, such as 125% or 150%. Whole scales, such as 200%, keep them sharp.
+    .logical = Looks like { $width } × { $height }
+    .unsupported = This scale is not supported by the display.
+
+display-vrr = Variable refresh rate
+    .desc = Matches the refresh rate to the content, for smoother games and videos
+    .off = Off
+    .on = On
+    .info = Some displays flicker while their refresh rate varies, most often in dark scenes or at low frame rates. If yours does, turn it off.
+    .refused = The display did not switch its variable refresh rate: { $why }
+
+display-color-profile = Color profile
+    .none = None (sRGB)
+    .active = Use
+    .import = Import profile…
+    .import-title = Import Color Profile
+    .filter = ICC profiles
+    .remove = Remove
+    .invalid = The profile could not be imported: { $why }
+
+## Display: Night Light
+
+night-light = Night Light
+    .desc = Warmer colors at night
+    .enable = Night Light
+    .enable-desc = Makes colors warmer, which may be easier on the eyes at night.
+    .temperature = Color temperature
+    .temperature-desc = Lower temperatures are warmer.
+    .kelvin = { $value } K
+
+night-light-schedule = Schedule
+    .sunset = Sunset to sunrise
+    .sunset-desc = Follows the sun at your location.
+    .manual = Manual schedule
+    .manual-desc = On between the times which you choose.
+    .start = Turns on at
+    .end = Turns off at
+    .locating = Finding your location…
+    .sun-times = Turns on at sunset, { $sunset }, and off at sunrise, { $sunrise }.
+    .no-location = Your location is unavailable, so the times below are used instead: { $why }
+
+## Input
+
+input = Input Devices
+    .desc = Keyboard, mouse, and touchpad settings.
+
+## Input: Keyboard
+
+keyboard = Keyboard
+    .desc = Key repeat, layouts, and shortcuts
+
+keyboard-repeat = Key Repeat
+    .delay = Repeat delay
+    .rate = Repeat rate
+    .test = Hold a key here to try the key repeat out
+    .ms = { $value } ms
+    .per-second = { $value } per second
+
+keyboard-numlock = Num Lock
+    .login = Turn on Num Lock at login
+    .next-login = Takes effect the next time you log in.
+
+## Input: Mouse & Touchpad
+
+mouse = Mouse & Touchpad
+    .desc = Scrolling and pointer settings
+
+mouse-scrolling = Natural Scrolling
+    .natural-desc = Scrolling moves the content, rather than the view.
+    .mouse = Mouse
+    .touchpad = Touchpad
+
+## Input: Drawing Tablet
+
+tablet = Drawing Tablet
+    .desc = Display mapping and stylus buttons
+    .display = Display
+    .display-desc = The display which the area of the tablet draws on
+    .all-displays = All displays
+    .aspect-ratio = Keep aspect ratio
+    .aspect-ratio-desc = Crops the area of the tablet to the shape of the display, so that drawings are not stretched.
+
+tablet-stylus = Stylus
+    .lower = Lower stylus button
+    .upper = Upper stylus button
+    .default = Default
+    .middle-click = Middle click
+    .right-click = Right click
+    .back = Back
+    .forward = Forward
+    .disabled = Disabled
+
+## Networking
+
+networking = Network & Wireless
+
+airplane-mode = Airplane Mode
+    .desc = Turns off every wireless radio. Radios are restored as they were once it is turned off.
+    .wlan = Wi-Fi
+    .bluetooth = Bluetooth
+    .wwan = Mobile Broadband
+    .hardware = Turned off by a hardware switch
+
+## Networking: Wi-Fi
+
+wifi = Wi-Fi
+    .desc = Wireless networks, connection profiles
+
+wifi-networks = Visible Networks
+    .scan = Scan for networks
+    .scanning = Scanning…
+    .connected = Connected
+
+wifi-known = Known Networks
+    .auto-connect = Connect automatically
+    .forget = Forget
+    .forget-title = Forget “{ $network }”?
+    .forget-desc = Its password and settings will be removed from this computer.
+    .forget-active = You will be disconnected from it, and its password and settings will be removed from this computer.
+    .none = No networks have been saved.
+    .metered = Metered connection
+    .metered-desc = Background data is limited on metered connections.
+    .metered-auto = Automatic
+    .metered-yes = Metered
+    .metered-no = Not metered
+    .detected-metered = Detected as metered, so background data is limited.
+    .detected-unmetered = Detected as not metered.
+
+wifi-hidden = Connect to Hidden Network
+    .desc = Networks which do not broadcast their name
+    .network = Network
+    .ssid = Network name (SSID)
+    .security = Security
+    .security-none = None
+    .security-wep = WEP
+    .security-wpa = WPA & WPA2 Personal
+    .security-wpa3 = WPA3 Personal
+    .password = Password
+    .wep-hint = WEP keys are 5 or 13 characters, or 10 or 26 hexadecimal digits.
+    .wpa-hint = Passwords are 8 to 63 characters, or 64 hexadecimal digits.
+    .connect = Connect
+    .connecting = Connecting…
+    .connected = Connected to { $ssid }
+    .auth-failed = The password was not accepted by the network.
+
+hotspot = Hotspot
+    .desc = Share the connection of this device over Wi-Fi
+    .toggle = Broadcast hotspot
+    .ssid = Network name (SSID)
+    .ssid-hint = Network names are 1 to 32 characters.
+    .password = Password
+    .band = Band
+    .band-auto = Automatic
+    .band-2ghz = 2.4 GHz
+    .band-5ghz = 5 GHz
+    .save = Save
+    .starting = Starting hotspot…
+    .incomplete = Enter a network name and password to start the hotspot.
+    .qr-code = Scan this code with a phone to join the hotspot.
+
+hotspot-confirm = Start Hotspot?
+    .body = The Wi-Fi device will disconnect from { $network } while the hotspot is broadcasting.
+    .enable = Start Hotspot
+    .cancel = Cancel
 
 ## Networking: Wired
 
 wired = Wired
     .desc = Wired connection, connection profiles
 
+wired-devices = Ethernet Devices
+    .none = No Ethernet devices were found.
+    .status = Status
+    .connected = Connected
+    .connecting = Connecting
+    .disconnected = Disconnected
+    .unplugged = Cable unplugged
+    .connection = Connection
+    .speed = Speed
+    .mbps = { $speed } Mb/s
+    .addresses = IP Addresses
+
+wired-ip = IP Settings
+    .ipv4 = IPv4
+    .ipv6 = IPv6
+    .method = How addresses are assigned
+    .auto = Automatic (DHCP)
+    .manual = Manual
+    .link-local = Link-Local Only
+    .disabled = Disabled
+    .addresses = Addresses
+    .gateway = Gateway
+    .dns = DNS Servers
+    .apply = Apply
+    .no-address = Enter an address, such as 192.168.1.2/24.
+    .invalid-address = Addresses need a prefix length, such as 192.168.1.2/24.
+    .invalid-gateway = The gateway is not a valid address.
+    .invalid-dns = A DNS server is not a valid address.
+
+## Networking: IP Settings
+
+ip-config = IP Settings
+    .desc = Addresses, gateway, DNS servers
+    .open = IP Settings…
+    .none = Open the IP settings of a connection from its wired or Wi-Fi details.
+    .ipv4 = IPv4
+    .ipv6 = IPv6
+    .addresses = Addresses
+    .address = Address
+    .netmask = Netmask
+    .prefix = Prefix length
+    .add = Add
+    .remove = Remove
+    .discard = Discard Changes
+    .invalid = Correct the highlighted rows to apply the settings.
+    .applied = IP settings were applied.
+    .not-applied = IP settings were not applied, as some of their rows are not valid.
+
 ## Networking: Online Accounts
 
 online-accounts = Online Accounts
@@ -96,6 +565,123 @@ time-format = Date & Time Format
 time-region = Region & Language
     .desc = Format dates, times, and numbers based on your region
 
+## Power
+
+power = Power
+    .desc = Screen blanking, suspend, and battery settings.
+
+power-battery = Battery
+    .numbered = Battery { $number }
+    .percent = { $value }%
+    .health = Health
+    .health-raw = Reported as { $value }% of the design capacity
+    .charging = Charging
+    .discharging = Discharging
+    .full = Fully charged
+    .not-charging = Plugged in, not charging
+    .empty = Empty
+    .until-full = Charging, { $time } until full
+    .until-empty = { $time } remaining
+    .minutes = { $minutes } min
+    .hours = { $hours } h { $minutes } min
+
+power-charge-limit = Battery Charge Limit
+    .desc = Stopping charging before the battery is full extends its lifespan, for laptops which are mostly plugged in.
+    .full = Full Charge
+    .full-desc = Charges to 100%, for the longest time on battery.
+    .balanced = Balanced
+    .balanced-desc = Stops charging at 80%.
+    .lifespan = Maximum Lifespan
+    .lifespan-desc = Stops charging at 60%, for the longest lifespan of the battery.
+    .custom = Custom
+    .custom-desc = Stops charging at a chosen charge.
+    .custom-value = Stop charging at
+    .percent-suffix = %
+    .apply = Apply
+    .denied = The charge limit was not changed, as authentication was not given.
+    .rejected = The firmware of the battery rejected this charge limit.
+    .failed = The charge limit could not be changed: { $why }
+
+power-usage = Battery Usage
+    .subtitle = Charge over the last 24 hours
+    .full = 100%
+    .empty = 0%
+    .start = 24 h ago
+    .middle = 12 h ago
+    .now = Now
+    .charging = Shaded periods were spent charging. Gaps are times when the computer was asleep or off.
+    .sample = { $time }: { $percent }%
+    .no-history = No usage history has been recorded yet. The battery is at { $percent }%. { $status }
+    .no-history-unknown = No usage history has been recorded yet.
+
+power-devices = Device Batteries
+    .desc = The battery levels of connected devices, such as wireless mice, keyboards, and headsets.
+    .unnamed = Unnamed device
+    .low = Battery low
+
+power-profile = Power Mode
+    .performance = Performance
+    .performance-desc = Highest performance, which uses more power and may run warmer and louder.
+    .balanced = Balanced
+    .balanced-desc = Standard performance and power usage.
+    .power-saver = Battery Saver
+    .power-saver-desc = Reduced performance and power usage, which extends battery life.
+    .unavailable = Power modes are unavailable, as power-profiles-daemon is not running.
+    .degraded-lap = Performance is reduced, as the computer was detected on a lap.
+    .degraded-temperature = Performance is reduced, as the computer is running too warm.
+    .degraded-other = Performance is reduced ({ $reason }).
+
+power-brightness = Brightness
+    .auto = Automatic brightness
+    .auto-desc = Adjusts the brightness of the screen to the light around it while this page is open.
+    .sensitivity = Sensitivity
+    .percent = { $value }%
+    .paused = Paused for a few minutes, as the brightness was changed by hand.
+    .no-backlight = The brightness of the screen cannot be adjusted.
+
+power-screen-off = Screen Blank
+    .after = Turn off the screen after
+    .on-ac = Turn off the screen when plugged in after
+    .on-battery = Turn off the screen on battery after
+    .dim = Dim the screen before turning it off
+    .lock = Lock the screen after it turns off
+    .lock-delay = Lock the screen after
+    .after-suspend = The system is suspended before the screen would turn off, so it will not turn off first.
+    .inhibited = The screen is currently kept from turning off by { $apps }. The time still applies once it stops.
+    .never = Never
+    .immediately = Immediately
+    .seconds = { $value } seconds
+    .minutes = { $value ->
+        [one] 1 minute
+       *[other] { $value } minutes
+    }
+
+power-suspend = Automatic Suspend
+    .after = Suspend after
+    .on-ac = Suspend when plugged in after
+    .on-battery = Suspend on battery after
+    .lid = When the lid is closed
+    .lid-suspend = Suspend
+    .lid-hibernate = Hibernate
+    .lid-lock = Lock the screen
+    .lid-nothing = Do nothing
+    .lid-on-ac = When the lid is closed while plugged in
+    .lid-on-battery = When the lid is closed on battery
+    .docked = While an external display is connected, closing the lid does nothing.
+    .managed = Managed by your administrator
+
+power-button = Power Button
+    .action = When the power button is pressed
+    .suspend = Suspend
+    .power-off = Power off
+    .hibernate = Hibernate
+    .nothing = Do nothing
+    .no-hibernate = Hibernating is not offered, as the system does not permit it. It needs swap space on disk which is large enough to hold the memory of the system.
+
+power-lid-nothing = Do nothing when the lid is closed on battery?
+    .desc = The laptop keeps running when it is closed, which drains its battery, and may overheat it if it is put in a bag.
+    .confirm = Do Nothing
+
 ## Sound
 
 sound = Sound
@@ -107,18 +693,73 @@ sound-output = Output
     .level = Output level
     .config = Configuration
     .balance = Balance
+    .mute = Mute output
+    .amplification = Allow volume above 100%
+    .amplification-desc = Extends the volume sliders to 150%
+    .amplification-warning = Volumes above 100% amplify the sound beyond what it was recorded at, which distorts most sounds.
+
+    Playing loud sounds amplified may also damage speakers and hearing. Disallowing it brings volumes above 100% back to 100%.
+
+sound-device = Output Device
+    .desc = Profile, port, and channels of an output device
+    .profile = Profile
+    .port = Port
+    .channels = Channels
+    .unplugged = Unplugged
+    .mono = Mono
+    .stereo = Stereo
+    .surround-51 = 5.1 Surround
+    .surround-71 = 7.1 Surround
+    .channel-count = { $count } channels
+    .switching = Switching profile…
+    .no-output = This profile has no output.
+    .disconnected = The device is disconnected.
+
+sound-speaker-test = Test Speakers
+    .desc = Select a speaker to play a test sound from it alone.
+    .close = Close
+    .test = Test
+    .mono = Mono
+    .front-left = Front Left
+    .front-center = Front Center
+    .front-right = Front Right
+    .side-left = Side Left
+    .lfe = Subwoofer
+    .side-right = Side Right
+    .rear-left = Rear Left
+    .rear-center = Rear Center
+    .rear-right = Rear Right
+
+sound-balance = Balance
+    .left = Left
+    .center = Center
+    .right = Right
+    .reset = Center the balance
+
+sound-fade = Fade
+    .rear = Rear
+    .center = Center
+    .front = Front
+    .reset = Center the fade
 
 sound-input = Input
     .volume = Input volume
     .device = Input device
     .level = Input level
+    .mute = Mute input
+    .level-unavailable = The input device is missing, or is being used by another application.
+    .show-monitors = Show monitors
+    .show-monitors-desc = List the outputs as input devices, to record what they play.
 
 sound-alerts = Alerts
     .volume = Alerts volume
     .sound = Alerts sound
+    .none = No sound
 
 sound-applications = Applications
     .desc = Application volumes and settings
+    .streams = Streams
+    .none = No applications are playing sound.
 
 ## System
 
@@ -153,6 +794,11 @@ about-related = Related settings
 firmware = Firmware
     .desc = Firmware details.
 
+firmware-updates = Firmware Updates
+    .check = Check for updates
+    .checking = Checking for updates…
+    .refreshed = Firmware metadata is up to date
+
 ## System: Users
 
 users = Users
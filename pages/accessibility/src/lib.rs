@@ -0,0 +1,124 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{
+    env, fs,
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+};
+
+/// The screen reader, which is started and stopped by its own command.
+const ORCA: &str = "orca";
+
+/// The schema of the key which desktops watch to start the screen reader at login.
+const APPLICATIONS_SCHEMA: &str = "org.gnome.desktop.a11y.applications";
+
+/// Whether Orca is installed, which is found by its command in the `PATH`.
+#[must_use]
+pub fn screen_reader_installed() -> bool {
+    let Some(paths) = env::var_os("PATH") else {
+        return false;
+    };
+
+    env::split_paths(&paths).any(|dir| dir.join(ORCA).is_file())
+}
+
+/// Whether Orca is running, whether it was started here or by other means.
+#[must_use]
+pub fn screen_reader_running() -> bool {
+    !orca_processes().is_empty()
+}
+
+/// Starts or stops Orca, and sets whether it is started at login.
+///
+/// # Errors
+///
+/// Fails if Orca could not be started, or the setting could not be written.
+pub fn set_screen_reader(enabled: bool) -> Result<(), String> {
+    gsettings_set(
+        APPLICATIONS_SCHEMA,
+        "screen-reader-enabled",
+        if enabled { "true" } else { "false" },
+    )?;
+
+    if enabled {
+        if !screen_reader_running() {
+            spawn(&[ORCA, "--replace"])?;
+        }
+
+        return Ok(());
+    }
+
+    for pid in orca_processes() {
+        let _res = Command::new("kill")
+            .arg(pid.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    }
+
+    Ok(())
+}
+
+/// Opens the preferences of Orca in a window of its own.
+///
+/// # Errors
+///
+/// Fails if Orca could not be executed.
+pub fn open_screen_reader_preferences() -> Result<(), String> {
+    spawn(&[ORCA, "--setup"])
+}
+
+/// The processes of Orca, which runs as a Python script named by its command.
+fn orca_processes() -> Vec<u32> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+            let cmdline = fs::read(entry.path().join("cmdline")).ok()?;
+
+            // The command is the interpreter, followed by the path of the script.
+            let is_orca = cmdline
+                .split(|&byte| byte == 0)
+                .take(2)
+                .filter_map(|arg| std::str::from_utf8(arg).ok())
+                .any(|arg| matches!(Path::new(arg).file_name(), Some(name) if name == ORCA));
+
+            is_orca.then_some(pid)
+        })
+        .collect()
+}
+
+/// Runs a command in the background, which is left running once settings is closed.
+fn spawn(args: &[&str]) -> Result<(), String> {
+    let mut child = Command::new(args[0])
+        .args(&args[1..])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|why| format!("failed to execute {}: {why}", args[0]))?;
+
+    // The child is waited on once it exits, so that it is not left behind as a zombie.
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+fn gsettings_set(schema: &str, key: &str, value: &str) -> Result<(), String> {
+    let output = Command::new("gsettings")
+        .args(["set", schema, key, value])
+        .output()
+        .map_err(|why| format!("failed to execute gsettings: {why}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,229 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Cursor themes, which are icon themes with a directory of Xcursor files.
+
+use std::{
+    env, fs,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+
+use crate::{data_dirs, gsettings_get, gsettings_set, INTERFACE_SCHEMA};
+
+/// Sizes of the cursor which are offered, in pixels.
+pub const SIZES: RangeInclusive<u32> = 16..=96;
+
+/// The size of the cursor where it is not set.
+const DEFAULT_SIZE: u32 = 24;
+
+/// The cursor which is shown as the preview of a theme, by its names in different themes.
+const PREVIEW_CURSORS: [&str; 2] = ["default", "left_ptr"];
+
+/// The type of the chunks of an Xcursor file which hold an image.
+const XCURSOR_IMAGE: u32 = 0xfffd_0002;
+
+/// An installed cursor theme.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CursorTheme {
+    /// The name of the directory of the theme, which it is chosen by.
+    pub id: String,
+    /// The name which the theme gives itself.
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// The cursor theme and size which are chosen.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cursor {
+    pub theme: String,
+    pub size: u32,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self {
+            theme: String::from("default"),
+            size: DEFAULT_SIZE,
+        }
+    }
+}
+
+/// The directories which icon and cursor themes are installed in, from the most preferred.
+fn theme_dirs() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+
+    let mut dirs = vec![home.join(".icons")];
+    dirs.extend(data_dirs().into_iter().map(|dir| dir.join("icons")));
+    dirs.push(PathBuf::from("/usr/share/pixmaps"));
+    dirs
+}
+
+/// The installed cursor themes, by name. A theme installed for the user hides a theme of
+/// the same directory name which is installed for the system.
+#[must_use]
+pub fn themes() -> Vec<CursorTheme> {
+    let mut themes: Vec<CursorTheme> = Vec::new();
+
+    for dir in theme_dirs() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let id = entry.file_name().to_string_lossy().into_owned();
+
+            // The default theme only points at another, which is what is chosen here.
+            if id == "default"
+                || !path.join("cursors").is_dir()
+                || themes.iter().any(|theme| theme.id == id)
+            {
+                continue;
+            }
+
+            let name = theme_name(&path).unwrap_or_else(|| id.clone());
+            themes.push(CursorTheme { id, name, path });
+        }
+    }
+
+    themes.sort_by_key(|theme| theme.name.to_lowercase());
+    themes
+}
+
+/// The name of a theme from its `index.theme` file.
+fn theme_name(path: &Path) -> Option<String> {
+    let index = fs::read_to_string(path.join("index.theme")).ok()?;
+
+    index
+        .lines()
+        .find_map(|line| line.strip_prefix("Name="))
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty())
+}
+
+/// The chosen cursor, as toolkits see it, with the environment as the fallback.
+#[must_use]
+pub fn cursor() -> Cursor {
+    let default = Cursor::default();
+
+    let theme = gsettings_get(INTERFACE_SCHEMA, "cursor-theme")
+        .or_else(|| env::var("XCURSOR_THEME").ok())
+        .filter(|theme| !theme.is_empty())
+        .unwrap_or(default.theme);
+
+    let size = gsettings_get(INTERFACE_SCHEMA, "cursor-size")
+        .or_else(|| env::var("XCURSOR_SIZE").ok())
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(default.size);
+
+    Cursor { theme, size }
+}
+
+/// Chooses the cursor. Toolkits follow it in windows which are opened from then on, while
+/// the compositor and applications for X11 only follow it from the next login.
+///
+/// # Errors
+///
+/// Fails if the size is out of range, or if a config could not be written.
+pub fn set_cursor(cursor: &Cursor) -> Result<(), String> {
+    if !SIZES.contains(&cursor.size) {
+        return Err(format!("a cursor size of {} is out of range", cursor.size));
+    }
+
+    gsettings_set(INTERFACE_SCHEMA, "cursor-theme", &cursor.theme)?;
+    gsettings_set(INTERFACE_SCHEMA, "cursor-size", &cursor.size.to_string())?;
+
+    let home = env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| String::from("HOME is not set"))?;
+
+    // The default theme is what applications for X11 fall back to.
+    write(
+        &home.join(".icons/default/index.theme"),
+        &format!("[Icon Theme]\nInherits={}\n", cursor.theme),
+    )?;
+
+    // The session reads its environment at login, which the compositor inherits.
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| home.join(".config"));
+
+    write(
+        &config_home.join("environment.d/cursor.conf"),
+        &format!(
+            "XCURSOR_THEME={}\nXCURSOR_SIZE={}\n",
+            cursor.theme, cursor.size
+        ),
+    )
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|why| why.to_string())?;
+    }
+
+    fs::write(path, contents).map_err(|why| format!("failed to write {}: {why}", path.display()))
+}
+
+/// The pointer of a theme at the size nearest to a size, as its width, height, and RGBA
+/// pixels, if the theme has one.
+#[must_use]
+pub fn preview(theme: &CursorTheme, size: u32) -> Option<(u32, u32, Vec<u8>)> {
+    PREVIEW_CURSORS
+        .iter()
+        .find_map(|name| fs::read(theme.path.join("cursors").join(name)).ok())
+        .and_then(|file| xcursor_image(&file, size))
+}
+
+/// Reads the image of an Xcursor file nearest to a size, converting its premultiplied
+/// ARGB pixels to RGBA.
+fn xcursor_image(file: &[u8], size: u32) -> Option<(u32, u32, Vec<u8>)> {
+    let u32_at = |offset: usize| -> Option<u32> {
+        let bytes = file.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+
+    if file.get(..4) != Some(b"Xcur".as_slice()) {
+        return None;
+    }
+
+    let entries = u32_at(12)? as usize;
+
+    // Each entry of the table of contents is a type, a nominal size, and a position.
+    let position = (0..entries)
+        .filter_map(|index| {
+            let entry = 16 + index * 12;
+            let kind = u32_at(entry)?;
+            let nominal = u32_at(entry + 4)?;
+            let position = u32_at(entry + 8)? as usize;
+            (kind == XCURSOR_IMAGE).then_some((nominal, position))
+        })
+        .min_by_key(|(nominal, _)| nominal.abs_diff(size))
+        .map(|(_, position)| position)?;
+
+    // The header of an image chunk is followed by its dimensions, hotspot, and delay.
+    let width = u32_at(position + 16)?;
+    let height = u32_at(position + 20)?;
+    let pixels_at = position + 36;
+
+    let len = (width as usize) * (height as usize) * 4;
+    let argb = file.get(pixels_at..pixels_at + len)?;
+
+    let rgba = argb
+        .chunks_exact(4)
+        .flat_map(|pixel| {
+            // Pixels are stored little-endian, as blue, green, red, and alpha.
+            let [blue, green, red, alpha] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+            let unmultiply = |channel: u8| match alpha {
+                0 => 0,
+                _ => (u16::from(channel) * 255 / u16::from(alpha)).min(255) as u8,
+            };
+
+            [unmultiply(red), unmultiply(green), unmultiply(blue), alpha]
+        })
+        .collect();
+
+    Some((width, height, rgba))
+}
@@ -0,0 +1,341 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Fonts, which are listed by fontconfig, and chosen for each role of the interface.
+
+use cosmic_settings_config as config;
+use std::{fmt::Write, path::PathBuf, process::Command};
+
+use crate::{gsettings_get, gsettings_set, INTERFACE_SCHEMA};
+
+/// Sizes of fonts which are offered, in points.
+pub const SIZES: std::ops::RangeInclusive<u32> = 6..=72;
+
+/// The config of the toolkit of COSMIC applications, whose keys are each a file of their own.
+const COSMIC_TK: &str = "com.system76.CosmicTk";
+
+/// What a font is used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FontRole {
+    /// Labels, buttons, and menus.
+    Interface,
+    /// The contents of documents, such as in a text editor.
+    Document,
+    /// Code and terminals, where every character is the same width.
+    Monospace,
+}
+
+impl FontRole {
+    pub const ALL: [Self; 3] = [Self::Interface, Self::Document, Self::Monospace];
+
+    fn gsettings_key(self) -> &'static str {
+        match self {
+            Self::Interface => "font-name",
+            Self::Document => "document-font-name",
+            Self::Monospace => "monospace-font-name",
+        }
+    }
+
+    /// The key of the config of COSMIC applications, which have no document font.
+    fn cosmic_key(self) -> Option<&'static str> {
+        match self {
+            Self::Interface => Some("interface_font"),
+            Self::Document => None,
+            Self::Monospace => Some("monospace_font"),
+        }
+    }
+
+    fn default_spec(self) -> FontSpec {
+        let (family, size) = match self {
+            Self::Interface | Self::Document => ("Fira Sans", 10),
+            Self::Monospace => ("Fira Mono", 11),
+        };
+
+        FontSpec {
+            family: String::from(family),
+            style: String::from("Regular"),
+            size,
+        }
+    }
+}
+
+/// A font by its family, style, and size in points.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontSpec {
+    pub family: String,
+    pub style: String,
+    pub size: u32,
+}
+
+impl FontSpec {
+    /// The font as toolkits describe it, such as `Fira Sans Semi-Bold 10`.
+    fn description(&self) -> String {
+        let mut description = self.family.clone();
+
+        if !self.style.is_empty() && self.style != "Regular" {
+            let _res = write!(description, " {}", self.style);
+        }
+
+        let _res = write!(description, " {}", self.size);
+        description
+    }
+
+    /// Reads a description of a font, telling its family apart from its style by the
+    /// installed families.
+    fn parse(description: &str, families: &[FontFamily]) -> Option<Self> {
+        let (name, size) = description.trim().rsplit_once(' ')?;
+        let size = size.parse::<f32>().ok()?.round() as u32;
+
+        // The longest family which the name begins with is its family.
+        let family = families
+            .iter()
+            .filter(|family| name == family.name || name.starts_with(&format!("{} ", family.name)))
+            .max_by_key(|family| family.name.len());
+
+        let (family, style) = match family {
+            Some(family) => (family.name.clone(), name[family.name.len()..].trim()),
+            None => (name.to_owned(), ""),
+        };
+
+        Some(Self {
+            family,
+            style: String::from(if style.is_empty() { "Regular" } else { style }),
+            size,
+        })
+    }
+}
+
+/// An installed font family, with its styles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontFamily {
+    pub name: String,
+    pub styles: Vec<FontStyle>,
+    /// Whether every character of the family is the same width.
+    pub monospace: bool,
+}
+
+/// A style of a font family, such as `Bold Italic`, and the file which it is in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontStyle {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl FontFamily {
+    /// The style by its name, or the regular style if it has none by that name.
+    #[must_use]
+    pub fn style(&self, name: &str) -> Option<&FontStyle> {
+        self.styles
+            .iter()
+            .find(|style| style.name == name)
+            .or_else(|| self.styles.iter().find(|style| style.name == "Regular"))
+            .or_else(|| self.styles.first())
+    }
+}
+
+/// The installed font families, by name, as listed by fontconfig.
+#[must_use]
+pub fn families() -> Vec<FontFamily> {
+    let Ok(output) = Command::new("fc-list")
+        .args([
+            "--format",
+            "%{family[0]}\\t%{style[0]}\\t%{spacing}\\t%{file}\\n",
+        ])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let mut families: Vec<FontFamily> = Vec::new();
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut fields = line.split('\t');
+
+        let (Some(family), Some(style), Some(spacing), Some(path)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        if family.is_empty() {
+            continue;
+        }
+
+        let style = FontStyle {
+            name: String::from(if style.is_empty() { "Regular" } else { style }),
+            path: PathBuf::from(path),
+        };
+
+        // Fontconfig gives monospace fonts a spacing of 100.
+        let monospace = spacing == "100";
+
+        match families.iter_mut().find(|other| other.name == family) {
+            Some(family) => {
+                family.monospace &= monospace;
+
+                if !family.styles.iter().any(|other| other.name == style.name) {
+                    family.styles.push(style);
+                }
+            }
+
+            None => families.push(FontFamily {
+                name: String::from(family),
+                styles: vec![style],
+                monospace,
+            }),
+        }
+    }
+
+    families.sort_by_key(|family| family.name.to_lowercase());
+
+    for family in &mut families {
+        family.styles.sort_by_key(|style| style_order(&style.name));
+    }
+
+    families
+}
+
+/// Orders styles from the lightest to the heaviest, with italics after their upright style.
+fn style_order(style: &str) -> (u16, bool, String) {
+    let (weight, _) = weight(style);
+    let italic = style.contains("Italic") || style.contains("Oblique");
+    (weight, italic, style.to_owned())
+}
+
+/// The weight of a style, as a number from 100 to 900, and its name in COSMIC's config.
+fn weight(style: &str) -> (u16, &'static str) {
+    let style = style.to_lowercase().replace(['-', ' '], "");
+
+    const WEIGHTS: [(&str, u16, &str); 9] = [
+        ("thin", 100, "Thin"),
+        ("extralight", 200, "ExtraLight"),
+        ("semibold", 600, "Semibold"),
+        ("extrabold", 800, "ExtraBold"),
+        ("light", 300, "Light"),
+        ("medium", 500, "Medium"),
+        ("bold", 700, "Bold"),
+        ("black", 900, "Black"),
+        ("heavy", 900, "Black"),
+    ];
+
+    WEIGHTS
+        .iter()
+        .find(|(name, ..)| style.contains(name))
+        .map_or((400, "Normal"), |&(_, weight, name)| (weight, name))
+}
+
+/// The font chosen for a role, or its default if it is not set.
+#[must_use]
+pub fn font(role: FontRole, families: &[FontFamily]) -> FontSpec {
+    gsettings_get(INTERFACE_SCHEMA, role.gsettings_key())
+        .and_then(|description| FontSpec::parse(&description, families))
+        .unwrap_or_else(|| role.default_spec())
+}
+
+/// Chooses the font of a role. Toolkits which follow the interface settings apply it at
+/// once, while COSMIC applications apply it once they are opened again.
+///
+/// # Errors
+///
+/// Fails if the size is out of range, or if a config could not be written.
+pub fn set_font(role: FontRole, font: &FontSpec) -> Result<(), String> {
+    if !SIZES.contains(&font.size) {
+        return Err(format!("a font size of {} is out of range", font.size));
+    }
+
+    gsettings_set(INTERFACE_SCHEMA, role.gsettings_key(), &font.description())?;
+
+    let Some(key) = role.cosmic_key() else {
+        return Ok(());
+    };
+
+    let (_, weight) = weight(&font.style);
+    let style = if font.style.contains("Italic") {
+        "Italic"
+    } else if font.style.contains("Oblique") {
+        "Oblique"
+    } else {
+        "Normal"
+    };
+
+    let config = format!(
+        "(\n    family: {:?},\n    weight: {weight},\n    stretch: Normal,\n    style: {style},\n)",
+        font.family
+    );
+
+    write_cosmic_config(key, &config)
+}
+
+/// How glyphs are fitted to the pixel grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Hinting {
+    None,
+    #[default]
+    Slight,
+    Medium,
+    Full,
+}
+
+impl Hinting {
+    pub const ALL: [Self; 4] = [Self::None, Self::Slight, Self::Medium, Self::Full];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Slight => "slight",
+            Self::Medium => "medium",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// How the edges of glyphs are smoothed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Antialiasing {
+    None,
+    #[default]
+    Grayscale,
+    /// Smooths edges by the subpixels of LCD displays, which is sharper on them.
+    Subpixel,
+}
+
+impl Antialiasing {
+    pub const ALL: [Self; 3] = [Self::None, Self::Grayscale, Self::Subpixel];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Grayscale => "grayscale",
+            Self::Subpixel => "rgba",
+        }
+    }
+}
+
+/// How fonts are rendered, with the defaults where it is not set.
+#[must_use]
+pub fn rendering() -> (Hinting, Antialiasing) {
+    let hinting = gsettings_get(INTERFACE_SCHEMA, "font-hinting")
+        .and_then(|value| Hinting::ALL.into_iter().find(|h| h.as_str() == value))
+        .unwrap_or_default();
+
+    let antialiasing = gsettings_get(INTERFACE_SCHEMA, "font-antialiasing")
+        .and_then(|value| Antialiasing::ALL.into_iter().find(|a| a.as_str() == value))
+        .unwrap_or_default();
+
+    (hinting, antialiasing)
+}
+
+/// Sets how fonts are rendered, which toolkits that follow the interface settings apply at
+/// once.
+///
+/// # Errors
+///
+/// Fails if the settings could not be written.
+pub fn set_rendering(hinting: Hinting, antialiasing: Antialiasing) -> Result<(), String> {
+    gsettings_set(INTERFACE_SCHEMA, "font-hinting", hinting.as_str())?;
+    gsettings_set(INTERFACE_SCHEMA, "font-antialiasing", antialiasing.as_str())
+}
+
+fn write_cosmic_config(key: &str, contents: &str) -> Result<(), String> {
+    config::write(COSMIC_TK, key, contents)
+}
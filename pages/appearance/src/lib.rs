@@ -0,0 +1,55 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+pub mod cursor;
+pub mod font;
+
+use std::{env, path::PathBuf, process::Command};
+
+/// The schema of the interface settings which toolkits follow while applications run.
+const INTERFACE_SCHEMA: &str = "org.gnome.desktop.interface";
+
+/// The directories which themes are installed in, with those of the user first, as they
+/// take precedence over those of the system.
+fn data_dirs() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| home.join(".local/share"));
+
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+
+    std::iter::once(data_home)
+        .chain(env::split_paths(&data_dirs))
+        .collect()
+}
+
+fn gsettings_get(schema: &str, key: &str) -> Option<String> {
+    let output = Command::new("gsettings")
+        .args(["get", schema, key])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let value = String::from_utf8_lossy(&output.stdout);
+    Some(value.trim().trim_matches('\'').to_owned())
+}
+
+fn gsettings_set(schema: &str, key: &str, value: &str) -> Result<(), String> {
+    let output = Command::new("gsettings")
+        .args(["set", schema, key, value])
+        .output()
+        .map_err(|why| format!("failed to execute gsettings: {why}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    Ok(())
+}
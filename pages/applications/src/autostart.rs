@@ -0,0 +1,168 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::DesktopEntry;
+use std::{collections::HashSet, io, path::PathBuf};
+
+/// An application started with the desktop session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Autostart {
+    pub entry: DesktopEntry,
+    /// Whether the entry was added by the user, rather than installed by the system.
+    pub user_added: bool,
+}
+
+impl Autostart {
+    /// Whether the application is started with the session.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        !self.entry.hidden && self.entry.autostart_enabled
+    }
+}
+
+/// `$XDG_CONFIG_HOME/autostart`, where the user's autostart entries and overrides are kept.
+#[must_use]
+pub fn user_dir() -> PathBuf {
+    crate::config_home().join("autostart")
+}
+
+/// Every autostart entry, including disabled entries, with user overrides taking precedence.
+#[must_use]
+pub fn entries() -> Vec<Autostart> {
+    let mut system_seen = HashSet::new();
+    let mut system = Vec::new();
+
+    for dir in crate::config_dirs() {
+        let dir = dir.join("autostart");
+        crate::desktop_entry::collect(&dir, &dir, &mut system_seen, &mut system);
+    }
+
+    let user_dir = user_dir();
+    let mut user_seen = HashSet::new();
+    let mut user = Vec::new();
+    crate::desktop_entry::collect(&user_dir, &user_dir, &mut user_seen, &mut user);
+
+    let mut entries: Vec<Autostart> = user
+        .into_iter()
+        .map(|entry| Autostart {
+            user_added: !system_seen.contains(&entry.id),
+            entry,
+        })
+        .chain(
+            system
+                .into_iter()
+                .filter(|entry| !user_seen.contains(&entry.id))
+                .map(|entry| Autostart {
+                    entry,
+                    user_added: false,
+                }),
+        )
+        .collect();
+
+    entries.sort_by_cached_key(|autostart| autostart.entry.name.to_lowercase());
+    entries
+}
+
+/// Enables or disables an autostart entry.
+///
+/// System entries are never modified; a user entry of the same ID overrides them instead.
+///
+/// # Errors
+///
+/// Fails if the entry's desktop file cannot be read, or its override cannot be written.
+pub fn set_enabled(entry: &DesktopEntry, enabled: bool) -> io::Result<()> {
+    let contents = std::fs::read_to_string(&entry.path)?;
+    let hidden = if enabled { "false" } else { "true" };
+
+    let contents = set_key(&contents, "Hidden", hidden);
+    let contents = set_key(&contents, "X-GNOME-Autostart-enabled", &enabled.to_string());
+
+    write(&entry.id, &contents)
+}
+
+/// Adds an application to the autostart entries, optionally with a custom command.
+///
+/// # Errors
+///
+/// Fails if the application's desktop file cannot be read, or the entry cannot be written.
+pub fn add(application: &DesktopEntry, command: Option<&str>) -> io::Result<()> {
+    let mut contents = std::fs::read_to_string(&application.path)?;
+
+    if let Some(command) = command.filter(|command| !command.trim().is_empty()) {
+        contents = set_key(&contents, "Exec", command.trim());
+    }
+
+    contents = set_key(&contents, "Hidden", "false");
+
+    write(&application.id, &contents)
+}
+
+/// Removes an autostart entry which was added by the user.
+///
+/// # Errors
+///
+/// Fails if the entry is not a user entry, or its file cannot be removed.
+pub fn remove(autostart: &Autostart) -> io::Result<()> {
+    if !autostart.user_added {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "system autostart entries can only be disabled",
+        ));
+    }
+
+    std::fs::remove_file(user_dir().join(&autostart.entry.id))
+}
+
+fn write(id: &str, contents: &str) -> io::Result<()> {
+    let dir = user_dir();
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(id), contents)
+}
+
+/// Sets a key of the `[Desktop Entry]` group, discarding its localized variants.
+fn set_key(contents: &str, key: &str, value: &str) -> String {
+    let mut output = String::with_capacity(contents.len() + key.len() + value.len() + 2);
+    let mut in_group = false;
+    let mut written = false;
+
+    let is_key = |line: &str| match line.split_once('=') {
+        Some((name, _)) => {
+            let name = name.trim();
+            name == key || matches!(name.strip_prefix(key), Some(rest) if rest.starts_with('['))
+        }
+        None => false,
+    };
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') {
+            if in_group && !written {
+                output.push_str(&format!("{key}={value}\n"));
+                written = true;
+            }
+
+            in_group = trimmed == "[Desktop Entry]";
+        } else if in_group && is_key(trimmed) {
+            if !written {
+                output.push_str(&format!("{key}={value}\n"));
+                written = true;
+            }
+
+            continue;
+        }
+
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    if !written {
+        if !in_group {
+            output.push_str("[Desktop Entry]\n");
+        }
+
+        output.push_str(&format!("{key}={value}\n"));
+    }
+
+    output
+}
@@ -0,0 +1,162 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// The keys of a desktop entry which are relevant to the settings pages.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DesktopEntry {
+    /// The desktop file ID, such as `org.gnome.Nautilus.desktop`.
+    pub id: String,
+    pub path: PathBuf,
+    pub name: String,
+    pub comment: String,
+    pub icon: String,
+    pub exec: String,
+    pub mime_types: Vec<String>,
+    pub hidden: bool,
+    pub no_display: bool,
+    /// Whether an autostart entry is enabled by `X-GNOME-Autostart-enabled`.
+    pub autostart_enabled: bool,
+    /// Whether the entry is an applet of the panel, by `X-CosmicApplet`.
+    pub applet: bool,
+}
+
+impl fmt::Display for DesktopEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name.is_empty() {
+            f.write_str(&self.id)
+        } else {
+            f.write_str(&self.name)
+        }
+    }
+}
+
+impl DesktopEntry {
+    /// Parses the `[Desktop Entry]` group of a desktop file.
+    #[must_use]
+    pub fn from_path(id: String, path: &Path) -> Option<Self> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        let mut entry = DesktopEntry {
+            id,
+            path: path.to_path_buf(),
+            autostart_enabled: true,
+            ..DesktopEntry::default()
+        };
+
+        let mut in_group = false;
+        let mut is_application = false;
+
+        for line in contents.lines() {
+            let line = line.trim();
+
+            if line.starts_with('[') {
+                in_group = line == "[Desktop Entry]";
+                continue;
+            }
+
+            if !in_group || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+
+            let value = value.trim();
+
+            match key.trim() {
+                "Type" => is_application = value == "Application",
+                "Name" => entry.name = value.to_owned(),
+                "Comment" => entry.comment = value.to_owned(),
+                "Icon" => entry.icon = value.to_owned(),
+                "Exec" => entry.exec = value.to_owned(),
+                "MimeType" => {
+                    entry.mime_types = value
+                        .split(';')
+                        .filter(|mime| !mime.is_empty())
+                        .map(String::from)
+                        .collect();
+                }
+                "Hidden" => entry.hidden = value == "true",
+                "NoDisplay" => entry.no_display = value == "true",
+                "X-GNOME-Autostart-enabled" => entry.autostart_enabled = value != "false",
+                "X-CosmicApplet" => entry.applet = value == "true",
+                _ => (),
+            }
+        }
+
+        is_application.then_some(entry)
+    }
+}
+
+/// Every application installed in the data directories, with entries of the same ID
+/// resolved to the most important directory.
+#[must_use]
+pub fn applications() -> Vec<DesktopEntry> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+
+    for dir in crate::data_dirs() {
+        let dir = dir.join("applications");
+        collect(&dir, &dir, &mut seen, &mut entries);
+    }
+
+    entries.retain(|entry| !entry.hidden);
+    entries.sort_by_cached_key(|entry| entry.name.to_lowercase());
+    entries
+}
+
+/// Every applet of the panel which is installed, by name. Applets are hidden from menus of
+/// applications, so they are kept regardless of `NoDisplay`.
+#[must_use]
+pub fn applets() -> Vec<DesktopEntry> {
+    let mut applets = applications();
+    applets.retain(|entry| entry.applet);
+    applets
+}
+
+/// Collects desktop entries from a directory, deriving IDs from their path relative to `base`.
+pub(crate) fn collect(
+    base: &Path,
+    dir: &Path,
+    seen: &mut HashSet<String>,
+    entries: &mut Vec<DesktopEntry>,
+) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for dir_entry in read_dir.flatten() {
+        let path = dir_entry.path();
+
+        if path.is_dir() {
+            collect(base, &path, seen, entries);
+            continue;
+        }
+
+        if path.extension() != Some(OsStr::new("desktop")) {
+            continue;
+        }
+
+        let Ok(relative) = path.strip_prefix(base) else {
+            continue;
+        };
+
+        // Sub-directories are part of the ID, so `kde/foo.desktop` becomes `kde-foo.desktop`.
+        let id = relative.to_string_lossy().replace('/', "-");
+
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+
+        if let Some(entry) = DesktopEntry::from_path(id, &path) {
+            entries.push(entry);
+        }
+    }
+}
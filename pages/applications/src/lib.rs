@@ -0,0 +1,62 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+pub mod autostart;
+pub mod desktop_entry;
+pub mod mime;
+
+pub use desktop_entry::DesktopEntry;
+pub use mime::Category;
+
+use std::path::PathBuf;
+
+/// `$XDG_CONFIG_HOME`, or `~/.config` when it is not set.
+#[must_use]
+pub fn config_home() -> PathBuf {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| home().join(".config"))
+}
+
+/// `$XDG_DATA_HOME`, or `~/.local/share` when it is not set.
+#[must_use]
+pub fn data_home() -> PathBuf {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| home().join(".local/share"))
+}
+
+/// The data directories, from most to least important, including the user's own.
+#[must_use]
+pub fn data_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![data_home()];
+
+    let system = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+
+    dirs.extend(system.split(':').map(PathBuf::from));
+    dirs.push(home().join(".local/share/flatpak/exports/share"));
+    dirs.push(PathBuf::from("/var/lib/flatpak/exports/share"));
+    dirs.dedup();
+    dirs
+}
+
+/// The config directories, from most to least important, excluding the user's own.
+#[must_use]
+pub fn config_dirs() -> Vec<PathBuf> {
+    std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/etc/xdg"))
+        .split(':')
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn home() -> PathBuf {
+    std::env::var_os("HOME").map_or_else(|| PathBuf::from("/"), PathBuf::from)
+}
@@ -0,0 +1,196 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::DesktopEntry;
+use std::{io, path::PathBuf};
+
+const DEFAULT_APPLICATIONS: &str = "[Default Applications]";
+
+/// Kinds of applications which may be chosen as the default handler of their MIME types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Category {
+    WebBrowser,
+    Email,
+    FileManager,
+    Music,
+    Video,
+    ImageViewer,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::WebBrowser,
+        Category::Email,
+        Category::FileManager,
+        Category::Music,
+        Category::Video,
+        Category::ImageViewer,
+    ];
+
+    /// The MIME types handled by the category, with the one that identifies it first.
+    #[must_use]
+    pub fn mime_types(self) -> &'static [&'static str] {
+        match self {
+            Category::WebBrowser => &[
+                "x-scheme-handler/http",
+                "x-scheme-handler/https",
+                "text/html",
+                "application/xhtml+xml",
+            ],
+            Category::Email => &["x-scheme-handler/mailto"],
+            Category::FileManager => &["inode/directory"],
+            Category::Music => &[
+                "audio/mpeg",
+                "audio/flac",
+                "audio/x-vorbis+ogg",
+                "audio/x-wav",
+            ],
+            Category::Video => &["video/mp4", "video/x-matroska", "video/webm"],
+            Category::ImageViewer => &["image/png", "image/jpeg", "image/gif", "image/webp"],
+        }
+    }
+}
+
+/// The applications which may handle a category, and which of them is the default.
+#[derive(Clone, Debug)]
+pub struct Choices {
+    pub category: Category,
+    pub apps: Vec<DesktopEntry>,
+    /// The desktop file ID of the default application, if there is one.
+    pub default: Option<String>,
+}
+
+/// The candidates and current default application of every category.
+#[must_use]
+pub fn default_apps() -> Vec<Choices> {
+    let applications = crate::desktop_entry::applications();
+
+    Category::ALL
+        .iter()
+        .map(|&category| {
+            let mime = category.mime_types()[0];
+
+            let apps: Vec<DesktopEntry> = applications
+                .iter()
+                .filter(|app| app.mime_types.iter().any(|m| m == mime))
+                .cloned()
+                .collect();
+
+            let default = default_app(mime).filter(|id| apps.iter().any(|app| &app.id == id));
+
+            Choices {
+                category,
+                apps,
+                default,
+            }
+        })
+        .collect()
+}
+
+/// Makes an application the default handler of every MIME type of a category.
+///
+/// # Errors
+///
+/// Returns an error if the user's `mimeapps.list` could not be written.
+pub fn set_default_app(category: Category, app_id: &str) -> io::Result<()> {
+    let path = crate::config_home().join("mimeapps.list");
+    let contents = std::fs::read_to_string(&path).unwrap_or_default();
+
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+
+    let section = match lines
+        .iter()
+        .position(|line| line.trim() == DEFAULT_APPLICATIONS)
+    {
+        Some(position) => position,
+        None => {
+            if matches!(lines.last(), Some(line) if !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+
+            lines.push(DEFAULT_APPLICATIONS.to_owned());
+            lines.len() - 1
+        }
+    };
+
+    for mime in category.mime_types() {
+        let value = [mime, "=", app_id, ";"].concat();
+
+        let section_end = lines[section + 1..]
+            .iter()
+            .position(|line| line.trim_start().starts_with('['))
+            .map_or(lines.len(), |position| section + 1 + position);
+
+        let existing = lines[section + 1..section_end]
+            .iter()
+            .position(|line| matches!(line.split_once('='), Some((key, _)) if key.trim() == *mime));
+
+        match existing {
+            Some(position) => lines[section + 1 + position] = value,
+            None => lines.insert(section_end, value),
+        }
+    }
+
+    let mut contents = lines.join("\n");
+    contents.push('\n');
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, contents)
+}
+
+/// The default application of a MIME type, searched in the order of the XDG specification.
+#[must_use]
+pub fn default_app(mime: &str) -> Option<String> {
+    mimeapps_lists()
+        .into_iter()
+        .find_map(|path| lookup(&std::fs::read_to_string(path).ok()?, mime))
+}
+
+fn mimeapps_lists() -> Vec<PathBuf> {
+    let mut lists = vec![crate::config_home().join("mimeapps.list")];
+
+    lists.extend(
+        crate::config_dirs()
+            .into_iter()
+            .map(|dir| dir.join("mimeapps.list")),
+    );
+
+    for dir in crate::data_dirs() {
+        lists.push(dir.join("applications/mimeapps.list"));
+        lists.push(dir.join("applications/defaults.list"));
+    }
+
+    lists
+}
+
+fn lookup(contents: &str, mime: &str) -> Option<String> {
+    let mut in_section = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            in_section = line == DEFAULT_APPLICATIONS;
+            continue;
+        }
+
+        if !in_section {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            if key.trim() == mime {
+                return value
+                    .split(';')
+                    .map(str::trim)
+                    .find(|id| !id.is_empty())
+                    .map(String::from);
+            }
+        }
+    }
+
+    None
+}
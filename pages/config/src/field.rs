@@ -0,0 +1,90 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Edits fields of RON structs in place, keeping the rest of the text as it was written.
+
+use std::ops::Range;
+
+/// Finds the value of a field of a RON struct, up to the comma or parenthesis ending it.
+/// Values which begin with a parenthesis, such as `Some(true)`, end at the parenthesis
+/// closing it instead.
+///
+/// Only fields with values which are not strings are found reliably.
+pub fn field_span(contents: &str, name: &str) -> Option<Range<usize>> {
+    let mut from = 0;
+
+    while let Some(found) = contents[from..].find(name) {
+        let start = from + found;
+        from = start + name.len();
+
+        // Names which merely end with the name of the field are not the field.
+        let before = contents[..start].chars().next_back();
+        if matches!(before, Some(c) if c.is_alphanumeric() || c == '_') {
+            continue;
+        }
+
+        let Some(value) = contents[from..].trim_start().strip_prefix(':') else {
+            continue;
+        };
+
+        let value_start = contents.len() - value.len();
+        let value_len = match value.find('(') {
+            Some(open) if !value[..open].contains([',', ')']) => {
+                closing_paren(&value[open..]).map_or(value.len(), |close| open + close + 1)
+            }
+            _ => value.find([',', ')']).unwrap_or(value.len()),
+        };
+
+        return Some(value_start..value_start + value_len);
+    }
+
+    None
+}
+
+/// The offset of the parenthesis which closes the one that the text begins with.
+pub fn closing_paren(text: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (offset, char) in text.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(offset);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Replaces the value of a field of a RON struct, or adds the field if it is missing.
+pub fn set_field(contents: &str, name: &str, value: &str) -> String {
+    if let Some(span) = field_span(contents, name) {
+        return format!(
+            "{} {value}{}",
+            &contents[..span.start],
+            &contents[span.end..]
+        );
+    }
+
+    let Some(close) = contents.rfind(')') else {
+        return format!("({name}: {value})");
+    };
+
+    let fields = contents[..close].trim_end();
+
+    let separator = if fields.ends_with('(') {
+        ""
+    } else if fields.ends_with(',') {
+        " "
+    } else {
+        ", "
+    };
+
+    format!("{fields}{separator}{name}: {value}{}", &contents[close..])
+}
@@ -0,0 +1,83 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Keys of the cosmic configs of components, each of which is a file of a RON value, and the
+//! fields of the values which are RON structs.
+
+mod field;
+
+pub use field::{closing_paren, field_span, set_field};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The file which stores a key of the config of a component.
+#[must_use]
+pub fn path(component: &str, key: &str) -> PathBuf {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_default();
+
+    config_home
+        .join("cosmic")
+        .join(component)
+        .join("v1")
+        .join(key)
+}
+
+/// The value of a key of the config of a component, if it is set.
+#[must_use]
+pub fn read(component: &str, key: &str) -> Option<String> {
+    let value = fs::read_to_string(path(component, key)).ok()?;
+    Some(value.trim().to_owned())
+}
+
+/// Sets the value of a key of the config of a component.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn write(component: &str, key: &str, value: &str) -> Result<(), String> {
+    write_file(&path(component, key), value)
+}
+
+/// Writes a file of a config, creating the directory of the config if it is missing.
+///
+/// # Errors
+///
+/// Fails if the file could not be written.
+pub fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|why| why.to_string())?;
+    }
+
+    fs::write(path, contents).map_err(|why| why.to_string())
+}
+
+/// The value of a field of a key of the config of a component, if it is set.
+#[must_use]
+pub fn field(component: &str, key: &str, name: &str) -> Option<String> {
+    let contents = fs::read_to_string(path(component, key)).ok()?;
+    let span = field_span(&contents, name)?;
+    Some(contents[span].trim().to_owned())
+}
+
+/// Replaces or adds fields of a key of the config of a component, keeping its other fields.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_fields(component: &str, key: &str, fields: &[(&str, &str)]) -> Result<(), String> {
+    let path = path(component, key);
+    let mut contents = fs::read_to_string(&path).unwrap_or_else(|_| String::from("()"));
+
+    for (name, value) in fields {
+        contents = set_field(&contents, name, value);
+    }
+
+    write_file(&path, &contents)
+}
@@ -0,0 +1,32 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! How the desktop behaves, as configured in the compositor, which watches its config and
+//! applies changes at once.
+
+pub mod window;
+pub mod workspaces;
+
+use cosmic_settings_config as config;
+
+/// The config of the compositor, whose keys are each stored in a file of their own.
+const COMPOSITOR: &str = "com.system76.CosmicComp";
+
+/// The value of a key of the compositor config, if it is set.
+fn read(key: &str) -> Option<String> {
+    config::read(COMPOSITOR, key)
+}
+
+fn write(key: &str, value: &str) -> Result<(), String> {
+    config::write(COMPOSITOR, key, value)
+}
+
+/// The value of a field of a key of the compositor config, if it is set.
+fn field(key: &str, name: &str) -> Option<String> {
+    config::field(COMPOSITOR, key, name)
+}
+
+/// Replaces or adds fields of a key of the compositor config, keeping its other fields.
+fn set_fields(key: &str, fields: &[(&str, &str)]) -> Result<(), String> {
+    config::set_fields(COMPOSITOR, key, fields)
+}
@@ -0,0 +1,100 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! How windows are focused, and how new windows are placed.
+
+use std::ops::RangeInclusive;
+
+use crate::{read, write};
+
+/// Delays before a window under the pointer is raised, in milliseconds.
+pub const AUTO_RAISE_DELAY: RangeInclusive<u32> = 0..=1000;
+
+/// How new windows are placed on a workspace.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tiling {
+    /// Windows are placed where the application asks, and may overlap.
+    #[default]
+    Floating,
+    /// Windows are tiled beside those already on the workspace.
+    AutoTile,
+}
+
+impl Tiling {
+    pub const ALL: [Self; 2] = [Self::Floating, Self::AutoTile];
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowBehavior {
+    /// Whether windows are focused as the pointer moves over them.
+    pub focus_follows_mouse: bool,
+    /// How long the pointer rests on a window which it focused before the window is raised.
+    pub auto_raise_delay: u32,
+    pub tiling: Tiling,
+}
+
+impl Default for WindowBehavior {
+    fn default() -> Self {
+        Self {
+            focus_follows_mouse: false,
+            auto_raise_delay: 250,
+            tiling: Tiling::default(),
+        }
+    }
+}
+
+/// How windows behave, with the defaults of the compositor where it is not set.
+#[must_use]
+pub fn behavior() -> WindowBehavior {
+    let default = WindowBehavior::default();
+
+    let focus_follows_mouse =
+        read("focus_follows_cursor").map_or(default.focus_follows_mouse, |value| value == "true");
+
+    let auto_raise_delay = read("focus_follows_cursor_delay")
+        .and_then(|value| value.parse::<u32>().ok())
+        .filter(|delay| AUTO_RAISE_DELAY.contains(delay))
+        .unwrap_or(default.auto_raise_delay);
+
+    let tiling = match read("autotile").as_deref() {
+        Some("true") => Tiling::AutoTile,
+        Some(_) => Tiling::Floating,
+        None => default.tiling,
+    };
+
+    WindowBehavior {
+        focus_follows_mouse,
+        auto_raise_delay,
+        tiling,
+    }
+}
+
+/// Writes how windows behave. The compositor follows the focus at once, and tiles windows
+/// which are opened from then on, leaving windows which are already open as they are.
+///
+/// # Errors
+///
+/// Fails if the delay is out of range, or if the config could not be written.
+pub fn set_behavior(behavior: &WindowBehavior) -> Result<(), String> {
+    if !AUTO_RAISE_DELAY.contains(&behavior.auto_raise_delay) {
+        return Err(format!(
+            "an auto-raise delay of {} ms is out of range",
+            behavior.auto_raise_delay
+        ));
+    }
+
+    write(
+        "focus_follows_cursor",
+        &behavior.focus_follows_mouse.to_string(),
+    )?;
+
+    write(
+        "focus_follows_cursor_delay",
+        &behavior.auto_raise_delay.to_string(),
+    )?;
+
+    write(
+        "autotile",
+        &(behavior.tiling == Tiling::AutoTile).to_string(),
+    )
+}
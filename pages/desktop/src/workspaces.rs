@@ -0,0 +1,142 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! How workspaces are created, shared between displays, and laid out in the overview.
+
+use std::ops::RangeInclusive;
+
+use crate::{field, set_fields};
+
+/// The key of the compositor config which workspaces are configured by.
+const KEY: &str = "workspaces";
+
+/// Numbers of workspaces which a fixed number of workspaces may be set to.
+pub const COUNT: RangeInclusive<u8> = 1..=36;
+
+/// The number of workspaces which is offered when a fixed number is first chosen.
+pub const DEFAULT_COUNT: u8 = 4;
+
+/// How many workspaces there are.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WorkspaceAmount {
+    /// A workspace is added whenever the last one has a window, and empty ones are removed.
+    #[default]
+    Dynamic,
+    Fixed(u8),
+}
+
+/// Which displays have workspaces of their own.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WorkspaceDisplays {
+    /// Only the primary display switches between workspaces, while the windows of the others
+    /// stay shown.
+    PrimaryOnly,
+    /// Every display switches between workspaces.
+    #[default]
+    All,
+}
+
+impl WorkspaceDisplays {
+    pub const ALL: [Self; 2] = [Self::PrimaryOnly, Self::All];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::PrimaryOnly => "PrimaryOnly",
+            Self::All => "Global",
+        }
+    }
+}
+
+/// The direction which workspaces are arranged in, in the overview and when switching.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WorkspaceLayout {
+    Horizontal,
+    #[default]
+    Vertical,
+}
+
+impl WorkspaceLayout {
+    pub const ALL: [Self; 2] = [Self::Horizontal, Self::Vertical];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Horizontal => "Horizontal",
+            Self::Vertical => "Vertical",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WorkspaceConfig {
+    pub amount: WorkspaceAmount,
+    pub displays: WorkspaceDisplays,
+    pub layout: WorkspaceLayout,
+}
+
+/// The workspace config of the compositor, with its defaults where it is not set.
+#[must_use]
+pub fn config() -> WorkspaceConfig {
+    let default = WorkspaceConfig::default();
+
+    let amount = field(KEY, "workspace_amount")
+        .and_then(|value| {
+            if value == "Dynamic" {
+                return Some(WorkspaceAmount::Dynamic);
+            }
+
+            let count = value.strip_prefix("Static(")?.strip_suffix(')')?;
+            let count = count.trim().parse::<u8>().ok()?;
+            COUNT
+                .contains(&count)
+                .then_some(WorkspaceAmount::Fixed(count))
+        })
+        .unwrap_or(default.amount);
+
+    let displays = field(KEY, "workspace_mode")
+        .and_then(|value| {
+            WorkspaceDisplays::ALL
+                .into_iter()
+                .find(|d| d.as_str() == value)
+        })
+        .unwrap_or(default.displays);
+
+    let layout = field(KEY, "workspace_layout")
+        .and_then(|value| {
+            WorkspaceLayout::ALL
+                .into_iter()
+                .find(|l| l.as_str() == value)
+        })
+        .unwrap_or(default.layout);
+
+    WorkspaceConfig {
+        amount,
+        displays,
+        layout,
+    }
+}
+
+/// Writes the workspace config of the compositor, which rearranges the workspaces and the
+/// overview at once.
+///
+/// # Errors
+///
+/// Fails if a fixed number of workspaces is out of range, or if the config could not be
+/// written.
+pub fn set_config(config: &WorkspaceConfig) -> Result<(), String> {
+    let amount = match config.amount {
+        WorkspaceAmount::Dynamic => String::from("Dynamic"),
+        WorkspaceAmount::Fixed(count) if COUNT.contains(&count) => format!("Static({count})"),
+        WorkspaceAmount::Fixed(count) => {
+            return Err(format!("{count} workspaces are out of range"));
+        }
+    };
+
+    set_fields(
+        KEY,
+        &[
+            ("workspace_amount", &amount),
+            ("workspace_mode", config.displays.as_str()),
+            ("workspace_layout", config.layout.as_str()),
+        ],
+    )
+}
@@ -0,0 +1,190 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Placement of outputs in the compositor's layout, which must stay contiguous so that
+//! the pointer can reach every output.
+
+/// The logical area of an output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    #[must_use]
+    pub fn right(&self) -> i32 {
+        self.x + self.width
+    }
+
+    #[must_use]
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height
+    }
+
+    #[must_use]
+    pub fn overlaps(&self, other: &Rect) -> bool {
+        self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+
+    /// Whether the rects share a length of edge, which the pointer may cross.
+    #[must_use]
+    pub fn touches(&self, other: &Rect) -> bool {
+        let shares_column = self.x < other.right() && other.x < self.right();
+        let shares_row = self.y < other.bottom() && other.y < self.bottom();
+
+        ((self.right() == other.x || other.right() == self.x) && shares_row)
+            || ((self.bottom() == other.y || other.bottom() == self.y) && shares_column)
+    }
+
+    fn distance(&self, x: i32, y: i32) -> i64 {
+        let (dx, dy) = (i64::from(self.x - x), i64::from(self.y - y));
+        dx * dx + dy * dy
+    }
+}
+
+/// Whether no rects overlap, and every rect can be reached from every other.
+#[must_use]
+pub fn is_valid(rects: &[Rect]) -> bool {
+    for (index, rect) in rects.iter().enumerate() {
+        if rects[index + 1..].iter().any(|other| rect.overlaps(other)) {
+            return false;
+        }
+    }
+
+    if rects.is_empty() {
+        return true;
+    }
+
+    let mut reached = vec![false; rects.len()];
+    let mut pending = vec![0];
+    reached[0] = true;
+
+    while let Some(index) = pending.pop() {
+        for (other, rect) in rects.iter().enumerate() {
+            if !reached[other] && rects[index].touches(rect) {
+                reached[other] = true;
+                pending.push(other);
+            }
+        }
+    }
+
+    reached.into_iter().all(|reached| reached)
+}
+
+/// Moves a rect near `others` onto their edges, if it is within `threshold` of them.
+///
+/// If the rect would then overlap or float apart from the others, it is moved to the
+/// nearest position along their edges instead.
+#[must_use]
+pub fn snap(others: &[Rect], moving: Rect, threshold: i32) -> (i32, i32) {
+    if others.is_empty() {
+        return (moving.x, moving.y);
+    }
+
+    let nearest = |value: i32, candidates: &mut dyn Iterator<Item = i32>| {
+        candidates
+            .filter(|candidate| (candidate - value).abs() <= threshold)
+            .min_by_key(|candidate| (candidate - value).abs())
+            .unwrap_or(value)
+    };
+
+    let x = nearest(
+        moving.x,
+        &mut others.iter().flat_map(|other| {
+            [
+                other.x - moving.width,
+                other.right(),
+                other.x,
+                other.right() - moving.width,
+            ]
+        }),
+    );
+
+    let y = nearest(
+        moving.y,
+        &mut others.iter().flat_map(|other| {
+            [
+                other.y - moving.height,
+                other.bottom(),
+                other.y,
+                other.bottom() - moving.height,
+            ]
+        }),
+    );
+
+    let snapped = Rect { x, y, ..moving };
+
+    if fits(others, &snapped) {
+        return (x, y);
+    }
+
+    // Positions along each side of every other rect, sharing at least a pixel of edge.
+    let candidates = others.iter().flat_map(|other| {
+        let along_y = moving
+            .y
+            .clamp(other.y - moving.height + 1, other.bottom() - 1);
+        let along_x = moving
+            .x
+            .clamp(other.x - moving.width + 1, other.right() - 1);
+
+        [
+            (other.right(), along_y),
+            (other.x - moving.width, along_y),
+            (along_x, other.bottom()),
+            (along_x, other.y - moving.height),
+        ]
+    });
+
+    candidates
+        .filter(|&(x, y)| fits(others, &Rect { x, y, ..moving }))
+        .min_by_key(|&(x, y)| moving.distance(x, y))
+        .unwrap_or((moving.x, moving.y))
+}
+
+/// Resizes a rect, such as once its output is scaled, and moves the rects beyond its right
+/// and bottom edges by as much as it grew or shrank, so that they stay along its edges.
+pub fn resize(rects: &mut [Rect], index: usize, width: i32, height: i32) {
+    let Some(&resized) = rects.get(index) else {
+        return;
+    };
+
+    let (grown_x, grown_y) = (width - resized.width, height - resized.height);
+
+    for (other, rect) in rects.iter_mut().enumerate() {
+        if other == index {
+            rect.width = width;
+            rect.height = height;
+            continue;
+        }
+
+        if rect.x >= resized.right() {
+            rect.x += grown_x;
+        }
+
+        if rect.y >= resized.bottom() {
+            rect.y += grown_y;
+        }
+    }
+}
+
+fn fits(others: &[Rect], rect: &Rect) -> bool {
+    !others.iter().any(|other| other.overlaps(rect))
+        && others.iter().any(|other| other.touches(rect))
+}
+
+/// Shifts positions so that the top-left corner of the layout is at the origin.
+pub fn normalize(positions: &mut [(String, i32, i32)]) {
+    let min_x = positions.iter().map(|(_, x, _)| *x).min().unwrap_or(0);
+    let min_y = positions.iter().map(|(_, _, y)| *y).min().unwrap_or(0);
+
+    for (_, x, y) in positions {
+        *x -= min_x;
+        *y -= min_y;
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! ICC color profiles, which are assigned to outputs by colord, or by the config of the
+//! compositor where colord is not running.
+
+use cosmic_settings_config as config;
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// The directory which profiles are installed to for every user.
+const SYSTEM_DIR: &str = "/usr/share/color/icc";
+
+/// The config of the compositor.
+const COMPOSITOR: &str = "com.system76.CosmicComp";
+
+/// The key of the compositor config which maps the names of outputs to their profiles.
+const COMP_CONFIG: &str = "color_profiles";
+
+/// The class of profiles which describe displays.
+const DISPLAY_CLASS: &[u8; 4] = b"mntr";
+
+/// The size of the header of an ICC profile, which is followed by its table of tags.
+const HEADER_LEN: usize = 128;
+
+/// An installed ICC profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub path: PathBuf,
+    /// The description which the profile gives itself.
+    pub name: String,
+    /// Whether the profile was imported by the user, who may remove it.
+    pub imported: bool,
+}
+
+/// The directory which profiles are imported to.
+fn user_dir() -> PathBuf {
+    let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| home.join(".local/share"))
+        .join("icc")
+}
+
+/// The installed display profiles, by name. Files which are not display profiles are
+/// left out.
+#[must_use]
+pub fn profiles() -> Vec<Profile> {
+    let mut profiles = Vec::new();
+
+    for (dir, imported) in [(PathBuf::from(SYSTEM_DIR), false), (user_dir(), true)] {
+        let mut dirs = vec![dir];
+
+        // Packages install their profiles to directories of their own.
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+
+            for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+                if path.is_dir() {
+                    dirs.push(path);
+                    continue;
+                }
+
+                if !is_icc_file(&path) {
+                    continue;
+                }
+
+                let Some(name) = fs::read(&path).ok().and_then(|bytes| validate(&bytes).ok())
+                else {
+                    continue;
+                };
+
+                profiles.push(Profile {
+                    path,
+                    name,
+                    imported,
+                });
+            }
+        }
+    }
+
+    profiles.sort_by_key(|profile| profile.name.to_lowercase());
+    profiles
+}
+
+fn is_icc_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some(ext) if ext.eq_ignore_ascii_case("icc") || ext.eq_ignore_ascii_case("icm")
+    )
+}
+
+/// Checks that a file is an ICC profile of a display, returning its description.
+///
+/// # Errors
+///
+/// Fails if the file is not a well-formed ICC profile, or describes another class of device.
+pub fn validate(bytes: &[u8]) -> Result<String, String> {
+    let u32_at = |offset: usize| -> Option<u32> {
+        let bytes = bytes.get(offset..offset + 4)?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+
+    let corrupt = || String::from("the file is not a valid ICC profile");
+
+    // The header begins with the size of the profile, and holds its signature.
+    let size = u32_at(0).ok_or_else(corrupt)? as usize;
+    if bytes.len() < HEADER_LEN || size > bytes.len() || bytes.get(36..40) != Some(b"acsp") {
+        return Err(corrupt());
+    }
+
+    if bytes.get(12..16) != Some(DISPLAY_CLASS.as_slice()) {
+        return Err(String::from("the profile is not for a display"));
+    }
+
+    let tags = u32_at(HEADER_LEN).ok_or_else(corrupt)? as usize;
+
+    let description = (0..tags).find_map(|index| {
+        let entry = HEADER_LEN + 4 + index * 12;
+        let offset = u32_at(entry + 4)? as usize;
+        let len = u32_at(entry + 8)? as usize;
+
+        (bytes.get(entry..entry + 4)? == b"desc")
+            .then(|| bytes.get(offset..offset.checked_add(len)?))
+            .flatten()
+            .and_then(description)
+    });
+
+    // Every tag must lie within the profile.
+    let tags_valid = (0..tags).all(|index| {
+        let entry = HEADER_LEN + 4 + index * 12;
+        matches!(
+            (u32_at(entry + 4), u32_at(entry + 8)),
+            (Some(offset), Some(len)) if (offset as usize).saturating_add(len as usize) <= size
+        )
+    });
+
+    if !tags_valid {
+        return Err(corrupt());
+    }
+
+    Ok(description.unwrap_or_else(|| String::from("Unnamed profile")))
+}
+
+/// Reads a description tag, which is ASCII in version 2 profiles, and UTF-16 in the
+/// localized strings of version 4 profiles.
+fn description(tag: &[u8]) -> Option<String> {
+    let u32_at = |offset: usize| -> Option<u32> {
+        let bytes = tag.get(offset..offset + 4)?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+
+    let text = match tag.get(..4)? {
+        b"desc" => {
+            let len = u32_at(8)? as usize;
+            let ascii = tag.get(12..12 + len)?;
+            String::from_utf8_lossy(ascii)
+                .trim_end_matches('\0')
+                .to_owned()
+        }
+
+        // The first record of localized strings is the one which is shown.
+        b"mluc" => {
+            let len = u32_at(20)? as usize;
+            let offset = u32_at(24)? as usize;
+            let utf16: Vec<u16> = tag
+                .get(offset..offset + len)?
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+
+            String::from_utf16_lossy(&utf16)
+                .trim_end_matches('\0')
+                .to_owned()
+        }
+
+        _ => return None,
+    };
+
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_owned())
+}
+
+/// Copies a profile into the directory of imported profiles, once it is checked to be a
+/// display profile.
+///
+/// # Errors
+///
+/// Fails if the file could not be read or copied, or is not a display profile.
+pub fn import(path: &Path) -> Result<Profile, String> {
+    let bytes =
+        fs::read(path).map_err(|why| format!("failed to read {}: {why}", path.display()))?;
+    let name = validate(&bytes)?;
+
+    let dir = user_dir();
+    fs::create_dir_all(&dir).map_err(|why| why.to_string())?;
+
+    let stem = path.file_stem().map_or_else(
+        || String::from("profile"),
+        |stem| stem.to_string_lossy().into_owned(),
+    );
+
+    // A profile of the same file name is kept, rather than replaced.
+    let mut target = dir.join(format!("{stem}.icc"));
+    let mut copy = 1;
+    while target.exists() {
+        copy += 1;
+        target = dir.join(format!("{stem}-{copy}.icc"));
+    }
+
+    fs::write(&target, bytes).map_err(|why| why.to_string())?;
+
+    Ok(Profile {
+        path: target,
+        name,
+        imported: true,
+    })
+}
+
+/// Removes an imported profile, which is unassigned from outputs which use it.
+///
+/// # Errors
+///
+/// Fails if the profile was not imported, or could not be removed.
+pub fn remove(path: &Path) -> Result<(), String> {
+    if path.parent() != Some(user_dir().as_path()) {
+        return Err(String::from("only imported profiles can be removed"));
+    }
+
+    for (output, profile) in active() {
+        if profile == path {
+            assign(&output, None)?;
+        }
+    }
+
+    fs::remove_file(path).map_err(|why| why.to_string())
+}
+
+/// The profiles assigned to outputs, by the names of the outputs.
+#[must_use]
+pub fn active() -> HashMap<String, PathBuf> {
+    if let Some(devices) = colord::devices() {
+        return devices
+            .into_iter()
+            .filter_map(|(output, device)| Some((output, colord::default_profile(&device)?)))
+            .collect();
+    }
+
+    comp_config()
+}
+
+/// Assigns a profile to an output, or unassigns its profile, which shows it as sRGB.
+pub fn assign(output: &str, profile: Option<&Path>) -> Result<(), String> {
+    if let Some(devices) = colord::devices() {
+        if let Some(device) = devices.get(output) {
+            return colord::assign(device, profile);
+        }
+    }
+
+    let mut config = comp_config();
+
+    match profile {
+        Some(profile) => {
+            config.insert(output.to_owned(), profile.to_owned());
+        }
+        None => {
+            config.remove(output);
+        }
+    }
+
+    write_comp_config(&config)
+}
+
+fn comp_config_path() -> PathBuf {
+    config::path(COMPOSITOR, COMP_CONFIG)
+}
+
+/// The profiles in the config of the compositor, which is a map of quoted names to quoted
+/// paths, one on each line.
+fn comp_config() -> HashMap<String, PathBuf> {
+    let Ok(contents) = fs::read_to_string(comp_config_path()) else {
+        return HashMap::new();
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (output, path) = line.trim().trim_end_matches(',').split_once(':')?;
+            let unquote = |text: &str| {
+                let text = text.trim().strip_prefix('"')?.strip_suffix('"')?;
+                Some(text.replace("\\\"", "\"").replace("\\\\", "\\"))
+            };
+
+            Some((unquote(output)?, PathBuf::from(unquote(path)?)))
+        })
+        .collect()
+}
+
+fn write_comp_config(config: &HashMap<String, PathBuf>) -> Result<(), String> {
+    let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+
+    let mut entries: Vec<_> = config.iter().collect();
+    entries.sort();
+
+    let mut contents = String::from("{\n");
+    for (output, path) in entries {
+        contents.push_str(&format!(
+            "    {}: {},\n",
+            quote(output),
+            quote(&path.to_string_lossy())
+        ));
+    }
+    contents.push('}');
+
+    let path = comp_config_path();
+    config::write_file(&path, &contents)
+        .map_err(|why| format!("failed to write {}: {why}", path.display()))
+}
+
+/// Profiles assigned through `colormgr`, the command line client of colord.
+mod colord {
+    use super::{Command, HashMap, Path, PathBuf};
+
+    /// Removals of default profiles which are attempted before giving up on unassigning.
+    const MAX_PROFILES: usize = 16;
+
+    fn colormgr(args: &[&str]) -> Result<String, String> {
+        let output = Command::new("colormgr")
+            .args(args)
+            .output()
+            .map_err(|why| format!("failed to execute colormgr: {why}"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(String::from(stderr.trim()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// The values of a field in the output of `colormgr`, such as `Object Path`.
+    fn values<'a>(output: &'a str, field: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        output.lines().filter_map(move |line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == field).then(|| value.trim())
+        })
+    }
+
+    /// The object paths of display devices by the names of their outputs, if colord is
+    /// running.
+    pub(super) fn devices() -> Option<HashMap<String, String>> {
+        let output = colormgr(&["get-devices-by-kind", "display"]).ok()?;
+
+        let mut devices = HashMap::new();
+        let mut device = None;
+
+        for line in output.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            match key.trim() {
+                "Object Path" => device = Some(value.trim().to_owned()),
+
+                // Outputs are named in the metadata of their devices.
+                "Metadata" => {
+                    if let (Some(name), Some(device)) =
+                        (value.trim().strip_prefix("XRANDR_name="), &device)
+                    {
+                        devices.insert(name.to_owned(), device.clone());
+                    }
+                }
+
+                _ => (),
+            }
+        }
+
+        Some(devices)
+    }
+
+    pub(super) fn default_profile(device: &str) -> Option<PathBuf> {
+        let output = colormgr(&["device-get-default-profile", device]).ok()?;
+        let profile = values(&output, "Filename").next().map(PathBuf::from);
+        profile
+    }
+
+    fn default_profile_object(device: &str) -> Option<String> {
+        let output = colormgr(&["device-get-default-profile", device]).ok()?;
+        let object = values(&output, "Object Path").next().map(str::to_owned);
+        object
+    }
+
+    pub(super) fn assign(device: &str, profile: Option<&Path>) -> Result<(), String> {
+        let Some(profile) = profile else {
+            // Removing every profile of the device leaves it unmanaged.
+            for _ in 0..MAX_PROFILES {
+                let Some(object) = default_profile_object(device) else {
+                    return Ok(());
+                };
+
+                colormgr(&["device-remove-profile", device, &object])?;
+            }
+
+            return Ok(());
+        };
+
+        let filename = profile.to_string_lossy();
+
+        // Profiles in the directories which colord watches are known to it already.
+        let output = colormgr(&["find-profile-by-filename", &filename])
+            .or_else(|_| colormgr(&["import-profile", &filename]))?;
+
+        let object = values(&output, "Object Path")
+            .next()
+            .ok_or_else(|| format!("colord did not find {filename}"))?
+            .to_owned();
+
+        // The profile may be added to the device already, which is not an error.
+        let _res = colormgr(&["device-add-profile", device, &object]);
+        colormgr(&["device-make-profile-default", device, &object]).map(|_| ())
+    }
+}
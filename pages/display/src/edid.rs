@@ -0,0 +1,55 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! The capabilities which displays advertise in their EDID, as read from the kernel.
+
+use std::fs;
+
+/// The length of the base block and of each extension block.
+const BLOCK_LEN: usize = 128;
+
+/// The feature of the base block which tells that the display accepts a continuous range
+/// of timings, rather than only those which it lists.
+const CONTINUOUS_FREQUENCY: u8 = 1 << 0;
+
+/// The tag of the display descriptor which holds the range of rates of the display.
+const RANGE_LIMITS: u8 = 0xfd;
+
+/// The smallest range of vertical rates, in hertz, which is worth varying the refresh rate
+/// within, as graphics drivers require.
+const MIN_VRR_RANGE: u8 = 10;
+
+/// Whether the display of a connector, such as `DP-1`, accepts a range of refresh rates
+/// which is wide enough to vary within, as graphics drivers decide before enabling adaptive
+/// sync.
+pub fn supports_vrr(connector: &str) -> bool {
+    matches!(edid(connector), Some(edid) if advertises_vrr(&edid))
+}
+
+/// The EDID of a connector, which the kernel lists as `card0-DP-1`.
+fn edid(connector: &str) -> Option<Vec<u8>> {
+    let suffix = format!("-{connector}");
+
+    fs::read_dir("/sys/class/drm")
+        .ok()?
+        .filter_map(Result::ok)
+        .find(|entry| entry.file_name().to_string_lossy().ends_with(&suffix))
+        .map(|entry| entry.path().join("edid"))
+        .and_then(|path| fs::read(path).ok())
+        .filter(|edid| edid.len() >= BLOCK_LEN)
+}
+
+fn advertises_vrr(edid: &[u8]) -> bool {
+    if edid[0x18] & CONTINUOUS_FREQUENCY == 0 {
+        return false;
+    }
+
+    // The four descriptors of the base block follow the standard timings.
+    edid[0x36..0x7e].chunks_exact(18).any(|descriptor| {
+        let (min, max) = (descriptor[5], descriptor[6]);
+
+        descriptor[..3] == [0, 0, 0]
+            && descriptor[3] == RANGE_LIMITS
+            && max.saturating_sub(min) >= MIN_VRR_RANGE
+    })
+}
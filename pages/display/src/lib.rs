@@ -0,0 +1,450 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+pub mod arrangement;
+pub mod color_profile;
+mod edid;
+pub mod mirror;
+pub mod night_light;
+mod wlr_randr;
+
+use cosmic_settings_config as config;
+use std::{fmt, path::PathBuf, process::Command, str::FromStr};
+
+/// Configs of the panels which are placed on the primary output.
+const PANELS: [&str; 2] = [
+    "com.system76.CosmicPanel.Panel",
+    "com.system76.CosmicPanel.Dock",
+];
+
+/// A display mode supported by an output.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mode {
+    pub width: u32,
+    pub height: u32,
+    /// The refresh rate in hertz, exactly as advertised by the compositor.
+    pub refresh: f64,
+    pub preferred: bool,
+    pub current: bool,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} × {}", self.width, self.height)
+    }
+}
+
+/// Aspect ratios which resolutions are rounded to, as many are not exactly proportional,
+/// such as 1366 × 768 for 16:9.
+const ASPECT_RATIOS: [(u32, u32); 8] = [
+    (4, 3),
+    (5, 4),
+    (3, 2),
+    (16, 10),
+    (16, 9),
+    (21, 9),
+    (32, 9),
+    (1, 1),
+];
+
+/// The difference in hertz below which refresh rates are the same.
+const REFRESH_TOLERANCE: f64 = 0.001;
+
+impl Mode {
+    /// The aspect ratio of the resolution, such as `(16, 9)`.
+    #[must_use]
+    pub fn aspect_ratio(&self) -> (u32, u32) {
+        if self.width == 0 || self.height == 0 {
+            return (0, 0);
+        }
+
+        let ratio = f64::from(self.width) / f64::from(self.height);
+
+        let closest = ASPECT_RATIOS
+            .iter()
+            .copied()
+            .find(|&(width, height)| (ratio - f64::from(width) / f64::from(height)).abs() < 0.02);
+
+        closest.unwrap_or_else(|| {
+            let divisor = gcd(self.width, self.height);
+            (self.width / divisor, self.height / divisor)
+        })
+    }
+
+    /// Whether both modes have the same resolution, whatever their refresh rates.
+    #[must_use]
+    pub fn same_size(&self, other: &Mode) -> bool {
+        self.width == other.width && self.height == other.height
+    }
+
+    /// Whether both modes refresh at the same rate, within the rounding of the compositor,
+    /// which reports rates in millihertz.
+    #[must_use]
+    pub fn same_refresh(&self, other: &Mode) -> bool {
+        (self.refresh - other.refresh).abs() < REFRESH_TOLERANCE
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The rotation and reflection of an output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transform {
+    #[default]
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    Flipped,
+    Flipped90,
+    Flipped180,
+    Flipped270,
+}
+
+impl Transform {
+    /// The name of the transform as understood by the compositor.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Transform::Normal => "normal",
+            Transform::Rotate90 => "90",
+            Transform::Rotate180 => "180",
+            Transform::Rotate270 => "270",
+            Transform::Flipped => "flipped",
+            Transform::Flipped90 => "flipped-90",
+            Transform::Flipped180 => "flipped-180",
+            Transform::Flipped270 => "flipped-270",
+        }
+    }
+
+    /// Whether the width and height of the output are swapped.
+    #[must_use]
+    pub fn is_rotated(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Flipped90
+                | Transform::Flipped270
+        )
+    }
+}
+
+impl FromStr for Transform {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name {
+            "normal" => Transform::Normal,
+            "90" => Transform::Rotate90,
+            "180" => Transform::Rotate180,
+            "270" => Transform::Rotate270,
+            "flipped" => Transform::Flipped,
+            "flipped-90" => Transform::Flipped90,
+            "flipped-180" => Transform::Flipped180,
+            "flipped-270" => Transform::Flipped270,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Whether the refresh rate of an output varies with the content shown on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AdaptiveSync {
+    #[default]
+    Disabled,
+    Enabled,
+}
+
+impl AdaptiveSync {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::Enabled => "enabled",
+        }
+    }
+}
+
+impl FromStr for AdaptiveSync {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(match value {
+            "disabled" => Self::Disabled,
+            "enabled" => Self::Enabled,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// A display connected to the compositor.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Output {
+    /// The connector name, such as `DP-1`, which identifies the output.
+    pub name: String,
+    pub description: String,
+    pub make: String,
+    pub model: String,
+    pub enabled: bool,
+    pub modes: Vec<Mode>,
+    pub position: (i32, i32),
+    pub transform: Transform,
+    pub scale: f64,
+    /// Whether the refresh rate varies with the content, if the output reports it.
+    pub adaptive_sync: Option<AdaptiveSync>,
+    /// Whether the EDID of the display advertises a range of refresh rates to vary within.
+    pub vrr_capable: bool,
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.make.is_empty(), self.model.is_empty()) {
+            (false, false) => write!(f, "{} {}", self.make, self.model),
+            (true, false) => f.write_str(&self.model),
+            _ => f.write_str(&self.name),
+        }
+    }
+}
+
+impl Output {
+    #[must_use]
+    pub fn current_mode(&self) -> Option<&Mode> {
+        self.modes.iter().find(|mode| mode.current)
+    }
+
+    /// Whether the output is the built-in display of a laptop or tablet.
+    #[must_use]
+    pub fn is_internal(&self) -> bool {
+        ["eDP", "LVDS", "DSI"]
+            .iter()
+            .any(|prefix| self.name.starts_with(prefix))
+    }
+
+    /// The size of the output in the compositor's layout, after scaling and rotation.
+    #[must_use]
+    pub fn logical_size(&self) -> (u32, u32) {
+        self.logical_size_at(self.scale)
+    }
+
+    /// The size which the output would have in the compositor's layout at another scale.
+    #[must_use]
+    pub fn logical_size_at(&self, scale: f64) -> (u32, u32) {
+        let Some(mode) = self.current_mode() else {
+            return (0, 0);
+        };
+
+        let scale = if scale > 0.0 { scale } else { 1.0 };
+        let width = (f64::from(mode.width) / scale).round() as u32;
+        let height = (f64::from(mode.height) / scale).round() as u32;
+
+        if self.transform.is_rotated() {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+}
+
+/// Scales offered for outputs, as multiples of their physical size.
+pub const SCALES: [f64; 5] = [1.0, 1.25, 1.5, 1.75, 2.0];
+
+/// The smallest logical size which an output may be scaled down to.
+const MIN_LOGICAL_SIZE: (u32, u32) = (800, 480);
+
+impl Output {
+    /// The scales which the compositor supports for the current mode of the output.
+    ///
+    /// Scales which would shrink the output below a usable logical size are excluded.
+    #[must_use]
+    pub fn supported_scales(&self) -> Vec<f64> {
+        let Some(mode) = self.current_mode() else {
+            return vec![1.0];
+        };
+
+        SCALES
+            .iter()
+            .copied()
+            .filter(|&scale| {
+                scale <= 1.0
+                    || (f64::from(mode.width) / scale >= f64::from(MIN_LOGICAL_SIZE.0)
+                        && f64::from(mode.height) / scale >= f64::from(MIN_LOGICAL_SIZE.1))
+            })
+            .collect()
+    }
+}
+
+/// Whether a scale is not a whole number, at which some applications may be blurry.
+#[must_use]
+pub fn is_fractional(scale: f64) -> bool {
+    scale.fract().abs() > f64::EPSILON
+}
+
+/// The outputs of the compositor, including those which are disabled.
+///
+/// # Errors
+///
+/// Fails if the outputs could not be queried from the compositor.
+pub fn outputs() -> Result<Vec<Output>, String> {
+    let mut outputs = wlr_randr::run(&[]).map(|output| wlr_randr::parse(&output))?;
+
+    for output in &mut outputs {
+        output.vrr_capable = edid::supports_vrr(&output.name);
+    }
+
+    Ok(outputs)
+}
+
+/// Sets whether the refresh rate of an output varies, and checks that the compositor did so.
+pub fn set_adaptive_sync(name: &str, adaptive_sync: AdaptiveSync) -> Result<(), String> {
+    let value = adaptive_sync.as_str();
+    wlr_randr::run(&["--output", name, "--adaptive-sync", value])?;
+
+    let applied = outputs()?
+        .into_iter()
+        .find(|output| output.name == name)
+        .and_then(|output| output.adaptive_sync);
+
+    if applied == Some(adaptive_sync) {
+        Ok(())
+    } else {
+        Err(format!("{name} did not switch adaptive sync to {value}"))
+    }
+}
+
+/// Moves outputs to new logical positions, all at once.
+pub fn set_positions(positions: &[(String, i32, i32)]) -> Result<(), String> {
+    let args: Vec<String> = positions
+        .iter()
+        .flat_map(|(name, x, y)| {
+            [
+                String::from("--output"),
+                name.clone(),
+                String::from("--pos"),
+                format!("{x},{y}"),
+            ]
+        })
+        .collect();
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    wlr_randr::run(&args).map(|_| ())
+}
+
+/// The output which the panel and dock are placed on, if one was chosen.
+#[must_use]
+pub fn primary() -> Option<String> {
+    let contents = std::fs::read_to_string(panel_output(PANELS[0])).ok()?;
+
+    // Stored as `Name("DP-1")`, or `All` when panels are placed on every output.
+    let name = contents
+        .trim()
+        .strip_prefix("Name(\"")?
+        .strip_suffix("\")")?;
+    Some(name.to_owned())
+}
+
+/// Places the panel and dock on an output, which becomes the primary output.
+pub fn set_primary(name: &str) -> Result<(), String> {
+    for panel in PANELS {
+        config::write(panel, "output", &format!("Name(\"{name}\")"))?;
+    }
+
+    Ok(())
+}
+
+fn panel_output(panel: &str) -> PathBuf {
+    config::path(panel, "output")
+}
+
+/// Scales an output, and moves the outputs to new positions in the same change.
+pub fn set_scale(
+    output: &Output,
+    scale: f64,
+    positions: &[(String, i32, i32)],
+) -> Result<(), String> {
+    if !output
+        .supported_scales()
+        .iter()
+        .any(|&supported| (supported - scale).abs() < f64::EPSILON)
+    {
+        return Err(format!(
+            "{} does not support a scale of {scale}",
+            output.name
+        ));
+    }
+
+    let mut args = vec![
+        String::from("--output"),
+        output.name.clone(),
+        String::from("--scale"),
+        scale.to_string(),
+    ];
+
+    for (name, x, y) in positions {
+        args.extend([
+            String::from("--output"),
+            name.clone(),
+            String::from("--pos"),
+            format!("{x},{y}"),
+        ]);
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    wlr_randr::run(&args).map(|_| ())
+}
+
+/// Rotates or reflects an output, and moves the outputs to new positions in the same change.
+pub fn set_transform(
+    name: &str,
+    transform: Transform,
+    positions: &[(String, i32, i32)],
+) -> Result<(), String> {
+    let mut args = vec![
+        String::from("--output"),
+        name.to_owned(),
+        String::from("--transform"),
+        transform.as_str().to_owned(),
+    ];
+
+    for (name, x, y) in positions {
+        args.extend([
+            String::from("--output"),
+            name.clone(),
+            String::from("--pos"),
+            format!("{x},{y}"),
+        ]);
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    wlr_randr::run(&args).map(|_| ())
+}
+
+/// Changes the mode of an output, and checks that the compositor is using it.
+pub fn set_mode(name: &str, mode: &Mode) -> Result<(), String> {
+    let mode_arg = format!("{}x{}@{}Hz", mode.width, mode.height, mode.refresh);
+    wlr_randr::run(&["--output", name, "--mode", &mode_arg])?;
+
+    let current = outputs()?
+        .into_iter()
+        .find(|output| output.name == name)
+        .and_then(|output| output.current_mode().cloned());
+
+    match current {
+        Some(current) if current.same_size(mode) && current.same_refresh(mode) => Ok(()),
+        _ => Err(format!("{name} did not switch to {mode}")),
+    }
+}
+
+/// A command which prints a line whenever a display is connected, disconnected, or changed.
+#[must_use]
+pub fn hotplug_command() -> Command {
+    let mut command = Command::new("udevadm");
+    command.args(["monitor", "--udev", "--subsystem-match=drm"]);
+    command
+}
@@ -0,0 +1,191 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Mirroring, which shows the same content on every output by giving them all the same
+//! logical size at the same position.
+
+use crate::{wlr_randr, Output, Transform};
+
+/// Where an output was in an extended layout, which is restored once mirroring stops.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placement {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    /// The refresh rate in hertz, exactly as advertised by the compositor.
+    pub refresh: f64,
+    pub scale: f64,
+    pub transform: Transform,
+    pub position: (i32, i32),
+}
+
+impl Placement {
+    /// The placement of an output, if it is enabled with a mode.
+    #[must_use]
+    pub fn of(output: &Output) -> Option<Self> {
+        let mode = output.current_mode().filter(|_| output.enabled)?;
+
+        Some(Self {
+            name: output.name.clone(),
+            width: mode.width,
+            height: mode.height,
+            refresh: mode.refresh,
+            scale: output.scale,
+            transform: output.transform,
+            position: output.position,
+        })
+    }
+}
+
+/// The sizes of the modes which every enabled output supports, from the largest.
+#[must_use]
+pub fn common_sizes(outputs: &[Output]) -> Vec<(u32, u32)> {
+    let mut enabled = outputs.iter().filter(|output| output.enabled);
+
+    let Some(first) = enabled.next() else {
+        return Vec::new();
+    };
+
+    let others: Vec<&Output> = enabled.collect();
+
+    let mut sizes: Vec<(u32, u32)> = first
+        .modes
+        .iter()
+        .map(|mode| (mode.width, mode.height))
+        .filter(|&(width, height)| {
+            others.iter().all(|output| {
+                output
+                    .modes
+                    .iter()
+                    .any(|mode| mode.width == width && mode.height == height)
+            })
+        })
+        .collect();
+
+    sizes.sort_by_key(|&(width, height)| std::cmp::Reverse(u64::from(width) * u64::from(height)));
+    sizes.dedup();
+    sizes
+}
+
+/// Whether every enabled output shows the same content, as there are several of them in
+/// the same place at the same logical size.
+#[must_use]
+pub fn is_mirrored(outputs: &[Output]) -> bool {
+    let enabled: Vec<&Output> = outputs.iter().filter(|output| output.enabled).collect();
+
+    enabled.len() > 1
+        && enabled.windows(2).all(|pair| {
+            pair[0].position == pair[1].position && pair[0].logical_size() == pair[1].logical_size()
+        })
+}
+
+/// Mirrors every enabled output at a size which they all support, at its highest refresh rate.
+pub fn mirror(outputs: &[Output], (width, height): (u32, u32)) -> Result<(), String> {
+    let enabled: Vec<&Output> = outputs.iter().filter(|output| output.enabled).collect();
+
+    let scale = enabled
+        .iter()
+        .map(|output| output.scale)
+        .fold(f64::INFINITY, f64::min);
+    let scale = if scale.is_finite() { scale } else { 1.0 };
+
+    let mut args = Vec::new();
+
+    for output in enabled {
+        let refresh = output
+            .modes
+            .iter()
+            .filter(|mode| mode.width == width && mode.height == height)
+            .map(|mode| mode.refresh)
+            .max_by(f64::total_cmp)
+            .ok_or_else(|| format!("{} has no mode of {width} × {height}", output.name))?;
+
+        args.extend([
+            String::from("--output"),
+            output.name.clone(),
+            String::from("--mode"),
+            format!("{width}x{height}@{refresh}Hz"),
+            String::from("--scale"),
+            scale.to_string(),
+            String::from("--transform"),
+            Transform::Normal.as_str().to_owned(),
+            String::from("--pos"),
+            String::from("0,0"),
+        ]);
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    wlr_randr::run(&args).map(|_| ())
+}
+
+/// Extends the outputs again, restoring the placements which they had. Outputs which have
+/// no placement, such as those connected since, are placed to the right of the others.
+///
+/// # Errors
+///
+/// Fails if the compositor rejected the layout.
+pub fn restore(outputs: &[Output], placements: &[Placement]) -> Result<(), String> {
+    let mut args = Vec::new();
+    let mut right = 0;
+
+    let enabled = outputs.iter().filter(|output| output.enabled);
+
+    let (placed, unplaced): (Vec<&Output>, Vec<&Output>) = enabled.partition(|output| {
+        placements
+            .iter()
+            .any(|placement| placement.name == output.name)
+    });
+
+    for output in placed {
+        let Some(placement) = placements.iter().find(|p| p.name == output.name) else {
+            continue;
+        };
+
+        let (x, y) = placement.position;
+        let scale = if placement.scale > 0.0 {
+            placement.scale
+        } else {
+            1.0
+        };
+
+        let width = if placement.transform.is_rotated() {
+            placement.height
+        } else {
+            placement.width
+        };
+
+        right = right.max(x + (f64::from(width) / scale).round() as i32);
+
+        args.extend([
+            String::from("--output"),
+            placement.name.clone(),
+            String::from("--mode"),
+            format!(
+                "{}x{}@{}Hz",
+                placement.width, placement.height, placement.refresh
+            ),
+            String::from("--scale"),
+            scale.to_string(),
+            String::from("--transform"),
+            placement.transform.as_str().to_owned(),
+            String::from("--pos"),
+            format!("{x},{y}"),
+        ]);
+    }
+
+    for output in unplaced {
+        let (width, _) = output.logical_size();
+
+        args.extend([
+            String::from("--output"),
+            output.name.clone(),
+            String::from("--pos"),
+            format!("{right},0"),
+        ]);
+
+        right += width as i32;
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    wlr_randr::run(&args).map(|_| ())
+}
@@ -0,0 +1,111 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Warmer colors at night, which are applied to every output through the gamma control of
+//! the compositor.
+
+use std::{
+    f64::consts::PI,
+    ops::RangeInclusive,
+    process::{Child, Command, Stdio},
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+
+/// Color temperatures which night light offers, in kelvin, from the warmest.
+pub const TEMPERATURE: RangeInclusive<u32> = 2500..=6000;
+
+/// The gamma of the compositor is reset once the client which set it exits, so the client
+/// is kept running for as long as night light is on.
+static GAMMA: Mutex<Option<Child>> = Mutex::new(None);
+
+/// Minutes in a day, which times of the schedule are counted in from midnight.
+pub const DAY_MINUTES: u32 = 24 * 60;
+
+/// Whether night light is on at a time, from a start time which may be later than its end
+/// time when the schedule crosses midnight. All times are in minutes from midnight.
+#[must_use]
+pub fn is_active(now: u32, start: u32, end: u32) -> bool {
+    match start.cmp(&end) {
+        std::cmp::Ordering::Less => start <= now && now < end,
+        std::cmp::Ordering::Greater => now >= start || now < end,
+        // A schedule which ends as it starts is on all day.
+        std::cmp::Ordering::Equal => true,
+    }
+}
+
+/// The times of sunrise and sunset on a day of the year, from 1 to 366, at a location. Times
+/// are in minutes from midnight UTC, and may be before or after that day.
+///
+/// There is no sunrise or sunset on days of polar night or midnight sun.
+#[must_use]
+pub fn sun_times(day_of_year: u32, latitude: f64, longitude: f64) -> Option<(f64, f64)> {
+    // The fractional year, in radians, at noon.
+    let year = 2.0 * PI / 365.0 * f64::from(day_of_year.saturating_sub(1));
+
+    // How far solar time is ahead of clock time, in minutes.
+    let equation_of_time = 229.18
+        * (0.000_075 + 0.001_868 * year.cos()
+            - 0.032_077 * year.sin()
+            - 0.014_615 * (2.0 * year).cos()
+            - 0.040_849 * (2.0 * year).sin());
+
+    let declination = 0.006_918 - 0.399_912 * year.cos() + 0.070_257 * year.sin()
+        - 0.006_758 * (2.0 * year).cos()
+        + 0.000_907 * (2.0 * year).sin()
+        - 0.002_697 * (3.0 * year).cos()
+        + 0.001_48 * (3.0 * year).sin();
+
+    // The sun rises and sets once its center is just below the horizon, due to refraction.
+    let latitude = latitude.to_radians();
+    let cos_hour_angle = 90.833_f64.to_radians().cos() / (latitude.cos() * declination.cos())
+        - latitude.tan() * declination.tan();
+
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+
+    let hour_angle = cos_hour_angle.acos().to_degrees();
+    let sunrise = 720.0 - 4.0 * (longitude + hour_angle) - equation_of_time;
+    let sunset = 720.0 - 4.0 * (longitude - hour_angle) - equation_of_time;
+
+    Some((sunrise, sunset))
+}
+
+/// Tints every output to a color temperature in kelvin, or removes the tint.
+pub fn set_temperature(temperature: Option<u32>) -> Result<(), String> {
+    let mut gamma = GAMMA.lock().map_err(|why| why.to_string())?;
+
+    if let Some(mut child) = gamma.take() {
+        let _res = child.kill();
+        let _res = child.wait();
+    }
+
+    let Some(temperature) = temperature else {
+        return Ok(());
+    };
+
+    if !TEMPERATURE.contains(&temperature) {
+        return Err(format!("a temperature of {temperature} K is out of range"));
+    }
+
+    let mut child = Command::new("gammastep")
+        .args(["-m", "wayland", "-P", "-O", &temperature.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|why| format!("failed to execute gammastep: {why}"))?;
+
+    // A compositor without gamma control is only reported by gammastep exiting at once.
+    thread::sleep(Duration::from_millis(100));
+
+    if let Ok(Some(_)) = child.try_wait() {
+        let output = child.wait_with_output().map_err(|why| why.to_string())?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    *gamma = Some(child);
+    Ok(())
+}
@@ -0,0 +1,155 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{Mode, Output};
+use std::process::Command;
+
+/// Runs `wlr-randr` and returns its standard output.
+pub fn run(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("wlr-randr")
+        .env("LC_ALL", "C")
+        .args(args)
+        .output()
+        .map_err(|why| format!("failed to execute wlr-randr: {why}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| String::from("wlr-randr output is not UTF-8"))
+}
+
+/// Parses the outputs listed by `wlr-randr`.
+///
+/// Each output starts with an unindented `NAME "description"` line, followed by indented
+/// `Key: value` lines, with the modes indented further beneath `Modes:`.
+pub fn parse(output: &str) -> Vec<Output> {
+    let mut outputs: Vec<Output> = Vec::new();
+
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if !line.starts_with(' ') {
+            let (name, description) = line.split_once(' ').unwrap_or((line, ""));
+
+            outputs.push(Output {
+                name: name.to_owned(),
+                description: description.trim().trim_matches('"').to_owned(),
+                scale: 1.0,
+                ..Output::default()
+            });
+
+            continue;
+        }
+
+        let Some(output) = outputs.last_mut() else {
+            continue;
+        };
+
+        let line = line.trim();
+
+        if let Some(mode) = parse_mode(line) {
+            output.modes.push(mode);
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let value = value.trim();
+
+        match key {
+            "Make" => output.make = value.to_owned(),
+            "Model" => output.model = value.to_owned(),
+            "Enabled" => output.enabled = value == "yes",
+            "Position" => {
+                if let Some((x, y)) = value.split_once(',') {
+                    output.position = (x.parse().unwrap_or(0), y.parse().unwrap_or(0));
+                }
+            }
+            "Transform" => output.transform = value.parse().unwrap_or_default(),
+            "Scale" => output.scale = value.parse().unwrap_or(1.0),
+            "Adaptive Sync" => output.adaptive_sync = value.parse().ok(),
+            _ => (),
+        }
+    }
+
+    outputs
+}
+
+/// Parses a mode such as `1920x1080 px, 59.940000 Hz (preferred, current)`.
+fn parse_mode(line: &str) -> Option<Mode> {
+    let (size, rest) = line.split_once(" px, ")?;
+    let (width, height) = size.split_once('x')?;
+    let (refresh, flags) = rest.split_once(" Hz").unwrap_or((rest, ""));
+
+    Some(Mode {
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+        refresh: refresh.trim().parse().ok()?,
+        preferred: flags.contains("preferred"),
+        current: flags.contains("current"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdaptiveSync, Transform};
+
+    const LISTING: &str = r#"eDP-1 "Sharp Corporation 0x14FA (eDP-1)"
+  Make: Sharp Corporation
+  Model: 0x14FA
+  Enabled: yes
+  Modes:
+    2560x1440 px, 165.003006 Hz (preferred, current)
+    2560x1440 px, 60.000999 Hz
+  Position: 0,0
+  Transform: 90
+  Scale: 1.500000
+  Adaptive Sync: enabled
+HDMI-A-1 ""
+  Enabled: no
+"#;
+
+    #[test]
+    fn parses_every_key() {
+        let outputs = parse(LISTING);
+        let output = &outputs[0];
+
+        assert_eq!(output.name, "eDP-1");
+        assert_eq!(output.description, "Sharp Corporation 0x14FA (eDP-1)");
+        assert_eq!(output.make, "Sharp Corporation");
+        assert_eq!(output.model, "0x14FA");
+        assert!(output.enabled);
+        assert_eq!(output.modes.len(), 2);
+        assert!(output.modes[0].preferred && output.modes[0].current);
+        assert_eq!(output.position, (0, 0));
+        assert_eq!(output.transform, Transform::Rotate90);
+        assert!((output.scale - 1.5).abs() < f64::EPSILON);
+        assert_eq!(output.adaptive_sync, Some(AdaptiveSync::Enabled));
+    }
+
+    #[test]
+    fn missing_keys_are_left_at_their_defaults() {
+        let outputs = parse(LISTING);
+        assert_eq!(outputs.len(), 2);
+
+        let output = &outputs[1];
+
+        assert_eq!(output.name, "HDMI-A-1");
+        assert!(output.description.is_empty());
+        assert!(output.make.is_empty() && output.model.is_empty());
+        assert!(!output.enabled);
+        assert!(output.modes.is_empty());
+        assert_eq!(output.current_mode(), None);
+        assert_eq!(output.position, (0, 0));
+        assert_eq!(output.transform, Transform::Normal);
+        assert!((output.scale - 1.0).abs() < f64::EPSILON);
+        assert_eq!(output.adaptive_sync, None);
+    }
+}
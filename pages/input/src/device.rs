@@ -0,0 +1,93 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! The config of single devices, which the compositor keeps in a map by their names.
+
+use std::{fs, ops::Range};
+
+use cosmic_settings_config::{closing_paren, field_span, set_field, write_file};
+
+use crate::config_path;
+
+/// The key of the compositor config which maps the names of devices to their own config.
+const CONFIG_KEY: &str = "input_devices";
+
+/// The value of a field of the config of a device, if it has one.
+pub(crate) fn field(name: &str, field: &str) -> Option<String> {
+    let contents = fs::read_to_string(config_path(CONFIG_KEY)).ok()?;
+    let entry = &contents[entry_span(&contents, name)?];
+
+    field_span(entry, field).map(|span| String::from(entry[span].trim()))
+}
+
+/// Replaces or adds fields of the config of a device, keeping the config of other devices.
+pub(crate) fn set_fields(name: &str, fields: &[(&str, &str)]) -> Result<(), String> {
+    let path = config_path(CONFIG_KEY);
+    let contents = fs::read_to_string(&path).unwrap_or_else(|_| String::from("{}"));
+
+    let contents = match entry_span(&contents, name) {
+        Some(span) => {
+            let mut entry = String::from(&contents[span.clone()]);
+
+            for (field, value) in fields {
+                entry = set_field(&entry, field, value);
+            }
+
+            format!(
+                "{}{entry}{}",
+                &contents[..span.start],
+                &contents[span.end..]
+            )
+        }
+
+        None => {
+            let mut entry = String::from("()");
+
+            for (field, value) in fields {
+                entry = set_field(&entry, field, value);
+            }
+
+            // The device is added to the end of the map.
+            let close = contents.rfind('}').unwrap_or(contents.len());
+            let devices = contents[..close].trim_end();
+            let devices = if devices.is_empty() { "{" } else { devices };
+
+            let separator = if devices.ends_with(['{', ',']) {
+                ""
+            } else {
+                ","
+            };
+
+            format!("{devices}{separator}\n    {}: {entry},\n}}", quote(name))
+        }
+    };
+
+    write_file(&path, &contents)
+}
+
+/// A string as it is written in RON, in quotes and with its quotes escaped.
+pub(crate) fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The string of a `Some("...")` value, as written by [`quote`].
+pub(crate) fn unquote(value: &str) -> Option<String> {
+    let text = value.strip_prefix("Some(\"")?.strip_suffix("\")")?;
+    Some(text.replace("\\\"", "\"").replace("\\\\", "\\"))
+}
+
+/// Finds the config of a device in the map of every device, from its opening parenthesis
+/// to the one closing it.
+fn entry_span(contents: &str, name: &str) -> Option<Range<usize>> {
+    let key = quote(name);
+    let start = contents.find(&key)? + key.len();
+
+    let value = contents[start..]
+        .trim_start()
+        .strip_prefix(':')?
+        .trim_start();
+    let open = contents.len() - value.len();
+    let close = value.starts_with('(').then(|| closing_paren(value))??;
+
+    Some(open..open + close + 1)
+}
@@ -0,0 +1,494 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+pub mod tablet;
+
+mod device;
+
+use cosmic_settings_config::{self as config, field_span, set_field, write_file};
+use std::{fs, ops::RangeInclusive, path::PathBuf};
+
+/// The config of the compositor, whose keys are each stored in a file of their own.
+const COMPOSITOR: &str = "com.system76.CosmicComp";
+
+/// Delays before a held key begins to repeat, in milliseconds.
+///
+/// Shorter delays would repeat keys which were only meant to be pressed once.
+pub const REPEAT_DELAY: RangeInclusive<u32> = 200..=1000;
+
+/// Rates at which a held key repeats, in characters per second.
+pub const REPEAT_RATE: RangeInclusive<u32> = 5..=60;
+
+/// How a held key is repeated by the compositor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Repeat {
+    /// The delay before the key begins to repeat, in milliseconds.
+    pub delay: u32,
+    /// The rate at which the key repeats, in characters per second.
+    pub rate: u32,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Self {
+            delay: 600,
+            rate: 25,
+        }
+    }
+}
+
+impl Repeat {
+    /// Limits the delay and rate to the ranges which the settings offer.
+    #[must_use]
+    pub fn clamp(self) -> Self {
+        Self {
+            delay: self.delay.clamp(*REPEAT_DELAY.start(), *REPEAT_DELAY.end()),
+            rate: self.rate.clamp(*REPEAT_RATE.start(), *REPEAT_RATE.end()),
+        }
+    }
+}
+
+/// The key repeat of the keyboard, with the defaults of the compositor where it is not set.
+#[must_use]
+pub fn repeat() -> Repeat {
+    let default = Repeat::default();
+
+    let Ok(contents) = fs::read_to_string(config_path("xkb_config")) else {
+        return default;
+    };
+
+    let number = |name| {
+        field_span(&contents, name).and_then(|span| contents[span].trim().parse::<u32>().ok())
+    };
+
+    Repeat {
+        delay: number("repeat_delay").unwrap_or(default.delay),
+        rate: number("repeat_rate").unwrap_or(default.rate),
+    }
+    .clamp()
+}
+
+/// Sets the key repeat of the keyboard, keeping the rest of its config, such as its layout.
+///
+/// # Errors
+///
+/// Fails if the delay or rate is out of range, or if the config could not be written.
+pub fn set_repeat(repeat: Repeat) -> Result<(), String> {
+    if !REPEAT_DELAY.contains(&repeat.delay) {
+        return Err(format!(
+            "a repeat delay of {} ms is out of range",
+            repeat.delay
+        ));
+    }
+
+    if !REPEAT_RATE.contains(&repeat.rate) {
+        return Err(format!(
+            "a repeat rate of {} per second is out of range",
+            repeat.rate
+        ));
+    }
+
+    set_fields(
+        "xkb_config",
+        &[
+            ("repeat_delay", &repeat.delay.to_string()),
+            ("repeat_rate", &repeat.rate.to_string()),
+        ],
+    )
+}
+
+/// Whether Num Lock is turned on when logging in.
+#[must_use]
+pub fn numlock_on_login() -> bool {
+    let Ok(contents) = fs::read_to_string(config_path("keyboard_config")) else {
+        return false;
+    };
+
+    let state = field_span(&contents, "numlock_state").map(|span| contents[span].trim());
+    matches!(state, Some("BootOn"))
+}
+
+/// Turns Num Lock on or off when logging in, which takes effect the next time the user logs in.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_numlock_on_login(enabled: bool) -> Result<(), String> {
+    let state = if enabled { "BootOn" } else { "BootOff" };
+    set_fields("keyboard_config", &[("numlock_state", state)])
+}
+
+/// A keyboard accessibility feature, for those who have difficulty pressing keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyboardA11y {
+    /// Modifiers stay pressed once they are released, until the next key is pressed, or
+    /// until the delay passes.
+    StickyKeys,
+    /// Keys are only accepted once they are held for the delay.
+    SlowKeys,
+    /// Presses of a key which follow the last within the delay are ignored.
+    BounceKeys,
+}
+
+impl KeyboardA11y {
+    pub const ALL: [Self; 3] = [Self::StickyKeys, Self::SlowKeys, Self::BounceKeys];
+
+    /// The delays which the feature offers, in milliseconds.
+    #[must_use]
+    pub fn delays(self) -> RangeInclusive<u32> {
+        match self {
+            Self::StickyKeys => 1000..=10000,
+            Self::SlowKeys | Self::BounceKeys => 100..=1000,
+        }
+    }
+
+    fn default_delay(self) -> u32 {
+        match self {
+            Self::StickyKeys => 5000,
+            Self::SlowKeys | Self::BounceKeys => 300,
+        }
+    }
+
+    /// The fields of the compositor config which enable the feature and set its delay.
+    fn fields(self) -> (&'static str, &'static str) {
+        match self {
+            Self::StickyKeys => ("sticky_keys", "sticky_keys_timeout"),
+            Self::SlowKeys => ("slow_keys", "slow_keys_delay"),
+            Self::BounceKeys => ("bounce_keys", "bounce_keys_delay"),
+        }
+    }
+}
+
+/// Whether a keyboard accessibility feature is enabled, with its delay in milliseconds,
+/// which is kept while it is disabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct A11yFeature {
+    pub enabled: bool,
+    pub delay: u32,
+}
+
+/// Whether a keyboard accessibility feature is enabled, and its delay, with the defaults of
+/// the compositor where they are not set.
+#[must_use]
+pub fn keyboard_a11y(feature: KeyboardA11y) -> A11yFeature {
+    let (enabled, delay) = feature.fields();
+    let contents = fs::read_to_string(config_path("keyboard_a11y")).unwrap_or_default();
+    let field = |name| field_span(&contents, name).map(|span| contents[span].trim());
+
+    let range = feature.delays();
+
+    A11yFeature {
+        enabled: matches!(field(enabled), Some("true")),
+        delay: field(delay)
+            .and_then(|delay| delay.parse::<u32>().ok())
+            .unwrap_or_else(|| feature.default_delay())
+            .clamp(*range.start(), *range.end()),
+    }
+}
+
+/// Enables or disables a keyboard accessibility feature, and sets its delay.
+///
+/// # Errors
+///
+/// Fails if the delay is out of range, or if the config could not be written.
+pub fn set_keyboard_a11y(feature: KeyboardA11y, state: A11yFeature) -> Result<(), String> {
+    if !feature.delays().contains(&state.delay) {
+        return Err(format!("a delay of {} ms is out of range", state.delay));
+    }
+
+    let (enabled, delay) = feature.fields();
+
+    set_fields(
+        "keyboard_a11y",
+        &[
+            (enabled, if state.enabled { "true" } else { "false" }),
+            (delay, &state.delay.to_string()),
+        ],
+    )
+}
+
+/// Whether pressing Shift five times in a row turns sticky keys on or off.
+#[must_use]
+pub fn sticky_keys_shortcut() -> bool {
+    let Ok(contents) = fs::read_to_string(config_path("keyboard_a11y")) else {
+        return true;
+    };
+
+    !matches!(
+        field_span(&contents, "sticky_keys_shortcut").map(|span| contents[span].trim()),
+        Some("false")
+    )
+}
+
+/// Sets whether pressing Shift five times in a row turns sticky keys on or off.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_sticky_keys_shortcut(enabled: bool) -> Result<(), String> {
+    let value = if enabled { "true" } else { "false" };
+    set_fields("keyboard_a11y", &[("sticky_keys_shortcut", value)])
+}
+
+/// A kind of pointing device, which the compositor configures separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DeviceClass {
+    Mouse,
+    Touchpad,
+}
+
+impl DeviceClass {
+    pub const ALL: [Self; 2] = [Self::Mouse, Self::Touchpad];
+
+    /// The key of the compositor config which configures the devices of this class.
+    fn config_key(self) -> &'static str {
+        match self {
+            Self::Mouse => "input_default",
+            Self::Touchpad => "input_touchpad",
+        }
+    }
+}
+
+/// The classes of pointing devices which are connected, in the order of [`DeviceClass::ALL`].
+///
+/// Touchpads are told apart from mice by reporting fingers, and from tablets by reporting no
+/// pens. Touchscreens, which report no relative motion, are not mice.
+#[must_use]
+pub fn device_classes() -> Vec<DeviceClass> {
+    const REL_X: usize = 0;
+    const BTN_LEFT: usize = 272;
+    const BTN_TOOL_PEN: usize = 320;
+    const BTN_TOOL_FINGER: usize = 325;
+
+    let Ok(devices) = fs::read_to_string("/proc/bus/input/devices") else {
+        return Vec::new();
+    };
+
+    let mut classes = Vec::new();
+
+    // Each device is described by a block of lines, and blocks are separated by blank lines.
+    for device in devices.split("\n\n") {
+        let value = |prefix: &str| {
+            device
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix))
+                .unwrap_or("")
+        };
+
+        let pointer = value("H: Handlers=")
+            .split_whitespace()
+            .any(|h| h.starts_with("mouse"));
+
+        if !pointer {
+            continue;
+        }
+
+        let keys = value("B: KEY=");
+
+        let class = if has_key(keys, BTN_TOOL_FINGER) && !has_key(keys, BTN_TOOL_PEN) {
+            DeviceClass::Touchpad
+        } else if has_key(keys, BTN_LEFT) && has_key(value("B: REL="), REL_X) {
+            DeviceClass::Mouse
+        } else {
+            continue;
+        };
+
+        if !classes.contains(&class) {
+            classes.push(class);
+        }
+    }
+
+    classes.sort_by_key(|class| DeviceClass::ALL.iter().position(|other| other == class));
+    classes
+}
+
+/// Whether scrolling moves the content rather than the view, as on a touchscreen, for the
+/// devices of a class. Touchpads scroll naturally unless they are set not to.
+#[must_use]
+pub fn natural_scroll(class: DeviceClass) -> bool {
+    let default = class == DeviceClass::Touchpad;
+
+    let Ok(contents) = fs::read_to_string(config_path(class.config_key())) else {
+        return default;
+    };
+
+    match field_span(&contents, "natural_scroll").map(|span| contents[span].trim()) {
+        Some("Some(true)" | "true") => true,
+        Some("Some(false)" | "false") => false,
+        _ => default,
+    }
+}
+
+/// Sets whether the devices of a class scroll naturally, keeping the rest of their config.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_natural_scroll(class: DeviceClass, natural: bool) -> Result<(), String> {
+    let path = config_path(class.config_key());
+    let contents = fs::read_to_string(&path).unwrap_or_else(|_| String::from("()"));
+    let value = format!("Some({natural})");
+
+    let contents = if field_span(&contents, "natural_scroll").is_some() {
+        set_field(&contents, "natural_scroll", &value)
+    } else if let Some(span) = field_span(&contents, "scroll_config")
+        .filter(|span| contents[span.clone()].trim().starts_with("Some(("))
+    {
+        // The field is added to the scroll config which the devices already have.
+        let inner = span.start + contents[span].find("((").unwrap_or(0) + 2;
+        let fields = contents[inner..].trim_start();
+        let separator = if fields.starts_with(')') { "" } else { ", " };
+
+        format!(
+            "{}natural_scroll: {value}{separator}{fields}",
+            &contents[..inner],
+        )
+    } else {
+        set_field(
+            &contents,
+            "scroll_config",
+            &format!("Some((natural_scroll: {value}))"),
+        )
+    };
+
+    write_file(&path, &contents)
+}
+
+/// A clockwise rotation of the output which a touchscreen or tablet is mapped to, which
+/// its input is rotated by so that it still lines up with the output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rotation {
+    #[default]
+    Normal,
+    Right,
+    Inverted,
+    Left,
+}
+
+impl Rotation {
+    /// The libinput calibration matrix which rotates absolute input from the device.
+    fn calibration_matrix(self) -> &'static str {
+        match self {
+            Self::Normal => "None",
+            Self::Right => "Some((0.0, -1.0, 1.0, 1.0, 0.0, 0.0))",
+            Self::Inverted => "Some((-1.0, 0.0, 1.0, 0.0, -1.0, 1.0))",
+            Self::Left => "Some((0.0, 1.0, 0.0, -1.0, 0.0, 1.0))",
+        }
+    }
+}
+
+/// The names of the touchscreens which are connected. Touchscreens report direct input,
+/// which is on the surface of a display, and touch rather than a pen.
+#[must_use]
+pub fn touchscreens() -> Vec<String> {
+    const INPUT_PROP_DIRECT: usize = 1;
+    const BTN_TOUCH: usize = 330;
+    const BTN_TOOL_PEN: usize = 320;
+
+    let Ok(devices) = fs::read_to_string("/proc/bus/input/devices") else {
+        return Vec::new();
+    };
+
+    let mut touchscreens = Vec::new();
+
+    for device in devices.split("\n\n") {
+        let value = |prefix: &str| {
+            device
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix))
+                .unwrap_or("")
+        };
+
+        let keys = value("B: KEY=");
+
+        if !has_key(value("B: PROP="), INPUT_PROP_DIRECT)
+            || !has_key(keys, BTN_TOUCH)
+            || has_key(keys, BTN_TOOL_PEN)
+        {
+            continue;
+        }
+
+        let name = value("N: Name=").trim_matches('"');
+
+        if !name.is_empty() && !touchscreens.iter().any(|other| other == name) {
+            touchscreens.push(String::from(name));
+        }
+    }
+
+    touchscreens
+}
+
+/// Rotates the input of the touchscreens and tablets which are mapped to an output, once
+/// the output is rotated. Touchscreens which are mapped to no output are taken to be on the
+/// built-in display, so they are rotated along with an internal output.
+///
+/// # Errors
+///
+/// Fails if the config of a device could not be written.
+pub fn follow_rotation(output: &str, internal: bool, rotation: Rotation) -> Result<(), String> {
+    let mapped = |name: &str, unmapped: bool| match device::field(name, "map_to_output")
+        .and_then(|value| device::unquote(&value))
+    {
+        Some(mapped) => mapped == output,
+        None => unmapped,
+    };
+
+    let devices = touchscreens()
+        .into_iter()
+        .filter(|name| mapped(name, internal))
+        .chain(
+            tablet::tablets()
+                .into_iter()
+                .filter(|name| mapped(name, false)),
+        );
+
+    for name in devices {
+        device::set_fields(
+            &name,
+            &[("calibration_matrix", rotation.calibration_matrix())],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Whether any keyboard has a numeric keypad, as reported by the kernel.
+///
+/// Keyboards which report the keypad enter and zero keys are taken to have a keypad.
+#[must_use]
+pub fn has_keypad() -> bool {
+    const KEY_KP0: usize = 82;
+    const KEY_KPENTER: usize = 96;
+
+    let Ok(devices) = fs::read_to_string("/proc/bus/input/devices") else {
+        return false;
+    };
+
+    devices
+        .lines()
+        .filter_map(|line| line.strip_prefix("B: KEY="))
+        .any(|keys| has_key(keys, KEY_KP0) && has_key(keys, KEY_KPENTER))
+}
+
+/// Whether a key is set in a bitmap of keys, which is listed as hexadecimal words with the
+/// most significant word first.
+fn has_key(bitmap: &str, key: usize) -> bool {
+    let bits = usize::BITS as usize;
+
+    let word = bitmap
+        .split_whitespace()
+        .rev()
+        .nth(key / bits)
+        .and_then(|word| usize::from_str_radix(word, 16).ok());
+
+    matches!(word, Some(word) if word & (1 << (key % bits)) != 0)
+}
+
+/// Replaces or adds fields of a key of the compositor config, keeping its other fields.
+fn set_fields(key: &str, fields: &[(&str, &str)]) -> Result<(), String> {
+    config::set_fields(COMPOSITOR, key, fields)
+}
+
+fn config_path(key: &str) -> PathBuf {
+    config::path(COMPOSITOR, key)
+}
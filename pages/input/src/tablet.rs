@@ -0,0 +1,140 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Drawing tablets, which the compositor configures by the name of their device.
+
+use std::fs;
+
+use crate::{device, has_key};
+
+/// An action which a button of the stylus performs while it is pressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ButtonAction {
+    /// The action which the tablet reports for the button.
+    #[default]
+    Default,
+    MiddleClick,
+    RightClick,
+    Back,
+    Forward,
+    Disabled,
+}
+
+impl ButtonAction {
+    pub const ALL: [Self; 6] = [
+        Self::Default,
+        Self::MiddleClick,
+        Self::RightClick,
+        Self::Back,
+        Self::Forward,
+        Self::Disabled,
+    ];
+
+    fn ron(self) -> &'static str {
+        match self {
+            Self::Default => "None",
+            Self::MiddleClick => "Some(Middle)",
+            Self::RightClick => "Some(Right)",
+            Self::Back => "Some(Back)",
+            Self::Forward => "Some(Forward)",
+            Self::Disabled => "Some(Disabled)",
+        }
+    }
+
+    fn from_ron(value: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.ron() == value)
+            .unwrap_or_default()
+    }
+}
+
+/// How a tablet is mapped to the outputs, and what the buttons of its stylus do.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TabletConfig {
+    /// The output which the area of the tablet is mapped to, or every output if none.
+    pub output: Option<String>,
+    /// Whether the area is cropped to the aspect ratio of the output, so that drawings are
+    /// not stretched.
+    pub keep_aspect_ratio: bool,
+    /// The actions of the lower and upper buttons on the side of the stylus.
+    pub buttons: [ButtonAction; 2],
+}
+
+/// The names of the drawing tablets which are connected, which are devices that report a pen.
+///
+/// A tablet with touch or buttons of its own also lists those as devices, which are left out.
+#[must_use]
+pub fn tablets() -> Vec<String> {
+    const BTN_TOOL_PEN: usize = 320;
+
+    let Ok(devices) = fs::read_to_string("/proc/bus/input/devices") else {
+        return Vec::new();
+    };
+
+    let mut tablets = Vec::new();
+
+    for device in devices.split("\n\n") {
+        let value = |prefix: &str| {
+            device
+                .lines()
+                .find_map(|line| line.strip_prefix(prefix))
+                .unwrap_or("")
+        };
+
+        if !has_key(value("B: KEY="), BTN_TOOL_PEN) {
+            continue;
+        }
+
+        let name = value("N: Name=").trim_matches('"');
+
+        if !name.is_empty() && !tablets.iter().any(|tablet| tablet == name) {
+            tablets.push(String::from(name));
+        }
+    }
+
+    tablets
+}
+
+/// The config of a tablet, which is the default if it has none.
+#[must_use]
+pub fn config(name: &str) -> TabletConfig {
+    let field = |field: &str| device::field(name, field);
+
+    TabletConfig {
+        output: field("map_to_output").and_then(|value| device::unquote(&value)),
+        keep_aspect_ratio: matches!(field("keep_aspect_ratio").as_deref(), Some("true")),
+        buttons: [
+            ButtonAction::from_ron(field("stylus_button").as_deref().unwrap_or("None")),
+            ButtonAction::from_ron(field("stylus_button2").as_deref().unwrap_or("None")),
+        ],
+    }
+}
+
+/// Sets the config of a tablet, keeping the config of other devices.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_config(name: &str, config: &TabletConfig) -> Result<(), String> {
+    let output = match &config.output {
+        Some(output) => format!("Some({})", device::quote(output)),
+        None => String::from("None"),
+    };
+
+    let keep_aspect_ratio = if config.keep_aspect_ratio {
+        "true"
+    } else {
+        "false"
+    };
+
+    device::set_fields(
+        name,
+        &[
+            ("map_to_output", &output),
+            ("keep_aspect_ratio", keep_aspect_ratio),
+            ("stylus_button", config.buttons[0].ron()),
+            ("stylus_button2", config.buttons[1].ron()),
+        ],
+    )
+}
@@ -0,0 +1,259 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Sharing the connection of the system through its Wi-Fi device, as an access point.
+
+use crate::{fields, nmcli, wifi::Security};
+
+/// The name of the connection which the hotspot is saved as, which is also the name that
+/// `nmcli device wifi hotspot` gives it.
+pub const CONNECTION: &str = "Hotspot";
+
+/// SSIDs are at most 32 bytes long.
+const SSID_MAX_LEN: usize = 32;
+
+/// The frequency band which the hotspot broadcasts on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Band {
+    /// Chosen by the Wi-Fi device.
+    #[default]
+    Automatic,
+    /// 2.4 GHz, which reaches further and which every device supports.
+    Ghz2,
+    /// 5 GHz, which is faster but not supported by every device.
+    Ghz5,
+}
+
+impl Band {
+    pub const ALL: [Self; 3] = [Self::Automatic, Self::Ghz2, Self::Ghz5];
+
+    /// The value of the `802-11-wireless.band` property.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Automatic => "",
+            Self::Ghz2 => "bg",
+            Self::Ghz5 => "a",
+        }
+    }
+
+    fn from_str(value: &str) -> Self {
+        match value {
+            "bg" => Self::Ghz2,
+            "a" => Self::Ghz5,
+            _ => Self::Automatic,
+        }
+    }
+}
+
+/// The network which the hotspot broadcasts, which is secured with WPA2.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct HotspotConfig {
+    pub ssid: String,
+    pub password: String,
+    pub band: Band,
+}
+
+impl std::fmt::Debug for HotspotConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HotspotConfig")
+            .field("ssid", &self.ssid)
+            .field("band", &self.band)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The part of a hotspot config which is not valid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Invalid {
+    Ssid,
+    /// WPA passwords are 8 to 63 characters, or 64 hexadecimal digits.
+    Password,
+}
+
+impl HotspotConfig {
+    /// Checks the SSID and password of the config.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first part of the config which is invalid.
+    pub fn validate(&self) -> Result<(), Invalid> {
+        if self.ssid.trim().is_empty() || self.ssid.len() > SSID_MAX_LEN {
+            return Err(Invalid::Ssid);
+        }
+
+        if !Security::WpaPersonal.accepts(&self.password) {
+            return Err(Invalid::Password);
+        }
+
+        Ok(())
+    }
+
+    /// The text of a QR code which phones join the hotspot with, such as
+    /// `WIFI:T:WPA;S:name;P:password;;`.
+    #[must_use]
+    pub fn qr_payload(&self) -> String {
+        let escape = |value: &str| {
+            let mut escaped = String::with_capacity(value.len());
+
+            for char in value.chars() {
+                if matches!(char, '\\' | ';' | ',' | ':' | '"') {
+                    escaped.push('\\');
+                }
+
+                escaped.push(char);
+            }
+
+            escaped
+        };
+
+        format!(
+            "WIFI:T:WPA;S:{};P:{};;",
+            escape(&self.ssid),
+            escape(&self.password)
+        )
+    }
+}
+
+/// Whether the hotspot is broadcasting, and the network which it broadcasts.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Hotspot {
+    /// The saved config, if the hotspot was ever set up.
+    pub config: Option<HotspotConfig>,
+    pub active: bool,
+    /// The Wi-Fi network which the system is connected to as a client, which is disconnected
+    /// once the hotspot is enabled.
+    pub client: Option<String>,
+}
+
+/// Returns the state of the hotspot, with its password.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the connections could not be read.
+pub fn hotspot() -> Result<Hotspot, String> {
+    let stdout = nmcli(&["--fields", "NAME,TYPE,ACTIVE", "connection", "show"])?;
+
+    let mut hotspot = Hotspot::default();
+    let mut saved = false;
+
+    for line in stdout.lines() {
+        let fields = fields(line);
+        let [name, kind, active] = fields.as_slice() else {
+            continue;
+        };
+
+        if kind != "802-11-wireless" {
+            continue;
+        }
+
+        if name == CONNECTION {
+            saved = true;
+            hotspot.active = active == "yes";
+        } else if active == "yes" {
+            hotspot.client = Some(name.clone());
+        }
+    }
+
+    if saved {
+        let stdout = nmcli(&[
+            "--show-secrets",
+            "--get-values",
+            "802-11-wireless.ssid,802-11-wireless-security.psk,802-11-wireless.band",
+            "connection",
+            "show",
+            "id",
+            CONNECTION,
+        ])?;
+
+        let values: Vec<String> = stdout.lines().map(|line| fields(line).join(":")).collect();
+        let value = |id: usize| values.get(id).map_or("", String::as_str);
+
+        hotspot.config = Some(HotspotConfig {
+            ssid: value(0).to_owned(),
+            password: value(1).to_owned(),
+            band: Band::from_str(value(2).trim()),
+        });
+    }
+
+    Ok(hotspot)
+}
+
+/// Saves the config of the hotspot, creating its connection if it was never set up. A hotspot
+/// which is broadcasting is restarted with the new config.
+///
+/// # Errors
+///
+/// Fails if the config is invalid, or `nmcli` could not save or restart the connection.
+pub fn set_config(config: &HotspotConfig, restart: bool) -> Result<(), String> {
+    config
+        .validate()
+        .map_err(|why| format!("invalid hotspot config: {why:?}"))?;
+
+    let exists = nmcli(&["--fields", "NAME", "connection", "show"])?
+        .lines()
+        .any(|line| fields(line).join(":") == CONNECTION);
+
+    let mut args = if exists {
+        vec!["connection", "modify", "id", CONNECTION]
+    } else {
+        // The same settings as `nmcli device wifi hotspot`, which is never connected to
+        // by itself, so that the system stays a client of its usual networks.
+        vec![
+            "connection",
+            "add",
+            "type",
+            "wifi",
+            "ifname",
+            "*",
+            "con-name",
+            CONNECTION,
+            "connection.autoconnect",
+            "no",
+            "802-11-wireless.mode",
+            "ap",
+            "ipv4.method",
+            "shared",
+            "ipv6.method",
+            "shared",
+            "wifi-sec.key-mgmt",
+            "wpa-psk",
+            "wifi-sec.proto",
+            "rsn",
+            "wifi-sec.pairwise",
+            "ccmp",
+            "wifi-sec.group",
+            "ccmp",
+        ]
+    };
+
+    args.extend([
+        "802-11-wireless.ssid",
+        config.ssid.as_str(),
+        "802-11-wireless.band",
+        config.band.as_str(),
+        "wifi-sec.psk",
+        config.password.as_str(),
+    ]);
+
+    nmcli(&args)?;
+
+    if restart {
+        set_enabled(true)?;
+    }
+
+    Ok(())
+}
+
+/// Starts or stops broadcasting the saved hotspot. Starting it disconnects the Wi-Fi device
+/// from the network that it is a client of.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the connection could not be activated.
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    if enabled {
+        nmcli(&["--wait", "30", "connection", "up", "id", CONNECTION]).map(drop)
+    } else {
+        nmcli(&["connection", "down", "id", CONNECTION]).map(drop)
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! The IPv4 and IPv6 settings of `NetworkManager` connections.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::{fields, nmcli};
+
+/// A version of the Internet Protocol, which is configured separately for each connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Family {
+    V4,
+    V6,
+}
+
+impl Family {
+    pub const ALL: [Self; 2] = [Self::V4, Self::V6];
+
+    /// The name of the setting of the connection, such as `ipv4`.
+    fn setting(self) -> &'static str {
+        match self {
+            Self::V4 => "ipv4",
+            Self::V6 => "ipv6",
+        }
+    }
+
+    /// The longest prefix of an address, such as 32 for IPv4.
+    fn max_prefix(self) -> u8 {
+        match self {
+            Self::V4 => 32,
+            Self::V6 => 128,
+        }
+    }
+
+    fn parse(self, address: &str) -> Option<IpAddr> {
+        match self {
+            Self::V4 => address.parse::<Ipv4Addr>().ok().map(IpAddr::V4),
+            Self::V6 => address.parse::<Ipv6Addr>().ok().map(IpAddr::V6),
+        }
+    }
+}
+
+/// How a connection is given its addresses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Method {
+    /// Given by the network, with DHCP or router advertisements.
+    #[default]
+    Auto,
+    Manual,
+    LinkLocal,
+    /// No addresses of the family are used, which may only be chosen outside of this page.
+    Disabled,
+}
+
+impl Method {
+    /// The methods which may be chosen.
+    pub const ALL: [Self; 3] = [Self::Auto, Self::Manual, Self::LinkLocal];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Manual => "manual",
+            Self::LinkLocal => "link-local",
+            Self::Disabled => "disabled",
+        }
+    }
+
+    fn from_str(method: &str) -> Self {
+        match method {
+            "manual" => Self::Manual,
+            "link-local" => Self::LinkLocal,
+            "disabled" | "ignore" => Self::Disabled,
+            _ => Self::Auto,
+        }
+    }
+}
+
+/// The settings of one family of addresses of a connection.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IpConfig {
+    pub method: Method,
+    /// Addresses with their prefix length, such as `192.168.1.2/24`.
+    pub addresses: Vec<String>,
+    pub gateway: String,
+    pub dns: Vec<String>,
+}
+
+/// The part of a config which is not valid for its family.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Invalid {
+    /// A manual config has no addresses.
+    NoAddress,
+    Address(usize),
+    Gateway,
+    Dns(usize),
+}
+
+/// Whether an address is valid for a family, such as `192.168.1.1`.
+#[must_use]
+pub fn is_valid_address(family: Family, address: &str) -> bool {
+    family.parse(address.trim()).is_some()
+}
+
+/// Whether an address with its prefix length is valid for a family, such as `192.168.1.2/24`.
+#[must_use]
+pub fn is_valid_cidr(family: Family, cidr: &str) -> bool {
+    let Some((address, prefix)) = cidr.trim().split_once('/') else {
+        return false;
+    };
+
+    let prefix_valid =
+        matches!(prefix.parse::<u8>(), Ok(prefix) if prefix > 0 && prefix <= family.max_prefix());
+
+    prefix_valid && is_valid_address(family, address)
+}
+
+/// The prefix length of a netmask, which is either a length such as `24`, or for IPv4 a
+/// mask such as `255.255.255.0`.
+#[must_use]
+pub fn prefix_len(family: Family, netmask: &str) -> Option<u8> {
+    let netmask = netmask.trim();
+
+    if let Ok(prefix) = netmask.parse::<u8>() {
+        return (prefix > 0 && prefix <= family.max_prefix()).then_some(prefix);
+    }
+
+    if family != Family::V4 {
+        return None;
+    }
+
+    // A mask is only valid if its set bits are all leading ones.
+    let mask = u32::from(netmask.parse::<Ipv4Addr>().ok()?);
+    let prefix = mask.leading_ones();
+    (prefix > 0 && mask.checked_shl(prefix).unwrap_or(0) == 0).then_some(prefix as u8)
+}
+
+/// The netmask of an IPv4 prefix length, such as `255.255.255.0` for 24.
+#[must_use]
+pub fn netmask(prefix: u8) -> Ipv4Addr {
+    let prefix = u32::from(prefix.min(32));
+    Ipv4Addr::from(u32::MAX.checked_shl(32 - prefix).unwrap_or(0))
+}
+
+impl IpConfig {
+    /// Checks the addresses of a manual config, which are ignored by other methods.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first part of the config which is invalid.
+    pub fn validate(&self, family: Family) -> Result<(), Invalid> {
+        if self.method != Method::Manual {
+            return Ok(());
+        }
+
+        if self.addresses.is_empty() {
+            return Err(Invalid::NoAddress);
+        }
+
+        if let Some(id) = self
+            .addresses
+            .iter()
+            .position(|address| !is_valid_cidr(family, address))
+        {
+            return Err(Invalid::Address(id));
+        }
+
+        if !self.gateway.is_empty() && !is_valid_address(family, &self.gateway) {
+            return Err(Invalid::Gateway);
+        }
+
+        match self
+            .dns
+            .iter()
+            .position(|server| !is_valid_address(family, server))
+        {
+            Some(id) => Err(Invalid::Dns(id)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Splits a list of values printed by `nmcli`, such as `1.1.1.1,8.8.8.8`.
+fn list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Returns the IPv4 and IPv6 settings of a saved connection.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the connection could not be read.
+pub fn config(uuid: &str) -> Result<(IpConfig, IpConfig), String> {
+    let mut configs = Vec::with_capacity(2);
+
+    for family in Family::ALL {
+        let setting = family.setting();
+        let properties = ["method", "addresses", "gateway", "dns"]
+            .map(|property| format!("{setting}.{property}"))
+            .join(",");
+
+        let stdout = nmcli(&[
+            "--get-values",
+            &properties,
+            "connection",
+            "show",
+            "uuid",
+            uuid,
+        ])?;
+
+        // Each value is printed on its own line, with the colons of IPv6 addresses escaped.
+        let values: Vec<String> = stdout.lines().map(|line| fields(line).join(":")).collect();
+        let value = |id: usize| values.get(id).map_or("", String::as_str);
+
+        configs.push(IpConfig {
+            method: Method::from_str(value(0)),
+            addresses: list(value(1)),
+            gateway: value(2).trim().to_owned(),
+            dns: list(value(3)),
+        });
+    }
+
+    let ipv6 = configs.pop().unwrap_or_default();
+    let ipv4 = configs.pop().unwrap_or_default();
+    Ok((ipv4, ipv6))
+}
+
+/// Replaces the IPv4 and IPv6 settings of a saved connection at once, so that the connection
+/// is never left with only some of them changed. The settings are applied to the device
+/// which the connection is active on, if any.
+///
+/// # Errors
+///
+/// Fails if either config is invalid, or `nmcli` could not modify the connection.
+pub fn set_config(
+    uuid: &str,
+    device: Option<&str>,
+    ipv4: &IpConfig,
+    ipv6: &IpConfig,
+) -> Result<(), String> {
+    let mut args: Vec<String> = ["connection", "modify", "uuid", uuid]
+        .map(String::from)
+        .to_vec();
+
+    for (family, config) in [(Family::V4, ipv4), (Family::V6, ipv6)] {
+        config
+            .validate(family)
+            .map_err(|why| format!("invalid {} config: {why:?}", family.setting()))?;
+
+        let setting = family.setting();
+        let manual = config.method == Method::Manual;
+
+        // Addresses are cleared by other methods, as link-local addressing refuses them.
+        let (addresses, gateway) = if manual {
+            (config.addresses.join(","), config.gateway.clone())
+        } else {
+            (String::new(), String::new())
+        };
+
+        args.extend([
+            format!("{setting}.method"),
+            String::from(config.method.as_str()),
+            format!("{setting}.addresses"),
+            addresses,
+            format!("{setting}.gateway"),
+            gateway,
+            format!("{setting}.dns"),
+            config.dns.join(","),
+        ]);
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    nmcli(&args)?;
+
+    if let Some(device) = device {
+        nmcli(&["device", "reapply", device])?;
+    }
+
+    Ok(())
+}
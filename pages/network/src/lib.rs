@@ -0,0 +1,48 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+pub mod hotspot;
+pub mod ip;
+pub mod rfkill;
+pub mod wifi;
+pub mod wired;
+
+use std::process::Command;
+
+/// Runs `nmcli` in terse mode and returns its standard output.
+pub(crate) fn nmcli(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("nmcli")
+        .arg("--terse")
+        .args(args)
+        .output()
+        .map_err(|why| format!("failed to execute nmcli: {why}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| String::from("nmcli output is not UTF-8"))
+}
+
+/// Splits a line of terse `nmcli` output into its fields, unescaping `\:` and `\\`.
+pub(crate) fn fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    field.push(escaped);
+                }
+            }
+            ':' => fields.push(std::mem::take(&mut field)),
+            _ => field.push(char),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
@@ -0,0 +1,116 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Radios which may be blocked with `rfkill`, such as for airplane mode.
+
+use std::process::Command;
+
+/// A kind of radio, which every device of that kind is blocked or unblocked with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Radio {
+    Wlan,
+    Bluetooth,
+    Wwan,
+}
+
+impl Radio {
+    pub const ALL: [Self; 3] = [Self::Wlan, Self::Bluetooth, Self::Wwan];
+
+    /// The name of the kind of radio, as known to `rfkill`.
+    #[must_use]
+    pub fn kind(self) -> &'static str {
+        match self {
+            Self::Wlan => "wlan",
+            Self::Bluetooth => "bluetooth",
+            Self::Wwan => "wwan",
+        }
+    }
+
+    #[must_use]
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|radio| radio.kind() == kind)
+    }
+}
+
+/// Whether the devices of a kind of radio are blocked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RadioState {
+    pub radio: Radio,
+    /// Blocked in software, which may be changed with [`set_blocked`].
+    pub soft_blocked: bool,
+    /// Blocked by a hardware switch, which only the switch may change.
+    pub hard_blocked: bool,
+}
+
+impl RadioState {
+    #[must_use]
+    pub fn blocked(&self) -> bool {
+        self.soft_blocked || self.hard_blocked
+    }
+}
+
+/// The kinds of radio present, in the order of [`Radio::ALL`].
+///
+/// A kind is blocked when every one of its devices is.
+pub fn radios() -> Result<Vec<RadioState>, String> {
+    let output = rfkill(&["--noheadings", "--raw", "--output", "TYPE,SOFT,HARD"])?;
+    let mut radios: Vec<RadioState> = Vec::new();
+
+    for line in output.lines() {
+        let mut fields = line.split_whitespace();
+
+        let (Some(kind), Some(soft), Some(hard)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        let Some(radio) = Radio::from_kind(kind) else {
+            continue;
+        };
+
+        let (soft_blocked, hard_blocked) = (soft == "blocked", hard == "blocked");
+
+        match radios.iter_mut().find(|state| state.radio == radio) {
+            Some(state) => {
+                state.soft_blocked &= soft_blocked;
+                state.hard_blocked &= hard_blocked;
+            }
+            None => radios.push(RadioState {
+                radio,
+                soft_blocked,
+                hard_blocked,
+            }),
+        }
+    }
+
+    radios.sort_by_key(|state| Radio::ALL.iter().position(|radio| *radio == state.radio));
+    Ok(radios)
+}
+
+/// Blocks or unblocks every device of a kind of radio.
+pub fn set_blocked(radio: Radio, blocked: bool) -> Result<(), String> {
+    let action = if blocked { "block" } else { "unblock" };
+    rfkill(&[action, radio.kind()]).map(drop)
+}
+
+/// A command which prints a line whenever a radio is added, removed, blocked, or unblocked.
+#[must_use]
+pub fn events_command() -> Command {
+    let mut command = Command::new("rfkill");
+    command.arg("event");
+    command
+}
+
+fn rfkill(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("rfkill")
+        .args(args)
+        .output()
+        .map_err(|why| format!("failed to execute rfkill: {why}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| String::from("rfkill output is not UTF-8"))
+}
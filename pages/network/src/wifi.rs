@@ -0,0 +1,362 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{fields, hotspot, nmcli};
+
+#[derive(Clone, Debug, Default)]
+pub struct AccessPoint {
+    pub ssid: String,
+    pub signal: u8,
+    pub security: String,
+    pub active: bool,
+}
+
+/// Requests a rescan from `NetworkManager` and returns the visible access points,
+/// strongest signal first.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the scan could not be performed.
+pub fn scan() -> Result<Vec<AccessPoint>, String> {
+    let stdout = nmcli(&[
+        "--fields",
+        "IN-USE,SSID,SIGNAL,SECURITY",
+        "device",
+        "wifi",
+        "list",
+        "--rescan",
+        "yes",
+    ])?;
+
+    let mut access_points: Vec<AccessPoint> = Vec::new();
+
+    for line in stdout.lines() {
+        let fields = fields(line);
+        let [in_use, ssid, signal, security] = fields.as_slice() else {
+            continue;
+        };
+
+        // Hidden networks have no SSID to display.
+        if ssid.is_empty() {
+            continue;
+        }
+
+        let access_point = AccessPoint {
+            ssid: ssid.clone(),
+            signal: signal.parse().unwrap_or(0),
+            security: security.clone(),
+            active: in_use == "*",
+        };
+
+        // The same SSID is reported once per BSSID, so only keep the strongest.
+        match access_points
+            .iter_mut()
+            .find(|ap| ap.ssid == access_point.ssid)
+        {
+            Some(existing) => {
+                existing.active |= access_point.active;
+                if access_point.signal > existing.signal {
+                    existing.signal = access_point.signal;
+                }
+            }
+            None => access_points.push(access_point),
+        }
+    }
+
+    access_points.sort_by_key(|ap| std::cmp::Reverse(ap.signal));
+
+    Ok(access_points)
+}
+
+/// The security of a network, which decides the passwords that it accepts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Security {
+    None,
+    Wep,
+    #[default]
+    WpaPersonal,
+    Wpa3Personal,
+}
+
+impl Security {
+    pub const ALL: [Self; 4] = [Self::None, Self::Wep, Self::WpaPersonal, Self::Wpa3Personal];
+
+    /// Whether a password has a length and characters which networks of this security accept.
+    #[must_use]
+    pub fn accepts(self, password: &str) -> bool {
+        let is_hex = |len| password.len() == len && password.chars().all(|c| c.is_ascii_hexdigit());
+
+        match self {
+            Self::None => true,
+            Self::Wep => {
+                matches!(password.len(), 5 | 13) && password.is_ascii() || is_hex(10) || is_hex(26)
+            }
+            Self::WpaPersonal | Self::Wpa3Personal => {
+                (8..=63).contains(&password.len()) && password.is_ascii() || is_hex(64)
+            }
+        }
+    }
+
+    fn key_mgmt(self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Wep => Some("none"),
+            Self::WpaPersonal => Some("wpa-psk"),
+            Self::Wpa3Personal => Some("sae"),
+        }
+    }
+}
+
+/// A network which does not broadcast its SSID, and so is connected to by name.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct HiddenNetwork {
+    pub ssid: String,
+    pub security: Security,
+    pub password: String,
+}
+
+impl std::fmt::Debug for HiddenNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HiddenNetwork")
+            .field("ssid", &self.ssid)
+            .field("security", &self.security)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectError {
+    /// The network rejected the password.
+    Authentication,
+    Failed(String),
+}
+
+/// Creates a connection to a hidden network, and connects to it.
+///
+/// The connection is deleted again if it could not be activated, so that it is not retried
+/// with a password that was rejected.
+///
+/// # Errors
+///
+/// Returns whether the password was rejected, or else the error reported by `nmcli`.
+pub fn connect_hidden(network: &HiddenNetwork) -> Result<(), ConnectError> {
+    let ssid = network.ssid.as_str();
+
+    let mut args = vec![
+        "connection",
+        "add",
+        "type",
+        "wifi",
+        "con-name",
+        ssid,
+        "ifname",
+        "*",
+        "ssid",
+        ssid,
+        "802-11-wireless.hidden",
+        "yes",
+    ];
+
+    if let Some(key_mgmt) = network.security.key_mgmt() {
+        args.extend(["wifi-sec.key-mgmt", key_mgmt]);
+
+        if network.security == Security::Wep {
+            args.extend(["wifi-sec.wep-key-type", "1", "wifi-sec.wep-key0"]);
+        } else {
+            args.push("wifi-sec.psk");
+        }
+
+        args.push(network.password.as_str());
+    }
+
+    let stdout = nmcli(&args).map_err(ConnectError::Failed)?;
+
+    // Reported as "Connection 'name' (uuid) successfully added."
+    let uuid = stdout
+        .rsplit_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(uuid, _)| uuid.to_owned())
+        .ok_or_else(|| ConnectError::Failed(String::from(stdout.trim())))?;
+
+    if let Err(why) = nmcli(&["--wait", "30", "connection", "up", "uuid", &uuid]) {
+        let _ = nmcli(&["connection", "delete", "uuid", &uuid]);
+
+        return Err(if why.to_lowercase().contains("secrets were required") {
+            ConnectError::Authentication
+        } else {
+            ConnectError::Failed(why)
+        });
+    }
+
+    Ok(())
+}
+
+/// Whether a connection is metered, so that the system limits the data it uses in the
+/// background.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Metered {
+    /// Left for `NetworkManager` to detect, such as from the hotspot that it connects to.
+    #[default]
+    Automatic,
+    Yes,
+    No,
+}
+
+impl Metered {
+    pub const ALL: [Self; 3] = [Self::Automatic, Self::Yes, Self::No];
+
+    /// The value of the `connection.metered` property.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Automatic => "unknown",
+            Self::Yes => "yes",
+            Self::No => "no",
+        }
+    }
+
+    fn from_str(value: &str) -> Self {
+        match value {
+            "yes" => Self::Yes,
+            "no" => Self::No,
+            _ => Self::Automatic,
+        }
+    }
+}
+
+/// A Wi-Fi connection which `NetworkManager` has saved.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KnownNetwork {
+    pub uuid: String,
+    pub name: String,
+    pub auto_connect: bool,
+    /// Whether the network is connected to.
+    pub active: bool,
+    /// The device which the network is connected through, if it is connected to.
+    pub device: Option<String>,
+    /// When the network was last connected to, in seconds since the Unix epoch, or 0 if never.
+    pub last_used: u64,
+    pub metered: Metered,
+    /// Whether `NetworkManager` found the network to be metered, once connected to it.
+    pub detected_metered: Option<bool>,
+}
+
+/// Returns the saved Wi-Fi connections, most recently used first, and then by name. The
+/// hotspot is not a network which is connected to, so it is left out.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the connections could not be listed.
+pub fn known() -> Result<Vec<KnownNetwork>, String> {
+    let stdout = nmcli(&[
+        "--fields",
+        "NAME,UUID,TYPE,TIMESTAMP,AUTOCONNECT,ACTIVE,DEVICE",
+        "connection",
+        "show",
+    ])?;
+
+    let mut networks: Vec<KnownNetwork> = stdout
+        .lines()
+        .filter_map(|line| {
+            let fields = fields(line);
+            let [name, uuid, kind, timestamp, auto_connect, active, device] = fields.as_slice()
+            else {
+                return None;
+            };
+
+            (kind == "802-11-wireless" && name != hotspot::CONNECTION).then(|| KnownNetwork {
+                uuid: uuid.clone(),
+                name: name.clone(),
+                auto_connect: auto_connect == "yes",
+                active: active == "yes",
+                device: Some(device.clone()).filter(|device| !device.is_empty() && device != "--"),
+                last_used: timestamp.parse().unwrap_or(0),
+                ..KnownNetwork::default()
+            })
+        })
+        .collect();
+
+    for network in &mut networks {
+        let metered = nmcli(&[
+            "--get-values",
+            "connection.metered",
+            "connection",
+            "show",
+            "uuid",
+            &network.uuid,
+        ]);
+        network.metered = Metered::from_str(metered.unwrap_or_default().trim());
+
+        // Reported as "yes (guessed)" or "no (guessed)" when it was detected.
+        if let Some(device) = network.device.as_deref() {
+            let detected = nmcli(&["--get-values", "GENERAL.METERED", "device", "show", device]);
+            network.detected_metered = match detected.unwrap_or_default().split_whitespace().next()
+            {
+                Some("yes") => Some(true),
+                Some("no") => Some(false),
+                _ => None,
+            };
+        }
+    }
+
+    networks.sort_by(|a, b| {
+        b.last_used
+            .cmp(&a.last_used)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    Ok(networks)
+}
+
+/// Sets whether `NetworkManager` connects to a saved network by itself.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the connection could not be modified.
+pub fn set_auto_connect(uuid: &str, auto_connect: bool) -> Result<(), String> {
+    let value = if auto_connect { "yes" } else { "no" };
+    nmcli(&[
+        "connection",
+        "modify",
+        "uuid",
+        uuid,
+        "connection.autoconnect",
+        value,
+    ])
+    .map(drop)
+}
+
+/// Deletes a saved network, disconnecting from it first if it is connected to.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the connection could not be deleted.
+pub fn forget(network: &KnownNetwork) -> Result<(), String> {
+    if network.active {
+        nmcli(&["connection", "down", "uuid", &network.uuid])?;
+    }
+
+    nmcli(&["connection", "delete", "uuid", &network.uuid]).map(drop)
+}
+
+/// Sets whether a saved network is metered, applying it at once if it is connected to.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the connection could not be modified.
+pub fn set_metered(network: &KnownNetwork, metered: Metered) -> Result<(), String> {
+    nmcli(&[
+        "connection",
+        "modify",
+        "uuid",
+        &network.uuid,
+        "connection.metered",
+        metered.as_str(),
+    ])?;
+
+    // Otherwise the modified connection only applies once it is connected to again.
+    if let Some(device) = network.device.as_deref() {
+        let _ = nmcli(&["device", "reapply", device]);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,137 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{
+    fields,
+    ip::{self, IpConfig},
+    nmcli,
+};
+
+/// Whether an Ethernet device is connected to a network.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LinkState {
+    Connected,
+    Connecting,
+    #[default]
+    Disconnected,
+    /// No cable is plugged into the device.
+    Unplugged,
+}
+
+impl LinkState {
+    fn from_str(state: &str) -> Self {
+        match state {
+            "connected" => Self::Connected,
+            "unavailable" => Self::Unplugged,
+            state if state.starts_with("connecting") => Self::Connecting,
+            _ => Self::Disconnected,
+        }
+    }
+}
+
+/// The connection which is active on an Ethernet device.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WiredConnection {
+    pub uuid: String,
+    pub name: String,
+    pub ipv4: IpConfig,
+    pub ipv6: IpConfig,
+}
+
+/// An Ethernet device managed by `NetworkManager`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WiredDevice {
+    /// The name of the interface, such as `enp5s0`.
+    pub interface: String,
+    pub state: LinkState,
+    /// The speed of the link in megabits per second, once connected.
+    pub speed: Option<u32>,
+    /// The IPv4 and IPv6 addresses of the device, with their prefix lengths.
+    pub addresses: Vec<String>,
+    pub connection: Option<WiredConnection>,
+}
+
+/// Returns the Ethernet devices, along with the connections which are active on them.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the devices could not be listed.
+pub fn devices() -> Result<Vec<WiredDevice>, String> {
+    let stdout = nmcli(&[
+        "--fields",
+        "DEVICE,TYPE,STATE,CON-UUID,CONNECTION",
+        "device",
+        "status",
+    ])?;
+
+    let mut devices = Vec::new();
+
+    for line in stdout.lines() {
+        let values = fields(line);
+        let [interface, kind, state, uuid, name] = values.as_slice() else {
+            continue;
+        };
+
+        if kind != "ethernet" {
+            continue;
+        }
+
+        let show = |properties: &str| {
+            nmcli(&["--get-values", properties, "device", "show", interface]).unwrap_or_default()
+        };
+
+        // Reported as "1000 Mb/s", or "unknown" without a link.
+        let speed = show("CAPABILITIES.SPEED")
+            .split_whitespace()
+            .next()
+            .and_then(|speed| speed.parse().ok());
+
+        // Each family is printed on its own line, with multiple addresses separated by " | ".
+        let addresses = show("IP4.ADDRESS,IP6.ADDRESS")
+            .lines()
+            .flat_map(|line| {
+                fields(line)
+                    .join(":")
+                    .split(" | ")
+                    .map(str::trim)
+                    .filter(|address| !address.is_empty())
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let connection = if uuid.is_empty() || uuid == "--" {
+            None
+        } else {
+            let (ipv4, ipv6) = ip::config(uuid)?;
+
+            Some(WiredConnection {
+                uuid: uuid.clone(),
+                name: name.clone(),
+                ipv4,
+                ipv6,
+            })
+        };
+
+        devices.push(WiredDevice {
+            interface: interface.clone(),
+            state: LinkState::from_str(state),
+            speed,
+            addresses,
+            connection,
+        });
+    }
+
+    Ok(devices)
+}
+
+/// Connects a device with its best saved connection, or disconnects it until it is connected
+/// again, even if it would otherwise connect by itself.
+///
+/// # Errors
+///
+/// Returns the error reported by `nmcli` if the device could not be connected or disconnected.
+pub fn set_enabled(interface: &str, enabled: bool) -> Result<(), String> {
+    let action = if enabled { "connect" } else { "disconnect" };
+    nmcli(&["device", action, interface]).map(drop)
+}
@@ -0,0 +1,297 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! The config of the panel and dock, which the running panel watches and applies at once.
+
+use cosmic_settings_config as config;
+
+/// How long the pointer must leave a hidden panel before it hides, in milliseconds.
+const AUTOHIDE_WAIT: u32 = 1000;
+
+/// How long a panel takes to slide in or out of view, in milliseconds.
+const AUTOHIDE_TRANSITION: u32 = 200;
+
+/// The size of the strip which a hidden panel leaves at the edge, in pixels.
+const AUTOHIDE_HANDLE: u32 = 4;
+
+/// The panels which are configured independently of each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PanelKind {
+    /// The panel along the top of the screen, with the workspaces, clock, and status applets.
+    Panel,
+    /// The dock of favorite and running applications.
+    Dock,
+}
+
+impl PanelKind {
+    pub const ALL: [Self; 2] = [Self::Panel, Self::Dock];
+
+    fn config_name(self) -> &'static str {
+        match self {
+            Self::Panel => "com.system76.CosmicPanel.Panel",
+            Self::Dock => "com.system76.CosmicPanel.Dock",
+        }
+    }
+}
+
+/// The edge of the screen which a panel is placed along.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Anchor {
+    pub const ALL: [Self; 4] = [Self::Top, Self::Bottom, Self::Left, Self::Right];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Top => "Top",
+            Self::Bottom => "Bottom",
+            Self::Left => "Left",
+            Self::Right => "Right",
+        }
+    }
+}
+
+/// The size of a panel, and of the applets in it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanelSize {
+    ExtraSmall,
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl PanelSize {
+    pub const ALL: [Self; 5] = [
+        Self::ExtraSmall,
+        Self::Small,
+        Self::Medium,
+        Self::Large,
+        Self::ExtraLarge,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ExtraSmall => "XS",
+            Self::Small => "S",
+            Self::Medium => "M",
+            Self::Large => "L",
+            Self::ExtraLarge => "XL",
+        }
+    }
+}
+
+/// The part of a panel which an applet is placed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppletArea {
+    Start,
+    Center,
+    End,
+}
+
+impl AppletArea {
+    pub const ALL: [Self; 3] = [Self::Start, Self::Center, Self::End];
+}
+
+/// An applet which is shown in a panel, by the ID of its desktop entry without its suffix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlacedApplet {
+    pub id: String,
+    pub area: AppletArea,
+}
+
+/// How a panel is placed and behaves, and the applets which are shown in it, in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PanelConfig {
+    pub anchor: Anchor,
+    pub size: PanelSize,
+    /// Whether the panel hides until the pointer is moved to its edge.
+    pub autohide: bool,
+    pub applets: Vec<PlacedApplet>,
+}
+
+impl PanelConfig {
+    /// The config of the panel where it is not set, as the panel itself defaults to.
+    #[must_use]
+    pub fn default_for(kind: PanelKind) -> Self {
+        let applets = |area, ids: &[&str]| {
+            ids.iter()
+                .map(move |&id| PlacedApplet {
+                    id: String::from(id),
+                    area,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        match kind {
+            PanelKind::Panel => Self {
+                anchor: Anchor::Top,
+                size: PanelSize::ExtraSmall,
+                autohide: false,
+                applets: [
+                    applets(
+                        AppletArea::Start,
+                        &[
+                            "com.system76.CosmicPanelWorkspacesButton",
+                            "com.system76.CosmicPanelAppButton",
+                        ],
+                    ),
+                    applets(AppletArea::Center, &["com.system76.CosmicAppletTime"]),
+                    applets(
+                        AppletArea::End,
+                        &[
+                            "com.system76.CosmicAppletNetwork",
+                            "com.system76.CosmicAppletAudio",
+                            "com.system76.CosmicAppletBattery",
+                            "com.system76.CosmicAppletPower",
+                        ],
+                    ),
+                ]
+                .concat(),
+            },
+
+            PanelKind::Dock => Self {
+                anchor: Anchor::Bottom,
+                size: PanelSize::Large,
+                autohide: true,
+                applets: applets(AppletArea::Center, &["com.system76.CosmicAppList"]),
+            },
+        }
+    }
+}
+
+/// The config of a panel, with its defaults where it is not set.
+#[must_use]
+pub fn config(kind: PanelKind) -> PanelConfig {
+    let default = PanelConfig::default_for(kind);
+
+    let anchor = read(kind, "anchor")
+        .and_then(|value| Anchor::ALL.into_iter().find(|a| a.as_str() == value))
+        .unwrap_or(default.anchor);
+
+    let size = read(kind, "size")
+        .and_then(|value| PanelSize::ALL.into_iter().find(|s| s.as_str() == value))
+        .unwrap_or(default.size);
+
+    let autohide = read(kind, "autohide").map_or(default.autohide, |value| value != "None");
+
+    let wings = read(kind, "plugins_wings").map(|value| string_lists(&value));
+    let center = read(kind, "plugins_center").map(|value| string_lists(&value));
+
+    let applets = if wings.is_none() && center.is_none() {
+        default.applets
+    } else {
+        let mut wings = wings.unwrap_or_default().into_iter();
+        let start = wings.next().unwrap_or_default();
+        let end = wings.next().unwrap_or_default();
+        let center = center.unwrap_or_default().into_iter().next();
+
+        [
+            (AppletArea::Start, start),
+            (AppletArea::Center, center.unwrap_or_default()),
+            (AppletArea::End, end),
+        ]
+        .into_iter()
+        .flat_map(|(area, ids)| ids.into_iter().map(move |id| PlacedApplet { id, area }))
+        .collect()
+    };
+
+    PanelConfig {
+        anchor,
+        size,
+        autohide,
+        applets,
+    }
+}
+
+/// Writes the config of a panel, which the running panel applies at once.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_config(kind: PanelKind, config: &PanelConfig) -> Result<(), String> {
+    write(kind, "anchor", config.anchor.as_str())?;
+    write(kind, "size", config.size.as_str())?;
+
+    let autohide = if config.autohide {
+        format!(
+            "Some((\n    wait_time: {AUTOHIDE_WAIT},\n    transition_time: {AUTOHIDE_TRANSITION},\n    handle_size: {AUTOHIDE_HANDLE},\n))"
+        )
+    } else {
+        String::from("None")
+    };
+
+    write(kind, "autohide", &autohide)?;
+
+    let area = |area: AppletArea| -> String {
+        let ids: Vec<String> = config
+            .applets
+            .iter()
+            .filter(|applet| applet.area == area)
+            .map(|applet| format!("\n    {:?},", applet.id))
+            .collect();
+
+        format!("[{}\n]", ids.concat())
+    };
+
+    write(
+        kind,
+        "plugins_wings",
+        &format!(
+            "Some(({}, {}))",
+            area(AppletArea::Start),
+            area(AppletArea::End)
+        ),
+    )?;
+
+    write(
+        kind,
+        "plugins_center",
+        &format!("Some({})", area(AppletArea::Center)),
+    )
+}
+
+/// The lists of strings in a value, in order, such as both lists of `Some((["a"], ["b"]))`.
+fn string_lists(value: &str) -> Vec<Vec<String>> {
+    let mut lists = Vec::new();
+    let mut list: Option<Vec<String>> = None;
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => list = Some(Vec::new()),
+            ']' => lists.extend(list.take()),
+            '"' => {
+                let mut text = String::new();
+
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => text.extend(chars.next()),
+                        c => text.push(c),
+                    }
+                }
+
+                if let Some(list) = &mut list {
+                    list.push(text);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    lists
+}
+
+fn read(kind: PanelKind, key: &str) -> Option<String> {
+    config::read(kind.config_name(), key)
+}
+
+fn write(kind: PanelKind, key: &str, value: &str) -> Result<(), String> {
+    config::write(kind.config_name(), key, value)
+}
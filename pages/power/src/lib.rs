@@ -0,0 +1,677 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use cosmic_settings_config as config;
+use std::{fs, ops::RangeInclusive, path::PathBuf, process, time::Duration};
+
+/// The config of the idle daemon, whose keys are each stored in a file of their own.
+const IDLE: &str = "com.system76.CosmicIdle";
+
+/// Where an administrator sets keys of the idle daemon, which override those of the user.
+const POLICY_DIR: &str = "/etc/cosmic";
+
+/// Where the system draws its power from, for settings which differ between them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+impl PowerSource {
+    fn suffix(self) -> &'static str {
+        match self {
+            PowerSource::Ac => "on_ac",
+            PowerSource::Battery => "on_battery",
+        }
+    }
+}
+
+/// When the screen is turned off while the system is idle, and what happens before and after.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScreenOff {
+    /// The time after which the screen is turned off while plugged in, unless it is never.
+    pub on_ac: Option<Duration>,
+    /// The time after which the screen is turned off on battery, unless it is never.
+    pub on_battery: Option<Duration>,
+    /// Whether the screen is dimmed shortly before it is turned off.
+    pub dim: bool,
+    /// Whether the screen is locked once it was turned off.
+    pub lock: bool,
+    /// The time between turning the screen off and locking it.
+    pub lock_delay: Duration,
+}
+
+impl Default for ScreenOff {
+    fn default() -> Self {
+        Self {
+            on_ac: Some(Duration::from_secs(15 * 60)),
+            on_battery: Some(Duration::from_secs(5 * 60)),
+            dim: true,
+            lock: true,
+            lock_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl ScreenOff {
+    #[must_use]
+    pub fn timeout(&self, source: PowerSource) -> Option<Duration> {
+        match source {
+            PowerSource::Ac => self.on_ac,
+            PowerSource::Battery => self.on_battery,
+        }
+    }
+}
+
+/// What happens when the lid of a laptop is closed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LidAction {
+    #[default]
+    Suspend,
+    Hibernate,
+    Lock,
+    Nothing,
+}
+
+impl LidAction {
+    pub const ALL: [LidAction; 4] = [
+        LidAction::Suspend,
+        LidAction::Hibernate,
+        LidAction::Lock,
+        LidAction::Nothing,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            LidAction::Suspend => "Suspend",
+            LidAction::Hibernate => "Hibernate",
+            LidAction::Lock => "Lock",
+            LidAction::Nothing => "Nothing",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value)
+    }
+}
+
+/// What happens when the power button is pressed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PowerButtonAction {
+    Suspend,
+    #[default]
+    PowerOff,
+    Hibernate,
+    Nothing,
+}
+
+impl PowerButtonAction {
+    pub const ALL: [PowerButtonAction; 4] = [
+        PowerButtonAction::Suspend,
+        PowerButtonAction::PowerOff,
+        PowerButtonAction::Hibernate,
+        PowerButtonAction::Nothing,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            PowerButtonAction::Suspend => "Suspend",
+            PowerButtonAction::PowerOff => "PowerOff",
+            PowerButtonAction::Hibernate => "Hibernate",
+            PowerButtonAction::Nothing => "Nothing",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == value)
+    }
+}
+
+/// When the system is suspended while idle, and what happens when its lid is closed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suspend {
+    /// The time after which the system is suspended while plugged in, unless it is never.
+    pub on_ac: Option<Duration>,
+    /// The time after which the system is suspended on battery, unless it is never.
+    pub on_battery: Option<Duration>,
+    /// What happens when the lid is closed while plugged in.
+    pub lid_on_ac: LidAction,
+    /// What happens when the lid is closed on battery.
+    pub lid_on_battery: LidAction,
+}
+
+impl Default for Suspend {
+    fn default() -> Self {
+        Self {
+            on_ac: None,
+            on_battery: Some(Duration::from_secs(20 * 60)),
+            lid_on_ac: LidAction::Suspend,
+            lid_on_battery: LidAction::Suspend,
+        }
+    }
+}
+
+impl Suspend {
+    #[must_use]
+    pub fn timeout(&self, source: PowerSource) -> Option<Duration> {
+        match source {
+            PowerSource::Ac => self.on_ac,
+            PowerSource::Battery => self.on_battery,
+        }
+    }
+
+    #[must_use]
+    pub fn lid(&self, source: PowerSource) -> LidAction {
+        match source {
+            PowerSource::Ac => self.lid_on_ac,
+            PowerSource::Battery => self.lid_on_battery,
+        }
+    }
+}
+
+/// The settings which an administrator manages, which the user may not change.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Managed {
+    pub suspend_on_ac: bool,
+    pub suspend_on_battery: bool,
+}
+
+impl Managed {
+    #[must_use]
+    pub fn suspend(&self, source: PowerSource) -> bool {
+        match source {
+            PowerSource::Ac => self.suspend_on_ac,
+            PowerSource::Battery => self.suspend_on_battery,
+        }
+    }
+}
+
+/// Finds which settings are managed by an administrator.
+#[must_use]
+pub fn managed() -> Managed {
+    let suspend =
+        |source: PowerSource| policy_path(&format!("suspend_{}_time", source.suffix())).is_file();
+
+    Managed {
+        suspend_on_ac: suspend(PowerSource::Ac),
+        suspend_on_battery: suspend(PowerSource::Battery),
+    }
+}
+
+/// The hardware of the system, which decides which of the power settings apply to it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Hardware {
+    pub battery: bool,
+    pub lid: bool,
+    pub hibernate: bool,
+    /// Whether an ambient light sensor can drive the brightness of a backlight.
+    pub light_sensor: bool,
+}
+
+impl Hardware {
+    #[must_use]
+    pub fn probe() -> Self {
+        Self {
+            battery: has_battery(),
+            lid: has_lid(),
+            hibernate: can_hibernate(),
+            light_sensor: has_light_sensor() && backlight().is_some(),
+        }
+    }
+}
+
+/// Whether the system has a battery which powers it, rather than that of a peripheral.
+#[must_use]
+pub fn has_battery() -> bool {
+    let Ok(supplies) = fs::read_dir("/sys/class/power_supply") else {
+        return false;
+    };
+
+    supplies.filter_map(Result::ok).any(|supply| {
+        let path = supply.path();
+        let kind = fs::read_to_string(path.join("type")).unwrap_or_default();
+
+        // Peripherals, such as wireless mice, report their batteries with a device scope.
+        let scope = fs::read_to_string(path.join("scope")).unwrap_or_default();
+
+        kind.trim() == "Battery" && scope.trim() != "Device"
+    })
+}
+
+/// Whether the system has a lid switch, as laptops do.
+#[must_use]
+pub fn has_lid() -> bool {
+    let acpi = fs::read_dir("/proc/acpi/button/lid").map(|mut lids| lids.next());
+
+    matches!(acpi, Ok(Some(_)))
+        || fs::read_to_string("/proc/bus/input/devices")
+            .unwrap_or_default()
+            .contains("N: Name=\"Lid Switch\"")
+}
+
+/// Whether an external display is connected, such as while a laptop is docked.
+///
+/// Displays which are built in, through eDP, LVDS, or DSI, are not external.
+#[must_use]
+pub fn has_external_display() -> bool {
+    let Ok(connectors) = fs::read_dir("/sys/class/drm") else {
+        return false;
+    };
+
+    connectors.filter_map(Result::ok).any(|connector| {
+        let name = connector.file_name();
+        let name = name.to_string_lossy();
+
+        // Connectors are named after their card, such as `card0-HDMI-A-1`.
+        let Some((_, kind)) = name.split_once('-') else {
+            return false;
+        };
+
+        let internal = ["eDP", "LVDS", "DSI"]
+            .iter()
+            .any(|internal| kind.starts_with(internal));
+
+        let status = fs::read_to_string(connector.path().join("status"));
+        !internal && matches!(status.as_deref().map(str::trim), Ok("connected"))
+    })
+}
+
+/// Whether the kernel supports hibernation, and there is swap space which is not in memory to
+/// write the image of the system to.
+#[must_use]
+pub fn can_hibernate() -> bool {
+    let supported = fs::read_to_string("/sys/power/state")
+        .unwrap_or_default()
+        .split_whitespace()
+        .any(|state| state == "disk");
+
+    if !supported {
+        return false;
+    }
+
+    let Ok(swaps) = fs::read_to_string("/proc/swaps") else {
+        return false;
+    };
+
+    // Compressed swap in memory, such as zram, does not survive powering off.
+    swaps
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        .any(|device| !device.starts_with("/dev/zram"))
+}
+
+/// Whether the system has an ambient light sensor, which iio-sensor-proxy reads.
+#[must_use]
+pub fn has_light_sensor() -> bool {
+    let Ok(devices) = fs::read_dir("/sys/bus/iio/devices") else {
+        return false;
+    };
+
+    devices.filter_map(Result::ok).any(|device| {
+        let path = device.path();
+        [
+            "in_illuminance_raw",
+            "in_illuminance_input",
+            "in_intensity_clear_raw",
+        ]
+        .iter()
+        .any(|channel| path.join(channel).exists())
+    })
+}
+
+/// The backlight of a built-in display, whose brightness is set in steps up to its maximum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Backlight {
+    /// The name of the device, which logind sets its brightness by.
+    pub name: String,
+    pub brightness: u32,
+    pub max: u32,
+}
+
+/// The backlight of the built-in display, preferring the interfaces of the firmware and
+/// platform over the raw registers of the graphics card, as systemd does.
+#[must_use]
+pub fn backlight() -> Option<Backlight> {
+    let devices = fs::read_dir("/sys/class/backlight").ok()?;
+
+    let read = |path: &PathBuf, file: &str| -> Option<String> {
+        fs::read_to_string(path.join(file))
+            .ok()
+            .map(|value| value.trim().to_owned())
+    };
+
+    devices
+        .filter_map(Result::ok)
+        .filter_map(|device| {
+            let path = device.path();
+            let priority = match read(&path, "type")?.as_str() {
+                "firmware" => 0,
+                "platform" => 1,
+                _ => 2,
+            };
+
+            let backlight = Backlight {
+                name: device.file_name().to_string_lossy().into_owned(),
+                brightness: read(&path, "brightness")?.parse().ok()?,
+                max: read(&path, "max_brightness")?.parse().ok()?,
+            };
+
+            (backlight.max > 0).then_some((priority, backlight))
+        })
+        .min_by_key(|(priority, _)| *priority)
+        .map(|(_, backlight)| backlight)
+}
+
+/// The charge limits which may be set, in percent, below which firmware may not go.
+pub const CHARGE_LIMIT: RangeInclusive<u8> = 50..=100;
+
+/// How far below the limit a battery is let to discharge before it is charged again, so that
+/// it is not topped up by every percent which it loses while plugged in.
+const CHARGE_RESTART_MARGIN: u8 = 5;
+
+/// The helper which writes the thresholds as root, which polkit authorizes through pkexec,
+/// as installed under the prefix which the app was built for.
+const CHARGE_LIMIT_HELPER: &str = match option_env!("CHARGE_LIMIT_HELPER") {
+    Some(path) => path,
+    None => "/usr/libexec/cosmic-settings-charge-limit",
+};
+
+/// The charge at which the firmware stops charging a battery, to extend its lifespan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChargeLimit {
+    /// The name of the battery in sysfs, such as `BAT0`.
+    pub battery: String,
+    /// The charge at which charging stops, in percent.
+    pub end: u8,
+}
+
+/// Why a charge limit could not be set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChargeLimitError {
+    /// Authentication was dismissed, or the user may not change it.
+    Denied,
+    /// The firmware refused the limit, or holds a different one than was written.
+    Rejected,
+    /// The helper could not be run, or failed otherwise.
+    Failed(String),
+}
+
+/// The charge limit of the battery, on hardware whose firmware supports one, such as
+/// ThinkPads and System76 laptops.
+#[must_use]
+pub fn charge_limit() -> Option<ChargeLimit> {
+    let supplies = fs::read_dir("/sys/class/power_supply").ok()?;
+
+    let mut limits: Vec<ChargeLimit> = supplies
+        .filter_map(Result::ok)
+        .filter_map(|supply| {
+            let end = fs::read_to_string(supply.path().join("charge_control_end_threshold"));
+
+            Some(ChargeLimit {
+                battery: supply.file_name().to_string_lossy().into_owned(),
+                end: end.ok()?.trim().parse().ok()?,
+            })
+        })
+        .collect();
+
+    // The firmware applies one limit to all batteries, so that of the first is shown.
+    limits.sort_by(|a, b| a.battery.cmp(&b.battery));
+    limits.into_iter().next()
+}
+
+/// Sets the charge at which the firmware stops charging the battery, and reads it back.
+///
+/// Writing the thresholds requires root, so it is done by a helper which polkit authorizes,
+/// asking the user to authenticate if they have not recently. Where the firmware also has a
+/// threshold at which charging starts, it is kept a little below the limit.
+///
+/// # Errors
+///
+/// Fails if authentication was denied, or the firmware rejected the limit.
+pub fn set_charge_limit(battery: &str, end: u8) -> Result<ChargeLimit, ChargeLimitError> {
+    let end = end.clamp(*CHARGE_LIMIT.start(), *CHARGE_LIMIT.end());
+    let path = PathBuf::from("/sys/class/power_supply").join(battery);
+
+    let mut command = process::Command::new("pkexec");
+    command
+        .arg(CHARGE_LIMIT_HELPER)
+        .arg(battery)
+        .arg(end.to_string());
+
+    if path.join("charge_control_start_threshold").exists() {
+        command.arg(end.saturating_sub(CHARGE_RESTART_MARGIN).to_string());
+    }
+
+    let output = command
+        .output()
+        .map_err(|why| ChargeLimitError::Failed(why.to_string()))?;
+
+    match output.status.code() {
+        Some(0) => (),
+
+        // pkexec exits with these when authentication was dismissed or refused.
+        Some(126 | 127) => return Err(ChargeLimitError::Denied),
+
+        // The shell of the helper fails to write a value which the firmware refuses.
+        Some(1) => return Err(ChargeLimitError::Rejected),
+
+        _ => {
+            let why = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+            return Err(ChargeLimitError::Failed(why));
+        }
+    }
+
+    // Some firmware accepts a value, but rounds it to one which it supports.
+    match charge_limit() {
+        Some(limit) if limit.battery == battery && limit.end == end => Ok(limit),
+        _ => Err(ChargeLimitError::Rejected),
+    }
+}
+
+/// How strongly automatic brightness follows the ambient light, from dim to bright.
+pub const SENSITIVITY: RangeInclusive<u32> = 0..=100;
+
+/// The ambient light in lux at which the backlight is fully bright, at the middle sensitivity.
+const FULL_BRIGHTNESS_LUX: f64 = 1000.0;
+
+/// The lowest share of its maximum which automatic brightness dims a backlight to, so that
+/// the screen stays readable in the dark.
+const MIN_BRIGHTNESS: f64 = 0.05;
+
+/// The brightness of a backlight for the ambient light, in its steps.
+///
+/// Brightness follows the logarithm of the light, as eyes perceive it. Each quarter of the
+/// sensitivity above or below its middle doubles or halves the light which is perceived.
+#[must_use]
+pub fn auto_brightness(lux: f64, sensitivity: u32, max: u32) -> u32 {
+    let sensitivity = sensitivity.min(*SENSITIVITY.end());
+    let gain = 2_f64.powf((f64::from(sensitivity) - 50.0) / 25.0);
+    let lux = (lux.max(0.0) * gain).min(FULL_BRIGHTNESS_LUX);
+
+    let share = (1.0 + lux).log10() / (1.0 + FULL_BRIGHTNESS_LUX).log10();
+    let share = share.clamp(MIN_BRIGHTNESS, 1.0);
+
+    (share * f64::from(max)).round() as u32
+}
+
+/// When the screen is turned off, with the defaults of the idle daemon where it is not set.
+#[must_use]
+pub fn screen_off() -> ScreenOff {
+    let default = ScreenOff::default();
+
+    let timeout = |source: PowerSource| match read(&format!("screen_off_{}_time", source.suffix()))
+    {
+        Some(value) => parse_timeout(&value),
+        None => default.timeout(source),
+    };
+
+    ScreenOff {
+        on_ac: timeout(PowerSource::Ac),
+        on_battery: timeout(PowerSource::Battery),
+        dim: read("dim_screen").map_or(default.dim, |value| value == "true"),
+        lock: read("lock_on_screen_off").map_or(default.lock, |value| value == "true"),
+        lock_delay: read("lock_delay")
+            .and_then(|value| value.parse().ok())
+            .map_or(default.lock_delay, Duration::from_millis),
+    }
+}
+
+/// Sets the time after which the screen is turned off, or never turns it off.
+pub fn set_screen_off_time(source: PowerSource, timeout: Option<Duration>) -> Result<(), String> {
+    write(
+        &format!("screen_off_{}_time", source.suffix()),
+        &format_timeout(timeout),
+    )
+}
+
+/// Dims the screen shortly before it is turned off.
+pub fn set_dim_screen(dim: bool) -> Result<(), String> {
+    write("dim_screen", &dim.to_string())
+}
+
+/// Locks the screen once it was turned off for a time, or never locks it.
+pub fn set_lock_on_screen_off(lock: bool, delay: Duration) -> Result<(), String> {
+    write("lock_on_screen_off", &lock.to_string())?;
+    write("lock_delay", &delay.as_millis().to_string())
+}
+
+/// When the system is suspended, with the defaults of the idle daemon where it is not set.
+#[must_use]
+pub fn suspend() -> Suspend {
+    let default = Suspend::default();
+
+    let timeout = |source: PowerSource| match read(&format!("suspend_{}_time", source.suffix())) {
+        Some(value) => parse_timeout(&value),
+        None => default.timeout(source),
+    };
+
+    // The action which applied to both sources is kept for those which were not set since.
+    let shared = read("lid_close_action").and_then(|value| LidAction::from_str(&value));
+
+    let lid = |source: PowerSource| {
+        read(&format!("lid_close_{}_action", source.suffix()))
+            .and_then(|value| LidAction::from_str(&value))
+            .or(shared)
+            .unwrap_or(default.lid(source))
+    };
+
+    Suspend {
+        on_ac: timeout(PowerSource::Ac),
+        on_battery: timeout(PowerSource::Battery),
+        lid_on_ac: lid(PowerSource::Ac),
+        lid_on_battery: lid(PowerSource::Battery),
+    }
+}
+
+/// Sets when the system is suspended, and what happens when its lid is closed.
+pub fn set_suspend(suspend: Suspend) -> Result<(), String> {
+    for source in [PowerSource::Ac, PowerSource::Battery] {
+        write(
+            &format!("suspend_{}_time", source.suffix()),
+            &format_timeout(suspend.timeout(source)),
+        )?;
+
+        write(
+            &format!("lid_close_{}_action", source.suffix()),
+            suspend.lid(source).as_str(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// What happens when the power button is pressed, which powers off as logind does by default.
+#[must_use]
+pub fn power_button() -> PowerButtonAction {
+    read("power_button_action")
+        .and_then(|value| PowerButtonAction::from_str(&value))
+        .unwrap_or_default()
+}
+
+/// Sets what happens when the power button is pressed.
+pub fn set_power_button(action: PowerButtonAction) -> Result<(), String> {
+    write("power_button_action", action.as_str())
+}
+
+/// Formats a timeout as it is stored, in milliseconds.
+fn format_timeout(timeout: Option<Duration>) -> String {
+    match timeout {
+        Some(timeout) => format!("Some({})", timeout.as_millis()),
+        None => String::from("None"),
+    }
+}
+
+/// Parses a timeout stored in milliseconds as `Some(300000)`, or `None` if it is never.
+fn parse_timeout(value: &str) -> Option<Duration> {
+    let millis = value.strip_prefix("Some(")?.strip_suffix(')')?;
+    millis.trim().parse().ok().map(Duration::from_millis)
+}
+
+/// Reads a key as it takes effect, which is as an administrator set it, if they did.
+fn read(key: &str) -> Option<String> {
+    let policy = fs::read_to_string(policy_path(key)).ok();
+    policy
+        .map(|value| value.trim().to_owned())
+        .or_else(|| config::read(IDLE, key))
+}
+
+fn write(key: &str, value: &str) -> Result<(), String> {
+    config::write(IDLE, key, value)
+}
+
+fn policy_path(key: &str) -> PathBuf {
+    PathBuf::from(POLICY_DIR).join(IDLE).join("v1").join(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_brightness_dims_negative_lux_to_the_minimum() {
+        let darkness = auto_brightness(0.0, 50, 1000);
+
+        assert_eq!(darkness, 50);
+        assert_eq!(auto_brightness(-20.0, 50, 1000), darkness);
+        assert_eq!(auto_brightness(f64::NEG_INFINITY, 50, 1000), darkness);
+    }
+
+    #[test]
+    fn auto_brightness_caps_huge_lux_at_the_maximum() {
+        assert_eq!(auto_brightness(1.0e12, 50, 1000), 1000);
+        assert_eq!(auto_brightness(f64::INFINITY, 100, 1000), 1000);
+        assert_eq!(auto_brightness(f64::MAX, 0, 255), 255);
+    }
+
+    #[test]
+    fn timeouts_parse_as_stored() {
+        assert_eq!(
+            parse_timeout("Some(300000)"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            parse_timeout("Some( 300000 )"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_timeout("None"), None);
+        assert_eq!(parse_timeout("Some(five)"), None);
+    }
+
+    #[test]
+    fn timeouts_format_as_they_parse() {
+        for timeout in [Some(Duration::from_secs(300)), None] {
+            assert_eq!(parse_timeout(&format_timeout(timeout)), timeout);
+        }
+
+        assert_eq!(
+            format_timeout(Some(Duration::from_secs(300))),
+            "Some(300000)"
+        );
+        assert_eq!(format_timeout(None), "None");
+    }
+}
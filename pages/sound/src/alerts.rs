@@ -0,0 +1,233 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! The sounds which alerts are played with, as configured for the notification daemon.
+
+use cosmic_settings_config as config;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The config which the notification daemon plays the sound of notifications by.
+const NOTIFICATIONS: &str = "com.system76.CosmicNotifications";
+
+/// How long a preview may play before it is stopped, so that a broken file cannot hang it.
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How deep sounds of the user are searched for below their sounds directory.
+const USER_DEPTH: usize = 3;
+
+/// A sound which alerts may be played with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlertSound {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// The sound and volume of alerts, apart from the volume of the output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlertConfig {
+    /// No sound is played when unset.
+    pub sound: Option<PathBuf>,
+    /// The volume of alerts, as a percentage of the volume of the output.
+    pub volume: u32,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            sound: Some(PathBuf::from(
+                "/usr/share/sounds/freedesktop/stereo/message-new-instant.oga",
+            )),
+            volume: 100,
+        }
+    }
+}
+
+/// The config of alerts, with its defaults where it is not set.
+#[must_use]
+pub fn config() -> AlertConfig {
+    let default = AlertConfig::default();
+
+    let sound = match config::read(NOTIFICATIONS, "alert_sound") {
+        Some(value) if value == "None" => None,
+        Some(value) => value
+            .strip_prefix("Some(")
+            .and_then(|value| value.strip_suffix(')'))
+            .and_then(unquote)
+            .map(PathBuf::from)
+            .or(default.sound),
+        None => default.sound,
+    };
+
+    let volume = config::read(NOTIFICATIONS, "alert_volume")
+        .and_then(|value| value.parse::<u32>().ok())
+        .map_or(default.volume, |volume| volume.min(100));
+
+    AlertConfig { sound, volume }
+}
+
+/// Writes the config of alerts, which the notification daemon plays its next sound by.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_config(config: &AlertConfig) -> Result<(), String> {
+    let sound = match &config.sound {
+        Some(path) => format!("Some({:?})", path.to_string_lossy()),
+        None => String::from("None"),
+    };
+
+    config::write(NOTIFICATIONS, "alert_sound", &sound)?;
+    config::write(
+        NOTIFICATIONS,
+        "alert_volume",
+        &config.volume.min(100).to_string(),
+    )
+}
+
+/// The sounds of the freedesktop sound theme, and the sounds which the user added to their
+/// sounds directory, by name.
+#[must_use]
+pub fn sounds() -> Vec<AlertSound> {
+    let mut paths = Vec::new();
+
+    for dir in data_dirs() {
+        list(&dir.join("sounds/freedesktop/stereo"), 0, &mut paths);
+    }
+
+    if let Some(home) = data_home() {
+        list(&home.join("sounds"), USER_DEPTH, &mut paths);
+    }
+
+    let mut sounds: Vec<AlertSound> = Vec::new();
+
+    for path in paths {
+        if sounds.iter().any(|sound| sound.path == path) {
+            continue;
+        }
+
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        sounds.push(AlertSound {
+            name: display_name(stem),
+            path,
+        });
+    }
+
+    sounds.sort_by(|a, b| a.name.cmp(&b.name));
+    sounds
+}
+
+/// Plays a sound once through the sound server as an event sound, at a percentage of the
+/// volume of the output.
+///
+/// # Errors
+///
+/// Fails if the sound could not be played, or did not finish within the timeout, in which
+/// case it is stopped.
+pub fn preview(path: &Path, volume: u32) -> Result<(), String> {
+    let mut child = Command::new("pw-play")
+        .arg("--media-role=Notification")
+        .arg(format!("--volume={}", f64::from(volume.min(100)) / 100.0))
+        .arg(path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|why| format!("failed to execute pw-play: {why}"))?;
+
+    let started = Instant::now();
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(_)) => return Err(format!("{} could not be played", path.display())),
+            Ok(None) if started.elapsed() < PREVIEW_TIMEOUT => {
+                thread::sleep(Duration::from_millis(50));
+            }
+            Ok(None) => {
+                let _res = child.kill();
+                let _res = child.wait();
+                return Err(format!("{} did not finish playing", path.display()));
+            }
+            Err(why) => return Err(why.to_string()),
+        }
+    }
+}
+
+/// Adds the Ogg sounds of a directory to the paths, and those of its subdirectories down
+/// to a depth.
+fn list(dir: &Path, depth: usize, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            if depth > 0 {
+                list(&path, depth - 1, paths);
+            }
+        } else if matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("oga" | "ogg")
+        ) {
+            paths.push(path);
+        }
+    }
+}
+
+/// A name such as `Message new instant` from the name of a sound such as `message-new-instant`.
+fn display_name(stem: &str) -> String {
+    let name = stem.replace(['-', '_'], " ");
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+/// The contents of a quoted RON string.
+fn unquote(value: &str) -> Option<String> {
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.extend(chars.next()),
+            c => text.push(c),
+        }
+    }
+
+    Some(text)
+}
+
+fn data_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
+
+/// The directories which sound themes are installed in.
+pub(crate) fn data_dirs() -> Vec<PathBuf> {
+    let dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+
+    dirs.split(':')
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .collect()
+}
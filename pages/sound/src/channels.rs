@@ -0,0 +1,166 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! The balance and fade of devices, from the volumes of their channels by position.
+//!
+//! Balances run from -1 for only the left to 1 for only the right, and fades from -1 for
+//! only the rear to 1 for only the front, as the sound server reports them.
+
+/// The raw volume of a channel which plays at full volume.
+pub const NORMAL: u32 = 0x10000;
+
+fn is_left(position: &str) -> bool {
+    position.contains("left")
+}
+
+fn is_right(position: &str) -> bool {
+    position.contains("right")
+}
+
+fn is_front(position: &str) -> bool {
+    position.starts_with("front") || position.starts_with("top-front")
+}
+
+fn is_rear(position: &str) -> bool {
+    position.starts_with("rear") || position.starts_with("top-rear")
+}
+
+/// Whether the channels of a device may be balanced between the left and right.
+#[must_use]
+pub fn has_balance(channel_map: &[String]) -> bool {
+    has_both(channel_map, is_left, is_right)
+}
+
+/// Whether the channels of a device may be faded between the front and rear, unlike those
+/// of stereo devices.
+#[must_use]
+pub fn has_fade(channel_map: &[String]) -> bool {
+    has_both(channel_map, is_rear, is_front)
+}
+
+#[must_use]
+pub fn balance(channel_map: &[String], volumes: &[u32]) -> f32 {
+    ratio(channel_map, volumes, is_left, is_right)
+}
+
+#[must_use]
+pub fn fade(channel_map: &[String], volumes: &[u32]) -> f32 {
+    ratio(channel_map, volumes, is_rear, is_front)
+}
+
+/// The volumes of channels once they are balanced, keeping the loudest side as loud.
+#[must_use]
+pub fn with_balance(channel_map: &[String], volumes: &[u32], balance: f32) -> Vec<u32> {
+    with_ratio(channel_map, volumes, is_left, is_right, balance)
+}
+
+/// The volumes of channels once they are faded, keeping the loudest end as loud.
+#[must_use]
+pub fn with_fade(channel_map: &[String], volumes: &[u32], fade: f32) -> Vec<u32> {
+    with_ratio(channel_map, volumes, is_rear, is_front, fade)
+}
+
+/// The volumes of channels once the loudest is at a percentage, keeping the others in
+/// proportion to it so that the balance and fade are kept.
+#[must_use]
+pub fn scaled(volumes: &[u32], percent: u32) -> Vec<u32> {
+    let target = u64::from(percent) * u64::from(NORMAL) / 100;
+    let loudest = volumes.iter().copied().max().unwrap_or(0);
+
+    volumes
+        .iter()
+        .map(|&volume| {
+            if loudest == 0 {
+                target as u32
+            } else {
+                (u64::from(volume) * target / u64::from(loudest)) as u32
+            }
+        })
+        .collect()
+}
+
+fn has_both(channel_map: &[String], a: fn(&str) -> bool, b: fn(&str) -> bool) -> bool {
+    channel_map.iter().any(|position| a(position)) && channel_map.iter().any(|position| b(position))
+}
+
+/// The average volume of the channels at some positions.
+fn average(channel_map: &[String], volumes: &[u32], at: fn(&str) -> bool) -> Option<f64> {
+    let volumes: Vec<f64> = channel_map
+        .iter()
+        .zip(volumes)
+        .filter(|(position, _)| at(position))
+        .map(|(_, &volume)| f64::from(volume))
+        .collect();
+
+    if volumes.is_empty() {
+        return None;
+    }
+
+    Some(volumes.iter().sum::<f64>() / volumes.len() as f64)
+}
+
+/// How far the volume leans from the positions of `a`, towards -1, to those of `b`, towards 1.
+fn ratio(channel_map: &[String], volumes: &[u32], a: fn(&str) -> bool, b: fn(&str) -> bool) -> f32 {
+    let (Some(a), Some(b)) = (
+        average(channel_map, volumes, a),
+        average(channel_map, volumes, b),
+    ) else {
+        return 0.0;
+    };
+
+    let ratio = if a == b {
+        0.0
+    } else if a > b {
+        b / a - 1.0
+    } else {
+        1.0 - a / b
+    };
+
+    ratio as f32
+}
+
+fn with_ratio(
+    channel_map: &[String],
+    volumes: &[u32],
+    a: fn(&str) -> bool,
+    b: fn(&str) -> bool,
+    ratio: f32,
+) -> Vec<u32> {
+    let (Some(old_a), Some(old_b)) = (
+        average(channel_map, volumes, a),
+        average(channel_map, volumes, b),
+    ) else {
+        return volumes.to_vec();
+    };
+
+    let loudest = old_a.max(old_b);
+    let ratio = f64::from(ratio.clamp(-1.0, 1.0));
+
+    let (new_a, new_b) = if ratio <= 0.0 {
+        (loudest, loudest * (1.0 + ratio))
+    } else {
+        (loudest * (1.0 - ratio), loudest)
+    };
+
+    let rescale = |volume: u32, old: f64, new: f64| {
+        if old == 0.0 {
+            new.round() as u32
+        } else {
+            (f64::from(volume) * new / old).round() as u32
+        }
+    };
+
+    channel_map
+        .iter()
+        .zip(volumes)
+        .map(|(position, &volume)| {
+            if a(position) {
+                rescale(volume, old_a, new_a)
+            } else if b(position) {
+                rescale(volume, old_b, new_b)
+            } else {
+                volume
+            }
+        })
+        .collect()
+}
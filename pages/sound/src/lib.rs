@@ -0,0 +1,321 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+pub mod alerts;
+pub mod channels;
+mod pactl;
+pub mod speaker_test;
+
+use cosmic_settings_config as config;
+use std::{fmt, process::Command};
+
+/// The config of the sound settings, which the volume applet and media keys also follow.
+const AUDIO: &str = "com.system76.CosmicAudio";
+
+/// The loudest volume, as a percentage, which devices and streams are set to.
+pub const MAX_VOLUME: u32 = 100;
+
+/// The loudest volume, as a percentage, once amplification above 100% is allowed.
+pub const MAX_AMPLIFIED_VOLUME: u32 = 150;
+
+/// A sink or source known to the sound server.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Device {
+    pub index: u32,
+    pub name: String,
+    pub description: String,
+    pub icon_name: String,
+    pub mute: bool,
+    /// The loudest channel volume, as a percentage.
+    pub volume: u32,
+    /// Set when a source is the monitor of a sink.
+    pub monitor: bool,
+    /// The description of the port which is in use, such as `Headphones`.
+    pub port: String,
+    /// What kind of device it is, such as `headset` or `speaker`, by which its icon is chosen.
+    pub form_factor: String,
+    /// The name of the port which is in use, such as `analog-output-headphones`.
+    pub active_port: String,
+    pub ports: Vec<Port>,
+    /// The positions of its channels, such as `front-left` and `front-right`.
+    pub channel_map: Vec<String>,
+    /// The raw volume of each channel, in the order of the channel map.
+    pub channel_volumes: Vec<u32>,
+    /// The name of the card which the device belongs to, which outlives the device when the
+    /// profile of the card is switched. Empty for devices which are not of a card.
+    pub card: String,
+}
+
+impl Device {
+    /// A symbolic icon of the kind of device.
+    #[must_use]
+    pub fn icon(&self) -> &'static str {
+        match self.form_factor.as_str() {
+            "headset" => "audio-headset-symbolic",
+            "headphone" | "headphones" => "audio-headphones-symbolic",
+            "hdmi" | "tv" | "display" => "video-display-symbolic",
+            "microphone" | "mic" => "audio-input-microphone-symbolic",
+            "webcam" => "camera-web-symbolic",
+            "handset" => "phone-symbolic",
+            _ => "audio-speakers-symbolic",
+        }
+    }
+}
+
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.description.is_empty() {
+            f.write_str(&self.name)
+        } else {
+            f.write_str(&self.description)
+        }
+    }
+}
+
+/// A connector of a device, such as its speakers or the headphone jack.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Port {
+    pub name: String,
+    pub description: String,
+    /// Unset when nothing is plugged into the port, as far as the card can tell.
+    pub available: bool,
+}
+
+/// A sound card, whose profile decides which sinks and sources it has.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Card {
+    pub name: String,
+    pub description: String,
+    pub profiles: Vec<Profile>,
+    pub active_profile: String,
+}
+
+/// A configuration of a card, such as `output:hdmi-stereo` or `output:analog-surround-51`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub description: String,
+    /// Unset when the profile cannot be used, such as HDMI without a display connected.
+    pub available: bool,
+}
+
+/// A stream which an application plays to a sink.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stream {
+    pub index: u32,
+    /// The name of the application which plays the stream, which its streams are grouped by.
+    pub application: String,
+    pub icon_name: String,
+    /// What is played, such as the title of a video.
+    pub media: String,
+    pub mute: bool,
+    /// The loudest channel volume, as a percentage.
+    pub volume: u32,
+}
+
+/// The devices of the sound server, and which of them are the defaults.
+#[derive(Clone, Debug, Default)]
+pub struct Devices {
+    pub default_sink: String,
+    pub default_source: String,
+    pub sinks: Vec<Device>,
+    pub sources: Vec<Device>,
+    pub cards: Vec<Card>,
+    /// The streams which applications play, in the order that they began.
+    pub streams: Vec<Stream>,
+}
+
+/// Whether volumes may be amplified above 100%, which distorts most sounds.
+#[must_use]
+pub fn amplification() -> bool {
+    matches!(
+        config::read(AUDIO, "amplification").as_deref(),
+        Some("true")
+    )
+}
+
+/// Allows or forbids volumes above 100%, without changing the volumes which are above it.
+///
+/// # Errors
+///
+/// Fails if the config could not be written.
+pub fn set_amplification(amplification: bool) -> Result<(), String> {
+    config::write(AUDIO, "amplification", &amplification.to_string())
+}
+
+/// The loudest volume, as a percentage, which may be set.
+#[must_use]
+pub fn max_volume(amplification: bool) -> u32 {
+    if amplification {
+        MAX_AMPLIFIED_VOLUME
+    } else {
+        MAX_VOLUME
+    }
+}
+
+/// Fetches the sinks and sources from the sound server.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the sound server could not be queried.
+pub fn devices() -> Result<Devices, String> {
+    let cards = pactl::list("cards")?;
+
+    Ok(Devices {
+        default_sink: pactl::run(&["get-default-sink"])?.trim().to_owned(),
+        default_source: pactl::run(&["get-default-source"])?.trim().to_owned(),
+        sinks: pactl::devices("sinks", &cards)?,
+        sources: pactl::devices("sources", &cards)?,
+        cards: cards.iter().map(pactl::card).collect(),
+        streams: pactl::streams()?,
+    })
+}
+
+/// Sets the default sink.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the sink could not be set.
+pub fn set_default_sink(name: &str) -> Result<(), String> {
+    pactl::run(&["set-default-sink", name]).map(drop)
+}
+
+/// Sets the default source.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the source could not be set.
+pub fn set_default_source(name: &str) -> Result<(), String> {
+    pactl::run(&["set-default-source", name]).map(drop)
+}
+
+/// Switches the profile of a card, which replaces its sinks and sources with those of the
+/// profile, under new names and indices.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the profile could not be switched to.
+pub fn set_card_profile(card: &str, profile: &str) -> Result<(), String> {
+    pactl::run(&["set-card-profile", card, profile]).map(drop)
+}
+
+/// Switches the port of a sink, such as from its speakers to headphones.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the port could not be switched to.
+pub fn set_sink_port(name: &str, port: &str) -> Result<(), String> {
+    pactl::run(&["set-sink-port", name, port]).map(drop)
+}
+
+/// Sets the volume of every channel of a sink, as a percentage.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the volume could not be set.
+pub fn set_sink_volume(name: &str, volume: u32) -> Result<(), String> {
+    pactl::run(&["set-sink-volume", name, &format!("{volume}%")]).map(drop)
+}
+
+/// Sets the raw volume of each channel of a sink, in the order of its channel map.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the volumes could not be set.
+pub fn set_sink_channel_volumes(name: &str, volumes: &[u32]) -> Result<(), String> {
+    let volumes: Vec<String> = volumes.iter().map(u32::to_string).collect();
+    let mut args = vec!["set-sink-volume", name];
+    args.extend(volumes.iter().map(String::as_str));
+    pactl::run(&args).map(drop)
+}
+
+/// Sets the volume of every channel of a source, as a percentage.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the volume could not be set.
+pub fn set_source_volume(name: &str, volume: u32) -> Result<(), String> {
+    pactl::run(&["set-source-volume", name, &format!("{volume}%")]).map(drop)
+}
+
+/// Mutes or unmutes a sink.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the sink could not be muted.
+pub fn set_sink_mute(name: &str, mute: bool) -> Result<(), String> {
+    pactl::run(&["set-sink-mute", name, if mute { "1" } else { "0" }]).map(drop)
+}
+
+/// Mutes or unmutes a source.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the source could not be muted.
+pub fn set_source_mute(name: &str, mute: bool) -> Result<(), String> {
+    pactl::run(&["set-source-mute", name, if mute { "1" } else { "0" }]).map(drop)
+}
+
+/// Sets the volume of every channel of a stream, as a percentage.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the volume could not be set, such as when the
+/// stream has ended.
+pub fn set_stream_volume(index: u32, volume: u32) -> Result<(), String> {
+    let index = index.to_string();
+    pactl::run(&["set-sink-input-volume", &index, &format!("{volume}%")]).map(drop)
+}
+
+/// Mutes or unmutes a stream.
+///
+/// # Errors
+///
+/// Returns the error reported by `pactl` if the stream could not be muted, such as when it
+/// has ended.
+pub fn set_stream_mute(index: u32, mute: bool) -> Result<(), String> {
+    let index = index.to_string();
+    pactl::run(&["set-sink-input-mute", &index, if mute { "1" } else { "0" }]).map(drop)
+}
+
+/// A command which prints a line for every change on the sound server.
+#[must_use]
+pub fn events_command() -> Command {
+    let mut command = pactl::command();
+    command.arg("subscribe");
+    command
+}
+
+/// The rate at which samples are captured by [`capture_command`], in hertz.
+pub const CAPTURE_RATE: usize = 8000;
+
+/// A command which streams mono signed 16-bit samples captured from a source, at
+/// [`CAPTURE_RATE`].
+#[must_use]
+pub fn capture_command(source: &str) -> Command {
+    let mut command = Command::new("parec");
+    command
+        .args([
+            "--device",
+            source,
+            "--raw",
+            "--format=s16le",
+            "--channels=1",
+        ])
+        .arg(format!("--rate={CAPTURE_RATE}"))
+        .arg("--latency-msec=30");
+    command
+}
+
+/// The peak level of a buffer of signed 16-bit little endian samples, from 0 to 1.
+#[must_use]
+pub fn peak(samples: &[u8]) -> f32 {
+    let peak = samples
+        .chunks_exact(2)
+        .map(|sample| i16::from_le_bytes([sample[0], sample[1]]).unsigned_abs())
+        .max()
+        .unwrap_or(0);
+
+    // The magnitude of `i16::MIN` is one more than that of `i16::MAX`.
+    (f32::from(peak) / f32::from(i16::MAX.unsigned_abs())).min(1.0)
+}
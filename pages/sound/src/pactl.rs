@@ -0,0 +1,307 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::{Card, Device, Port, Profile, Stream};
+use std::process::Command;
+
+/// A `pactl` command whose output is not localized.
+pub fn command() -> Command {
+    let mut command = Command::new("pactl");
+    command.env("LC_ALL", "C");
+    command
+}
+
+/// Runs `pactl` and returns its standard output.
+pub fn run(args: &[&str]) -> Result<String, String> {
+    let output = command()
+        .args(args)
+        .output()
+        .map_err(|why| format!("failed to execute pactl: {why}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    String::from_utf8(output.stdout).map_err(|_| String::from("pactl output is not UTF-8"))
+}
+
+/// An object from the output of `pactl list`.
+#[derive(Debug, Default)]
+pub struct Object {
+    pub index: u32,
+    /// Properties such as `Name: value`.
+    pub fields: Vec<(String, String)>,
+    /// Properties of the `Properties:` list, such as `device.class = "sound"`.
+    pub properties: Vec<(String, String)>,
+    /// Entries of other lists, such as `Ports:`, keyed by the name of the list.
+    pub lists: Vec<(String, Vec<String>)>,
+}
+
+impl Object {
+    pub fn field(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Lists and parses every object of a kind, such as `sinks` or `sink-inputs`.
+pub fn list(kind: &str) -> Result<Vec<Object>, String> {
+    run(&["list", kind]).map(|stdout| parse(&stdout))
+}
+
+/// Lists every sink or source of the sound server, along with the cards which they belong to.
+pub fn devices(kind: &str, cards: &[Object]) -> Result<Vec<Device>, String> {
+    let objects = list(kind)?;
+
+    Ok(objects
+        .iter()
+        .map(|object| {
+            let ports = ports(object);
+            let active_port = object.field("Active Port").unwrap_or_default();
+
+            let (port, port_type) = ports
+                .iter()
+                .find(|(port, _)| port.name == active_port)
+                .map_or((active_port, ""), |(port, port_type)| {
+                    (port.description.as_str(), port_type.as_str())
+                });
+
+            // Sound cards only tell what they are by the type of their port, such as the
+            // headphone jack of a laptop.
+            let form_factor = object
+                .property("device.form_factor")
+                .map_or_else(|| port_type.to_lowercase(), str::to_owned);
+
+            let card = cards
+                .iter()
+                .find(|card| same_card(object, card))
+                .and_then(|card| card.field("Name"))
+                .unwrap_or_default()
+                .to_owned();
+
+            Device {
+                index: object.index,
+                name: object.field("Name").unwrap_or_default().to_owned(),
+                description: object.field("Description").unwrap_or_default().to_owned(),
+                icon_name: object
+                    .property("device.icon_name")
+                    .unwrap_or("audio-card")
+                    .to_owned(),
+                mute: object.field("Mute") == Some("yes"),
+                volume: volumes(object.field("Volume").unwrap_or_default())
+                    .max()
+                    .unwrap_or(0),
+                monitor: matches!(object.field("Monitor of Sink"), Some(sink) if sink != "n/a"),
+                port: port.to_owned(),
+                form_factor,
+                active_port: active_port.to_owned(),
+                ports: ports.into_iter().map(|(port, _)| port).collect(),
+                channel_map: object
+                    .field("Channel Map")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|channel| !channel.is_empty())
+                    .map(str::to_owned)
+                    .collect(),
+                channel_volumes: raw_volumes(object.field("Volume").unwrap_or_default()),
+                card,
+            }
+        })
+        .collect())
+}
+
+/// Whether a sink or source belongs to a card, which devices do not name. Both share the
+/// bus path of the hardware, or the address of a Bluetooth device.
+fn same_card(device: &Object, card: &Object) -> bool {
+    ["device.bus_path", "api.bluez5.address", "bluez.path"]
+        .iter()
+        .find_map(|key| Some(device.property(key)? == card.property(key)?))
+        .unwrap_or(false)
+}
+
+/// A card with its profiles, from entries of its `Profiles:` list such as
+/// `output:analog-stereo: Analog Stereo Output (sinks: 1, ..., available: yes)`.
+pub fn card(object: &Object) -> Card {
+    let profiles = entries(object, "Profiles")
+        .filter_map(|entry| {
+            let (name, rest) = entry.split_once(": ")?;
+            let (description, details) = rest.rsplit_once(" (").unwrap_or((rest, ""));
+
+            Some(Profile {
+                name: name.to_owned(),
+                description: description.to_owned(),
+                available: !details.contains("available: no"),
+            })
+        })
+        .collect();
+
+    Card {
+        name: object.field("Name").unwrap_or_default().to_owned(),
+        description: object
+            .property("device.description")
+            .unwrap_or_default()
+            .to_owned(),
+        profiles,
+        active_profile: object
+            .field("Active Profile")
+            .unwrap_or_default()
+            .to_owned(),
+    }
+}
+
+/// Lists every stream which is played to a sink, leaving out the sounds of events which
+/// only play for a moment.
+pub fn streams() -> Result<Vec<Stream>, String> {
+    let objects = list("sink-inputs")?;
+
+    Ok(objects
+        .iter()
+        .filter(|object| object.property("media.role") != Some("event"))
+        .map(|object| {
+            let application = object
+                .property("application.name")
+                .or_else(|| object.property("application.process.binary"))
+                .or_else(|| object.property("media.name"))
+                .unwrap_or_default()
+                .to_owned();
+
+            Stream {
+                index: object.index,
+                application,
+                icon_name: object
+                    .property("application.icon_name")
+                    .unwrap_or("application-x-executable")
+                    .to_owned(),
+                media: object.property("media.name").unwrap_or_default().to_owned(),
+                mute: object.field("Mute") == Some("yes"),
+                volume: volumes(object.field("Volume").unwrap_or_default())
+                    .max()
+                    .unwrap_or(0),
+            }
+        })
+        .collect())
+}
+
+/// The ports of a device with their types, from the entries of its `Ports:` list such as
+/// `analog-output-headphones: Headphones (type: Headphones, ..., not available)`.
+fn ports(object: &Object) -> Vec<(Port, String)> {
+    entries(object, "Ports")
+        .filter_map(|entry| {
+            let (name, rest) = entry.split_once(": ")?;
+            let (description, details) = rest.rsplit_once(" (")?;
+
+            let port_type = details
+                .strip_prefix("type: ")
+                .and_then(|details| details.split([',', ')']).next())
+                .unwrap_or_default();
+
+            let port = Port {
+                name: name.to_owned(),
+                description: description.to_owned(),
+                available: !details.contains("not available"),
+            };
+
+            Some((port, port_type.to_owned()))
+        })
+        .collect()
+}
+
+/// The entries of every list of an object by the name of the list.
+fn entries<'a>(object: &'a Object, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    object
+        .lists
+        .iter()
+        .filter(move |(list, _)| list == name)
+        .flat_map(|(_, entries)| entries)
+        .map(String::as_str)
+}
+
+/// The raw volumes of each channel of a volume such as
+/// `front-left: 26214 /  40% / -23.88 dB,   front-right: 26214 /  40% / -23.88 dB`.
+fn raw_volumes(volume: &str) -> Vec<u32> {
+    volume
+        .split(',')
+        .filter_map(|channel| {
+            let (_, value) = channel.split_once(':')?;
+            value.split('/').next()?.trim().parse().ok()
+        })
+        .collect()
+}
+
+/// The percentages of each channel of a volume such as
+/// `front-left: 26214 /  40% / -23.88 dB,   front-right: 26214 /  40% / -23.88 dB`.
+pub fn volumes(volume: &str) -> impl Iterator<Item = u32> + '_ {
+    volume
+        .split_whitespace()
+        .filter_map(|word| word.strip_suffix('%'))
+        .filter_map(|percent| percent.parse().ok())
+}
+
+fn parse(stdout: &str) -> Vec<Object> {
+    let mut objects = Vec::new();
+    let mut object: Option<Object> = None;
+    let mut list = String::new();
+
+    for line in stdout.lines() {
+        let depth = line.chars().take_while(|&c| c == '\t').count();
+        let line = line.trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if depth == 0 {
+            if let Some((_, index)) = line.rsplit_once('#') {
+                objects.extend(object.take());
+                object = Some(Object {
+                    index: index.parse().unwrap_or(0),
+                    ..Object::default()
+                });
+            }
+
+            continue;
+        }
+
+        let Some(object) = object.as_mut() else {
+            continue;
+        };
+
+        if depth == 1 {
+            match line.split_once(':') {
+                Some((key, "")) => {
+                    key.clone_into(&mut list);
+                    object.lists.push((list.clone(), Vec::new()));
+                }
+                Some((key, value)) => {
+                    list.clear();
+                    object
+                        .fields
+                        .push((key.to_owned(), value.trim().to_owned()));
+                }
+                // Continuation of the previous field, such as the balance of a volume.
+                None => (),
+            }
+        } else if list == "Properties" {
+            if let Some((key, value)) = line.split_once(" = ") {
+                let value = value.trim_matches('"');
+                object.properties.push((key.to_owned(), value.to_owned()));
+            }
+        } else if let Some((_, entries)) = object.lists.last_mut() {
+            entries.push(line.to_owned());
+        }
+    }
+
+    objects.extend(object);
+    objects
+}
@@ -0,0 +1,182 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Voice samples played on one channel of a sink at a time, to test where its speakers are.
+
+use crate::alerts;
+use std::{
+    f64::consts::TAU,
+    fs,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    sync::Mutex,
+};
+
+/// The test which is playing, which is stopped before the next one plays.
+static PLAYING: Mutex<Option<Child>> = Mutex::new(None);
+
+/// The tone played on channels for which the sound theme has no voice sample.
+const TONE_FREQUENCY: f64 = 440.0;
+const TONE_RATE: u32 = 48_000;
+const TONE_SAMPLES: u32 = TONE_RATE;
+
+/// Plays the voice sample of a channel, such as `front-left`, on only that channel of a sink.
+///
+/// The sample is played by a stream of its own, with a single channel at the position of
+/// the channel, in the language of the user where the sound theme has it. Channels without
+/// a sample play a tone. Whatever test was playing is stopped first.
+///
+/// # Errors
+///
+/// Fails if the sample could not be played.
+pub fn play(sink: &str, position: &str) -> Result<(), String> {
+    stop();
+
+    let sample = match sample(position) {
+        Some(path) => path,
+        None => tone()?,
+    };
+
+    let child = Command::new("pw-play")
+        .arg(format!("--target={sink}"))
+        .arg("--media-role=Test")
+        .arg("--channels=1")
+        .arg(format!("--channel-map={}", pipewire_position(position)))
+        .arg(sample)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|why| format!("failed to execute pw-play: {why}"))?;
+
+    if let Ok(mut playing) = PLAYING.lock() {
+        *playing = Some(child);
+    }
+
+    Ok(())
+}
+
+/// Stops the test which is playing, if any.
+pub fn stop() {
+    let Ok(mut playing) = PLAYING.lock() else {
+        return;
+    };
+
+    if let Some(mut child) = playing.take() {
+        let _res = child.kill();
+        let _res = child.wait();
+    }
+}
+
+/// The position of a channel as PipeWire names it, such as `FL` for `front-left`.
+fn pipewire_position(position: &str) -> String {
+    let known = match position {
+        "mono" => "MONO",
+        "front-left" => "FL",
+        "front-right" => "FR",
+        "front-center" => "FC",
+        "lfe" => "LFE",
+        "rear-left" => "RL",
+        "rear-right" => "RR",
+        "rear-center" => "RC",
+        "side-left" => "SL",
+        "side-right" => "SR",
+        "front-left-of-center" => "FLC",
+        "front-right-of-center" => "FRC",
+        "top-center" => "TC",
+        "top-front-left" => "TFL",
+        "top-front-right" => "TFR",
+        "top-front-center" => "TFC",
+        "top-rear-left" => "TRL",
+        "top-rear-right" => "TRR",
+        "top-rear-center" => "TRC",
+        // Auxiliary channels, such as `aux0`, are named alike in capitals.
+        other => return other.replace('-', "").to_uppercase(),
+    };
+
+    known.to_owned()
+}
+
+/// The voice sample of the freedesktop sound theme for a channel, in the language of the
+/// user if the theme has it, or else in its default language.
+fn sample(position: &str) -> Option<PathBuf> {
+    let name = format!("audio-channel-{position}.oga");
+    let locales = locales();
+
+    alerts::data_dirs()
+        .into_iter()
+        .map(|dir| dir.join("sounds/freedesktop"))
+        .flat_map(|theme| {
+            let localized = locales
+                .iter()
+                .flat_map(|locale| {
+                    [
+                        theme.join(locale).join("stereo"),
+                        theme.join("stereo").join(locale),
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+            localized.into_iter().chain([theme.join("stereo")])
+        })
+        .map(|dir| dir.join(&name))
+        .find(|path| path.is_file())
+}
+
+/// The languages of the user, from the most to the least specific, such as `pt_BR` and `pt`.
+fn locales() -> Vec<String> {
+    let lang = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_default();
+
+    let locale = lang.split(['.', '@']).next().unwrap_or_default();
+
+    if locale.is_empty() || locale == "C" || locale == "POSIX" {
+        return Vec::new();
+    }
+
+    let mut locales = vec![locale.to_owned()];
+
+    if let Some((language, _)) = locale.split_once('_') {
+        locales.push(language.to_owned());
+    }
+
+    locales
+}
+
+/// A short sine tone, which is written once to a temporary file to be played.
+fn tone() -> Result<PathBuf, String> {
+    let path = std::env::temp_dir().join("cosmic-settings-speaker-test.wav");
+
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    let data_size = TONE_SAMPLES * 2;
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16_u32.to_le_bytes());
+    // Uncompressed 16-bit mono.
+    wav.extend_from_slice(&1_u16.to_le_bytes());
+    wav.extend_from_slice(&1_u16.to_le_bytes());
+    wav.extend_from_slice(&TONE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(TONE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2_u16.to_le_bytes());
+    wav.extend_from_slice(&16_u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+
+    for index in 0..TONE_SAMPLES {
+        let time = f64::from(index) / f64::from(TONE_RATE);
+        // Faded in and out, so that it does not click.
+        let envelope = (time * 20.0).min(1.0).min((1.0 - time) * 20.0);
+        let sample = (time * TONE_FREQUENCY * TAU).sin() * envelope * 0.5;
+        wav.extend_from_slice(&((sample * f64::from(i16::MAX)) as i16).to_le_bytes());
+    }
+
+    fs::write(&path, wav).map_err(|why| why.to_string())?;
+    Ok(path)
+}
@@ -0,0 +1,26 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+use std::process::Command;
+
+/// Refreshes the firmware metadata from the configured fwupd remotes.
+///
+/// The refresh is forced past the interval which fwupd keeps between refreshes, so it is
+/// only to be run when the user asks for it.
+///
+/// # Errors
+///
+/// Returns the error reported by `fwupdmgr` if the refresh failed.
+pub fn refresh() -> Result<(), String> {
+    let output = Command::new("fwupdmgr")
+        .args(["refresh", "--force", "--no-unreported-check"])
+        .output()
+        .map_err(|why| format!("failed to execute fwupdmgr: {why}"))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(String::from(stderr.trim()))
+}
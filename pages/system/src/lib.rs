@@ -1,6 +1,8 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: MPL-2.0
 
+pub mod firmware;
+
 use bumpalo::Bump;
 use std::{ffi::OsStr, io::Read};
 use sysinfo::{DiskExt, SystemExt};
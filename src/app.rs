@@ -4,14 +4,14 @@
 use apply::Apply;
 
 use cosmic::{
-    iced::widget::{self, column, container, horizontal_space, row},
+    iced::widget::{self, button, column, container, horizontal_space, row},
     iced::{self, Application, Command, Length, Subscription},
-    iced_native::{subscription, window},
+    iced_native::{event, keyboard, mouse, subscription, window},
     iced_winit::window::{close, drag, minimize, toggle_maximize},
     keyboard_nav,
-    theme::Theme,
+    theme::{self, Theme},
     widget::{
-        header_bar, nav_bar, nav_bar_toggle, scrollable, search, segmented_button, settings,
+        header_bar, icon, nav_bar, nav_bar_toggle, scrollable, segmented_button, settings, text,
         IconSource,
     },
     Element, ElementExt,
@@ -19,50 +19,209 @@ use cosmic::{
 
 use crate::{
     config::{self, Config},
-    page::{self, desktop, section, sound, system, time, Page},
-    widget::{page_title, parent_page_button, search_header, sub_page_button},
+    file_chooser, icon_theme,
+    page::{
+        self, accessibility, applications, desktop, display, input, networking, power, section,
+        sound, system, time, Page,
+    },
+    settings_file,
+    widget::{
+        confirm_dialog, dialog, expander, link_row, page_title, parent_page_button, popover,
+        search_header,
+        search_input::{self, SearchInput},
+        spinner, sub_page_button,
+        toast::{self, toast, Toast},
+        Confirm,
+    },
 };
+use i18n_embed::unic_langid::LanguageIdentifier;
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+/// Interval between the frames of animations, such as that of expanding sections.
+const ANIMATION_FRAME: Duration = Duration::from_millis(16);
+
+/// Progress made by an animation in each frame, which completes it in about 200 ms.
+const ANIMATION_STEP: f32 = 0.08;
+
+/// Interval between checks of whether the icon theme has changed.
+const ICON_THEME_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Relative distance which the page is scrolled by at full speed, while a row is dragged
+/// near its edges.
+const AUTOSCROLL_STEP: f32 = 0.02;
+
+/// The side buttons of a mouse which go back and forward through the history.
+///
+/// They are numbered 8 and 9 on X11, and by their evdev codes on Wayland, of which only the
+/// lowest byte is kept.
+const MOUSE_BACK: [u8; 2] = [8, 0x13];
+const MOUSE_FORWARD: [u8; 2] = [9, 0x14];
+
+/// The config which lists the confirmations that were chosen not to be asked again.
+const CONFIRM_SKIPPED: &str = "confirm-skipped";
 
 #[allow(clippy::struct_excessive_bools)]
 #[allow(clippy::module_name_repetitions)]
 pub struct SettingsApp {
     pub active_page: page::Entity,
+    /// The pages which were visited, which are gone back and forth through as in a browser.
+    pub history: Vec<page::Entity>,
+    /// The place of the active page in the history.
+    pub history_cursor: usize,
     pub config: Config,
     pub config_path: config::PathManager,
     pub debug: bool,
+    /// The icon theme which the icons of pages were resolved in.
+    pub icon_theme: String,
     pub is_condensed: bool,
     pub nav_bar_toggled_condensed: bool,
     pub nav_bar_toggled: bool,
     pub nav_bar: segmented_button::SingleSelectModel,
+    /// Whether the overflow menu of the active page is open.
+    pub page_menu: bool,
     pub pages: page::Model,
+    /// A page which was opened from another with `Message::OpenPage`, and the page which it
+    /// returns to when it is left, in place of its parent.
+    pub return_to: Option<(page::Entity, page::Entity)>,
+    /// An action which is shown to be confirmed in place of the page, until it is answered.
+    pub confirm: Option<Confirm>,
+    /// Whether "don't ask again" is checked in the confirmation.
+    pub confirm_dont_ask: bool,
     pub scaling_factor: f32,
-    pub search: search::Model,
-    pub search_selections: Vec<(page::Entity, section::Entity)>,
+    /// The scrollable of the page, which is scrolled while a row is dragged near its edges.
+    pub scroll_id: widget::scrollable::Id,
+    /// The relative offset which the page is scrolled to.
+    pub scroll_offset: f32,
+    pub search: SearchInput,
+    pub search_selections: Vec<(page::Entity, Vec<section::Entity>)>,
     pub show_maximize: bool,
     pub show_minimize: bool,
+    /// The step of the spinners which are shown beside busy pages.
+    pub spinner_step: usize,
     pub theme: Theme,
     pub title: String,
+    /// Brief messages stacked at the bottom of the window, by their IDs, and when they were
+    /// shown.
+    pub toasts: Vec<(u64, Toast, Instant)>,
+    next_toast: u64,
+    pub unsaved_changes: Option<Navigation>,
     pub window_width: u32,
 }
 
+/// Navigation that is deferred until the user decides what to do with unapplied changes.
+#[derive(Clone, Copy, Debug)]
+pub enum Navigation {
+    Close,
+    Page(page::Entity),
+    /// A page of the history, by its place in it.
+    History(usize),
+}
+
+/// The choices offered when leaving a page with unapplied changes.
+#[derive(Clone, Copy, Debug)]
+pub enum UnsavedChanges {
+    Apply,
+    Cancel,
+    Discard,
+}
+
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
 pub enum Message {
     About(system::about::Message),
+    Accessibility(accessibility::Message),
+    Animate,
+    Appearance(desktop::appearance::Message),
+    /// Scrolls the page at a speed from -1 to 1, while a row is dragged near its edges.
+    AutoScroll(f32),
+    Autostart(applications::startup::Message),
     Close,
+    /// Checks or unchecks "don't ask again" in the confirmation of an action.
+    ConfirmDontAsk(bool),
+    /// The answer to the confirmation of an action.
+    ConfirmResult(dialog::Response),
+    /// Copies a value to the clipboard, such as that of a copy row.
+    CopyToClipboard(String),
     DateAndTime(time::date::Message),
+    DefaultApps(applications::defaults::Message),
     Desktop(desktop::Message),
+    DismissToast(u64),
+    Display(display::Message),
+    Dock(desktop::dock::Message),
     Drag,
+    /// Asks where to export the settings to, and then exports them.
+    ExportSettings,
+    Firmware(system::firmware::Message),
+    IconThemeCheck,
+    /// The name of the icon theme which is active, as it was read in the background.
+    IconThemeChecked(String),
+    /// The names of every icon in the icon theme, which the icons of pages are resolved in.
+    IconThemeLoaded(HashSet<String>),
+    /// Asks for an export to import the settings of, and then imports them.
+    ImportSettings,
+    IpConfig(networking::ip_config::Message),
+    Keyboard(input::keyboard::Message),
     KeyboardNav(keyboard_nav::Message),
+    /// Localizes the app again in the languages of the system locale, once they have changed.
+    LanguageChanged(Vec<LanguageIdentifier>),
     Maximize,
     Minimize,
+    Mouse(input::mouse::Message),
     NavBar(segmented_button::Entity),
+    /// Goes back to the page which was visited before the active one.
+    NavigateBack,
+    /// Goes forward again to the page which was gone back from.
+    NavigateForward,
+    Networking(networking::Message),
+    NightLight(display::night_light::Message),
     None,
+    /// Opens a hidden page by its ID, which returns to the active page when it is left.
+    OpenPage(&'static str),
+    /// Opens a URL in the browser, such as that of a link row.
+    OpenUrl(String),
     Page(page::Entity),
-    Search(search::Message),
+    PageBusy(page::Entity, bool),
+    /// Marks a page as having changes which have not been applied yet, or as having none.
+    PageDirty(page::Entity, bool),
+    PageMenu(bool),
+    /// Changes the title of a page, and of its entry in the nav bar.
+    PageTitle(page::Entity, String),
+    Power(power::Message),
+    ResetPageDefaults(page::Entity),
+    Scrolled(f32),
+    Search(search_input::Message),
+    /// Searches the settings for a phrase, once it has been typed.
+    SearchChanged(String),
+    SectionExpanded(section::Entity, bool),
+    SectionRetry(section::Entity),
+    SectionStateChanged(section::Entity, page::SectionState),
+    /// The number of settings exported, or none if no file was chosen.
+    SettingsExported(Result<Option<usize>, String>),
+    SettingsImported(Result<Option<settings_file::ImportReport>, String>),
+    /// Asks to confirm an action, which emits its message once it is confirmed.
+    ShowConfirm(Confirm),
+    ShowToast(Toast),
+    Sound(sound::Message),
+    /// Turns the spinners which are shown beside busy pages.
+    SpinnerStep,
+    Tablet(input::tablet::Message),
+    /// Emits the message of the action of a toast, and dismisses it.
+    ToastAction(u64),
+    /// Dismisses the toasts which have been shown for long enough.
+    ToastTimeout,
     ToggleNavBar,
     ToggleNavBarCondensed,
+    UnsavedChanges(UnsavedChanges),
+    Wallpaper(desktop::wallpaper::Message),
+    Wifi(networking::wifi::Message),
+    WifiHidden(networking::wifi::hidden::Message),
+    WifiHotspot(networking::wifi::hotspot::Message),
     WindowResize(u32, u32),
+    Wired(networking::wired::Message),
+    Workspaces(desktop::workspaces::Message),
 }
 
 impl Application for SettingsApp {
@@ -76,37 +235,49 @@ impl Application for SettingsApp {
 
         let mut app = SettingsApp {
             active_page: page::Entity::default(),
+            history: Vec::new(),
+            history_cursor: 0,
             config: config_path.config("main", Config::deserialize),
             config_path,
             debug: false,
+            icon_theme: icon_theme::current(),
             is_condensed: false,
             nav_bar: segmented_button::Model::default(),
             nav_bar_toggled: true,
             nav_bar_toggled_condensed: false,
+            page_menu: false,
             pages: page::Model::default(),
+            return_to: None,
+            confirm: None,
+            confirm_dont_ask: false,
             title: crate::fl!("app"),
             scaling_factor: std::env::var("COSMIC_SCALE")
                 .ok()
                 .and_then(|scale| scale.parse::<f32>().ok())
                 .unwrap_or(1.0),
-            search: search::Model::default(),
+            scroll_id: widget::scrollable::Id::unique(),
+            scroll_offset: 0.0,
+            search: SearchInput::default(),
             search_selections: Vec::default(),
             show_maximize: true,
             show_minimize: true,
+            spinner_step: 0,
             theme: Theme::Dark,
+            toasts: Vec::new(),
+            next_toast: 0,
+            unsaved_changes: None,
             window_width: 0,
         };
 
-        // app.insert_page::<wifi::Page>();
-        // app.insert_page::<networking::Page>();
+        app.insert_page::<networking::Page>();
         // app.insert_page::<bluetooth::Page>();
 
         app.insert_page::<desktop::Page>();
 
-        // app.insert_page::<input::Page>();
+        app.insert_page::<input::Page>();
 
-        // app.insert_page::<displays::Page>();
-        // app.insert_page::<power::Page>();
+        app.insert_page::<display::Page>();
+        app.insert_page::<power::Page>();
 
         app.insert_page::<sound::Page>();
 
@@ -116,17 +287,15 @@ impl Application for SettingsApp {
         app.insert_page::<system::Page>();
         app.insert_page::<time::Page>();
 
-        // app.insert_page::<accessibility::Page>();
-        // app.insert_page::<applications::Page>();
+        app.insert_page::<accessibility::Page>();
+        app.insert_page::<applications::Page>();
 
-        let mut command = Command::none();
+        let command = match app.pages.find_page_by_id(&app.config.active_page) {
+            Some(page) => app.activate_page(page),
+            None => Command::none(),
+        };
 
-        for (id, info) in app.pages.pages.iter() {
-            if info.id == &*app.config.active_page {
-                command = app.activate_page(id);
-                break;
-            }
-        }
+        let command = Command::batch(vec![command, load_icon_theme(app.icon_theme.clone())]);
 
         (app, command)
     }
@@ -140,13 +309,70 @@ impl Application for SettingsApp {
             iced::Event::Window(_window_id, window::Event::Resized { width, height }) => {
                 Some(Message::WindowResize(width, height))
             }
+            // Closing the window from the compositor asks about unapplied changes first, as
+            // the close button of the header does.
+            iced::Event::Window(_window_id, window::Event::CloseRequested) => Some(Message::Close),
             _ => None,
         });
 
-        Subscription::batch(vec![
+        // Slash focuses the search, and Alt with the arrows goes through the history, as they
+        // do in browsers, unless they are typed into an entry.
+        let shortcuts = subscription::events_with(|event, status| match event {
+            iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: keyboard::KeyCode::Slash,
+                modifiers,
+            }) if status == event::Status::Ignored
+                && !(modifiers.control() || modifiers.alt() || modifiers.logo()) =>
+            {
+                Some(Message::KeyboardNav(keyboard_nav::Message::Search))
+            }
+
+            iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) if status == event::Status::Ignored && modifiers.alt() => match key_code {
+                keyboard::KeyCode::Left => Some(Message::NavigateBack),
+                keyboard::KeyCode::Right => Some(Message::NavigateForward),
+                _ => None,
+            },
+
+            iced::Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Other(button))) => {
+                if MOUSE_BACK.contains(&button) {
+                    Some(Message::NavigateBack)
+                } else if MOUSE_FORWARD.contains(&button) {
+                    Some(Message::NavigateForward)
+                } else {
+                    None
+                }
+            }
+
+            _ => None,
+        });
+
+        let mut subscriptions = vec![
             window_break,
+            shortcuts,
+            iced::time::every(ICON_THEME_INTERVAL).map(|_| Message::IconThemeCheck),
             keyboard_nav::subscription().map(Message::KeyboardNav),
-        ])
+            self.search.subscription().map(Message::Search),
+            self.pages.subscription(self.active_page),
+            crate::localize::changes(),
+        ];
+
+        if self.pages.is_animating() {
+            subscriptions.push(iced::time::every(ANIMATION_FRAME).map(|_| Message::Animate));
+        }
+
+        if !self.pages.busy.is_empty() {
+            subscriptions.push(iced::time::every(spinner::STEP).map(|_| Message::SpinnerStep));
+        }
+
+        if !self.toasts.is_empty() {
+            subscriptions
+                .push(iced::time::every(Duration::from_secs(1)).map(|_| Message::ToastTimeout));
+        }
+
+        Subscription::batch(subscriptions)
     }
 
     fn update(&mut self, message: Message) -> iced::Command<Self::Message> {
@@ -162,18 +388,221 @@ impl Application for SettingsApp {
                 keyboard_nav::Message::FocusNext => ret = widget::focus_next(),
                 keyboard_nav::Message::FocusPrevious => ret = widget::focus_previous(),
                 keyboard_nav::Message::Escape => {
-                    if self.search.is_active() {
-                        self.search.state = search::State::Inactive;
-                        self.search_clear();
+                    if let Some(phrase) = self.search.update(search_input::Message::Clear) {
+                        self.search_changed(phrase);
                     }
                 }
+                // Pressed as Ctrl+F or Slash from any page, while no entry has focus.
                 keyboard_nav::Message::Search => {
                     return self.search.focus();
                 }
             },
             Message::Page(page) => return self.activate_page(page),
+            Message::OpenPage(id) => {
+                if let Some(page) = self.pages.find_page_by_id(id) {
+                    let from = self.active_page;
+                    let command = self.activate_page(page);
+
+                    // The page returns to where it was opened from, once it was activated,
+                    // unless that is beneath it, as returning there would lead back to it.
+                    let beneath =
+                        std::iter::successors(Some(from), |&page| self.pages.parent(page))
+                            .any(|ancestor| ancestor == page);
+
+                    if !beneath && self.active_page == page {
+                        self.return_to = Some((page, from));
+                    }
+
+                    return command;
+                }
+            }
+            Message::PageBusy(page, busy) => self.set_busy(page, busy),
+            Message::PageDirty(page, dirty) => self.pages.set_dirty(page, dirty),
+            Message::PageMenu(open) => self.page_menu = open,
+            Message::IconThemeCheck => {
+                return Command::perform(page::background(icon_theme::current), |theme| {
+                    theme.map_or(Message::None, Message::IconThemeChecked)
+                });
+            }
+            Message::IconThemeChecked(theme) => {
+                if theme != self.icon_theme {
+                    self.icon_theme = theme.clone();
+                    return load_icon_theme(theme);
+                }
+            }
+            Message::PageTitle(page, title) => {
+                self.pages.set_title(page, title);
+                self.nav_entry_update(page);
+            }
+            Message::IconThemeLoaded(icons) => {
+                self.pages.resolve_icons(&icons);
+
+                let pages: Vec<page::Entity> = self.pages.pages.keys().collect();
+
+                for page in pages {
+                    self.nav_entry_update(page);
+                }
+            }
+            Message::SpinnerStep => {
+                self.spinner_step = self.spinner_step.wrapping_add(1);
+
+                let busy: Vec<page::Entity> = self.pages.busy.keys().collect();
+
+                for page in busy {
+                    self.nav_entry_update(page);
+                }
+            }
+            Message::OpenUrl(url) => {
+                return Command::perform(link_row::open_url(url), |result| match result {
+                    Ok(()) => Message::None,
+                    Err(why) => Message::ShowToast(Toast::error(why)),
+                });
+            }
+            Message::CopyToClipboard(value) => {
+                self.show_toast(Toast::new(crate::fl!("copy-row", "copied")));
+                return iced::clipboard::write(value);
+            }
+            Message::ShowToast(toast) => self.show_toast(toast),
+            Message::DismissToast(id) => self.toasts.retain(|(shown, ..)| *shown != id),
+            Message::ToastAction(id) => {
+                let Some(index) = self.toasts.iter().position(|(shown, ..)| *shown == id) else {
+                    return Command::none();
+                };
+
+                let (_, toast, _) = self.toasts.remove(index);
+
+                if let Some((_, message)) = toast.action {
+                    return self.update(*message);
+                }
+            }
+            Message::AutoScroll(speed) => {
+                self.scroll_offset = (self.scroll_offset + speed * AUTOSCROLL_STEP).clamp(0.0, 1.0);
+                return widget::scrollable::snap_to(self.scroll_id.clone(), self.scroll_offset);
+            }
+            Message::Scrolled(offset) => self.scroll_offset = offset,
+            Message::ToastTimeout => self
+                .toasts
+                .retain(|(_, toast, shown)| shown.elapsed() < toast.duration()),
+            Message::ExportSettings => {
+                self.page_menu = false;
+
+                return Command::perform(settings_file::choose_export(), Message::SettingsExported);
+            }
+            Message::ImportSettings => {
+                self.page_menu = false;
+
+                return Command::perform(settings_file::choose_import(), Message::SettingsImported);
+            }
+            Message::SettingsExported(Ok(Some(count))) => {
+                let text = crate::fl!("settings-file", "exported", count = count);
+                self.show_toast(Toast::success(text));
+            }
+            Message::SettingsImported(Ok(Some(report))) => {
+                let mut text = crate::fl!(
+                    "settings-file",
+                    "imported",
+                    applied = report.applied.len(),
+                    skipped = report.skipped.len()
+                );
+
+                let mut severity = toast::Severity::Success;
+
+                if !report.skipped.is_empty() {
+                    severity = toast::Severity::Warning;
+                    text.push('\n');
+                    text.push_str(&crate::fl!(
+                        "settings-file",
+                        "skipped",
+                        keys = report.skipped.join(", ")
+                    ));
+                }
+
+                self.show_toast(Toast::new(text).severity(severity));
+
+                // The active page is loaded again, so that it shows the imported settings.
+                if let Some(command) = self.pages.init_page(self.active_page) {
+                    return command;
+                }
+            }
+            Message::SettingsExported(Ok(None)) | Message::SettingsImported(Ok(None)) => {}
+            Message::SettingsExported(Err(why)) | Message::SettingsImported(Err(why)) => {
+                self.show_toast(Toast::error(why));
+            }
+            Message::ShowConfirm(confirm) => {
+                self.page_menu = false;
+
+                let skipped = confirm.skip_key.map_or(false, |key| {
+                    config::load::<Vec<String>>(CONFIRM_SKIPPED)
+                        .iter()
+                        .any(|skipped| skipped == key)
+                });
+
+                if skipped {
+                    return self.update(*confirm.on_confirm);
+                }
+
+                self.confirm = Some(confirm);
+                self.confirm_dont_ask = false;
+            }
+            Message::ConfirmDontAsk(dont_ask) => self.confirm_dont_ask = dont_ask,
+            Message::ConfirmResult(response) => {
+                let Some(confirm) = self.confirm.take() else {
+                    return Command::none();
+                };
+
+                if response.action != dialog::Action::Primary {
+                    return Command::none();
+                }
+
+                if let (Some(key), true) = (confirm.skip_key, response.dont_ask_again) {
+                    let mut skipped = config::load::<Vec<String>>(CONFIRM_SKIPPED);
+                    skipped.push(key.to_owned());
+                    config::store(CONFIRM_SKIPPED, &skipped);
+                }
+
+                return self.update(*confirm.on_confirm);
+            }
+            Message::ResetPageDefaults(page) => return self.pages.reset_defaults(page),
+            Message::SectionExpanded(section, expanded) => {
+                self.pages.set_expanded(section, expanded);
+            }
+            Message::SectionStateChanged(section, state) => {
+                self.pages.set_section_state(section, state);
+            }
+            Message::SectionRetry(section) => {
+                let Some(page) = self.pages.section_page(section) else {
+                    return ret;
+                };
+
+                self.pages
+                    .set_section_state(section, page::SectionState::Loading);
+
+                return self.pages.init_page(page).unwrap_or(Command::none());
+            }
+            Message::Animate => self.pages.animate(ANIMATION_STEP),
+            Message::UnsavedChanges(choice) => {
+                let page = self.active_page;
+
+                let Some(navigation) = self.unsaved_changes.take() else {
+                    return ret;
+                };
+
+                match choice {
+                    UnsavedChanges::Apply => {
+                        let apply = self.pages.apply(page);
+                        return Command::batch(vec![apply, self.navigate(navigation)]);
+                    }
+
+                    UnsavedChanges::Cancel => self.activate_navbar(page),
+
+                    UnsavedChanges::Discard => {
+                        self.pages.set_dirty(page, false);
+                        return self.navigate(navigation);
+                    }
+                }
+            }
             Message::Drag => return drag(window::Id::new(0)),
-            Message::Close => return close(window::Id::new(0)),
+            Message::Close => return self.navigate(Navigation::Close),
             Message::Minimize => return minimize(window::Id::new(0), true),
             Message::Maximize => return toggle_maximize(window::Id::new(0)),
             Message::NavBar(key) => {
@@ -181,20 +610,46 @@ impl Application for SettingsApp {
                     return self.activate_page(page);
                 }
             }
+            Message::NavigateBack => {
+                if let Some(index) = self.history_cursor.checked_sub(1) {
+                    return self.navigate(Navigation::History(index));
+                }
+            }
+            Message::NavigateForward => {
+                if self.history_cursor + 1 < self.history.len() {
+                    return self.navigate(Navigation::History(self.history_cursor + 1));
+                }
+            }
             Message::ToggleNavBar => self.nav_bar_toggled = !self.nav_bar_toggled,
             Message::ToggleNavBarCondensed => {
                 self.nav_bar_toggled_condensed = !self.nav_bar_toggled_condensed;
             }
-            Message::Search(search::Message::Activate) => {
-                return self.search.focus();
-            }
-            Message::Search(search::Message::Changed(phrase)) => {
-                self.search_changed(phrase);
+            Message::Search(message) => {
+                if let Some(phrase) = self.search.update(message) {
+                    return self.update(Message::SearchChanged(phrase));
+                }
             }
-            Message::Search(search::Message::Clear) => {
-                self.search_clear();
+            Message::SearchChanged(phrase) => self.search_changed(phrase),
+            Message::LanguageChanged(languages) => {
+                if let Err(why) = crate::localize::localizer().select(&languages) {
+                    self.show_toast(Toast::error(why.to_string()));
+                    return Command::none();
+                }
+
+                self.pages.reindex_search();
+
+                let pages: Vec<page::Entity> = self.pages.pages.keys().collect();
+
+                for page in pages {
+                    self.nav_entry_update(page);
+                }
+
+                // Results are found again in the new language.
+                if self.search.is_active() {
+                    self.search_changed(self.search.phrase().to_owned());
+                }
             }
-            Message::None | Message::Search(_) => {}
+            Message::None => {}
             Message::About(message) => {
                 if let Some(model) = self.pages.resource_mut::<system::about::Model>() {
                     model.update(message);
@@ -202,7 +657,7 @@ impl Application for SettingsApp {
             }
             Message::Desktop(message) => {
                 if let Some(model) = self.pages.resource_mut::<desktop::Model>() {
-                    model.update(message);
+                    ret = model.update(message);
                 }
             }
             Message::DateAndTime(message) => {
@@ -210,6 +665,120 @@ impl Application for SettingsApp {
                     model.update(message);
                 }
             }
+            Message::Sound(message) => {
+                if let Some(model) = self.pages.resource_mut::<sound::Sound>() {
+                    ret = model.update(message);
+                    let (page, state) = (model.page, model.state.clone());
+                    self.pages.set_page_state(page, &state);
+                }
+            }
+            Message::Appearance(message) => {
+                if let Some(model) = self.pages.resource_mut::<desktop::appearance::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Autostart(message) => {
+                if let Some(model) = self.pages.resource_mut::<applications::startup::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::DefaultApps(message) => {
+                if let Some(model) = self.pages.resource_mut::<applications::defaults::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Display(message) => {
+                if let Some(model) = self.pages.resource_mut::<display::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Dock(message) => {
+                if let Some(model) = self.pages.resource_mut::<desktop::dock::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::NightLight(message) => {
+                if let Some(model) = self.pages.resource_mut::<display::night_light::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Power(message) => {
+                if let Some(model) = self.pages.resource_mut::<power::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Keyboard(message) => {
+                if let Some(model) = self.pages.resource_mut::<input::keyboard::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Accessibility(message) => {
+                if let Some(model) = self.pages.resource_mut::<accessibility::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Mouse(message) => {
+                if let Some(model) = self.pages.resource_mut::<input::mouse::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Tablet(message) => {
+                if let Some(model) = self.pages.resource_mut::<input::tablet::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Firmware(message) => {
+                if let Some(model) = self.pages.resource_mut::<system::firmware::Model>() {
+                    ret = model.update(message);
+                    let (page, busy) = (model.page, model.refreshing);
+                    self.set_busy(page, busy);
+                }
+            }
+            Message::Wallpaper(message) => {
+                if let Some(model) = self.pages.resource_mut::<desktop::wallpaper::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Workspaces(message) => {
+                if let Some(model) = self.pages.resource_mut::<desktop::workspaces::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Networking(message) => {
+                if let Some(model) = self.pages.resource_mut::<networking::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Wifi(message) => {
+                if let Some(model) = self.pages.resource_mut::<networking::wifi::Model>() {
+                    ret = model.update(message);
+                    let (page, busy) = (model.page, model.scanning);
+                    self.set_busy(page, busy);
+                }
+            }
+            Message::WifiHidden(message) => {
+                if let Some(model) = self.pages.resource_mut::<networking::wifi::hidden::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::WifiHotspot(message) => {
+                if let Some(model) = self
+                    .pages
+                    .resource_mut::<networking::wifi::hotspot::Model>()
+                {
+                    ret = model.update(message);
+                }
+            }
+            Message::IpConfig(message) => {
+                if let Some(model) = self.pages.resource_mut::<networking::ip_config::Model>() {
+                    ret = model.update(message);
+                }
+            }
+            Message::Wired(message) => {
+                if let Some(model) = self.pages.resource_mut::<networking::wired::Model>() {
+                    ret = model.update(message);
+                }
+            }
         }
         ret
     }
@@ -225,20 +794,51 @@ impl Application for SettingsApp {
             (Message::ToggleNavBar, self.nav_bar_toggled)
         };
 
+        let history_button = |name, message: Option<Message>| {
+            let mut history_button = icon(name, 16)
+                .style(theme::Svg::Symbolic)
+                .apply(button)
+                .style(theme::Button::Transparent);
+
+            if let Some(message) = message {
+                history_button = history_button.on_press(message);
+            }
+
+            history_button
+        };
+
+        // The buttons are disabled at either end of the history.
+        let back = history_button(
+            "go-previous-symbolic",
+            (self.history_cursor > 0).then_some(Message::NavigateBack),
+        );
+
+        let forward = history_button(
+            "go-next-symbolic",
+            (self.history_cursor + 1 < self.history.len()).then_some(Message::NavigateForward),
+        );
+
+        let mut header_start = iced::widget::row!(
+            nav_bar_toggle()
+                .on_nav_bar_toggled(nav_bar_message)
+                .nav_bar_active(nav_bar_toggled),
+            back,
+            forward,
+            self.search
+                .view(&crate::fl!("search-input"))
+                .map(Message::Search)
+        )
+        .align_items(iced::Alignment::Center);
+
+        if self.pages.is_busy(self.active_page) {
+            header_start = header_start.push(spinner(self.spinner_step));
+        }
+
         let mut header = header_bar()
             .title("")
             .on_close(Message::Close)
             .on_drag(Message::Drag)
-            .start(
-                iced::widget::row!(
-                    nav_bar_toggle()
-                        .on_nav_bar_toggled(nav_bar_message)
-                        .nav_bar_active(nav_bar_toggled),
-                    search::search(&self.search, Message::Search)
-                )
-                .align_items(iced::Alignment::Center)
-                .into(),
-            );
+            .start(header_start.into());
 
         if self.show_maximize {
             header = header.on_maximize(Message::Maximize);
@@ -267,18 +867,24 @@ impl Application for SettingsApp {
             widgets.push(
                 scrollable(row![
                     horizontal_space(Length::Fill),
-                    (if self.search.is_active() {
+                    (if self.unsaved_changes.is_some() {
+                        self.unsaved_changes_view()
+                    } else if let Some(confirm) = &self.confirm {
+                        self.confirm_view(confirm)
+                    } else if self.search.is_active() {
                         self.search_view()
-                    } else if let Some(sub_pages) = self.pages.sub_pages(self.active_page) {
-                        self.sub_page_view(sub_pages)
                     } else if let Some(content) = self.pages.content(self.active_page) {
                         self.page_view(content)
+                    } else if let Some(sub_pages) = self.pages.sub_pages(self.active_page) {
+                        self.sub_page_view(sub_pages)
                     } else {
                         panic!("page without sub-pages or content");
                     })
                     .debug(self.debug),
                     horizontal_space(Length::Fill),
                 ])
+                .id(self.scroll_id.clone())
+                .on_scroll(Message::Scrolled)
                 .into(),
             );
         }
@@ -289,7 +895,26 @@ impl Application for SettingsApp {
             .height(Length::Fill)
             .into();
 
-        column(vec![header, content]).into()
+        let mut column_children = vec![header, content];
+
+        // The newest toast is stacked at the bottom, closest to where the first was shown.
+        for (id, current, _) in &self.toasts {
+            column_children.push(
+                row![
+                    horizontal_space(Length::Fill),
+                    toast(
+                        current,
+                        Message::ToastAction(*id),
+                        Message::DismissToast(*id)
+                    ),
+                    horizontal_space(Length::Fill),
+                ]
+                .padding([0, 8, 8, 8])
+                .into(),
+            );
+        }
+
+        column(column_children).into()
     }
 
     fn theme(&self) -> Theme {
@@ -302,28 +927,113 @@ impl Application for SettingsApp {
 }
 
 impl SettingsApp {
-    /// Activates a page.
+    /// Stacks a toast below those which are shown, dismissing the oldest if too many are.
+    fn show_toast(&mut self, toast: Toast) {
+        if self.toasts.len() >= toast::MAX_SHOWN {
+            self.toasts.remove(0);
+        }
+
+        self.toasts.push((self.next_toast, toast, Instant::now()));
+        self.next_toast += 1;
+    }
+
+    /// Activates a page, unless the active page has changes which have not been applied.
     fn activate_page(&mut self, page: page::Entity) -> Command<crate::Message> {
+        if page != self.active_page && self.pages.is_dirty(self.active_page) {
+            self.unsaved_changes = Some(Navigation::Page(page));
+            self.activate_navbar(self.active_page);
+            return Command::none();
+        }
+
         self.nav_bar_toggled_condensed = false;
+        self.page_menu = false;
+        self.confirm = None;
         let current_page = self.active_page;
         self.active_page = page;
 
+        if self.return_to.map_or(false, |(opened, _)| opened != page) {
+            self.return_to = None;
+        }
+
         if current_page != page {
             self.config.active_page = Box::from(self.pages.pages[page].id);
             self.config_path
                 .config("main", |path| self.config.serialize(path));
         }
 
+        // Visiting a page from the middle of the history drops the pages ahead of it, while
+        // going back and forth through it keeps them.
+        if self.history.get(self.history_cursor) != Some(&page) {
+            self.history.truncate(self.history_cursor + 1);
+            self.history.push(page);
+            self.history_cursor = self.history.len() - 1;
+        }
+
+        // Search results within collapsed sections are revealed on the page they link to.
+        let results = self
+            .search_selections
+            .iter()
+            .find(|(result_page, _)| *result_page == page);
+
+        if let Some((_, sections)) = results {
+            for section in sections.iter().copied() {
+                self.pages.expand(section);
+            }
+        }
+
         self.search_clear();
-        self.search.state = search::State::Inactive;
+        self.search.clear();
         self.activate_navbar(page);
 
-        self.pages.init_page(page).unwrap_or(Command::none())
+        let init = self.pages.init_page(page).unwrap_or(Command::none());
+
+        if current_page == page {
+            return init;
+        }
+
+        Command::batch(vec![self.pages.leave(current_page), init])
+    }
+
+    /// Closes the window or activates a page, once unapplied changes have been dealt with.
+    fn navigate(&mut self, navigation: Navigation) -> Command<crate::Message> {
+        match navigation {
+            Navigation::Close if self.pages.is_dirty(self.active_page) => {
+                self.unsaved_changes = Some(navigation);
+                Command::none()
+            }
+
+            Navigation::Close => close(window::Id::new(0)),
+
+            Navigation::Page(page) => self.activate_page(page),
+
+            Navigation::History(_) if self.pages.is_dirty(self.active_page) => {
+                self.unsaved_changes = Some(navigation);
+                Command::none()
+            }
+
+            Navigation::History(index) => match self.history.get(index).copied() {
+                Some(page) => {
+                    self.history_cursor = index;
+                    self.activate_page(page)
+                }
+                None => Command::none(),
+            },
+        }
     }
 
-    /// Activates the navbar item associated with a page.
+    /// The page which a page returns to, which is the page that it was opened from if it was
+    /// opened by another, or else its parent.
+    fn return_target(&self, page: page::Entity) -> Option<page::Entity> {
+        match self.return_to {
+            Some((opened, from)) if opened == page => Some(from),
+            _ => self.pages.parent(page),
+        }
+    }
+
+    /// Activates the navbar item associated with a page, which is that of its root page, or of
+    /// the page which it was opened from.
     fn activate_navbar(&mut self, mut page: page::Entity) {
-        if let Some(parent) = self.pages.pages[page].parent {
+        while let Some(parent) = self.return_target(page) {
             page = parent;
         }
 
@@ -332,15 +1042,62 @@ impl SettingsApp {
         }
     }
 
-    /// Adds a main page to the settings application.
-    fn insert_page<P: Page>(&mut self) -> page::Insert {
-        let id = self.pages.register::<P>().id();
+    /// Marks a page as busy, and shows a spinner on its navbar item while it, or
+    /// any of its sub-pages, are busy.
+    fn set_busy(&mut self, page: page::Entity, busy: bool) {
+        self.pages.set_busy(page, busy);
+        self.nav_entry_update(page);
+    }
+
+    /// Updates the nav bar entry of the root page of a page with the icon and title of the
+    /// root, beside which a spinner turns while it or its sub-pages are busy.
+    fn nav_entry_update(&mut self, page: page::Entity) {
+        if !self.pages.pages.contains_key(page) {
+            return;
+        }
+
+        let mut root = page;
+        while let Some(parent) = self.pages.parent(root) {
+            root = parent;
+        }
+
+        let busy = self.pages.is_busy(root)
+            || self.pages.sub_pages(root).map_or(false, |sub_pages| {
+                sub_pages.iter().any(|&id| self.pages.is_busy(id))
+            });
+
+        let meta = &self.pages.pages[root];
+        let icon_name = meta.icon_handle();
+
+        let title = if busy {
+            format!("{}  {}", meta.title, spinner::text_step(self.spinner_step))
+        } else {
+            meta.title.clone()
+        };
+
+        if let Some(nav_id) = self.pages.data::<segmented_button::Entity>(root).copied() {
+            self.nav_bar.icon_set(nav_id, IconSource::from(icon_name));
+            self.nav_bar.text_set(nav_id, title);
+        }
+    }
+
+    /// Adds a main page to the settings application, unless its id was already registered,
+    /// in which case the page which registered it first is kept in the nav bar.
+    fn insert_page<P: Page>(&mut self) -> Option<page::Insert> {
+        let id = match self.pages.register::<P>() {
+            Ok(insert) => insert.id(),
+            Err(why) => {
+                why.report();
+                return None;
+            }
+        };
+
         self.navbar_insert(id);
 
-        page::Insert {
+        Some(page::Insert {
             model: &mut self.pages,
             id,
-        }
+        })
     }
 
     fn navbar_insert(&mut self, id: page::Entity) -> segmented_button::SingleSelectEntityMut {
@@ -349,7 +1106,7 @@ impl SettingsApp {
         self.nav_bar
             .insert()
             .text(page.title.clone())
-            .icon(IconSource::from(page.icon_name))
+            .icon(IconSource::from(page.icon_handle()))
             .data(id)
             .with_id(|nav_id| self.pages.data_set(id, nav_id))
     }
@@ -360,7 +1117,7 @@ impl SettingsApp {
 
         let mut column_widgets = Vec::with_capacity(1);
 
-        if let Some(parent) = page.parent {
+        if let Some(parent) = self.return_target(self.active_page) {
             column_widgets.push(parent_page_button(
                 &self.pages.pages[parent],
                 page,
@@ -368,15 +1125,80 @@ impl SettingsApp {
             ));
         }
 
+        column_widgets.push(self.page_menu_view());
+
         column_widgets.reserve_exact(1 + content.len());
         for id in content.iter().copied() {
-            let section = &self.pages.sections[id];
-            column_widgets.push((section.view_fn)(self, section));
+            if (self.pages.sections[id].visible)(&self.pages) {
+                column_widgets.push(self.section_view(id, false));
+            }
+        }
+
+        // Pages with content list their sub-pages beneath it.
+        for entity in self.pages.sub_pages(self.active_page).unwrap_or_default() {
+            let sub_page = &self.pages.pages[*entity];
+
+            if !sub_page.hidden {
+                column_widgets.push(sub_page_button(*entity, sub_page).map(Message::Page));
+            }
         }
 
         settings::view_column(column_widgets).into()
     }
 
+    /// Displays the buttons of a section in a row, dimming those which are disabled.
+    fn section_buttons<'a>(&self, section: &'a page::Section) -> cosmic::Element<'a, Message> {
+        section
+            .buttons
+            .iter()
+            .fold(row!().spacing(8), |row, section_button| {
+                let mut pressable =
+                    button(text(section_button.label.as_str())).style(theme::Button::Secondary);
+
+                if !(section_button.disabled)(&self.pages) {
+                    pressable = pressable.on_press(section_button.on_press());
+                }
+
+                row.push(pressable)
+            })
+            .into()
+    }
+
+    /// Displays the view of a section, beneath an expander if it is collapsible.
+    ///
+    /// Revealed sections are shown expanded regardless of their expansion state.
+    fn section_view(&self, id: section::Entity, reveal: bool) -> cosmic::Element<Message> {
+        let section = &self.pages.sections[id];
+
+        let view = match self.pages.section_state(id) {
+            Some(state) => section_state_view(id, section, state),
+            None if section.buttons.is_empty() => (section.view_fn)(self, section),
+            None => column!(
+                (section.view_fn)(self, section),
+                self.section_buttons(section)
+            )
+            .spacing(8)
+            .into(),
+        };
+
+        if !section.collapsible {
+            return view;
+        }
+
+        let (expanded, progress) = if reveal {
+            (true, 1.0)
+        } else {
+            (self.pages.is_expanded(id), self.pages.expansion(id))
+        };
+
+        expander(&section.title, &section.subtitle)
+            .expanded(expanded)
+            .progress(progress)
+            .on_toggle(move |expanded| Message::SectionExpanded(id, expanded))
+            .content(view)
+            .into()
+    }
+
     fn search_changed(&mut self, phrase: String) {
         // If the text was cleared, clear the search results too.
         if phrase.is_empty() {
@@ -384,51 +1206,155 @@ impl SettingsApp {
             return;
         }
 
-        // Create a case-insensitive regular expression for the search function.
-        let search_expression = regex::RegexBuilder::new(&phrase)
-            .case_insensitive(true)
-            .unicode(true)
-            .ignore_whitespace(true)
-            .size_limit(16 * 1024)
-            .build();
+        // Create a case-insensitive regular expression for the search function, from the
+        // same normalized form of the phrase which the sections are matched against. The
+        // phrase is matched literally, spaces included, as normalizing a pattern would turn
+        // escapes such as `\S` into `\s`.
+        let search_expression =
+            regex::RegexBuilder::new(&regex::escape(&section::normalize(&phrase)))
+                .case_insensitive(true)
+                .unicode(true)
+                .size_limit(16 * 1024)
+                .build();
 
         if let Ok(expression) = search_expression {
             // With the new search expression, generate new search results.
-            let results: Vec<_> = self.pages.search(&expression).collect();
+            let results = self.pages.search_grouped(&expression);
 
             // Use the results if results were found.
             if !results.is_empty() {
                 self.search_selections = results;
             }
         }
-
-        self.search.phrase = phrase;
     }
 
     /// Clears the search results so that the search page will not be shown.
     fn search_clear(&mut self) {
         self.search_selections.clear();
-        self.search.phrase.clear();
+    }
+
+    /// Asks what to do with the unapplied changes of the active page.
+    fn unsaved_changes_view(&self) -> cosmic::Element<Message> {
+        dialog(crate::fl!("unsaved-changes"), |response| {
+            Message::UnsavedChanges(match response.action {
+                dialog::Action::Primary => UnsavedChanges::Apply,
+                dialog::Action::Secondary => UnsavedChanges::Discard,
+                dialog::Action::Cancel => UnsavedChanges::Cancel,
+            })
+        })
+        .body(crate::fl!("unsaved-changes", "desc"))
+        .primary(crate::fl!("unsaved-changes", "apply"))
+        .secondary(crate::fl!("unsaved-changes", "discard"))
+        .cancel(crate::fl!("unsaved-changes", "cancel"))
+        .into()
+    }
+
+    /// Displays the overflow menu of the active page, with actions which affect all of its settings.
+    fn page_menu_view(&self) -> cosmic::Element<Message> {
+        let toggle = icon("view-more-symbolic", 16)
+            .style(theme::Svg::Symbolic)
+            .apply(button)
+            .style(theme::Button::Transparent)
+            .on_press(Message::PageMenu(!self.page_menu));
+
+        let mut menu = popover(toggle)
+            .width(200)
+            .on_dismiss(Message::PageMenu(false));
+
+        if self.page_menu {
+            let item = |label: String, message: Message| {
+                button(text(label))
+                    .width(Length::Fill)
+                    .style(theme::Button::Transparent)
+                    .on_press(message)
+            };
+
+            let mut items = column(Vec::new()).spacing(4);
+
+            if self.pages.pages[self.active_page].resettable {
+                items = items.push(item(
+                    crate::fl!("reset-defaults"),
+                    Message::ShowConfirm(self.reset_defaults_confirm(self.active_page)),
+                ));
+            }
+
+            menu = menu.popup(
+                items
+                    .push(item(
+                        crate::fl!("settings-file", "export"),
+                        Message::ExportSettings,
+                    ))
+                    .push(item(
+                        crate::fl!("settings-file", "import"),
+                        Message::ImportSettings,
+                    ))
+                    .apply(container)
+                    .padding(8),
+            );
+        }
+
+        row!(horizontal_space(Length::Fill), menu).into()
+    }
+
+    /// Asks to confirm an action, with "don't ask again" if it may be skipped.
+    fn confirm_view<'a>(&self, confirm: &'a Confirm) -> cosmic::Element<'a, Message> {
+        let mut view = confirm_dialog(
+            confirm.title.as_str(),
+            confirm.body.as_str(),
+            confirm.confirm_label.as_str(),
+            Message::ConfirmResult,
+        );
+
+        if confirm.destructive {
+            view = view.destructive(confirm.confirm_label.as_str());
+        }
+
+        if confirm.skip_key.is_some() {
+            view = view.dont_ask_again(self.confirm_dont_ask, Message::ConfirmDontAsk);
+        }
+
+        view.into()
+    }
+
+    /// The confirmation of resetting the settings of a page to their defaults.
+    fn reset_defaults_confirm(&self, page: page::Entity) -> Confirm {
+        let title = &self.pages.pages[page].title;
+
+        Confirm::new(
+            crate::fl!("reset-defaults", "title", page = title.as_str()),
+            crate::fl!("reset-defaults", "desc"),
+            crate::fl!("reset-defaults", "confirm"),
+            Message::ResetPageDefaults(page),
+        )
+        .destructive()
     }
 
     /// Displays the search view.
     fn search_view(&self) -> cosmic::Element<Message> {
         let mut sections: Vec<cosmic::Element<Message>> = Vec::new();
 
-        let mut current_page = page::Entity::default();
-        for (page, section) in self.search_selections.iter().copied() {
-            let section = &self.pages.sections[section];
+        for (page, results) in &self.search_selections {
+            // Sections which were hidden since the search are left out until it is repeated.
+            let shown: Vec<section::Entity> = results
+                .iter()
+                .copied()
+                .filter(|id| (self.pages.sections[*id].visible)(&self.pages))
+                .collect();
 
-            if page != current_page {
-                current_page = page;
-                sections.push(search_header(&self.pages, page));
+            if shown.is_empty() {
+                continue;
             }
 
-            let section = (section.view_fn)(self, section)
-                .apply(iced::widget::container)
-                .padding([0, 0, 0, 48]);
+            sections.push(search_header(&self.pages, *page));
+
+            for section in shown {
+                let section = self
+                    .section_view(section, true)
+                    .apply(iced::widget::container)
+                    .padding([0, 0, 0, 48]);
 
-            sections.push(section.into());
+                sections.push(section.into());
+            }
         }
 
         settings::view_column(sections).into()
@@ -443,7 +1369,10 @@ impl SettingsApp {
 
         for entity in sub_pages.iter().copied() {
             let sub_page = &self.pages.pages[entity];
-            column_widgets.push(sub_page_button(entity, sub_page));
+
+            if !sub_page.hidden {
+                column_widgets.push(sub_page_button(entity, sub_page));
+            }
         }
 
         settings::view_column(column_widgets)
@@ -451,3 +1380,71 @@ impl SettingsApp {
             .map(Message::Page)
     }
 }
+
+/// Displays a spinner in place of a loading section, or the error of a section that failed to load.
+fn section_state_view<'a>(
+    id: section::Entity,
+    section: &'a page::Section,
+    state: &'a page::SectionState,
+) -> cosmic::Element<'a, Message> {
+    let status: cosmic::Element<Message> = match state {
+        page::SectionState::Error(why) => row!(
+            icon("dialog-error-symbolic", 16).style(theme::Svg::Symbolic),
+            text(why.as_str()),
+            horizontal_space(Length::Fill),
+            button(text(crate::fl!("section-state", "retry")))
+                .style(theme::Button::Secondary)
+                .on_press(Message::SectionRetry(id)),
+        )
+        .spacing(8)
+        .align_items(iced::Alignment::Center)
+        .into(),
+
+        _ => row!(
+            icon("process-working-symbolic", 16).style(theme::Svg::Symbolic),
+            text(crate::fl!("section-state", "loading")),
+        )
+        .spacing(8)
+        .align_items(iced::Alignment::Center)
+        .into(),
+    };
+
+    settings::view_section(&section.title).add(status).into()
+}
+
+/// Reads the icons of an icon theme in the background, which the icons of pages are resolved in.
+fn load_icon_theme(theme: String) -> Command<Message> {
+    Command::perform(
+        page::background(move || icon_theme::icons(&theme)),
+        |icons| Message::IconThemeLoaded(icons.unwrap_or_default()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaving_dirty_page_asks_to_apply() {
+        let (mut app, _) = SettingsApp::new(());
+
+        let dirty = app
+            .pages
+            .find_page_by_id("desktop")
+            .expect("no desktop page");
+        let other = app.pages.find_page_by_id("sound").expect("no sound page");
+
+        let _ = app.update(Message::Page(dirty));
+        let _ = app.update(Message::PageDirty(dirty, true));
+        let _ = app.update(Message::Page(other));
+
+        assert_eq!(app.active_page, dirty);
+        assert!(matches!(app.unsaved_changes, Some(Navigation::Page(page)) if page == other));
+
+        let _ = app.update(Message::UnsavedChanges(UnsavedChanges::Discard));
+
+        assert_eq!(app.active_page, other);
+        assert!(!app.pages.is_dirty(dirty));
+        assert!(app.unsaved_changes.is_none());
+    }
+}
@@ -45,7 +45,11 @@ mod path {
 pub use path::Manager as PathManager;
 
 use bytecheck::CheckBytes;
-use rkyv::{ser::Serializer, Archive, Deserialize, Serialize};
+use rkyv::{
+    ser::{serializers::AllocSerializer, Serializer},
+    validation::validators::DefaultValidator,
+    Archive, Deserialize, Serialize,
+};
 use std::{io::Read, path::Path};
 
 #[must_use]
@@ -58,36 +62,11 @@ pub struct Config {
 
 impl Config {
     pub fn deserialize(path: &Path) -> Self {
-        let Ok(mut file) = std::fs::File::open(path) else {
-            return Self::default();
-        };
-
-        let mut buffer = Vec::with_capacity(128);
-        if file.read_to_end(&mut buffer).is_err() {
-            return Self::default();
-        }
-
-        buffer.shrink_to_fit();
-
-        let Ok(archived) = rkyv::check_archived_root::<Self>(buffer.as_slice()) else {
-            return Self::default();
-        };
-
-        archived
-            .deserialize(&mut rkyv::Infallible)
-            .unwrap_or_default()
+        read(path)
     }
 
     pub fn serialize(&self, path: &Path) {
-        let mut serializer = rkyv::ser::serializers::AllocSerializer::<0>::default();
-
-        if serializer.serialize_value(self).is_err() {
-            return;
-        };
-
-        let bytes = serializer.into_serializer().into_inner();
-
-        let _res = std::fs::write(path, &bytes);
+        write(path, self);
     }
 }
 
@@ -98,3 +77,58 @@ impl Default for Config {
         }
     }
 }
+
+/// Loads the config of a page, or its default if it is missing or invalid.
+#[must_use]
+pub fn load<T>(name: &str) -> T
+where
+    T: Archive + Default,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<T, rkyv::Infallible>,
+{
+    PathManager::new().config(name, read)
+}
+
+/// Stores the config of a page, to be loaded again with [`load`].
+pub fn store<T: Serialize<AllocSerializer<0>>>(name: &str, value: &T) {
+    PathManager::new().config(name, |path| write(path, value));
+}
+
+/// Reads a config from a path, or its default if it is missing or invalid.
+#[must_use]
+pub fn read<T>(path: &Path) -> T
+where
+    T: Archive + Default,
+    T::Archived: for<'a> CheckBytes<DefaultValidator<'a>> + Deserialize<T, rkyv::Infallible>,
+{
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return T::default();
+    };
+
+    let mut buffer = Vec::with_capacity(128);
+    if file.read_to_end(&mut buffer).is_err() {
+        return T::default();
+    }
+
+    buffer.shrink_to_fit();
+
+    let Ok(archived) = rkyv::check_archived_root::<T>(buffer.as_slice()) else {
+        return T::default();
+    };
+
+    archived
+        .deserialize(&mut rkyv::Infallible)
+        .unwrap_or_default()
+}
+
+/// Writes a config to a path.
+pub fn write<T: Serialize<AllocSerializer<0>>>(path: &Path, value: &T) {
+    let mut serializer = AllocSerializer::<0>::default();
+
+    if serializer.serialize_value(value).is_err() {
+        return;
+    };
+
+    let bytes = serializer.into_serializer().into_inner();
+
+    let _res = std::fs::write(path, &bytes);
+}
@@ -0,0 +1,143 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Files chosen through the file chooser portal, so that they may be chosen within a sandbox.
+
+use std::{collections::HashMap, path::PathBuf};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+
+/// Files which may be chosen, by their glob patterns and MIME types.
+pub struct Filter {
+    pub name: String,
+    pub globs: &'static [&'static str],
+    pub mime_types: &'static [&'static str],
+}
+
+/// Asks for a file to open, which resolves to `None` if the user cancelled.
+///
+/// The token tells apart the requests of different callers.
+///
+/// # Errors
+///
+/// Fails if the portal is not available.
+pub async fn open_file(title: &str, filter: Filter, token: &str) -> zbus::Result<Option<PathBuf>> {
+    let options = HashMap::from([
+        ("handle_token", Value::from(token)),
+        ("filters", Value::from(vec![filter.patterns()])),
+    ]);
+
+    choose("OpenFile", title, options, token).await
+}
+
+/// Asks where to save a file, suggesting a name for it, which resolves to `None` if the user
+/// cancelled.
+///
+/// # Errors
+///
+/// Fails if the portal is not available.
+pub async fn save_file(
+    title: &str,
+    name: &str,
+    filter: Filter,
+    token: &str,
+) -> zbus::Result<Option<PathBuf>> {
+    let options = HashMap::from([
+        ("handle_token", Value::from(token)),
+        ("current_name", Value::from(name)),
+        ("filters", Value::from(vec![filter.patterns()])),
+    ]);
+
+    choose("SaveFile", title, options, token).await
+}
+
+impl Filter {
+    /// Filters are matched by glob patterns, of kind 0, or by MIME types, of kind 1.
+    fn patterns(&self) -> (String, Vec<(u32, String)>) {
+        let globs = self.globs.iter().map(|glob| (0, (*glob).to_owned()));
+        let mime_types = self.mime_types.iter().map(|mime| (1, (*mime).to_owned()));
+        (self.name.clone(), globs.chain(mime_types).collect())
+    }
+}
+
+async fn choose(
+    method: &str,
+    title: &str,
+    options: HashMap<&str, Value<'_>>,
+    token: &str,
+) -> zbus::Result<Option<PathBuf>> {
+    use futures_util::StreamExt;
+
+    let connection = zbus::Connection::session().await?;
+
+    let sender = connection
+        .unique_name()
+        .map(|name| name.as_str().trim_start_matches(':').replace('.', "_"))
+        .unwrap_or_default();
+
+    // Subscribed before the request is made, so that an early response is not missed.
+    let request = zbus::Proxy::new(
+        &connection,
+        DESTINATION,
+        format!("/org/freedesktop/portal/desktop/request/{sender}/{token}"),
+        "org.freedesktop.portal.Request",
+    )
+    .await?;
+
+    let mut responses = request.receive_signal("Response").await?;
+
+    let file_chooser = zbus::Proxy::new(
+        &connection,
+        DESTINATION,
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.FileChooser",
+    )
+    .await?;
+
+    let _handle: OwnedObjectPath = file_chooser.call(method, &("", title, options)).await?;
+
+    let Some(response) = responses.next().await else {
+        return Ok(None);
+    };
+
+    let (code, results): (u32, HashMap<String, OwnedValue>) = response.body()?;
+
+    let Some(uris) = results.get("uris").filter(|_| code == 0) else {
+        return Ok(None);
+    };
+
+    let uris: Vec<String> = Value::clone(uris).try_into()?;
+
+    Ok(uris
+        .first()
+        .and_then(|uri| uri.strip_prefix("file://"))
+        .map(|path| PathBuf::from(percent_decode(path))))
+}
+
+/// Decodes the escaped bytes of the path of a file URI, such as `%20` for a space.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,132 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Icons of the active icon theme, so that pages whose icons are missing from it are
+//! given a fallback icon rather than a blank one.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The icon of pages whose own icon is not in the icon theme.
+pub const FALLBACK: &str = "preferences-system-symbolic";
+
+/// Directories nested deeper than this in a theme, such as `48x48/apps`, are not read.
+const MAX_DEPTH: usize = 3;
+
+/// The name of the icon theme which is active, as it is set for GTK applications.
+#[must_use]
+pub fn current() -> String {
+    let settings = dirs::config_dir()
+        .map(|dir| dir.join("gtk-3.0").join("settings.ini"))
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+
+    settings
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "gtk-icon-theme-name")
+        .map(|(_, value)| value.trim().trim_matches('"').to_owned())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from("hicolor"))
+}
+
+/// The names of every icon in a theme, in the themes it inherits, and in hicolor.
+#[must_use]
+pub fn icons(theme: &str) -> HashSet<String> {
+    let bases = bases();
+    let mut icons = HashSet::new();
+    let mut themes = vec![theme.to_owned()];
+    let mut visited = HashSet::new();
+
+    while let Some(theme) = themes.pop() {
+        if !visited.insert(theme.clone()) {
+            continue;
+        }
+
+        for base in &bases {
+            let dir = base.join(&theme);
+
+            if let Ok(index) = fs::read_to_string(dir.join("index.theme")) {
+                themes.extend(inherits(&index));
+            }
+
+            collect(&dir, 0, &mut icons);
+        }
+
+        // Every theme falls back to hicolor, once the themes it inherits are exhausted.
+        if themes.is_empty() {
+            themes.push(String::from("hicolor"));
+        }
+    }
+
+    // Icons which are not in a theme, of which nested directories are not read.
+    collect(Path::new("/usr/share/pixmaps"), MAX_DEPTH, &mut icons);
+
+    icons
+}
+
+/// Directories which icon themes are installed to, from the most to the least preferred.
+fn bases() -> Vec<PathBuf> {
+    let mut bases = Vec::new();
+
+    if let Some(home) = dirs::home_dir() {
+        bases.push(home.join(".icons"));
+    }
+
+    if let Some(data) = dirs::data_dir() {
+        bases.push(data.join("icons"));
+    }
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+
+    bases.extend(data_dirs.split(':').map(|dir| Path::new(dir).join("icons")));
+    bases
+}
+
+/// The themes which a theme inherits from, in the `Inherits` key of its index.
+fn inherits(index: &str) -> Vec<String> {
+    index
+        .lines()
+        .filter_map(|line| line.strip_prefix("Inherits="))
+        .flat_map(|themes| themes.split(','))
+        .map(|theme| theme.trim().to_owned())
+        .filter(|theme| !theme.is_empty())
+        // Popped from the end, so that the first inherited theme is read first.
+        .rev()
+        .collect()
+}
+
+fn collect(dir: &Path, depth: usize, icons: &mut HashSet<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+
+        if path.is_dir() {
+            if depth < MAX_DEPTH {
+                collect(&path, depth + 1, icons);
+            }
+
+            continue;
+        }
+
+        let is_icon = matches!(
+            path.extension().and_then(|extension| extension.to_str()),
+            Some("svg" | "png")
+        );
+
+        if is_icon {
+            if let Some(name) = path.file_stem().and_then(|name| name.to_str()) {
+                icons.insert(name.to_owned());
+            }
+        }
+    }
+}
@@ -1,13 +1,18 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use cosmic::{iced::Subscription, iced_native::subscription};
 use i18n_embed::{
     fluent::{fluent_language_loader, FluentLanguageLoader},
+    unic_langid::LanguageIdentifier,
     DefaultLocalizer, LanguageLoader, Localizer,
 };
 use once_cell::sync::Lazy;
 use rust_embed::RustEmbed;
 
+const LOCALED: &str = "org.freedesktop.locale1";
+const LOCALED_PATH: &str = "/org/freedesktop/locale1";
+
 #[derive(RustEmbed)]
 #[folder = "i18n/"]
 struct Localizations;
@@ -38,3 +43,93 @@ macro_rules! fl {
 pub fn localizer() -> Box<dyn Localizer> {
     Box::from(DefaultLocalizer::new(&*LANGUAGE_LOADER, &Localizations))
 }
+
+enum Watch {
+    Starting,
+    /// The locale as it was last read, to tell its changes from those of the keymap.
+    Watching(
+        zbus::Proxy<'static>,
+        zbus::SignalStream<'static>,
+        Vec<String>,
+    ),
+    Finished,
+}
+
+/// The languages of the system locale whenever localed changes it, such as from another
+/// settings app or `localectl set-locale`.
+pub fn changes() -> Subscription<crate::Message> {
+    use futures_util::StreamExt;
+
+    subscription::unfold("locale", Watch::Starting, |state| async move {
+        match state {
+            Watch::Starting => {
+                let watch = async {
+                    let connection = zbus::Connection::system().await?;
+                    let proxy =
+                        zbus::Proxy::new(&connection, LOCALED, LOCALED_PATH, LOCALED).await?;
+
+                    let properties = zbus::Proxy::new(
+                        &connection,
+                        LOCALED,
+                        LOCALED_PATH,
+                        "org.freedesktop.DBus.Properties",
+                    )
+                    .await?;
+
+                    let signals = properties.receive_signal("PropertiesChanged").await?;
+                    let locale: Vec<String> = proxy.get_property("Locale").await?;
+                    zbus::Result::Ok((proxy, signals, locale))
+                };
+
+                match watch.await {
+                    Ok((proxy, signals, locale)) => (None, Watch::Watching(proxy, signals, locale)),
+                    Err(_) => (None, Watch::Finished),
+                }
+            }
+
+            Watch::Watching(proxy, mut signals, previous) => {
+                if signals.next().await.is_none() {
+                    return (None, Watch::Finished);
+                }
+
+                // Changes of the keymap are signalled alike, and leave the locale as it was.
+                let locale: Vec<String> = proxy.get_property("Locale").await.unwrap_or_default();
+
+                let requested = languages(&locale);
+
+                if locale == previous || requested.is_empty() {
+                    return (None, Watch::Watching(proxy, signals, previous));
+                }
+
+                let message = crate::Message::LanguageChanged(requested);
+                (Some(message), Watch::Watching(proxy, signals, locale))
+            }
+
+            Watch::Finished => std::future::pending().await,
+        }
+    })
+}
+
+/// The languages of a locale as localed lists it, such as `LANG=de_DE.UTF-8`, preferring the
+/// fallbacks of `LANGUAGE` where they are set.
+fn languages(locale: &[String]) -> Vec<LanguageIdentifier> {
+    let var = |name: &str| {
+        locale
+            .iter()
+            .find_map(|entry| entry.strip_prefix(name)?.strip_prefix('='))
+    };
+
+    let names = var("LANGUAGE")
+        .filter(|names| !names.is_empty())
+        .or_else(|| var("LANG"))
+        .unwrap_or_default();
+
+    names
+        .split(':')
+        .filter_map(|name| {
+            // Encodings and modifiers, as in `sr_RS.UTF-8@latin`, are not part of the language.
+            let name = name.split(['.', '@']).next()?;
+            name.replace('_', "-").parse().ok()
+        })
+        .collect()
+}
@@ -11,6 +11,10 @@ pub use app::{Message, SettingsApp};
 
 pub mod config;
 
+pub mod file_chooser;
+
+pub mod icon_theme;
+
 #[macro_use]
 pub mod localize;
 
@@ -18,6 +22,10 @@ pub mod widget;
 
 pub mod page;
 
+pub mod settings_file;
+
+pub mod subscription;
+
 use cosmic::{iced::Application, settings};
 use i18n_embed::DesktopLanguageRequester;
 
@@ -41,6 +49,8 @@ pub fn main() -> color_eyre::Result<()> {
     settings::set_default_icon_theme("Pop");
     let mut settings = settings();
     settings.window.min_size = Some((600, 300));
+    // The app closes the window itself, once unapplied changes have been dealt with.
+    settings.exit_on_close_request = false;
     SettingsApp::run(settings)?;
 
     Ok(())
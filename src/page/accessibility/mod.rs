@@ -0,0 +1,330 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        self,
+        widget::{row, slider},
+        Alignment, Command, Length, Subscription,
+    },
+    widget::{settings, text},
+    Element,
+};
+use cosmic_settings_accessibility as accessibility;
+use cosmic_settings_input::{self as input, A11yFeature, KeyboardA11y};
+use slotmap::SlotMap;
+use std::time::{Duration, Instant};
+
+use crate::{
+    page::{
+        self,
+        section::{self, Button},
+        Content, Section,
+    },
+    widget::settings_row,
+};
+
+/// Interval between checks of whether the screen reader is running, as it may also be
+/// started and stopped by a shortcut or by other applications.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time given to the screen reader to start or stop once it is toggled, before checks
+/// which disagree with the toggle are shown.
+const SETTLE_TIME: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// A keyboard accessibility feature was enabled or disabled, or its delay was changed,
+    /// in milliseconds.
+    KeyboardA11yChanged(KeyboardA11y, bool, u32),
+    /// The keyboard accessibility features, and whether five presses of Shift toggle sticky
+    /// keys.
+    KeyboardA11yLoaded(Vec<(KeyboardA11y, A11yFeature)>, bool),
+    OpenScreenReaderPreferences,
+    Refresh,
+    /// Whether the screen reader is installed, and whether it is running.
+    ScreenReaderState(bool, bool),
+    ScreenReaderToggled(bool),
+    StickyKeysShortcutToggled(bool),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub screen_reader_installed: bool,
+    pub screen_reader_running: bool,
+    /// The state which the screen reader was toggled to, and when.
+    toggled: Option<(bool, Instant)>,
+    pub keyboard: Vec<(KeyboardA11y, A11yFeature)>,
+    pub sticky_keys_shortcut: bool,
+    pub error: Option<String>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::KeyboardA11yChanged(feature, enabled, delay) => {
+                let state = A11yFeature { enabled, delay };
+
+                let Some((_, current)) = self.keyboard.iter_mut().find(|(f, _)| *f == feature)
+                else {
+                    return Command::none();
+                };
+
+                // Delays are fixed while their feature is off.
+                if *current == state || (!current.enabled && !enabled) {
+                    return Command::none();
+                }
+
+                *current = state;
+
+                return Command::perform(
+                    page::background(move || input::set_keyboard_a11y(feature, state)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Accessibility(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::KeyboardA11yLoaded(keyboard, sticky_keys_shortcut) => {
+                self.keyboard = keyboard;
+                self.sticky_keys_shortcut = sticky_keys_shortcut;
+                return refresh();
+            }
+
+            Message::OpenScreenReaderPreferences => {
+                return Command::perform(
+                    page::background(accessibility::open_screen_reader_preferences),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Accessibility(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::Refresh => return refresh(),
+
+            Message::ScreenReaderState(installed, running) => {
+                self.screen_reader_installed = installed;
+
+                match self.toggled {
+                    Some((enabled, at)) if enabled != running && at.elapsed() < SETTLE_TIME => (),
+                    _ => {
+                        self.toggled = None;
+                        self.screen_reader_running = running;
+                    }
+                }
+            }
+
+            Message::ScreenReaderToggled(enabled) => {
+                self.screen_reader_running = enabled;
+                self.toggled = Some((enabled, Instant::now()));
+
+                return Command::perform(
+                    page::background(move || accessibility::set_screen_reader(enabled)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Accessibility(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::StickyKeysShortcutToggled(enabled) => {
+                self.sticky_keys_shortcut = enabled;
+
+                return Command::perform(
+                    page::background(move || input::set_sticky_keys_shortcut(enabled)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Accessibility(Message::Applied(result))
+                    },
+                );
+            }
+        }
+
+        Command::none()
+    }
+}
+
+fn refresh() -> Command<crate::Message> {
+    Command::perform(
+        page::background(|| {
+            (
+                accessibility::screen_reader_installed(),
+                accessibility::screen_reader_running(),
+            )
+        }),
+        |state| {
+            let (installed, running) = state.unwrap_or_default();
+            crate::Message::Accessibility(Message::ScreenReaderState(installed, running))
+        },
+    )
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new(
+            "accessibility",
+            "preferences-desktop-accessibility-symbolic",
+        )
+        .title(fl!("accessibility"))
+        .description(fl!("accessibility", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![
+            sections.insert(screen_reader()),
+            sections.insert(keyboard()),
+        ])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let loaded = page::background(|| {
+                let keyboard = KeyboardA11y::ALL
+                    .into_iter()
+                    .map(|feature| (feature, input::keyboard_a11y(feature)))
+                    .collect();
+
+                (keyboard, input::sticky_keys_shortcut())
+            })
+            .await;
+
+            let (keyboard, sticky_keys_shortcut) = loaded.unwrap_or_default();
+            crate::Message::Accessibility(Message::KeyboardA11yLoaded(
+                keyboard,
+                sticky_keys_shortcut,
+            ))
+        })
+    }
+
+    fn subscription(_model: &Model) -> Subscription<crate::Message> {
+        iced::time::every(POLL_INTERVAL).map(|_| crate::Message::Accessibility(Message::Refresh))
+    }
+}
+
+fn screen_reader() -> Section {
+    Section::new()
+        .title(fl!("screen-reader"))
+        .descriptions(vec![
+            fl!("screen-reader", "enable"),
+            fl!("screen-reader", "desc"),
+            fl!("screen-reader", "not-installed"),
+        ])
+        // The preferences of Orca are its own, and there are none without it.
+        .button_with(
+            Button::new(
+                fl!("screen-reader", "preferences"),
+                crate::Message::Accessibility(Message::OpenScreenReaderPreferences),
+            )
+            .disabled_when(|pages| {
+                pages
+                    .resource::<Model>()
+                    .map_or(true, |model| !model.screen_reader_installed)
+            }),
+        )
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("accessibility model is missing");
+
+            let desc = &section.descriptions;
+
+            // Without Orca, the toggle is shown off and dimmed, with how to install it.
+            let installed = model.screen_reader_installed;
+            let description = if installed { &desc[1] } else { &desc[2] };
+
+            let mut view = settings::view_section(&section.title).add(
+                settings_row(&desc[0], description)
+                    .toggler(
+                        installed && model.screen_reader_running,
+                        Message::ScreenReaderToggled,
+                    )
+                    .disabled(!installed),
+            );
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Accessibility)
+        })
+}
+
+fn keyboard() -> Section {
+    Section::new()
+        .title(fl!("keyboard-a11y"))
+        .descriptions(vec![
+            fl!("keyboard-a11y", "sticky-keys"),
+            fl!("keyboard-a11y", "sticky-keys-desc"),
+            fl!("keyboard-a11y", "sticky-keys-timeout"),
+            fl!("keyboard-a11y", "sticky-keys-shortcut"),
+            fl!("keyboard-a11y", "slow-keys"),
+            fl!("keyboard-a11y", "slow-keys-desc"),
+            fl!("keyboard-a11y", "slow-keys-delay"),
+            fl!("keyboard-a11y", "bounce-keys"),
+            fl!("keyboard-a11y", "bounce-keys-desc"),
+            fl!("keyboard-a11y", "bounce-keys-delay"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("accessibility model is missing");
+
+            let desc = &section.descriptions;
+            let mut view = settings::view_section(&section.title);
+
+            for &(feature, state) in &model.keyboard {
+                let (title, description, delay) = match feature {
+                    KeyboardA11y::StickyKeys => (&desc[0], &desc[1], &desc[2]),
+                    KeyboardA11y::SlowKeys => (&desc[4], &desc[5], &desc[6]),
+                    KeyboardA11y::BounceKeys => (&desc[7], &desc[8], &desc[9]),
+                };
+
+                view = view.add(
+                    settings_row(title, description).toggler(state.enabled, move |enabled| {
+                        Message::KeyboardA11yChanged(feature, enabled, state.delay)
+                    }),
+                );
+
+                // The delay is dimmed, and left as it is, while the feature is off.
+                let slider = slider(feature.delays(), state.delay, move |delay| {
+                    Message::KeyboardA11yChanged(feature, state.enabled, delay)
+                })
+                .step(100)
+                .width(Length::Units(250));
+
+                view = view.add(
+                    settings_row(delay, "")
+                        .control(
+                            row!(
+                                text(fl!("keyboard-a11y", "ms", value = state.delay)),
+                                slider
+                            )
+                            .spacing(16)
+                            .align_items(Alignment::Center),
+                        )
+                        .disabled(!state.enabled),
+                );
+
+                if feature == KeyboardA11y::StickyKeys {
+                    view = view.add(settings_row(&desc[3], "").toggler(
+                        model.sticky_keys_shortcut,
+                        Message::StickyKeysShortcutToggled,
+                    ));
+                }
+            }
+
+            view.apply(Element::from).map(crate::Message::Accessibility)
+        })
+}
@@ -0,0 +1,176 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{widget::pick_list, Command},
+    widget::{icon, settings},
+    Element,
+};
+use cosmic_settings_applications::{
+    mime::{self, Choices},
+    Category, DesktopEntry,
+};
+use slotmap::SlotMap;
+
+use crate::{
+    page::{self, section, Content, Section},
+    widget::Toast,
+    SettingsApp,
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    DefaultAppChanged(Category, String),
+    Loaded(Vec<Choices>),
+    /// Whether the default app was written to `mimeapps.list`.
+    Saved(Result<(), String>),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub choices: Vec<Choices>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::DefaultAppChanged(category, app_id) => {
+                if let Some(choices) = self.choices.iter_mut().find(|c| c.category == category) {
+                    choices.default = Some(app_id.clone());
+                }
+
+                return Command::perform(
+                    async move {
+                        let set = move || {
+                            mime::set_default_app(category, &app_id).map_err(|why| why.to_string())
+                        };
+
+                        page::background(set)
+                            .await
+                            .unwrap_or_else(|| Err(fl!("unknown")))
+                    },
+                    |result| crate::Message::DefaultApps(Message::Saved(result)),
+                );
+            }
+
+            Message::Loaded(choices) => self.choices = choices,
+
+            Message::Saved(Ok(())) => return Command::perform(load(), |message| message),
+
+            // The defaults are loaded again, which returns the selection to the app which
+            // is still the default.
+            Message::Saved(Err(why)) => {
+                let toast = Toast::error(fl!("default-apps", "save-failed", why = why));
+
+                return Command::batch(vec![
+                    Command::perform(load(), |message| message),
+                    Command::perform(async move { toast }, crate::Message::ShowToast),
+                ]);
+            }
+        }
+
+        Command::none()
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new(
+            "default-apps",
+            "preferences-desktop-default-applications-symbolic",
+        )
+        .title(fl!("default-apps"))
+        .description(fl!("default-apps", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(
+            Category::ALL
+                .iter()
+                .map(|&category| sections.insert(category_section(category)))
+                .collect(),
+        )
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(load())
+    }
+}
+
+async fn load() -> crate::Message {
+    let choices = page::background(mime::default_apps).await;
+    crate::Message::DefaultApps(Message::Loaded(choices.unwrap_or_default()))
+}
+
+fn category_section(category: Category) -> Section {
+    let title = match category {
+        Category::WebBrowser => fl!("default-apps", "web-browser"),
+        Category::Email => fl!("default-apps", "email"),
+        Category::FileManager => fl!("default-apps", "file-manager"),
+        Category::Music => fl!("default-apps", "music"),
+        Category::Video => fl!("default-apps", "video"),
+        Category::ImageViewer => fl!("default-apps", "image-viewer"),
+    };
+
+    let section = Section::new().title(title).descriptions(vec![
+        fl!("default-apps", "default"),
+        fl!("default-apps", "none"),
+    ]);
+
+    // View functions cannot capture, so each category is given its own.
+    match category {
+        Category::WebBrowser => {
+            section.view_fn(|app, section| view(app, section, Category::WebBrowser))
+        }
+        Category::Email => section.view_fn(|app, section| view(app, section, Category::Email)),
+        Category::FileManager => {
+            section.view_fn(|app, section| view(app, section, Category::FileManager))
+        }
+        Category::Music => section.view_fn(|app, section| view(app, section, Category::Music)),
+        Category::Video => section.view_fn(|app, section| view(app, section, Category::Video)),
+        Category::ImageViewer => {
+            section.view_fn(|app, section| view(app, section, Category::ImageViewer))
+        }
+    }
+}
+
+fn view<'a>(
+    app: &'a SettingsApp,
+    section: &'a Section,
+    category: Category,
+) -> Element<'a, crate::Message> {
+    let model = app
+        .pages
+        .resource::<Model>()
+        .expect("default apps model is missing");
+
+    let desc = &section.descriptions;
+    let choices = model.choices.iter().find(|c| c.category == category);
+
+    let apps: &[DesktopEntry] = choices.map_or(&[], |choices| &choices.apps);
+
+    let selected = choices
+        .and_then(|choices| choices.default.as_ref())
+        .and_then(|id| apps.iter().find(|app| &app.id == id));
+
+    let dropdown = pick_list(apps, selected.cloned(), move |app: DesktopEntry| {
+        Message::DefaultAppChanged(category, app.id)
+    })
+    .placeholder(&desc[1]);
+
+    let mut item = settings::item::builder(&desc[0]);
+
+    if let Some(app) = selected {
+        item = item.icon(icon(&app.icon, 24));
+    }
+
+    settings::view_section(&section.title)
+        .add(item.control(dropdown))
+        .apply(Element::from)
+        .map(crate::Message::DefaultApps)
+}
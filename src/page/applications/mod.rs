@@ -0,0 +1,23 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+pub mod defaults;
+pub mod startup;
+
+use crate::page;
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = ();
+
+    fn page() -> page::Meta {
+        page::Meta::new("applications", "preferences-desktop-apps-symbolic")
+            .title(fl!("applications"))
+    }
+
+    fn sub_pages(page: page::Insert) -> page::Insert {
+        page.sub_page::<defaults::Page>()
+            .sub_page::<startup::Page>()
+    }
+}
@@ -0,0 +1,246 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, horizontal_space, pick_list, row, text_input},
+        Command, Length,
+    },
+    theme,
+    widget::{settings, text},
+    Element,
+};
+use cosmic_settings_applications::{
+    autostart::{self, Autostart},
+    desktop_entry, DesktopEntry,
+};
+use slotmap::SlotMap;
+
+use crate::{
+    page::{self, section, Content, Section},
+    widget::settings_row,
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    AddApplication,
+    AddApplicationSelected(DesktopEntry),
+    AddCancel,
+    AddCommandChanged(String),
+    AutostartAdded,
+    AutostartRemoved(String),
+    AutostartToggled(String, bool),
+    Loaded(Vec<Autostart>, Vec<DesktopEntry>),
+    Saved(Result<(), String>),
+}
+
+/// An application which is being added to the autostart entries.
+#[derive(Debug, Default)]
+pub struct Adding {
+    pub application: Option<DesktopEntry>,
+    pub command: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub entries: Vec<Autostart>,
+    pub applications: Vec<DesktopEntry>,
+    pub adding: Option<Adding>,
+    pub error: Option<String>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::AddApplication => self.adding = Some(Adding::default()),
+
+            Message::AddApplicationSelected(application) => {
+                if let Some(adding) = self.adding.as_mut() {
+                    adding.command = application.exec.clone();
+                    adding.application = Some(application);
+                }
+            }
+
+            Message::AddCancel => self.adding = None,
+
+            Message::AddCommandChanged(command) => {
+                if let Some(adding) = self.adding.as_mut() {
+                    adding.command = command;
+                }
+            }
+
+            Message::AutostartAdded => {
+                if let Some(Adding {
+                    application: Some(application),
+                    command,
+                }) = self.adding.take()
+                {
+                    return save(move || autostart::add(&application, Some(&command)));
+                }
+            }
+
+            Message::AutostartRemoved(id) => {
+                if let Some(position) = self.entries.iter().position(|a| a.entry.id == id) {
+                    let autostart = self.entries.remove(position);
+                    return save(move || autostart::remove(&autostart));
+                }
+            }
+
+            Message::AutostartToggled(id, enabled) => {
+                if let Some(autostart) = self.entries.iter_mut().find(|a| a.entry.id == id) {
+                    autostart.entry.hidden = !enabled;
+                    autostart.entry.autostart_enabled = enabled;
+
+                    let entry = autostart.entry.clone();
+                    return save(move || autostart::set_enabled(&entry, enabled));
+                }
+            }
+
+            Message::Loaded(entries, applications) => {
+                self.entries = entries;
+                self.applications = applications;
+            }
+
+            Message::Saved(result) => {
+                self.error = result.err();
+                return Command::perform(load(), |message| message);
+            }
+        }
+
+        Command::none()
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("startup-apps", "system-run-symbolic")
+            .title(fl!("startup-apps"))
+            .description(fl!("startup-apps", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(startup_apps())])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(load())
+    }
+}
+
+async fn load() -> crate::Message {
+    let (entries, applications) =
+        page::background(|| (autostart::entries(), desktop_entry::applications()))
+            .await
+            .unwrap_or_default();
+
+    crate::Message::Autostart(Message::Loaded(entries, applications))
+}
+
+/// Writes a change to the autostart entries, and then reloads them.
+fn save(func: impl FnOnce() -> std::io::Result<()> + Send + 'static) -> Command<crate::Message> {
+    Command::perform(page::background(func), |result| {
+        let result = match result {
+            Some(result) => result.map_err(|why| why.to_string()),
+            None => Err(fl!("unknown")),
+        };
+
+        crate::Message::Autostart(Message::Saved(result))
+    })
+}
+
+fn startup_apps() -> Section {
+    Section::new()
+        .title(fl!("startup-apps"))
+        .descriptions(vec![
+            fl!("startup-apps", "none"),
+            fl!("startup-apps", "add"),
+            fl!("startup-apps", "remove"),
+            fl!("startup-apps", "application"),
+            fl!("startup-apps", "command"),
+            fl!("startup-apps", "cancel"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("startup apps model is missing");
+
+            let desc = &section.descriptions;
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            if model.entries.is_empty() {
+                view = view.add(text(&desc[0]));
+            }
+
+            for autostart in &model.entries {
+                let id = autostart.entry.id.clone();
+
+                let mut row = settings_row(&autostart.entry.name, &autostart.entry.comment)
+                    .toggler(autostart.enabled(), move |enabled| {
+                        Message::AutostartToggled(id.clone(), enabled)
+                    });
+
+                if autostart.user_added {
+                    row = row.control(
+                        button(text(&desc[2]))
+                            .style(theme::Button::Destructive)
+                            .on_press(Message::AutostartRemoved(autostart.entry.id.clone())),
+                    );
+                }
+
+                view = view.add(row);
+            }
+
+            let Some(adding) = &model.adding else {
+                return view
+                    .add(
+                        button(text(&desc[1]))
+                            .style(theme::Button::Secondary)
+                            .on_press(Message::AddApplication),
+                    )
+                    .apply(Element::from)
+                    .map(crate::Message::Autostart);
+            };
+
+            let mut add = button(text(&desc[1])).style(theme::Button::Primary);
+
+            if adding.application.is_some() {
+                add = add.on_press(Message::AutostartAdded);
+            }
+
+            view.add(settings::item(
+                &desc[3],
+                pick_list(
+                    &model.applications[..],
+                    adding.application.clone(),
+                    Message::AddApplicationSelected,
+                ),
+            ))
+            .add(settings::item(
+                &desc[4],
+                text_input("", &adding.command, Message::AddCommandChanged)
+                    .width(Length::Units(250)),
+            ))
+            .add(
+                row!(
+                    horizontal_space(Length::Fill),
+                    button(text(&desc[5]))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::AddCancel),
+                    add,
+                )
+                .spacing(8),
+            )
+            .apply(Element::from)
+            .map(crate::Message::Autostart)
+        })
+}
@@ -1,21 +1,681 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::page::{self, section, Content, Section};
+use apply::Apply;
+use bytecheck::CheckBytes;
+use cosmic::{
+    iced::{
+        widget::{column, container, image, pick_list, row, slider},
+        Alignment, Command, Font, Length,
+    },
+    widget::{settings, text},
+    Element,
+};
+use cosmic_settings_appearance::{
+    cursor::{self, Cursor, CursorTheme},
+    font::{self, Antialiasing, FontFamily, FontRole, FontSpec, Hinting},
+};
+use rkyv::{Archive, Deserialize, Serialize};
 use slotmap::SlotMap;
+use std::{collections::HashMap, fmt, fs, path::PathBuf};
+
+use crate::{
+    config,
+    page::{self, section, Content, Section},
+    widget::color_picker::{self, ColorPicker, Output, Srgba},
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    AccentColor(color_picker::Message),
+    /// The cursor theme or size was chosen.
+    CursorChanged(Cursor),
+    CursorApplied(Result<(), String>),
+    /// The installed cursor themes, and the chosen cursor.
+    CursorLoaded(Vec<CursorTheme>, Cursor),
+    CursorPreview(Option<image::Handle>),
+    /// The size of the cursor while its slider is dragged, which is previewed until it is
+    /// released.
+    CursorSizeDragged(u32),
+    /// The font of a role was chosen.
+    FontChanged(FontRole, FontSpec),
+    FontApplied(Result<(), String>),
+    /// A font file was loaded for previews, if it could be read.
+    FontPreview(PathBuf, Option<Font>),
+    /// The installed font families, the font of each role, and how fonts are rendered.
+    FontsLoaded(
+        Vec<FontFamily>,
+        Vec<(FontRole, FontSpec)>,
+        (Hinting, Antialiasing),
+    ),
+    Loaded(Config),
+    RenderingChanged(Hinting, Antialiasing),
+}
+
+/// The sizes of fonts which are listed, in points.
+const FONT_SIZES: [u32; 16] = [6, 7, 8, 9, 10, 11, 12, 13, 14, 16, 18, 20, 24, 32, 48, 72];
+
+/// Colors chosen on the appearance page, which are kept between sessions.
+#[must_use]
+#[derive(Archive, Clone, Debug, Default, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes, Debug))]
+pub struct Config {
+    pub accent_color: Option<[f32; 4]>,
+    pub recent_colors: Vec<[f32; 4]>,
+}
+
+fn to_rgba(color: Srgba) -> [f32; 4] {
+    [color.red, color.green, color.blue, color.alpha]
+}
+
+fn from_rgba([red, green, blue, alpha]: [f32; 4]) -> Srgba {
+    Srgba::new(red, green, blue, alpha)
+}
+
+#[derive(Debug)]
+pub struct Model {
+    pub accent_color: ColorPicker,
+    pub cursor_themes: Vec<CursorTheme>,
+    pub cursor: Cursor,
+    /// The size of the cursor while its slider is dragged.
+    pub cursor_size: Option<u32>,
+    /// The pointer of the chosen theme, at the chosen size.
+    pub cursor_preview: Option<image::Handle>,
+    pub cursor_error: Option<String>,
+    pub font_families: Vec<FontFamily>,
+    pub fonts: HashMap<FontRole, FontSpec>,
+    /// The font files which are loaded for previews, by their paths.
+    pub font_previews: HashMap<PathBuf, Font>,
+    pub font_error: Option<String>,
+    pub hinting: Hinting,
+    pub antialiasing: Antialiasing,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            accent_color: ColorPicker::default().without_alpha(),
+            cursor_themes: Vec::new(),
+            cursor: Cursor::default(),
+            cursor_size: None,
+            cursor_preview: None,
+            cursor_error: None,
+            font_families: Vec::new(),
+            fonts: HashMap::new(),
+            font_previews: HashMap::new(),
+            font_error: None,
+            hinting: Hinting::default(),
+            antialiasing: Antialiasing::default(),
+        }
+    }
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::AccentColor(message) => match self.accent_color.update(message) {
+                Some(Output::Committed(color)) => return self.store(Some(color)),
+
+                Some(Output::PickFromScreen) => {
+                    return Command::perform(color_picker::pick_from_screen(), |result| {
+                        crate::Message::Appearance(Message::AccentColor(
+                            color_picker::Message::Picked(result),
+                        ))
+                    });
+                }
+
+                Some(Output::Changed(_)) | None => (),
+            },
+
+            Message::CursorApplied(result) => self.cursor_error = result.err(),
+
+            Message::CursorChanged(cursor) => {
+                self.cursor_size = None;
+
+                if cursor == self.cursor {
+                    return Command::none();
+                }
+
+                self.cursor = cursor.clone();
+
+                return Command::batch(vec![
+                    self.preview_cursor(cursor.size),
+                    Command::perform(
+                        page::background(move || cursor::set_cursor(&cursor)),
+                        |result| {
+                            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                            crate::Message::Appearance(Message::CursorApplied(result))
+                        },
+                    ),
+                ]);
+            }
+
+            Message::CursorLoaded(themes, cursor) => {
+                self.cursor_themes = themes;
+                self.cursor = cursor;
+                return self.preview_cursor(self.cursor.size);
+            }
+
+            Message::CursorPreview(preview) => self.cursor_preview = preview,
+
+            Message::CursorSizeDragged(size) => {
+                self.cursor_size = Some(size);
+                return self.preview_cursor(size);
+            }
+
+            Message::FontApplied(result) => self.font_error = result.err(),
+
+            Message::FontChanged(role, mut spec) => {
+                // A family which lacks the chosen style is shown in its regular style.
+                if let Some(family) = self.family(&spec.family) {
+                    if let Some(style) = family.style(&spec.style) {
+                        spec.style = style.name.clone();
+                    }
+                }
+
+                if self.fonts.get(&role) == Some(&spec) {
+                    return Command::none();
+                }
+
+                self.fonts.insert(role, spec.clone());
+
+                return Command::batch(vec![
+                    self.preview_font(role),
+                    Command::perform(
+                        page::background(move || font::set_font(role, &spec)),
+                        |result| {
+                            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                            crate::Message::Appearance(Message::FontApplied(result))
+                        },
+                    ),
+                ]);
+            }
+
+            Message::FontPreview(path, preview) => {
+                if let Some(preview) = preview {
+                    self.font_previews.insert(path, preview);
+                }
+            }
+
+            Message::FontsLoaded(families, fonts, (hinting, antialiasing)) => {
+                self.font_families = families;
+                self.fonts = fonts.into_iter().collect();
+                self.hinting = hinting;
+                self.antialiasing = antialiasing;
+
+                return Command::batch(FontRole::ALL.map(|role| self.preview_font(role)));
+            }
+
+            Message::RenderingChanged(hinting, antialiasing) => {
+                self.hinting = hinting;
+                self.antialiasing = antialiasing;
+
+                return Command::perform(
+                    page::background(move || font::set_rendering(hinting, antialiasing)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Appearance(Message::FontApplied(result))
+                    },
+                );
+            }
+
+            Message::Loaded(config) => {
+                if let Some(color) = config.accent_color {
+                    self.accent_color.set_color(from_rgba(color));
+                }
+
+                self.accent_color
+                    .set_recent(config.recent_colors.into_iter().map(from_rgba).collect());
+
+                let cursor = Command::perform(
+                    page::background(|| (cursor::themes(), cursor::cursor())),
+                    |loaded| {
+                        let (themes, cursor) = loaded.unwrap_or_default();
+                        crate::Message::Appearance(Message::CursorLoaded(themes, cursor))
+                    },
+                );
+
+                let fonts = Command::perform(
+                    page::background(|| {
+                        let families = font::families();
+                        let fonts = FontRole::ALL
+                            .into_iter()
+                            .map(|role| (role, font::font(role, &families)))
+                            .collect();
+
+                        (families, fonts, font::rendering())
+                    }),
+                    |loaded| {
+                        let (families, fonts, rendering) = loaded.unwrap_or_default();
+                        crate::Message::Appearance(Message::FontsLoaded(families, fonts, rendering))
+                    },
+                );
+
+                return Command::batch(vec![cursor, fonts]);
+            }
+        }
+
+        Command::none()
+    }
+
+    /// Draws the pointer of the chosen cursor theme at a size, in the background.
+    fn preview_cursor(&self, size: u32) -> Command<crate::Message> {
+        let theme = self
+            .cursor_themes
+            .iter()
+            .find(|theme| theme.id == self.cursor.theme)
+            .cloned();
+
+        Command::perform(
+            page::background(move || {
+                let (width, height, pixels) = cursor::preview(&theme?, size)?;
+                Some(image::Handle::from_pixels(width, height, pixels))
+            }),
+            |preview| crate::Message::Appearance(Message::CursorPreview(preview.flatten())),
+        )
+    }
+
+    fn family(&self, name: &str) -> Option<&FontFamily> {
+        self.font_families.iter().find(|family| family.name == name)
+    }
+
+    /// The file of the chosen font of a role, which its preview is drawn with.
+    fn font_path(&self, role: FontRole) -> Option<&PathBuf> {
+        let spec = self.fonts.get(&role)?;
+        let style = self.family(&spec.family)?.style(&spec.style)?;
+        Some(&style.path)
+    }
+
+    /// Loads the file of the chosen font of a role in the background, unless it is loaded.
+    ///
+    /// Fonts given to the renderer are never freed, so each file is only loaded once.
+    fn preview_font(&self, role: FontRole) -> Command<crate::Message> {
+        let Some(path) = self.font_path(role).cloned() else {
+            return Command::none();
+        };
+
+        if self.font_previews.contains_key(&path) {
+            return Command::none();
+        }
+
+        Command::perform(
+            page::background({
+                let path = path.clone();
+                move || {
+                    let bytes = fs::read(&path).ok()?;
+                    let name = path.to_string_lossy().into_owned();
+
+                    Some(Font::External {
+                        name: Box::leak(name.into_boxed_str()),
+                        bytes: Box::leak(bytes.into_boxed_slice()),
+                    })
+                }
+            }),
+            move |preview| {
+                crate::Message::Appearance(Message::FontPreview(path, preview.flatten()))
+            },
+        )
+    }
+
+    /// Stores the accent color, which is the default color if there is none.
+    fn store(&self, accent_color: Option<Srgba>) -> Command<crate::Message> {
+        let config = Config {
+            accent_color: accent_color.map(to_rgba),
+            recent_colors: self
+                .accent_color
+                .recent()
+                .iter()
+                .copied()
+                .map(to_rgba)
+                .collect(),
+        };
+
+        Command::perform(
+            page::background(move || config::store("appearance", &config)),
+            |_| crate::Message::None,
+        )
+    }
+}
 
 pub struct Page;
 
 impl page::Page for Page {
-    type Model = super::Model;
+    type Model = Model;
 
     fn page() -> page::Meta {
         page::Meta::new("appearance", "preferences-pop-desktop-appearance-symbolic")
             .title(fl!("appearance"))
             .description(fl!("appearance", "desc"))
+            .resettable(true)
     }
 
     fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
-        Some(vec![sections.insert(Section::new())])
+        Some(vec![
+            sections.insert(accent_color()),
+            sections.insert(cursor_section()),
+            sections.insert(fonts_section()),
+            sections.insert(font_rendering()),
+        ])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let config = page::background(|| config::load::<Config>("appearance")).await;
+            crate::Message::Appearance(Message::Loaded(config.unwrap_or_default()))
+        })
+    }
+
+    /// Resets the accent color, keeping the recently chosen colors.
+    fn reset_defaults(model: &mut Model, _page: page::Entity) -> Command<crate::Message> {
+        model
+            .accent_color
+            .set_color(Model::default().accent_color.color());
+        model.store(None)
+    }
+}
+
+fn accent_color() -> Section {
+    Section::new()
+        .title(fl!("accent-color"))
+        .descriptions(vec![fl!("accent-color", "desc")])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("appearance model is missing");
+
+            settings::view_section(&section.title)
+                .add(model.accent_color.view())
+                .apply(Element::from)
+                .map(|message| crate::Message::Appearance(Message::AccentColor(message)))
+        })
+}
+
+/// A cursor theme listed by name in a dropdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ThemeChoice {
+    id: String,
+    name: String,
+}
+
+impl fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
     }
 }
+
+fn cursor_section() -> Section {
+    Section::new()
+        .title(fl!("cursor"))
+        .descriptions(vec![
+            fl!("cursor", "theme"),
+            fl!("cursor", "size"),
+            fl!("cursor", "relogin"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("appearance model is missing");
+
+            let desc = &section.descriptions;
+            let cursor = &model.cursor;
+            let size = model.cursor_size.unwrap_or(cursor.size);
+
+            let themes: Vec<ThemeChoice> = model
+                .cursor_themes
+                .iter()
+                .map(|theme| ThemeChoice {
+                    id: theme.id.clone(),
+                    name: theme.name.clone(),
+                })
+                .collect();
+
+            let selected = themes
+                .iter()
+                .find(|theme| theme.id == cursor.theme)
+                .cloned();
+
+            let theme_picker = pick_list(themes, selected, move |theme: ThemeChoice| {
+                Message::CursorChanged(Cursor {
+                    theme: theme.id,
+                    size,
+                })
+            });
+
+            let size_slider = slider(cursor::SIZES, size, Message::CursorSizeDragged)
+                .step(8)
+                .on_release(Message::CursorChanged(Cursor {
+                    theme: cursor.theme.clone(),
+                    size,
+                }))
+                .width(Length::Units(250));
+
+            // The preview is drawn at the size it will have, in a box which fits the largest.
+            let largest = *cursor::SIZES.end() as u16;
+            let preview: Element<_> = match &model.cursor_preview {
+                Some(handle) => image(handle.clone())
+                    .width(Length::Units(size as u16))
+                    .height(Length::Units(size as u16))
+                    .into(),
+                None => text("").into(),
+            };
+
+            let preview = container(preview)
+                .width(Length::Units(largest))
+                .height(Length::Units(largest))
+                .center_x()
+                .center_y();
+
+            let mut view = settings::view_section(&section.title)
+                .add(settings::item(&desc[0], theme_picker))
+                .add(settings::item(
+                    &desc[1],
+                    row!(text(fl!("cursor", "px", value = size)), size_slider)
+                        .spacing(16)
+                        .align_items(Alignment::Center),
+                ))
+                .add(preview)
+                .add(text(&desc[2]).size(12));
+
+            if let Some(why) = &model.cursor_error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Appearance)
+        })
+}
+
+/// A choice listed by name in a dropdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Choice<T> {
+    value: T,
+    name: String,
+}
+
+impl<T> fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+fn fonts_section() -> Section {
+    Section::new()
+        .title(fl!("fonts"))
+        .descriptions(vec![
+            fl!("fonts", "interface"),
+            fl!("fonts", "document"),
+            fl!("fonts", "monospace"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("appearance model is missing");
+
+            let desc = &section.descriptions;
+            let mut view = settings::view_section(&section.title);
+
+            for (role, title) in FontRole::ALL.into_iter().zip(desc) {
+                let Some(spec) = model.fonts.get(&role) else {
+                    continue;
+                };
+
+                // Code and terminals are only offered families whose characters are all the
+                // same width.
+                let families: Vec<String> = model
+                    .font_families
+                    .iter()
+                    .filter(|family| role != FontRole::Monospace || family.monospace)
+                    .map(|family| family.name.clone())
+                    .collect();
+
+                let styles: Vec<String> = model
+                    .family(&spec.family)
+                    .map(|family| {
+                        family
+                            .styles
+                            .iter()
+                            .map(|style| style.name.clone())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                let family_picker = pick_list(families, Some(spec.family.clone()), {
+                    let spec = spec.clone();
+                    move |family| {
+                        Message::FontChanged(
+                            role,
+                            FontSpec {
+                                family,
+                                ..spec.clone()
+                            },
+                        )
+                    }
+                });
+
+                let style_picker = pick_list(styles, Some(spec.style.clone()), {
+                    let spec = spec.clone();
+                    move |style| {
+                        Message::FontChanged(
+                            role,
+                            FontSpec {
+                                style,
+                                ..spec.clone()
+                            },
+                        )
+                    }
+                });
+
+                let size_picker = pick_list(FONT_SIZES.to_vec(), Some(spec.size), {
+                    let spec = spec.clone();
+                    move |size| {
+                        Message::FontChanged(
+                            role,
+                            FontSpec {
+                                size,
+                                ..spec.clone()
+                            },
+                        )
+                    }
+                });
+
+                // Points are drawn at 96 pixels per inch, as toolkits do.
+                let mut sample = text(fl!("fonts", "sample")).size((spec.size * 4 / 3) as u16);
+
+                if let Some(preview) = model
+                    .font_path(role)
+                    .and_then(|path| model.font_previews.get(path))
+                {
+                    sample = sample.font(*preview);
+                }
+
+                view = view.add(
+                    column!(
+                        settings::item(
+                            title,
+                            row!(family_picker, style_picker, size_picker)
+                                .spacing(8)
+                                .align_items(Alignment::Center),
+                        ),
+                        sample,
+                    )
+                    .spacing(8),
+                );
+            }
+
+            if let Some(why) = &model.font_error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Appearance)
+        })
+}
+
+fn font_rendering() -> Section {
+    Section::new()
+        .title(fl!("font-rendering"))
+        .descriptions(vec![
+            fl!("font-rendering", "hinting"),
+            fl!("font-rendering", "antialiasing"),
+            fl!("font-rendering", "desc"),
+        ])
+        .collapsible()
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("appearance model is missing");
+
+            let desc = &section.descriptions;
+            let (hinting, antialiasing) = (model.hinting, model.antialiasing);
+
+            let hintings: Vec<Choice<Hinting>> = Hinting::ALL
+                .into_iter()
+                .map(|value| Choice {
+                    value,
+                    name: match value {
+                        Hinting::None => fl!("font-rendering", "none"),
+                        Hinting::Slight => fl!("font-rendering", "slight"),
+                        Hinting::Medium => fl!("font-rendering", "medium"),
+                        Hinting::Full => fl!("font-rendering", "full"),
+                    },
+                })
+                .collect();
+
+            let antialiasings: Vec<Choice<Antialiasing>> = Antialiasing::ALL
+                .into_iter()
+                .map(|value| Choice {
+                    value,
+                    name: match value {
+                        Antialiasing::None => fl!("font-rendering", "none"),
+                        Antialiasing::Grayscale => fl!("font-rendering", "grayscale"),
+                        Antialiasing::Subpixel => fl!("font-rendering", "subpixel"),
+                    },
+                })
+                .collect();
+
+            let selected_hinting = hintings.iter().find(|c| c.value == hinting).cloned();
+            let selected_antialiasing = antialiasings
+                .iter()
+                .find(|c| c.value == antialiasing)
+                .cloned();
+
+            settings::view_section(&section.title)
+                .add(settings::item(
+                    &desc[0],
+                    pick_list(hintings, selected_hinting, move |choice| {
+                        Message::RenderingChanged(choice.value, antialiasing)
+                    }),
+                ))
+                .add(settings::item(
+                    &desc[1],
+                    pick_list(antialiasings, selected_antialiasing, move |choice| {
+                        Message::RenderingChanged(hinting, choice.value)
+                    }),
+                ))
+                .add(text(&desc[2]).size(12))
+                .apply(Element::from)
+                .map(crate::Message::Appearance)
+        })
+}
@@ -1,14 +1,92 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, horizontal_space, pick_list, row},
+        Alignment, Command, Length,
+    },
+    theme,
+    widget::{icon, settings, text, toggler},
+    Element,
+};
+use cosmic_settings_applications::desktop_entry::{self, DesktopEntry};
+use cosmic_settings_panel::{
+    self as panel, Anchor, AppletArea, PanelConfig, PanelKind, PanelSize, PlacedApplet,
+};
 use slotmap::SlotMap;
+use std::{collections::HashMap, fmt};
 
-use crate::page::{self, section, Content, Section};
+use crate::{
+    page::{self, section, Content, Section},
+    widget::reorder_list,
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// The installed applets, and the config of each panel.
+    Loaded(Vec<DesktopEntry>, Vec<(PanelKind, PanelConfig)>),
+    /// The position, size, behavior, or applets of a panel were changed.
+    PanelConfigChanged(PanelKind, PanelConfig),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub applets: Vec<DesktopEntry>,
+    pub configs: HashMap<PanelKind, PanelConfig>,
+    pub error: Option<String>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::Loaded(applets, configs) => {
+                self.applets = applets;
+                self.configs = configs.into_iter().collect();
+            }
+
+            Message::PanelConfigChanged(kind, config) => {
+                if self.configs.get(&kind) == Some(&config) {
+                    return Command::none();
+                }
+
+                self.configs.insert(kind, config.clone());
+
+                return Command::perform(
+                    page::background(move || panel::set_config(kind, &config)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Dock(Message::Applied(result))
+                    },
+                );
+            }
+        }
+
+        Command::none()
+    }
+
+    /// The name of an applet by the ID which panels list it by.
+    fn applet_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.applets
+            .iter()
+            .find(|applet| applet_id(applet) == id)
+            .map_or(id, |applet| applet.name.as_str())
+    }
+}
+
+/// Panels list applets by the IDs of their desktop entries, without the suffix.
+fn applet_id(applet: &DesktopEntry) -> &str {
+    applet.id.strip_suffix(".desktop").unwrap_or(&applet.id)
+}
 
 pub struct Page;
 
 impl page::Page for Page {
-    type Model = super::Model;
+    type Model = Model;
 
     fn page() -> page::Meta {
         page::Meta::new("dock", "preferences-pop-desktop-dock-symbolic")
@@ -17,6 +95,239 @@ impl page::Page for Page {
     }
 
     fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
-        Some(vec![sections.insert(Section::new())])
+        Some(vec![sections.insert(top_panel()), sections.insert(dock())])
     }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let loaded = page::background(|| {
+                let configs = PanelKind::ALL
+                    .into_iter()
+                    .map(|kind| (kind, panel::config(kind)))
+                    .collect();
+
+                (desktop_entry::applets(), configs)
+            })
+            .await;
+
+            let (applets, configs) = loaded.unwrap_or_default();
+            crate::Message::Dock(Message::Loaded(applets, configs))
+        })
+    }
+}
+
+/// A choice listed by name in a dropdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Choice<T> {
+    value: T,
+    name: String,
+}
+
+impl<T> fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+fn descriptions() -> Vec<String> {
+    vec![
+        fl!("panel", "position"),
+        fl!("panel", "size"),
+        fl!("panel", "autohide"),
+        fl!("panel", "autohide-desc"),
+        fl!("panel", "applets"),
+        fl!("panel", "add-applet"),
+    ]
+}
+
+fn top_panel() -> Section {
+    Section::new()
+        .title(fl!("panel", "top"))
+        .descriptions(descriptions())
+        .view_fn(|app, section| panel_view(app, section, PanelKind::Panel))
+}
+
+fn dock() -> Section {
+    Section::new()
+        .title(fl!("panel", "dock"))
+        .descriptions(descriptions())
+        .view_fn(|app, section| panel_view(app, section, PanelKind::Dock))
+}
+
+fn panel_view<'a>(
+    app: &'a crate::SettingsApp,
+    section: &'a Section,
+    kind: PanelKind,
+) -> Element<'a, crate::Message> {
+    let model = app
+        .pages
+        .resource::<Model>()
+        .expect("dock model is missing");
+
+    let desc = &section.descriptions;
+    let config = model
+        .configs
+        .get(&kind)
+        .cloned()
+        .unwrap_or_else(|| PanelConfig::default_for(kind));
+
+    let changed = {
+        let config = config.clone();
+        move |change: &dyn Fn(&mut PanelConfig)| {
+            let mut config = config.clone();
+            change(&mut config);
+            Message::PanelConfigChanged(kind, config)
+        }
+    };
+
+    let anchors: Vec<Choice<Anchor>> = Anchor::ALL
+        .into_iter()
+        .map(|value| Choice {
+            value,
+            name: match value {
+                Anchor::Top => fl!("panel", "top-edge"),
+                Anchor::Bottom => fl!("panel", "bottom-edge"),
+                Anchor::Left => fl!("panel", "left-edge"),
+                Anchor::Right => fl!("panel", "right-edge"),
+            },
+        })
+        .collect();
+
+    let sizes: Vec<Choice<PanelSize>> = PanelSize::ALL
+        .into_iter()
+        .map(|value| Choice {
+            value,
+            name: match value {
+                PanelSize::ExtraSmall => fl!("panel", "extra-small"),
+                PanelSize::Small => fl!("panel", "small"),
+                PanelSize::Medium => fl!("panel", "medium"),
+                PanelSize::Large => fl!("panel", "large"),
+                PanelSize::ExtraLarge => fl!("panel", "extra-large"),
+            },
+        })
+        .collect();
+
+    let areas: Vec<Choice<AppletArea>> = AppletArea::ALL
+        .into_iter()
+        .map(|value| Choice {
+            value,
+            name: match value {
+                AppletArea::Start => fl!("panel", "start"),
+                AppletArea::Center => fl!("panel", "center"),
+                AppletArea::End => fl!("panel", "end"),
+            },
+        })
+        .collect();
+
+    let selected_anchor = anchors.iter().find(|c| c.value == config.anchor).cloned();
+    let selected_size = sizes.iter().find(|c| c.value == config.size).cloned();
+
+    let mut view = settings::view_section(&section.title);
+
+    if let Some(why) = &model.error {
+        view = view.add(text(why));
+    }
+
+    view = view
+        .add(settings::item(
+            &desc[0],
+            pick_list(anchors, selected_anchor, {
+                let changed = changed.clone();
+                move |choice: Choice<Anchor>| changed(&|config| config.anchor = choice.value)
+            }),
+        ))
+        .add(settings::item(
+            &desc[1],
+            pick_list(sizes, selected_size, {
+                let changed = changed.clone();
+                move |choice: Choice<PanelSize>| changed(&|config| config.size = choice.value)
+            }),
+        ))
+        .add(
+            settings::item::builder(&desc[2])
+                .description(&desc[3])
+                .control(toggler(None, config.autohide, {
+                    let changed = changed.clone();
+                    move |autohide| changed(&|config| config.autohide = autohide)
+                })),
+        );
+
+    // Each applet is placed in a part of the panel, and dragged into order within it.
+    let rows: Vec<Element<Message>> = config
+        .applets
+        .iter()
+        .enumerate()
+        .map(|(index, applet)| {
+            let area = areas.iter().find(|c| c.value == applet.area).cloned();
+
+            let area_picker = pick_list(areas.clone(), area, {
+                let changed = changed.clone();
+                move |choice: Choice<AppletArea>| {
+                    changed(&|config| config.applets[index].area = choice.value)
+                }
+            });
+
+            let remove = icon("list-remove-symbolic", 16)
+                .style(theme::Svg::Symbolic)
+                .apply(button)
+                .style(theme::Button::Transparent)
+                .on_press(changed(&|config| {
+                    config.applets.remove(index);
+                }));
+
+            row!(
+                text(model.applet_name(&applet.id)),
+                horizontal_space(Length::Fill),
+                area_picker,
+                remove,
+            )
+            .spacing(8)
+            .align_items(Alignment::Center)
+            .into()
+        })
+        .collect();
+
+    let list = reorder_list(rows, {
+        let changed = changed.clone();
+        move |from, to| {
+            changed(&|config| {
+                let applet = config.applets.remove(from);
+                config.applets.insert(to, applet);
+            })
+        }
+    });
+
+    // Applets which are not shown yet are added to the end of the panel.
+    let hidden: Vec<Choice<String>> = model
+        .applets
+        .iter()
+        .filter(|applet| {
+            !config
+                .applets
+                .iter()
+                .any(|shown| shown.id == applet_id(applet))
+        })
+        .map(|applet| Choice {
+            value: applet_id(applet).to_owned(),
+            name: applet.name.clone(),
+        })
+        .collect();
+
+    view = view.add(text(&desc[4])).add(list);
+
+    if !hidden.is_empty() {
+        view = view.add(settings::item(
+            &desc[5],
+            pick_list(hidden, None, move |choice: Choice<String>| {
+                changed(&|config| {
+                    config.applets.push(PlacedApplet {
+                        id: choice.value.clone(),
+                        area: AppletArea::End,
+                    });
+                })
+            }),
+        ));
+    }
+
+    view.apply(Element::from).map(crate::Message::Dock)
 }
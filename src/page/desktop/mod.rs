@@ -8,7 +8,10 @@ pub mod options;
 pub mod wallpaper;
 pub mod workspaces;
 
-use crate::page;
+use cosmic::iced::Command;
+use cosmic_settings_desktop::window::{self, Tiling, WindowBehavior};
+
+use crate::{page, widget::segmented::Segment};
 
 pub struct Page;
 
@@ -29,7 +32,7 @@ impl page::Page for Page {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum Message {
     Slideshow(bool),
     SameBackground(bool),
@@ -38,9 +41,13 @@ pub enum Message {
     ShowMinimizeButton(bool),
     ShowMaximizeButton(bool),
     TopLeftHotCorner(bool),
+    WindowBehaviorApplied(Result<(), String>),
+    /// Focus follows the mouse, the delay before raising, or the tiling of new windows changed.
+    WindowBehaviorChanged(WindowBehavior),
+    WindowBehaviorLoaded(WindowBehavior),
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct Model {
     pub top_left_hot_corner: bool,
@@ -50,10 +57,40 @@ pub struct Model {
     pub show_maximize_button: bool,
     pub slideshow: bool,
     pub same_background: bool,
+    pub window: WindowBehavior,
+    pub window_error: Option<String>,
+    pub tilings: Vec<Segment<Tiling>>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            top_left_hot_corner: false,
+            show_workspaces_button: false,
+            show_applications_button: false,
+            show_minimize_button: false,
+            show_maximize_button: false,
+            slideshow: false,
+            same_background: false,
+            window: WindowBehavior::default(),
+            window_error: None,
+            tilings: Tiling::ALL
+                .into_iter()
+                .map(|tiling| {
+                    let name = match tiling {
+                        Tiling::Floating => fl!("window-management", "floating"),
+                        Tiling::AutoTile => fl!("window-management", "auto-tile"),
+                    };
+
+                    Segment::new(tiling, name)
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Model {
-    pub fn update(&mut self, message: Message) {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
         match message {
             Message::SameBackground(value) => self.same_background = value,
             Message::ShowApplicationsButton(value) => self.show_applications_button = value,
@@ -62,7 +99,28 @@ impl Model {
             Message::ShowWorkspacesButton(value) => self.show_workspaces_button = value,
             Message::Slideshow(value) => self.slideshow = value,
             Message::TopLeftHotCorner(value) => self.top_left_hot_corner = value,
+            Message::WindowBehaviorApplied(result) => self.window_error = result.err(),
+
+            Message::WindowBehaviorChanged(behavior) => {
+                if behavior == self.window {
+                    return Command::none();
+                }
+
+                self.window = behavior;
+
+                return Command::perform(
+                    page::background(move || window::set_behavior(&behavior)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Desktop(Message::WindowBehaviorApplied(result))
+                    },
+                );
+            }
+
+            Message::WindowBehaviorLoaded(behavior) => self.window = behavior,
         }
+
+        Command::none()
     }
 }
 
@@ -4,11 +4,14 @@
 use super::Message;
 use apply::Apply;
 use cosmic::{
-    iced::widget::horizontal_space,
-    iced::Length,
-    widget::{settings, toggler},
+    iced::widget::{horizontal_space, row, slider},
+    iced::{Alignment, Command, Length},
+    widget::{settings, text, toggler},
     Element,
 };
+use cosmic_settings_desktop::window::{self, WindowBehavior};
+
+use crate::widget::{segmented::segmented, settings_row};
 
 use slotmap::SlotMap;
 
@@ -23,6 +26,7 @@ impl page::Page for Page {
         page::Meta::new("desktop-options", "video-display-symbolic")
             .title(fl!("desktop-options"))
             .description(fl!("desktop-options", "desc"))
+            .resettable(true)
     }
 
     #[allow(clippy::too_many_lines)]
@@ -32,8 +36,28 @@ impl page::Page for Page {
             sections.insert(hot_corner()),
             sections.insert(top_panel()),
             sections.insert(window_controls()),
+            sections.insert(window_management()),
         ])
     }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let behavior = page::background(window::behavior).await;
+            crate::Message::Desktop(Message::WindowBehaviorLoaded(behavior.unwrap_or_default()))
+        })
+    }
+
+    fn reset_defaults(model: &mut super::Model, _page: page::Entity) -> Command<crate::Message> {
+        let defaults = super::Model::default();
+
+        model.top_left_hot_corner = defaults.top_left_hot_corner;
+        model.show_workspaces_button = defaults.show_workspaces_button;
+        model.show_applications_button = defaults.show_applications_button;
+        model.show_minimize_button = defaults.show_minimize_button;
+        model.show_maximize_button = defaults.show_maximize_button;
+
+        model.update(Message::WindowBehaviorChanged(defaults.window))
+    }
 }
 
 pub fn hot_corner() -> Section {
@@ -47,12 +71,10 @@ pub fn hot_corner() -> Section {
                 .expect("desktop model is missing");
             let descriptions = &section.descriptions;
             settings::view_section(&section.title)
-                .add(settings::item(
-                    &descriptions[0],
-                    toggler(None, desktop.top_left_hot_corner, |value| {
-                        Message::TopLeftHotCorner(value)
-                    }),
-                ))
+                .add(
+                    settings_row(&descriptions[0], "")
+                        .toggler(desktop.top_left_hot_corner, Message::TopLeftHotCorner),
+                )
                 .apply(Element::from)
                 .map(crate::Message::Desktop)
         })
@@ -142,23 +164,103 @@ pub fn window_controls() -> Section {
             let descriptions = &section.descriptions;
 
             settings::view_section(&section.title)
-                .add(settings::item(
-                    &descriptions[0],
-                    toggler(
-                        None,
-                        desktop.show_minimize_button,
-                        Message::ShowMinimizeButton,
-                    ),
-                ))
-                .add(settings::item(
-                    &descriptions[1],
-                    toggler(
-                        None,
-                        desktop.show_maximize_button,
-                        Message::ShowMaximizeButton,
-                    ),
-                ))
+                .add(
+                    settings_row(&descriptions[0], "")
+                        .toggler(desktop.show_minimize_button, Message::ShowMinimizeButton),
+                )
+                .add(
+                    settings_row(&descriptions[1], "")
+                        .toggler(desktop.show_maximize_button, Message::ShowMaximizeButton),
+                )
                 .apply(Element::from)
                 .map(crate::Message::Desktop)
         })
 }
+
+pub fn window_management() -> Section {
+    Section::new()
+        .title(fl!("window-management"))
+        .descriptions(vec![
+            fl!("window-management", "focus-follows-mouse"),
+            fl!("window-management", "focus-follows-mouse-desc"),
+            fl!("window-management", "auto-raise"),
+            fl!("window-management", "tiling"),
+            fl!("window-management", "tiling-desc"),
+        ])
+        .view_fn(|app, section| {
+            let desktop = app
+                .pages
+                .resource::<super::Model>()
+                .expect("desktop model is missing");
+
+            let descriptions = &section.descriptions;
+            let behavior = desktop.window;
+
+            let focus = settings_row(&descriptions[0], &descriptions[1]).toggler(
+                behavior.focus_follows_mouse,
+                move |focus_follows_mouse| {
+                    Message::WindowBehaviorChanged(WindowBehavior {
+                        focus_follows_mouse,
+                        ..behavior
+                    })
+                },
+            );
+
+            // The delay is dimmed, and left as it is, while focus does not follow the mouse.
+            let delay = slider(
+                window::AUTO_RAISE_DELAY,
+                behavior.auto_raise_delay,
+                move |auto_raise_delay| {
+                    Message::WindowBehaviorChanged(if behavior.focus_follows_mouse {
+                        WindowBehavior {
+                            auto_raise_delay,
+                            ..behavior
+                        }
+                    } else {
+                        behavior
+                    })
+                },
+            )
+            .step(50)
+            .width(Length::Units(250));
+
+            let mut view = settings::view_section(&section.title)
+                .add(focus)
+                .add(
+                    settings_row(&descriptions[2], "")
+                        .control(
+                            row!(
+                                text(fl!(
+                                    "window-management",
+                                    "ms",
+                                    value = behavior.auto_raise_delay
+                                )),
+                                delay
+                            )
+                            .spacing(16)
+                            .align_items(Alignment::Center),
+                        )
+                        .disabled(!behavior.focus_follows_mouse),
+                )
+                .add(
+                    settings::item::builder(&descriptions[3])
+                        .description(&descriptions[4])
+                        .control(segmented(
+                            &desktop.tilings,
+                            Some(&behavior.tiling),
+                            move |tiling| {
+                                Message::WindowBehaviorChanged(WindowBehavior {
+                                    tiling,
+                                    ..behavior
+                                })
+                            },
+                        )),
+                );
+
+            if let Some(why) = &desktop.window_error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Desktop)
+        })
+}
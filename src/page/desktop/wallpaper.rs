@@ -1,23 +1,123 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use super::Message;
 use apply::Apply;
+use bytecheck::CheckBytes;
 use cosmic::{
-    iced::widget::{column, container, horizontal_space, image, row, svg, text},
-    iced::Length,
+    iced::widget::{container, horizontal_space, image, row, text},
+    iced::{Command, Length},
     theme,
     widget::{list_column, settings, toggler},
     Element,
 };
+use rkyv::{Archive, Deserialize, Serialize};
 use slotmap::SlotMap;
+use std::path::{Path, PathBuf};
 
-use crate::page::{self, section, Content, Section};
+use crate::{
+    config,
+    page::{self, section, Content, Section},
+    widget::image_grid::{self, ImageGrid},
+};
+
+/// Wallpapers are found in this directory, and in the directories within it.
+const BACKGROUNDS: &str = "/usr/share/backgrounds";
+
+const DEFAULT_WALLPAPER: &str =
+    "/usr/share/backgrounds/pop/kate-hazen-COSMIC-desktop-wallpaper.png";
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Grid(image_grid::Message<PathBuf>),
+    Loaded(Config, Vec<PathBuf>),
+}
+
+/// The wallpaper chosen on the wallpaper page, which is kept between sessions.
+#[must_use]
+#[derive(Archive, Clone, Debug, Default, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes, Debug))]
+pub struct Config {
+    pub wallpaper: Option<String>,
+}
+
+#[derive(Default)]
+pub struct Model {
+    pub grid: ImageGrid<PathBuf>,
+    pub selected: Option<PathBuf>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Grid(image_grid::Message::Select(path)) => {
+                let config = Config {
+                    wallpaper: Some(path.to_string_lossy().into_owned()),
+                };
+
+                self.selected = Some(path);
+
+                Command::perform(
+                    page::background(move || config::store("wallpaper", &config)),
+                    |_| crate::Message::None,
+                )
+            }
+
+            Message::Grid(message) => self.grid.update(message).map(grid_message),
+
+            Message::Loaded(config, wallpapers) => {
+                self.selected = config.wallpaper.map(PathBuf::from);
+
+                self.grid
+                    .set_images(wallpapers.into_iter().map(|path| (path.clone(), path)))
+                    .map(grid_message)
+            }
+        }
+    }
+}
+
+fn grid_message(message: image_grid::Message<PathBuf>) -> crate::Message {
+    crate::Message::Wallpaper(Message::Grid(message))
+}
+
+/// Lists the images in the backgrounds directory, and in the directories within it.
+fn wallpapers() -> Vec<PathBuf> {
+    let mut wallpapers = Vec::new();
+    let mut directories = vec![PathBuf::from(BACKGROUNDS)];
+
+    while let Some(directory) = directories.pop() {
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+
+            // Only the directories directly within the backgrounds directory are searched.
+            if path.is_dir() && directory == Path::new(BACKGROUNDS) {
+                directories.push(path);
+            } else if is_image(&path) {
+                wallpapers.push(path);
+            }
+        }
+    }
+
+    wallpapers.sort_unstable();
+    wallpapers
+}
+
+fn is_image(path: &Path) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    matches!(extension.as_deref(), Some("png" | "jpg" | "jpeg" | "webp"))
+}
 
 pub struct Page;
 
 impl page::Page for Page {
-    type Model = super::Model;
+    type Model = Model;
 
     fn page() -> page::Meta {
         page::Meta::new("wallpaper", "preferences-desktop-wallpaper-symbolic")
@@ -28,6 +128,16 @@ impl page::Page for Page {
     fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
         Some(vec![sections.insert(settings())])
     }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let loaded =
+                page::background(|| (config::load::<Config>("wallpaper"), wallpapers())).await;
+
+            let (config, wallpapers) = loaded.unwrap_or_default();
+            crate::Message::Wallpaper(Message::Loaded(config, wallpapers))
+        })
+    }
 }
 
 pub fn settings() -> Section {
@@ -44,52 +154,46 @@ pub fn settings() -> Section {
                 .pages
                 .resource::<super::Model>()
                 .expect("desktop model is missing");
-            let image_paths: Vec<std::path::PathBuf> = Vec::new();
-
-            let mut image_column = Vec::with_capacity(image_paths.len() / 4);
-            for chunk in image_paths.chunks(4) {
-                let mut image_row = Vec::with_capacity(chunk.len());
-                for image_path in chunk.iter() {
-                    image_row.push(if image_path.ends_with(".svg") {
-                        svg(svg::Handle::from_path(image_path))
-                            .width(Length::Units(150))
-                            .into()
-                    } else {
-                        image(image_path).width(Length::Units(150)).into()
-                    });
-                }
-                image_column.push(row(image_row).spacing(16).into());
-            }
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("wallpaper model is missing");
+
+            let preview = model
+                .selected
+                .as_deref()
+                .unwrap_or_else(|| Path::new(DEFAULT_WALLPAPER));
+
+            let options = list_column()
+                .add(settings::item(
+                    &descriptions[0],
+                    toggler(
+                        None,
+                        desktop.same_background,
+                        super::Message::SameBackground,
+                    ),
+                ))
+                .add(settings::item(&descriptions[1], text("TODO")))
+                .add(settings::item(
+                    &descriptions[2],
+                    toggler(None, desktop.slideshow, super::Message::Slideshow),
+                ))
+                .apply(Element::from)
+                .map(crate::Message::Desktop);
 
             let children = vec![
                 row!(
                     horizontal_space(Length::Fill),
-                    container(
-                        image("/usr/share/backgrounds/pop/kate-hazen-COSMIC-desktop-wallpaper.png")
-                            .width(Length::Units(300))
-                    )
-                    .padding(4)
-                    .style(theme::Container::Box),
+                    container(image(preview).width(Length::Units(300)))
+                        .padding(4)
+                        .style(theme::Container::Box),
                     horizontal_space(Length::Fill),
                 )
                 .into(),
-                list_column()
-                    .add(settings::item(
-                        &descriptions[0],
-                        toggler(None, desktop.same_background, Message::SameBackground),
-                    ))
-                    .add(settings::item(&descriptions[1], text("TODO")))
-                    .add(settings::item(
-                        &descriptions[2],
-                        toggler(None, desktop.slideshow, Message::Slideshow),
-                    ))
-                    .into(),
-                column(image_column).spacing(16).into(),
+                options,
+                model.grid.view(model.selected.as_ref()).map(grid_message),
             ];
 
-            settings::view_column(children)
-                .padding(0)
-                .apply(Element::from)
-                .map(crate::Message::Desktop)
+            settings::view_column(children).padding(0).into()
         })
 }
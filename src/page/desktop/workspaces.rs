@@ -1,16 +1,150 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use cosmic::iced::{widget::horizontal_space, Length};
-use cosmic::widget::settings;
+use apply::Apply;
+use cosmic::iced::{Command, Subscription};
+use cosmic::widget::{settings, text};
+use cosmic::Element;
+use cosmic_settings_desktop::workspaces::{
+    self, WorkspaceAmount, WorkspaceConfig, WorkspaceDisplays, WorkspaceLayout,
+};
 use slotmap::SlotMap;
 
-use crate::page::{self, section, Content, Section};
+use crate::{
+    page::{self, section, Content, Section},
+    widget::{
+        radio_list::{radio_list, Entry},
+        segmented::{segmented, Segment},
+        spin_button::{self, spin_button, SpinButton},
+    },
+};
+
+/// Whether the number of workspaces is dynamic, or fixed to the count of the spin button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Amount {
+    Dynamic,
+    Fixed,
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// The fixed number of workspaces was stepped or typed.
+    Count(spin_button::Message),
+    Loaded(WorkspaceConfig),
+    WorkspaceConfigChanged(WorkspaceConfig),
+}
+
+pub struct Model {
+    pub config: WorkspaceConfig,
+    pub count: SpinButton<u8>,
+    pub error: Option<String>,
+    pub amounts: Vec<Entry<Amount>>,
+    pub displays: Vec<Entry<WorkspaceDisplays>>,
+    pub layouts: Vec<Segment<WorkspaceLayout>>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            config: WorkspaceConfig::default(),
+            count: spin_button(workspaces::DEFAULT_COUNT, workspaces::COUNT, 1),
+            error: None,
+            amounts: vec![
+                Entry::new(
+                    Amount::Dynamic,
+                    fl!("workspaces-behavior", "dynamic"),
+                    fl!("workspaces-behavior", "dynamic-desc"),
+                ),
+                Entry::new(
+                    Amount::Fixed,
+                    fl!("workspaces-behavior", "fixed"),
+                    fl!("workspaces-behavior", "fixed-desc"),
+                ),
+            ],
+            displays: vec![
+                Entry::new(
+                    WorkspaceDisplays::PrimaryOnly,
+                    fl!("workspaces-multi-behavior", "primary"),
+                    fl!("workspaces-multi-behavior", "primary-desc"),
+                ),
+                Entry::new(
+                    WorkspaceDisplays::All,
+                    fl!("workspaces-multi-behavior", "all"),
+                    fl!("workspaces-multi-behavior", "all-desc"),
+                ),
+            ],
+            layouts: vec![
+                Segment::new(
+                    WorkspaceLayout::Horizontal,
+                    fl!("workspaces-layout", "horizontal"),
+                ),
+                Segment::new(
+                    WorkspaceLayout::Vertical,
+                    fl!("workspaces-layout", "vertical"),
+                ),
+            ],
+        }
+    }
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::Count(message) => {
+                if let Some(count) = self.count.update(message) {
+                    let config = WorkspaceConfig {
+                        amount: WorkspaceAmount::Fixed(count),
+                        ..self.config
+                    };
+
+                    return self.update(Message::WorkspaceConfigChanged(config));
+                }
+            }
+
+            Message::Loaded(config) => {
+                if let WorkspaceAmount::Fixed(count) = config.amount {
+                    self.count.set_value(count);
+                }
+
+                self.config = config;
+            }
+
+            Message::WorkspaceConfigChanged(config) => {
+                if config == self.config {
+                    return Command::none();
+                }
+
+                self.config = config;
+
+                // The compositor rearranges the workspaces, and the overview, as it is written.
+                return Command::perform(
+                    page::background(move || workspaces::set_config(&config)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Workspaces(Message::Applied(result))
+                    },
+                );
+            }
+        }
+
+        Command::none()
+    }
+
+    fn amount(&self) -> Amount {
+        match self.config.amount {
+            WorkspaceAmount::Dynamic => Amount::Dynamic,
+            WorkspaceAmount::Fixed(_) => Amount::Fixed,
+        }
+    }
+}
 
 pub struct Page;
 
 impl page::Page for Page {
-    type Model = super::Model;
+    type Model = Model;
 
     fn page() -> page::Meta {
         page::Meta::new("workspaces", "preferences-pop-desktop-workspaces-symbolic")
@@ -22,8 +156,23 @@ impl page::Page for Page {
         Some(vec![
             sections.insert(behavior()),
             sections.insert(multi_behavior()),
+            sections.insert(layout()),
         ])
     }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let config = page::background(workspaces::config).await;
+            crate::Message::Workspaces(Message::Loaded(config.unwrap_or_default()))
+        })
+    }
+
+    fn subscription(model: &Model) -> Subscription<crate::Message> {
+        model
+            .count
+            .subscription()
+            .map(|message| crate::Message::Workspaces(Message::Count(message)))
+    }
 }
 
 fn behavior() -> Section {
@@ -32,25 +181,43 @@ fn behavior() -> Section {
         .descriptions(vec![
             fl!("workspaces-behavior", "dynamic"),
             fl!("workspaces-behavior", "fixed"),
+            fl!("workspaces-behavior", "count"),
         ])
         .view_fn(|app, section| {
-            let _desktop = app
+            let model = app
                 .pages
-                .resource::<super::Model>()
-                .expect("desktop model is missing");
+                .resource::<Model>()
+                .expect("workspaces model is missing");
 
             let descriptions = &section.descriptions;
+            let config = model.config;
 
-            settings::view_section(&section.title)
-                .add(settings::item(
-                    &descriptions[0],
-                    horizontal_space(Length::Fill),
-                ))
-                .add(settings::item(
-                    &descriptions[1],
-                    horizontal_space(Length::Fill),
-                ))
-                .into()
+            let mut view = settings::view_section(&section.title).add(radio_list(
+                &model.amounts,
+                Some(&model.amount()),
+                move |amount| {
+                    let amount = match amount {
+                        Amount::Dynamic => WorkspaceAmount::Dynamic,
+                        Amount::Fixed => WorkspaceAmount::Fixed(model.count.value()),
+                    };
+
+                    Message::WorkspaceConfigChanged(WorkspaceConfig { amount, ..config })
+                },
+            ));
+
+            // The count is only asked for once the number of workspaces is fixed.
+            if model.amount() == Amount::Fixed {
+                view = view.add(settings::item(
+                    &descriptions[2],
+                    model.count.view().map(Message::Count),
+                ));
+            }
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Workspaces)
         })
 }
 
@@ -58,24 +225,55 @@ fn multi_behavior() -> Section {
     Section::new()
         .title(fl!("workspaces-multi-behavior"))
         .descriptions(vec![
-            fl!("workspaces-multi-behavior", "span"),
-            fl!("workspaces-multi-behavior", "separate"),
+            fl!("workspaces-multi-behavior", "primary"),
+            fl!("workspaces-multi-behavior", "all"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("workspaces model is missing");
+
+            let config = model.config;
+
+            settings::view_section(&section.title)
+                .add(radio_list(
+                    &model.displays,
+                    Some(&config.displays),
+                    move |displays| {
+                        Message::WorkspaceConfigChanged(WorkspaceConfig { displays, ..config })
+                    },
+                ))
+                .apply(Element::from)
+                .map(crate::Message::Workspaces)
+        })
+}
+
+fn layout() -> Section {
+    Section::new()
+        .title(fl!("workspaces-layout"))
+        .descriptions(vec![
+            fl!("workspaces-layout", "orientation"),
+            fl!("workspaces-layout", "horizontal"),
+            fl!("workspaces-layout", "vertical"),
         ])
         .view_fn(|app, section| {
-            let _desktop = app
+            let model = app
                 .pages
-                .resource::<super::Model>()
-                .expect("desktop model is missing");
+                .resource::<Model>()
+                .expect("workspaces model is missing");
+
             let descriptions = &section.descriptions;
+            let config = model.config;
+
             settings::view_section(&section.title)
                 .add(settings::item(
                     &descriptions[0],
-                    horizontal_space(Length::Fill),
-                ))
-                .add(settings::item(
-                    &descriptions[1],
-                    horizontal_space(Length::Fill),
+                    segmented(&model.layouts, Some(&config.layout), move |layout| {
+                        Message::WorkspaceConfigChanged(WorkspaceConfig { layout, ..config })
+                    }),
                 ))
-                .into()
+                .apply(Element::from)
+                .map(crate::Message::Workspaces)
         })
 }
@@ -0,0 +1,1739 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+pub mod night_light;
+
+use apply::Apply;
+use bytecheck::CheckBytes;
+use cosmic::{
+    iced::{
+        self,
+        widget::{button, container, horizontal_space, pick_list, row},
+        Alignment, Background, Command, Length, Subscription,
+    },
+    theme,
+    widget::{icon, settings, text},
+    Element,
+};
+use cosmic_settings_display::{
+    self as display,
+    arrangement::{self, Rect},
+    color_profile::{self, Profile},
+    mirror::{self, Placement},
+    AdaptiveSync, Mode, Output, Transform,
+};
+use cosmic_settings_input as input;
+use rkyv::{Archive, Deserialize, Serialize};
+use slotmap::SlotMap;
+use std::{collections::HashMap, fmt, path::PathBuf, time::Duration};
+
+use crate::{
+    config, file_chooser,
+    page::{self, section, Content, Section},
+    widget::{
+        dialog::{self, dialog},
+        display_arrangement::{display_arrangement, OutputInfo},
+        info_popover, marked_slider,
+        searchable_dropdown::{self, Item, SearchableDropdown},
+        segmented::{segmented, Segment},
+        settings_row,
+        toast::Toast,
+    },
+};
+
+/// Seconds for which a new mode or arrangement is kept before it is reverted, unless it is
+/// kept sooner.
+const REVERT_SECONDS: u32 = 15;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// The compositor applied a change which is reverted unless it is kept, or rejected it.
+    ChangeApplied(Result<(), String>),
+    /// A profile was assigned, imported, or removed, or the change failed.
+    ColorProfileApplied(Result<(), String>),
+    /// A file was chosen to be imported as a profile, unless the chooser was cancelled.
+    ColorProfileChosen(Result<Option<PathBuf>, String>),
+    ColorProfileImport,
+    /// The overflow menu of an imported profile was opened, or closed.
+    ColorProfileMenu(Option<PathBuf>),
+    /// The output whose profile is shown was chosen.
+    ColorProfileOutput(String),
+    ColorProfileRemove(PathBuf),
+    /// A profile was chosen for an output, or sRGB where there is none.
+    ColorProfileSelected(String, Option<PathBuf>),
+    /// The installed profiles, and the profiles assigned to outputs by their names.
+    ColorProfiles(Vec<Profile>, HashMap<String, PathBuf>),
+    DisplayScaleChanged(String, f64),
+    /// The extended layout which was kept before the outputs were mirrored.
+    MirrorLoaded(MirrorConfig),
+    /// The size of the modes which every output mirrors at.
+    MirrorSizeSelected((u32, u32)),
+    MirrorToggled(bool),
+    /// The compositor switched to a new mode, or rejected it.
+    ModeApplied(Result<(), String>),
+    /// An output was moved to a new logical position in the arrangement.
+    Moved(String, i32, i32),
+    OrientationSelected(String, Transform),
+    /// The output whose resolution is shown was chosen.
+    OutputSelected(String),
+    Outputs(Result<Vec<Output>, String>),
+    Primary(Option<String>),
+    PrimaryDisplaySet(String),
+    Refresh,
+    /// A refresh rate of the current resolution was chosen, in millihertz.
+    RefreshRateSelected(u32),
+    Resolution(searchable_dropdown::Message<(u32, u32)>),
+    /// The new mode or arrangement was kept, or is to be reverted.
+    RevertResponse(dialog::Response),
+    RevertTick,
+    /// The scale of an output is being dragged, which is previewed until it is released.
+    ScaleDragged(String, f64),
+    ScaleInfo(bool),
+    /// The compositor applied the adaptive sync of an output, or refused it.
+    VrrApplied(Result<(), String>),
+    VrrInfo(bool),
+    VrrSelected(String, AdaptiveSync),
+}
+
+/// The extended layout of the outputs while they are mirrored, which is kept between
+/// sessions so that it is restored once mirroring stops.
+#[must_use]
+#[derive(Archive, Clone, Debug, Default, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes, Debug))]
+pub struct MirrorConfig {
+    pub extended: Vec<SavedPlacement>,
+}
+
+/// The placement of an output, as it is kept in [`MirrorConfig`].
+#[derive(Archive, Clone, Debug, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes, Debug))]
+pub struct SavedPlacement {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub refresh: f64,
+    pub scale: f64,
+    pub transform: String,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl From<&Placement> for SavedPlacement {
+    fn from(placement: &Placement) -> Self {
+        Self {
+            name: placement.name.clone(),
+            width: placement.width,
+            height: placement.height,
+            refresh: placement.refresh,
+            scale: placement.scale,
+            transform: placement.transform.as_str().to_owned(),
+            x: placement.position.0,
+            y: placement.position.1,
+        }
+    }
+}
+
+impl From<&SavedPlacement> for Placement {
+    fn from(saved: &SavedPlacement) -> Self {
+        Self {
+            name: saved.name.clone(),
+            width: saved.width,
+            height: saved.height,
+            refresh: saved.refresh,
+            scale: saved.scale,
+            transform: saved.transform.parse().unwrap_or_default(),
+            position: (saved.x, saved.y),
+        }
+    }
+}
+
+/// A change which was just applied, and which is reverted unless it is kept in time.
+#[derive(Debug)]
+pub struct Revert {
+    pub previous: Change,
+    pub remaining: u32,
+}
+
+/// The settings which a change replaced, which are restored if it is reverted.
+#[derive(Debug)]
+pub enum Change {
+    /// The output as it was before a mode was applied to it.
+    Mode(Output),
+    /// The positions of the outputs before they were arranged, which may leave every
+    /// output out of sight.
+    Arrangement(Vec<(String, i32, i32)>),
+    /// The transform of an output before it was rotated, and the positions of the outputs
+    /// which were moved along with it.
+    Transform {
+        name: String,
+        transform: Transform,
+        positions: Vec<(String, i32, i32)>,
+    },
+}
+
+pub struct Model {
+    pub outputs: Vec<Output>,
+    /// The output which the panel and dock are placed on.
+    pub primary: Option<String>,
+    pub error: Option<String>,
+    /// The output whose resolution is shown.
+    pub selected: Option<String>,
+    pub resolutions: SearchableDropdown<(u32, u32)>,
+    /// The settings which the change being applied replaces.
+    applying: Option<Change>,
+    pub revert: Option<Revert>,
+    /// Why the compositor rejected the last mode.
+    pub mode_error: Option<String>,
+    /// Scales which are being dragged, by the name of their output.
+    pub scale_previews: HashMap<String, f64>,
+    /// Whether the explanation of fractional scales is shown.
+    pub scale_info: bool,
+    /// The orientations which outputs can be rotated to.
+    pub orientations: Vec<Segment<Transform>>,
+    /// The sizes of the modes which every enabled output supports, from the largest.
+    pub mirror_sizes: Vec<(u32, u32)>,
+    /// Where the outputs were before they were mirrored.
+    pub extended: Vec<Placement>,
+    pub color_profiles: Vec<Profile>,
+    /// The profiles assigned to outputs, by the names of the outputs.
+    pub active_profiles: HashMap<String, PathBuf>,
+    /// The output whose profile is shown.
+    pub profile_output: Option<String>,
+    /// The imported profile whose overflow menu is open.
+    pub profile_menu: Option<PathBuf>,
+    pub profile_error: Option<String>,
+    /// The states of adaptive sync which outputs can be set to.
+    pub vrr_modes: Vec<Segment<AdaptiveSync>>,
+    /// Why the compositor refused the last change of adaptive sync.
+    pub vrr_error: Option<String>,
+    /// Whether the explanation of flicker with adaptive sync is shown.
+    pub vrr_info: bool,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            outputs: Vec::new(),
+            primary: None,
+            error: None,
+            selected: None,
+            resolutions: SearchableDropdown::default(),
+            applying: None,
+            revert: None,
+            mode_error: None,
+            scale_previews: HashMap::new(),
+            scale_info: false,
+            orientations: vec![
+                Segment::new(Transform::Normal, fl!("display-orientation", "landscape")),
+                Segment::new(
+                    Transform::Rotate90,
+                    fl!("display-orientation", "portrait-right"),
+                ),
+                Segment::new(
+                    Transform::Rotate270,
+                    fl!("display-orientation", "portrait-left"),
+                ),
+                Segment::new(Transform::Rotate180, fl!("display-orientation", "flipped")),
+            ],
+            mirror_sizes: Vec::new(),
+            extended: Vec::new(),
+            color_profiles: Vec::new(),
+            active_profiles: HashMap::new(),
+            profile_output: None,
+            profile_menu: None,
+            profile_error: None,
+            vrr_modes: vec![
+                Segment::new(AdaptiveSync::Disabled, fl!("display-vrr", "off")),
+                Segment::new(AdaptiveSync::Enabled, fl!("display-vrr", "on")),
+            ],
+            vrr_error: None,
+            vrr_info: false,
+        }
+    }
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => {
+                self.error = result.err();
+                return refresh();
+            }
+
+            Message::ChangeApplied(result) => {
+                let previous = self.applying.take();
+
+                match result {
+                    Ok(()) => {
+                        self.error = None;
+                        self.revert = previous.map(|previous| Revert {
+                            previous,
+                            remaining: REVERT_SECONDS,
+                        });
+                    }
+                    Err(why) => self.error = Some(why),
+                }
+
+                return refresh();
+            }
+
+            Message::ColorProfileApplied(result) => {
+                self.profile_error = result.err();
+                return load_profiles();
+            }
+
+            Message::ColorProfileChosen(Ok(Some(path))) => {
+                return Command::perform(
+                    page::background(move || color_profile::import(&path).map(|_| ())),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        let result = result
+                            .map_err(|why| fl!("display-color-profile", "invalid", why = why));
+                        crate::Message::Display(Message::ColorProfileApplied(result))
+                    },
+                );
+            }
+
+            Message::ColorProfileChosen(Ok(None)) => (),
+
+            Message::ColorProfileChosen(Err(why)) => self.profile_error = Some(why),
+
+            Message::ColorProfileImport => {
+                return Command::perform(choose_profile(), |result| {
+                    crate::Message::Display(Message::ColorProfileChosen(result))
+                });
+            }
+
+            Message::ColorProfileMenu(menu) => self.profile_menu = menu,
+
+            Message::ColorProfileOutput(name) => self.profile_output = Some(name),
+
+            Message::ColorProfileRemove(path) => {
+                self.profile_menu = None;
+
+                return Command::perform(
+                    page::background(move || color_profile::remove(&path)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::ColorProfileApplied(result))
+                    },
+                );
+            }
+
+            Message::ColorProfileSelected(name, profile) => {
+                match &profile {
+                    Some(profile) => self.active_profiles.insert(name.clone(), profile.clone()),
+                    None => self.active_profiles.remove(&name),
+                };
+
+                return Command::perform(
+                    page::background(move || color_profile::assign(&name, profile.as_deref())),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::ColorProfileApplied(result))
+                    },
+                );
+            }
+
+            Message::ColorProfiles(profiles, active) => {
+                self.color_profiles = profiles;
+                self.active_profiles = active;
+            }
+
+            Message::DisplayScaleChanged(name, scale) => {
+                self.scale_previews.remove(&name);
+
+                let Some(output) = self.outputs.iter().find(|o| o.name == name).cloned() else {
+                    return Command::none();
+                };
+
+                // Scales outside of the supported set are never sent to the compositor.
+                if !output.supported_scales().contains(&scale) {
+                    self.error = Some(fl!("display-scale", "unsupported"));
+                    return Command::none();
+                }
+
+                if (output.scale - scale).abs() < f64::EPSILON {
+                    return Command::none();
+                }
+
+                // The logical size of the output changes with its scale, so the outputs
+                // beside it are moved along with it.
+                let positions = self.rescale(&name, scale);
+
+                for output in &mut self.outputs {
+                    if output.name == name {
+                        output.scale = scale;
+                    }
+
+                    if let Some((_, x, y)) = positions.iter().find(|(n, ..)| n == &output.name) {
+                        output.position = (*x, *y);
+                    }
+                }
+
+                return Command::perform(
+                    page::background(move || display::set_scale(&output, scale, &positions)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::ModeApplied(Ok(())) => {
+                self.revert = self.applying.take().map(|previous| Revert {
+                    previous,
+                    remaining: REVERT_SECONDS,
+                });
+
+                return refresh();
+            }
+
+            Message::ModeApplied(Err(why)) => {
+                self.applying = None;
+                self.mode_error = Some(why);
+                return refresh();
+            }
+
+            Message::Moved(name, x, y) => {
+                // The output may have been disconnected while it was being moved.
+                let Some(positions) = self.arrange(&name, x, y) else {
+                    return Command::none();
+                };
+
+                let previous = self.positions();
+
+                for (name, x, y) in &positions {
+                    if let Some(output) = self.outputs.iter_mut().find(|o| &o.name == name) {
+                        output.position = (*x, *y);
+                    }
+                }
+
+                self.applying = Some(Change::Arrangement(previous));
+                self.revert = None;
+
+                // Every output is moved at once, so that the layout is never left with only
+                // some of them moved, which the pointer could be trapped in.
+                return Command::perform(
+                    page::background(move || display::set_positions(&positions)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::ChangeApplied(result))
+                    },
+                );
+            }
+
+            Message::MirrorLoaded(config) => {
+                self.extended = config.extended.iter().map(Placement::from).collect();
+                return refresh();
+            }
+
+            Message::MirrorSizeSelected(size) => {
+                let outputs = self.outputs.clone();
+
+                return Command::perform(
+                    page::background(move || mirror::mirror(&outputs, size)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::MirrorToggled(true) => {
+                // The largest size which every output supports is mirrored at first.
+                let Some(&size) = self.mirror_sizes.first() else {
+                    return Command::none();
+                };
+
+                self.extended = self.outputs.iter().filter_map(Placement::of).collect();
+
+                let config = MirrorConfig {
+                    extended: self.extended.iter().map(SavedPlacement::from).collect(),
+                };
+                let outputs = self.outputs.clone();
+                self.revert = None;
+
+                return Command::perform(
+                    page::background(move || {
+                        config::store("display-mirror", &config);
+                        mirror::mirror(&outputs, size)
+                    }),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::MirrorToggled(false) => {
+                let extended = std::mem::take(&mut self.extended);
+                let outputs = self.outputs.clone();
+
+                return Command::perform(
+                    page::background(move || {
+                        mirror::restore(&outputs, &extended)?;
+                        config::store("display-mirror", &MirrorConfig::default());
+                        Ok(())
+                    }),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::OrientationSelected(name, transform) => {
+                let Some(output) = self.outputs.iter().find(|o| o.name == name) else {
+                    return Command::none();
+                };
+
+                if output.transform == transform {
+                    return Command::none();
+                }
+
+                let internal = output.is_internal();
+                let previous = Change::Transform {
+                    name: name.clone(),
+                    transform: output.transform,
+                    positions: self.positions(),
+                };
+
+                // Rotating back before the change is kept restores the positions which the
+                // outputs had, rather than moving them again, so that toggling back and
+                // forth never leaves them drifting away from where they were.
+                let positions = match self.revert.take() {
+                    Some(Revert {
+                        previous:
+                            Change::Transform {
+                                name: reverted,
+                                transform: previous,
+                                positions,
+                            },
+                        ..
+                    }) if reverted == name && previous == transform => positions,
+                    _ => self.reorient(&name, transform),
+                };
+
+                // The rect of the output is rotated in the arrangement at once, so that it
+                // can be moved before the compositor has applied the transform.
+                for output in &mut self.outputs {
+                    if output.name == name {
+                        output.transform = transform;
+                    }
+
+                    if let Some((_, x, y)) = positions.iter().find(|(n, ..)| n == &output.name) {
+                        output.position = (*x, *y);
+                    }
+                }
+
+                self.applying = Some(previous);
+
+                return Command::perform(
+                    page::background(move || {
+                        apply_transform(&name, internal, transform, &positions)
+                    }),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::ChangeApplied(result))
+                    },
+                );
+            }
+
+            Message::OutputSelected(name) => {
+                self.selected = Some(name);
+                self.mode_error = None;
+                self.update_resolutions();
+            }
+
+            Message::Outputs(Ok(outputs)) => {
+                self.outputs = outputs;
+                self.mirror_sizes = mirror::common_sizes(&self.outputs);
+                self.error = None;
+
+                // Falls back to the primary output when the selected output is disconnected.
+                if self.selected_output().is_none() {
+                    self.selected = self
+                        .outputs
+                        .iter()
+                        .filter(|output| output.enabled)
+                        .find(|output| self.primary.as_ref() == Some(&output.name))
+                        .or_else(|| self.outputs.iter().find(|output| output.enabled))
+                        .map(|output| output.name.clone());
+                }
+
+                self.update_resolutions();
+            }
+
+            Message::Outputs(Err(why)) => self.error = Some(why),
+
+            Message::Primary(primary) => self.primary = primary,
+
+            Message::PrimaryDisplaySet(name) => {
+                self.primary = Some(name.clone());
+
+                return Command::perform(
+                    page::background(move || display::set_primary(&name)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::Refresh => return refresh(),
+
+            Message::RefreshRateSelected(refresh) => return self.set_refresh_rate(refresh),
+
+            Message::Resolution(message) => match self.resolutions.update(message) {
+                Some(searchable_dropdown::Output::Opened) => return self.resolutions.focus(),
+                Some(searchable_dropdown::Output::Selected(size)) => {
+                    return self.set_resolution(size)
+                }
+                None => (),
+            },
+
+            Message::RevertResponse(response) => {
+                if response.action == dialog::Action::Primary {
+                    self.revert = None;
+                } else {
+                    return self.revert();
+                }
+            }
+
+            Message::ScaleDragged(name, scale) => {
+                self.scale_previews.insert(name, scale);
+            }
+
+            Message::ScaleInfo(open) => self.scale_info = open,
+
+            Message::RevertTick => {
+                if let Some(revert) = &mut self.revert {
+                    revert.remaining = revert.remaining.saturating_sub(1);
+
+                    if revert.remaining == 0 {
+                        return self.revert();
+                    }
+                }
+            }
+
+            Message::VrrApplied(result) => {
+                self.vrr_error = result.err();
+
+                // The state which the compositor kept is shown, whether or not it was applied.
+                return refresh();
+            }
+
+            Message::VrrInfo(open) => self.vrr_info = open,
+
+            Message::VrrSelected(name, adaptive_sync) => {
+                if let Some(output) = self.outputs.iter_mut().find(|o| o.name == name) {
+                    output.adaptive_sync = Some(adaptive_sync);
+                }
+
+                return Command::perform(
+                    page::background(move || display::set_adaptive_sync(&name, adaptive_sync)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Display(Message::VrrApplied(result))
+                    },
+                );
+            }
+        }
+
+        Command::none()
+    }
+
+    #[must_use]
+    pub fn selected_output(&self) -> Option<&Output> {
+        let name = self.selected.as_ref()?;
+        self.outputs
+            .iter()
+            .find(|output| output.enabled && &output.name == name)
+    }
+
+    fn update_resolutions(&mut self) {
+        let items = self
+            .selected_output()
+            .map(resolution_items)
+            .unwrap_or_default();
+
+        self.resolutions.set_items(items);
+    }
+
+    /// Applies a resolution to the selected output, keeping its refresh rate if the resolution
+    /// supports it, or otherwise the closest rate that it does.
+    fn set_resolution(&mut self, (width, height): (u32, u32)) -> Command<crate::Message> {
+        let Some(output) = self.selected_output() else {
+            return Command::none();
+        };
+
+        let current = output.current_mode();
+
+        if current.map_or(false, |mode| mode.width == width && mode.height == height) {
+            return Command::none();
+        }
+
+        let rates = refresh_rates(output, width, height);
+
+        // Rates are listed from the highest, which is chosen without a current mode to keep.
+        let refresh = match current {
+            Some(current) => rates
+                .iter()
+                .copied()
+                .min_by_key(|&rate| rate.abs_diff(millihertz(current.refresh))),
+            None => rates.first().copied(),
+        };
+
+        let Some(refresh) = refresh else {
+            return Command::none();
+        };
+
+        let kept = current.map_or(true, |current| millihertz(current.refresh) == refresh);
+        let command = self.set_mode(width, height, refresh);
+
+        if kept {
+            return command;
+        }
+
+        let toast = Toast::new(fl!(
+            "display-refresh-rate",
+            "changed",
+            rate = rate_label(&rates, refresh)
+        ));
+
+        Command::batch(vec![
+            command,
+            Command::perform(async move { toast }, crate::Message::ShowToast),
+        ])
+    }
+
+    /// Applies a refresh rate, in millihertz, to the selected output at its current resolution.
+    fn set_refresh_rate(&mut self, refresh: u32) -> Command<crate::Message> {
+        let Some(mode) = self.selected_output().and_then(Output::current_mode) else {
+            return Command::none();
+        };
+
+        let (width, height) = (mode.width, mode.height);
+        self.set_mode(width, height, refresh)
+    }
+
+    /// Applies a mode to the selected output, once its resolution and refresh rate are found
+    /// to be supported together. The rate is applied exactly as the output advertises it.
+    fn set_mode(&mut self, width: u32, height: u32, refresh: u32) -> Command<crate::Message> {
+        let Some(output) = self.selected_output() else {
+            return Command::none();
+        };
+
+        let Some(mode) = output
+            .modes
+            .iter()
+            .find(|mode| {
+                mode.width == width && mode.height == height && millihertz(mode.refresh) == refresh
+            })
+            .cloned()
+        else {
+            self.mode_error = Some(fl!("display-refresh-rate", "unsupported"));
+            return Command::none();
+        };
+
+        if mode.current {
+            return Command::none();
+        }
+
+        let name = output.name.clone();
+        self.applying = Some(Change::Mode(output.clone()));
+        self.revert = None;
+        self.mode_error = None;
+
+        Command::perform(
+            page::background(move || display::set_mode(&name, &mode)),
+            |result| {
+                let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                crate::Message::Display(Message::ModeApplied(result))
+            },
+        )
+    }
+
+    /// Restores the mode, arrangement, or transform which the outputs had before the last
+    /// change.
+    fn revert(&mut self) -> Command<crate::Message> {
+        let Some(Revert { previous, .. }) = self.revert.take() else {
+            return Command::none();
+        };
+
+        let result = |result: Option<Result<(), String>>| {
+            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+            crate::Message::Display(Message::Applied(result))
+        };
+
+        match previous {
+            Change::Mode(previous) => {
+                let Some(mode) = previous.current_mode().cloned() else {
+                    return Command::none();
+                };
+
+                Command::perform(
+                    page::background(move || display::set_mode(&previous.name, &mode)),
+                    result,
+                )
+            }
+
+            Change::Arrangement(mut positions) => {
+                // Outputs which were disconnected since are left out, as they can't be moved.
+                positions.retain(|(name, ..)| {
+                    self.outputs
+                        .iter()
+                        .any(|output| output.enabled && &output.name == name)
+                });
+
+                arrangement::normalize(&mut positions);
+
+                Command::perform(
+                    page::background(move || display::set_positions(&positions)),
+                    result,
+                )
+            }
+
+            Change::Transform {
+                name,
+                transform,
+                mut positions,
+            } => {
+                let Some(output) = self.outputs.iter().find(|output| output.name == name) else {
+                    return Command::none();
+                };
+
+                let internal = output.is_internal();
+
+                positions.retain(|(name, ..)| {
+                    self.outputs
+                        .iter()
+                        .any(|output| output.enabled && &output.name == name)
+                });
+
+                arrangement::normalize(&mut positions);
+
+                Command::perform(
+                    page::background(move || {
+                        apply_transform(&name, internal, transform, &positions)
+                    }),
+                    result,
+                )
+            }
+        }
+    }
+
+    /// The positions of every enabled output.
+    fn positions(&self) -> Vec<(String, i32, i32)> {
+        self.outputs
+            .iter()
+            .filter(|output| output.enabled)
+            .map(|output| (output.name.clone(), output.position.0, output.position.1))
+            .collect()
+    }
+
+    /// The positions of every enabled output once one is scaled, which are empty if the
+    /// outputs could not be kept together.
+    fn rescale(&self, name: &str, scale: f64) -> Vec<(String, i32, i32)> {
+        self.resize(name, |output| output.logical_size_at(scale))
+    }
+
+    /// The positions of every enabled output once one is rotated, which swaps its width and
+    /// height, or are empty if the outputs could not be kept together.
+    fn reorient(&self, name: &str, transform: Transform) -> Vec<(String, i32, i32)> {
+        self.resize(name, |output| {
+            let (width, height) = output.logical_size();
+
+            if output.transform.is_rotated() == transform.is_rotated() {
+                (width, height)
+            } else {
+                (height, width)
+            }
+        })
+    }
+
+    /// The positions of every enabled output once one is resized, with the outputs beyond
+    /// its edges moved along with them.
+    fn resize(
+        &self,
+        name: &str,
+        size: impl FnOnce(&Output) -> (u32, u32),
+    ) -> Vec<(String, i32, i32)> {
+        let enabled: Vec<&Output> = self
+            .outputs
+            .iter()
+            .filter(|output| output.enabled)
+            .collect();
+        let mut rects: Vec<Rect> = enabled.iter().map(|output| logical_rect(output)).collect();
+
+        let Some(index) = enabled.iter().position(|output| output.name == name) else {
+            return Vec::new();
+        };
+
+        let (width, height) = size(enabled[index]);
+        arrangement::resize(&mut rects, index, width as i32, height as i32);
+
+        if !arrangement::is_valid(&rects) {
+            return Vec::new();
+        }
+
+        let mut positions: Vec<(String, i32, i32)> = enabled
+            .iter()
+            .zip(rects)
+            .map(|(output, rect)| (output.name.clone(), rect.x, rect.y))
+            .collect();
+
+        arrangement::normalize(&mut positions);
+        positions
+    }
+
+    /// The positions of every enabled output once one is moved, if they would neither
+    /// overlap nor leave an output unreachable from the others.
+    fn arrange(&self, name: &str, x: i32, y: i32) -> Option<Vec<(String, i32, i32)>> {
+        let mut positions: Vec<(String, Rect)> = self
+            .outputs
+            .iter()
+            .filter(|output| output.enabled)
+            .map(|output| (output.name.clone(), logical_rect(output)))
+            .collect();
+
+        let (_, moved) = positions.iter_mut().find(|(id, _)| id == name)?;
+        moved.x = x;
+        moved.y = y;
+
+        let rects: Vec<Rect> = positions.iter().map(|(_, rect)| *rect).collect();
+
+        if !arrangement::is_valid(&rects) {
+            return None;
+        }
+
+        let mut positions: Vec<(String, i32, i32)> = positions
+            .into_iter()
+            .map(|(name, rect)| (name, rect.x, rect.y))
+            .collect();
+
+        arrangement::normalize(&mut positions);
+        Some(positions)
+    }
+}
+
+/// Rotates an output, along with the touchscreens and tablets which are mapped to it, so
+/// that their input still lines up with it.
+fn apply_transform(
+    name: &str,
+    internal: bool,
+    transform: Transform,
+    positions: &[(String, i32, i32)],
+) -> Result<(), String> {
+    display::set_transform(name, transform, positions)?;
+
+    let rotation = match transform {
+        Transform::Normal | Transform::Flipped => input::Rotation::Normal,
+        Transform::Rotate90 | Transform::Flipped90 => input::Rotation::Right,
+        Transform::Rotate180 | Transform::Flipped180 => input::Rotation::Inverted,
+        Transform::Rotate270 | Transform::Flipped270 => input::Rotation::Left,
+    };
+
+    input::follow_rotation(name, internal, rotation)
+}
+
+fn logical_rect(output: &Output) -> Rect {
+    let (width, height) = output.logical_size();
+
+    Rect {
+        x: output.position.0,
+        y: output.position.1,
+        width: width as i32,
+        height: height as i32,
+    }
+}
+
+/// The resolutions of an output, from the largest, grouped by their aspect ratios.
+fn resolution_items(output: &Output) -> Vec<Item<(u32, u32)>> {
+    let mut sizes: Vec<&Mode> = Vec::new();
+
+    for mode in &output.modes {
+        if !sizes.iter().any(|size| size.same_size(mode)) {
+            sizes.push(mode);
+        }
+    }
+
+    sizes.sort_by_key(|mode| std::cmp::Reverse(mode.width * mode.height));
+
+    // Groups are ordered by the largest resolution in each of them.
+    let mut ratios: Vec<(u32, u32)> = Vec::new();
+
+    for mode in &sizes {
+        if !ratios.contains(&mode.aspect_ratio()) {
+            ratios.push(mode.aspect_ratio());
+        }
+    }
+
+    sizes.sort_by_key(|mode| {
+        ratios
+            .iter()
+            .position(|ratio| *ratio == mode.aspect_ratio())
+    });
+
+    sizes
+        .into_iter()
+        .map(|mode| {
+            let native = output
+                .modes
+                .iter()
+                .any(|other| other.preferred && other.same_size(mode));
+
+            let label = if native {
+                fl!("display-resolution", "recommended", mode = mode.to_string())
+            } else {
+                mode.to_string()
+            };
+
+            let (width, height) = mode.aspect_ratio();
+            Item::new((mode.width, mode.height), label).group(format!("{width}:{height}"))
+        })
+        .collect()
+}
+
+/// A refresh rate in millihertz, which rates advertised by outputs are compared by.
+fn millihertz(refresh: f64) -> u32 {
+    (refresh * 1000.0).round() as u32
+}
+
+/// The refresh rates which an output supports at a resolution, in millihertz from the highest.
+fn refresh_rates(output: &Output, width: u32, height: u32) -> Vec<u32> {
+    let mut rates: Vec<u32> = output
+        .modes
+        .iter()
+        .filter(|mode| mode.width == width && mode.height == height)
+        .map(|mode| millihertz(mode.refresh))
+        .collect();
+
+    rates.sort_unstable_by(|a, b| b.cmp(a));
+    rates.dedup();
+    rates
+}
+
+/// Rounds a rate to whole hertz, such as 59.94 Hz to 60 Hz, unless another of the rates would
+/// then be shown the same.
+fn rate_label(rates: &[u32], rate: u32) -> String {
+    let hertz = |rate: u32| (rate + 500) / 1000;
+
+    if rates
+        .iter()
+        .any(|&other| other != rate && hertz(other) == hertz(rate))
+    {
+        format!("{:.2} Hz", f64::from(rate) / 1000.0)
+    } else {
+        format!("{} Hz", hertz(rate))
+    }
+}
+
+/// A refresh rate listed in a dropdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct RefreshRate {
+    millihertz: u32,
+    label: String,
+}
+
+impl fmt::Display for RefreshRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+fn refresh() -> Command<crate::Message> {
+    Command::batch(vec![
+        Command::perform(page::background(display::outputs), |result| {
+            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+            crate::Message::Display(Message::Outputs(result))
+        }),
+        Command::perform(page::background(display::primary), |primary| {
+            crate::Message::Display(Message::Primary(primary.flatten()))
+        }),
+        load_profiles(),
+    ])
+}
+
+fn load_profiles() -> Command<crate::Message> {
+    Command::perform(
+        page::background(|| (color_profile::profiles(), color_profile::active())),
+        |loaded| {
+            let (profiles, active) = loaded.unwrap_or_default();
+            crate::Message::Display(Message::ColorProfiles(profiles, active))
+        },
+    )
+}
+
+/// Asks the file chooser portal for a profile to import.
+///
+/// Resolves to `None` if the user cancelled.
+///
+/// # Errors
+///
+/// Fails if the portal is not available.
+async fn choose_profile() -> Result<Option<PathBuf>, String> {
+    let filter = file_chooser::Filter {
+        name: fl!("display-color-profile", "filter"),
+        globs: &["*.icc", "*.icm"],
+        mime_types: &["application/vnd.iccprofile"],
+    };
+
+    file_chooser::open_file(
+        &fl!("display-color-profile", "import-title"),
+        filter,
+        "cosmic_settings_icc",
+    )
+    .await
+    .map_err(|why| why.to_string())
+}
+
+/// A resolution which every mirrored output supports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct MirrorSize(u32, u32);
+
+impl fmt::Display for MirrorSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} × {}", self.0, self.1)
+    }
+}
+
+/// An output listed by name in a dropdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Choice {
+    name: String,
+    label: String,
+}
+
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+impl From<&Output> for Choice {
+    fn from(output: &Output) -> Self {
+        Self {
+            name: output.name.clone(),
+            label: format!("{output} ({})", output.name),
+        }
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("display", "preferences-desktop-display-symbolic")
+            .title(fl!("display"))
+            .description(fl!("display", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![
+            sections.insert(arrangement()),
+            sections.insert(resolution()),
+            sections.insert(scale()),
+            sections.insert(vrr()),
+            sections.insert(color_profile()),
+        ])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let config = page::background(|| config::load::<MirrorConfig>("display-mirror")).await;
+            crate::Message::Display(Message::MirrorLoaded(config.unwrap_or_default()))
+        })
+    }
+
+    fn sub_pages(page: page::Insert) -> page::Insert {
+        page.sub_page::<night_light::Page>()
+    }
+
+    fn subscription(model: &Model) -> Subscription<crate::Message> {
+        let hotplug =
+            crate::subscription::lines("display-hotplug", display::hotplug_command(), |line| {
+                line.contains("(drm)")
+                    .then_some(crate::Message::Display(Message::Refresh))
+            });
+
+        if model.revert.is_none() {
+            return hotplug;
+        }
+
+        Subscription::batch(vec![
+            hotplug,
+            iced::time::every(Duration::from_secs(1))
+                .map(|_| crate::Message::Display(Message::RevertTick)),
+        ])
+    }
+}
+
+fn arrangement() -> Section {
+    Section::new()
+        .title(fl!("display-arrangement"))
+        .descriptions(vec![
+            fl!("display-arrangement", "desc"),
+            fl!("display-arrangement", "primary"),
+            fl!("display-revert"),
+            fl!("display-revert", "keep"),
+            fl!("display-revert", "revert"),
+            fl!("display-mirror"),
+            fl!("display-mirror", "desc"),
+            fl!("display-mirror", "no-common-mode"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("display model is missing");
+
+            let desc = &section.descriptions;
+
+            if let Some(
+                revert @ Revert {
+                    previous: Change::Arrangement(_),
+                    ..
+                },
+            ) = &model.revert
+            {
+                return revert_dialog(revert, &desc[2], &desc[3], &desc[4]);
+            }
+
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            let mirrored = mirror::is_mirrored(&model.outputs);
+            let enabled_count = model.outputs.iter().filter(|output| output.enabled).count();
+
+            if enabled_count > 1 {
+                // Outputs which share no mode can't be mirrored.
+                let mirrorable = mirrored || !model.mirror_sizes.is_empty();
+                let description = if mirrorable { &desc[6] } else { &desc[7] };
+
+                view = view.add(
+                    settings_row(&desc[5], description)
+                        .toggler(mirrored, Message::MirrorToggled)
+                        .disabled(!mirrorable),
+                );
+            }
+
+            // Mirrored outputs all share one place, so there is nothing to arrange.
+            if mirrored {
+                return view.apply(Element::from).map(crate::Message::Display);
+            }
+
+            let outputs: Vec<OutputInfo> = model
+                .outputs
+                .iter()
+                .filter(|output| output.enabled)
+                .map(|output| OutputInfo {
+                    id: output.name.clone(),
+                    name: output.name.clone(),
+                    details: output
+                        .current_mode()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                    primary: model.primary.as_deref() == Some(output.name.as_str()),
+                    rect: logical_rect(output),
+                })
+                .collect();
+
+            view = view
+                .add(text(&section.descriptions[0]))
+                .add(display_arrangement(&outputs, Message::Moved));
+
+            let enabled: Vec<Choice> = model
+                .outputs
+                .iter()
+                .filter(|output| output.enabled)
+                .map(Choice::from)
+                .collect();
+
+            // There is no choice of primary display with only one display.
+            if enabled.len() > 1 {
+                let selected = model
+                    .primary
+                    .as_ref()
+                    .and_then(|name| enabled.iter().find(|choice| &choice.name == name))
+                    .cloned();
+
+                view = view.add(settings::item(
+                    &section.descriptions[1],
+                    pick_list(enabled, selected, |choice: Choice| {
+                        Message::PrimaryDisplaySet(choice.name)
+                    }),
+                ));
+            }
+
+            view.apply(Element::from).map(crate::Message::Display)
+        })
+}
+
+fn resolution() -> Section {
+    Section::new()
+        .title(fl!("display-resolution"))
+        .descriptions(vec![
+            fl!("display-resolution", "display"),
+            fl!("display-resolution", "desc"),
+            fl!("display-revert"),
+            fl!("display-revert", "keep"),
+            fl!("display-revert", "revert"),
+            fl!("display-refresh-rate"),
+            fl!("display-orientation"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("display model is missing");
+
+            let desc = &section.descriptions;
+
+            if let Some(
+                revert @ Revert {
+                    previous: Change::Mode(_) | Change::Transform { .. },
+                    ..
+                },
+            ) = &model.revert
+            {
+                return revert_dialog(revert, &desc[2], &desc[3], &desc[4]);
+            }
+
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.mode_error {
+                view = view.add(error_banner(fl!(
+                    "display-resolution",
+                    "rejected",
+                    why = why.as_str()
+                )));
+            }
+
+            // Mirrored outputs share one resolution, from the sizes which all of them support.
+            if mirror::is_mirrored(&model.outputs) {
+                let sizes: Vec<MirrorSize> = model
+                    .mirror_sizes
+                    .iter()
+                    .map(|&(width, height)| MirrorSize(width, height))
+                    .collect();
+
+                let selected = model
+                    .outputs
+                    .iter()
+                    .find(|output| output.enabled)
+                    .and_then(Output::current_mode)
+                    .map(|mode| MirrorSize(mode.width, mode.height));
+
+                return view
+                    .add(settings::item(
+                        &section.title,
+                        pick_list(sizes, selected, |size: MirrorSize| {
+                            Message::MirrorSizeSelected((size.0, size.1))
+                        }),
+                    ))
+                    .apply(Element::from)
+                    .map(crate::Message::Display);
+            }
+
+            let enabled: Vec<Choice> = model
+                .outputs
+                .iter()
+                .filter(|output| output.enabled)
+                .map(Choice::from)
+                .collect();
+
+            if enabled.len() > 1 {
+                let selected = model
+                    .selected
+                    .as_ref()
+                    .and_then(|name| enabled.iter().find(|choice| &choice.name == name))
+                    .cloned();
+
+                view = view.add(settings::item(
+                    &desc[0],
+                    pick_list(enabled, selected, |choice: Choice| {
+                        Message::OutputSelected(choice.name)
+                    }),
+                ));
+            }
+
+            if let Some(output) = model.selected_output() {
+                let current = output.current_mode();
+                let size = current.map(|mode| (mode.width, mode.height));
+
+                view = view.add(settings::item(
+                    &section.title,
+                    model
+                        .resolutions
+                        .view(size.as_ref())
+                        .map(Message::Resolution),
+                ));
+
+                // Only the rates of the current resolution are listed.
+                if let Some(current) = current {
+                    let rates = refresh_rates(output, current.width, current.height);
+
+                    let options: Vec<RefreshRate> = rates
+                        .iter()
+                        .map(|&rate| RefreshRate {
+                            millihertz: rate,
+                            label: rate_label(&rates, rate),
+                        })
+                        .collect();
+
+                    let selected = options
+                        .iter()
+                        .find(|option| option.millihertz == millihertz(current.refresh))
+                        .cloned();
+
+                    view = view.add(settings::item(
+                        &desc[5],
+                        pick_list(options, selected, |rate: RefreshRate| {
+                            Message::RefreshRateSelected(rate.millihertz)
+                        }),
+                    ));
+                }
+
+                let name = output.name.clone();
+
+                view = view.add(settings::item(
+                    &desc[6],
+                    segmented(
+                        &model.orientations,
+                        Some(&output.transform),
+                        move |transform| Message::OrientationSelected(name.clone(), transform),
+                    ),
+                ));
+            }
+
+            view.apply(Element::from).map(crate::Message::Display)
+        })
+}
+
+/// Asks whether to keep a change, in place of the section which it was made in.
+fn revert_dialog<'a>(
+    revert: &Revert,
+    title: &'a str,
+    keep: &'a str,
+    cancel: &'a str,
+) -> Element<'a, crate::Message> {
+    dialog(title, Message::RevertResponse)
+        .body(fl!("display-revert", "body", seconds = revert.remaining))
+        .primary(keep)
+        .cancel(cancel)
+        .apply(Element::from)
+        .map(crate::Message::Display)
+}
+
+/// Explains why a change was not applied, in place of it appearing to be applied.
+fn error_banner<'a, Message: 'a>(why: String) -> Element<'a, Message> {
+    row!(
+        icon("dialog-error-symbolic", 16).style(theme::Svg::Symbolic),
+        text(why)
+    )
+    .spacing(12)
+    .align_items(Alignment::Center)
+    .apply(container)
+    .padding([8, 16])
+    .style(theme::Container::Custom(error_style))
+    .into()
+}
+
+fn error_style(theme: &cosmic::Theme) -> container::Appearance {
+    let danger = theme.palette().danger;
+
+    container::Appearance {
+        background: Some(Background::Color(cosmic::iced::Color { a: 0.15, ..danger })),
+        border_radius: 8.0,
+        border_width: 1.0,
+        border_color: danger,
+        ..container::Appearance::default()
+    }
+}
+
+/// A scale shown as a percentage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Scale(u32);
+
+impl Scale {
+    fn new(scale: f64) -> Self {
+        Self((scale * 100.0).round() as u32)
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.0)
+    }
+}
+
+fn scale() -> Section {
+    Section::new()
+        .title(fl!("display-scale"))
+        .descriptions(vec![
+            fl!("display-scale", "desc"),
+            fl!("display-scale", "fractional"),
+            fl!("display-scale", "fractional-info"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("display model is missing");
+
+            let desc = &section.descriptions;
+            let mut view = settings::view_section(&section.title);
+
+            for output in model.outputs.iter().filter(|output| output.enabled) {
+                let scales = output.supported_scales();
+                let max = scales.last().copied().unwrap_or(1.0);
+
+                let value = model
+                    .scale_previews
+                    .get(&output.name)
+                    .copied()
+                    .unwrap_or(output.scale)
+                    .clamp(1.0, max);
+
+                let (width, height) = output.logical_size_at(value);
+                let logical = text(fl!(
+                    "display-scale",
+                    "logical",
+                    width = width,
+                    height = height
+                ));
+
+                // Outputs too small to be scaled up only show their scale.
+                let control: Element<_> = if scales.len() > 1 {
+                    let name = output.name.clone();
+
+                    // Unlike other sliders, the scale is not committed while it rests, as each
+                    // change of scale relayouts every window and asks for it to be kept.
+                    let mut slider = marked_slider(1.0..=max, value, move |scale| {
+                        Message::ScaleDragged(name.clone(), scale)
+                    })
+                    .step(0.25)
+                    .snap(0.05)
+                    .readout(|scale| Scale::new(scale).to_string())
+                    .on_release(Message::DisplayScaleChanged(output.name.clone(), value));
+
+                    for &scale in &scales {
+                        slider = slider.mark(scale, Scale::new(scale).to_string());
+                    }
+
+                    row!(slider, logical)
+                        .spacing(16)
+                        .align_items(Alignment::Center)
+                        .into()
+                } else {
+                    row!(text(Scale::new(value).to_string()), logical)
+                        .spacing(16)
+                        .into()
+                };
+
+                view = view.add(
+                    settings::item::builder(&output.name)
+                        .description(&desc[0])
+                        .control(control),
+                );
+
+                if display::is_fractional(value) {
+                    view = view.add(
+                        row!(
+                            text(&desc[1]).size(12),
+                            info_popover(&desc[2], model.scale_info, Message::ScaleInfo),
+                        )
+                        .spacing(8)
+                        .align_items(Alignment::Center),
+                    );
+                }
+            }
+
+            view.apply(Element::from).map(crate::Message::Display)
+        })
+}
+
+/// Whether the compositor can vary the refresh rate of an output, which the display advertises.
+fn supports_vrr(output: &Output) -> bool {
+    output.enabled && output.vrr_capable && output.adaptive_sync.is_some()
+}
+
+fn vrr() -> Section {
+    Section::new()
+        .title(fl!("display-vrr"))
+        .descriptions(vec![fl!("display-vrr", "desc"), fl!("display-vrr", "info")])
+        // Hidden unless a display and the compositor support adaptive sync.
+        .visible_when(|pages| {
+            pages
+                .resource::<Model>()
+                .map_or(false, |model| model.outputs.iter().any(supports_vrr))
+        })
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("display model is missing");
+
+            let desc = &section.descriptions;
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.vrr_error {
+                view = view.add(error_banner(fl!(
+                    "display-vrr",
+                    "refused",
+                    why = why.as_str()
+                )));
+            }
+
+            view = view.add(settings::item(
+                &desc[0],
+                info_popover(&desc[1], model.vrr_info, Message::VrrInfo),
+            ));
+
+            for output in model.outputs.iter().filter(|output| supports_vrr(output)) {
+                let name = output.name.clone();
+
+                view = view.add(settings::item(
+                    output.to_string(),
+                    segmented(
+                        &model.vrr_modes,
+                        output.adaptive_sync.as_ref(),
+                        move |adaptive_sync| Message::VrrSelected(name.clone(), adaptive_sync),
+                    ),
+                ));
+            }
+
+            view.apply(Element::from).map(crate::Message::Display)
+        })
+}
+
+fn color_profile() -> Section {
+    Section::new()
+        .title(fl!("display-color-profile"))
+        .descriptions(vec![
+            fl!("display-resolution", "display"),
+            fl!("display-color-profile", "none"),
+            fl!("display-color-profile", "import"),
+            fl!("display-color-profile", "remove"),
+            fl!("display-color-profile", "active"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("display model is missing");
+
+            let desc = &section.descriptions;
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.profile_error {
+                view = view.add(error_banner(why.clone()));
+            }
+
+            let enabled: Vec<&Output> = model.outputs.iter().filter(|o| o.enabled).collect();
+
+            // Falls back to the output whose resolution is shown.
+            let Some(output) = model
+                .profile_output
+                .as_ref()
+                .and_then(|name| enabled.iter().find(|output| &output.name == name))
+                .or_else(|| {
+                    let selected = model.selected_output()?;
+                    enabled.iter().find(|output| output.name == selected.name)
+                })
+                .or_else(|| enabled.first())
+            else {
+                return view.apply(Element::from).map(crate::Message::Display);
+            };
+
+            if enabled.len() > 1 {
+                let choices: Vec<Choice> =
+                    enabled.iter().map(|&output| Choice::from(output)).collect();
+
+                view = view.add(settings::item(
+                    &desc[0],
+                    pick_list(choices, Some(Choice::from(*output)), |choice| {
+                        Message::ColorProfileOutput(choice.name)
+                    }),
+                ));
+            }
+
+            let active = model.active_profiles.get(&output.name);
+
+            // The active profile is marked, and the others are chosen by pressing them.
+            let choose = |profile: Option<&PathBuf>| {
+                if active == profile {
+                    return icon("object-select-symbolic", 16)
+                        .style(theme::Svg::Symbolic)
+                        .apply(container)
+                        .padding([0, 8])
+                        .apply(Element::from);
+                }
+
+                button(text(&desc[4]))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::ColorProfileSelected(
+                        output.name.clone(),
+                        profile.cloned(),
+                    ))
+                    .apply(Element::from)
+            };
+
+            view = view.add(settings::item(&desc[1], choose(None)));
+
+            for profile in &model.color_profiles {
+                let mut control = row!(choose(Some(&profile.path)))
+                    .spacing(8)
+                    .align_items(Alignment::Center);
+
+                // Only imported profiles may be removed, from the overflow menu of their row.
+                if profile.imported {
+                    let open = model.profile_menu.as_ref() == Some(&profile.path);
+
+                    let toggle = icon("view-more-symbolic", 16)
+                        .style(theme::Svg::Symbolic)
+                        .apply(button)
+                        .style(theme::Button::Transparent)
+                        .on_press(Message::ColorProfileMenu(
+                            (!open).then(|| profile.path.clone()),
+                        ));
+
+                    let mut menu = popover(toggle)
+                        .width(200)
+                        .on_dismiss(Message::ColorProfileMenu(None));
+
+                    if open {
+                        menu = menu.popup(
+                            button(text(&desc[3]))
+                                .width(Length::Fill)
+                                .style(theme::Button::Transparent)
+                                .on_press(Message::ColorProfileRemove(profile.path.clone()))
+                                .apply(container)
+                                .padding(8),
+                        );
+                    }
+
+                    control = control.push(menu);
+                }
+
+                view = view.add(settings::item(&profile.name, control));
+            }
+
+            view = view.add(row!(
+                horizontal_space(Length::Fill),
+                button(text(&desc[2]))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::ColorProfileImport),
+            ));
+
+            view.apply(Element::from).map(crate::Message::Display)
+        })
+}
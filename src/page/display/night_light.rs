@@ -0,0 +1,526 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use bytecheck::CheckBytes;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use cosmic::{
+    iced::{self, Command, Subscription},
+    widget::{settings, text, toggler},
+    Element,
+};
+use cosmic_settings_display::night_light::{self, DAY_MINUTES, TEMPERATURE};
+use rkyv::{Archive, Deserialize, Serialize};
+use slotmap::SlotMap;
+
+use crate::{
+    config,
+    page::{self, section, Content, Section},
+    widget::{
+        labeled_slider,
+        radio_list::{radio_list, Entry},
+        time_picker::{self, time_picker, TimePicker},
+    },
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    End(time_picker::Message),
+    Loaded(Config),
+    /// The latitude and longitude of the system, or why they are unavailable.
+    Located(Result<(f64, f64), String>),
+    NightLightToggled(bool),
+    ScheduleChanged(Schedule),
+    Start(time_picker::Message),
+    /// The temperature while its slider is dragged, which is previewed until it is released.
+    TemperatureDragged(u32),
+    TemperatureReleased,
+    /// Night light is turned on or off as the schedule passes.
+    Tick,
+}
+
+/// When night light is on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Schedule {
+    SunsetToSunrise,
+    Manual,
+}
+
+#[must_use]
+#[derive(Archive, Clone, Debug, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes, Debug))]
+pub struct Config {
+    pub enabled: bool,
+    /// The color temperature in kelvin.
+    pub temperature: u32,
+    /// Whether night light is on from sunset to sunrise, rather than at the manual times.
+    pub sunset_to_sunrise: bool,
+    /// The manual times, in minutes from midnight, which may cross midnight.
+    pub start: u32,
+    pub end: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            temperature: 4000,
+            sunset_to_sunrise: true,
+            start: 20 * 60,
+            end: 6 * 60,
+        }
+    }
+}
+
+impl Config {
+    #[must_use]
+    pub fn schedule(&self) -> Schedule {
+        if self.sunset_to_sunrise {
+            Schedule::SunsetToSunrise
+        } else {
+            Schedule::Manual
+        }
+    }
+}
+
+pub struct Model {
+    pub config: Config,
+    /// The temperature which is previewed while its slider is dragged.
+    pub preview: Option<u32>,
+    pub start: TimePicker,
+    pub end: TimePicker,
+    pub schedules: Vec<Entry<Schedule>>,
+    /// The latitude and longitude which sunset and sunrise are found for.
+    pub location: Option<(f64, f64)>,
+    pub locating: bool,
+    /// Why the location is unavailable, in which case the manual times are used.
+    pub location_error: Option<String>,
+    /// The temperature which the outputs were last tinted to, if they were tinted since.
+    applied: Option<Option<u32>>,
+    pub error: Option<String>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        let config = Config::default();
+
+        Self {
+            start: time_picker(minutes_to_time(config.start)),
+            end: time_picker(minutes_to_time(config.end)),
+            config,
+            preview: None,
+            schedules: vec![
+                Entry::new(
+                    Schedule::SunsetToSunrise,
+                    fl!("night-light-schedule", "sunset"),
+                    fl!("night-light-schedule", "sunset-desc"),
+                ),
+                Entry::new(
+                    Schedule::Manual,
+                    fl!("night-light-schedule", "manual"),
+                    fl!("night-light-schedule", "manual-desc"),
+                ),
+            ],
+            location: None,
+            locating: false,
+            location_error: None,
+            applied: None,
+            error: None,
+        }
+    }
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::End(message) => {
+                if let Some(time) = self.end.update(message) {
+                    self.config.end = time_to_minutes(time);
+                    return self.store();
+                }
+            }
+
+            Message::Loaded(config) => {
+                self.start.set_value(minutes_to_time(config.start));
+                self.end.set_value(minutes_to_time(config.end));
+                self.config = config;
+
+                return Command::batch(vec![self.locate(), self.sync()]);
+            }
+
+            Message::Located(result) => {
+                self.locating = false;
+
+                match result {
+                    Ok(location) => {
+                        self.location = Some(location);
+                        self.location_error = None;
+                    }
+                    Err(why) => {
+                        self.location = None;
+                        self.location_error = Some(why);
+                    }
+                }
+
+                return self.sync();
+            }
+
+            Message::NightLightToggled(enabled) => {
+                self.config.enabled = enabled;
+                return self.store();
+            }
+
+            Message::ScheduleChanged(schedule) => {
+                self.config.sunset_to_sunrise = schedule == Schedule::SunsetToSunrise;
+                return Command::batch(vec![self.locate(), self.store()]);
+            }
+
+            Message::Start(message) => {
+                if let Some(time) = self.start.update(message) {
+                    self.config.start = time_to_minutes(time);
+                    return self.store();
+                }
+            }
+
+            Message::TemperatureDragged(temperature) => {
+                self.preview = Some(temperature);
+                return self.sync();
+            }
+
+            Message::TemperatureReleased => {
+                if let Some(temperature) = self.preview.take() {
+                    self.config.temperature = temperature;
+                }
+
+                return self.store();
+            }
+
+            Message::Tick => return self.sync(),
+        }
+
+        Command::none()
+    }
+
+    /// The times which night light is on between, in minutes from midnight. The manual times
+    /// are used while the times of sunset and sunrise are unknown.
+    #[must_use]
+    pub fn times(&self) -> (u32, u32) {
+        let manual = (self.config.start, self.config.end);
+
+        if !self.config.sunset_to_sunrise {
+            return manual;
+        }
+
+        let Some((latitude, longitude)) = self.location else {
+            return manual;
+        };
+
+        let today = Local::now().date_naive();
+
+        night_light::sun_times(today.ordinal(), latitude, longitude)
+            .and_then(|(sunrise, sunset)| {
+                Some((
+                    local_minutes(today, sunset)?,
+                    local_minutes(today, sunrise)?,
+                ))
+            })
+            .unwrap_or(manual)
+    }
+
+    /// The temperature which the outputs are tinted to now, if they are tinted.
+    fn target(&self) -> Option<u32> {
+        if self.preview.is_some() {
+            return self.preview;
+        }
+
+        let now = Local::now();
+        let (start, end) = self.times();
+
+        let active = self.config.enabled
+            && night_light::is_active(now.hour() * 60 + now.minute(), start, end);
+
+        active.then_some(self.config.temperature)
+    }
+
+    /// Tints the outputs to the temperature which they should be tinted to now, if it changed.
+    fn sync(&mut self) -> Command<crate::Message> {
+        let target = self.target();
+
+        if self.applied == Some(target) {
+            return Command::none();
+        }
+
+        self.applied = Some(target);
+
+        Command::perform(
+            page::background(move || night_light::set_temperature(target)),
+            |result| {
+                let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                crate::Message::NightLight(Message::Applied(result))
+            },
+        )
+    }
+
+    /// Stores the config, and applies it at once.
+    fn store(&mut self) -> Command<crate::Message> {
+        let config = self.config.clone();
+
+        Command::batch(vec![
+            Command::perform(
+                page::background(move || config::store("night-light", &config)),
+                |_| crate::Message::None,
+            ),
+            self.sync(),
+        ])
+    }
+
+    /// Finds the location of the system, which sunset and sunrise are found for, unless it was
+    /// found already.
+    fn locate(&mut self) -> Command<crate::Message> {
+        if !self.config.sunset_to_sunrise || self.location.is_some() || self.locating {
+            return Command::none();
+        }
+
+        self.locating = true;
+
+        Command::perform(
+            async { locate().await.map_err(|why| why.to_string()) },
+            |result| crate::Message::NightLight(Message::Located(result)),
+        )
+    }
+}
+
+fn minutes_to_time(minutes: u32) -> NaiveTime {
+    let minutes = minutes % DAY_MINUTES;
+    NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0).unwrap_or_default()
+}
+
+fn time_to_minutes(time: NaiveTime) -> u32 {
+    time.hour() * 60 + time.minute()
+}
+
+/// Converts a time on a day from minutes after midnight UTC to minutes after local midnight.
+fn local_minutes(date: NaiveDate, utc_minutes: f64) -> Option<u32> {
+    let midnight = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?);
+    let local = (midnight + Duration::minutes(utc_minutes.round() as i64)).with_timezone(&Local);
+    Some(time_to_minutes(local.time()))
+}
+
+/// Asks GeoClue for the location of the system, to the accuracy of a city.
+///
+/// # Errors
+///
+/// Fails if GeoClue is not available, or if the user did not permit the location to be used.
+async fn locate() -> zbus::Result<(f64, f64)> {
+    use futures_util::StreamExt;
+    use zbus::zvariant::OwnedObjectPath;
+
+    const DESTINATION: &str = "org.freedesktop.GeoClue2";
+    const ACCURACY_CITY: u32 = 4;
+
+    let connection = zbus::Connection::system().await?;
+
+    let manager = zbus::Proxy::new(
+        &connection,
+        DESTINATION,
+        "/org/freedesktop/GeoClue2/Manager",
+        "org.freedesktop.GeoClue2.Manager",
+    )
+    .await?;
+
+    let client: OwnedObjectPath = manager.call("GetClient", &()).await?;
+
+    let client = zbus::Proxy::new(
+        &connection,
+        DESTINATION,
+        client,
+        "org.freedesktop.GeoClue2.Client",
+    )
+    .await?;
+
+    client
+        .set_property("DesktopId", "com.system76.CosmicSettings")
+        .await?;
+    client
+        .set_property("RequestedAccuracyLevel", ACCURACY_CITY)
+        .await?;
+
+    // Subscribed before the client is started, so that the first location is not missed.
+    let mut updates = client.receive_signal("LocationUpdated").await?;
+    client.call::<_, _, ()>("Start", &()).await?;
+
+    let update = updates.next().await;
+    let _res = client.call::<_, _, ()>("Stop", &()).await;
+
+    let Some(update) = update else {
+        return Err(zbus::Error::Failure(String::from("no location was found")));
+    };
+
+    let (_previous, location): (OwnedObjectPath, OwnedObjectPath) = update.body()?;
+
+    let location = zbus::Proxy::new(
+        &connection,
+        DESTINATION,
+        location,
+        "org.freedesktop.GeoClue2.Location",
+    )
+    .await?;
+
+    let latitude: f64 = location.get_property("Latitude").await?;
+    let longitude: f64 = location.get_property("Longitude").await?;
+    Ok((latitude, longitude))
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("night-light", "night-light-symbolic")
+            .title(fl!("night-light"))
+            .description(fl!("night-light", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![
+            sections.insert(night_light()),
+            sections.insert(schedule()),
+        ])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let config = page::background(|| config::load::<Config>("night-light")).await;
+            crate::Message::NightLight(Message::Loaded(config.unwrap_or_default()))
+        })
+    }
+
+    fn subscription(model: &Model) -> Subscription<crate::Message> {
+        let mut subscriptions = vec![
+            model.start.subscription().map(Message::Start),
+            model.end.subscription().map(Message::End),
+        ];
+
+        if model.config.enabled {
+            subscriptions
+                .push(iced::time::every(std::time::Duration::from_secs(60)).map(|_| Message::Tick));
+        }
+
+        Subscription::batch(subscriptions).map(crate::Message::NightLight)
+    }
+}
+
+fn night_light() -> Section {
+    Section::new()
+        .title(fl!("night-light"))
+        .descriptions(vec![
+            fl!("night-light", "enable"),
+            fl!("night-light", "enable-desc"),
+            fl!("night-light", "temperature"),
+            fl!("night-light", "temperature-desc"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("night light model is missing");
+
+            let desc = &section.descriptions;
+            let temperature = model.preview.unwrap_or(model.config.temperature);
+
+            let mut view = settings::view_section(&section.title)
+                .add(
+                    settings::item::builder(&desc[0])
+                        .description(&desc[1])
+                        .control(toggler(
+                            None,
+                            model.config.enabled,
+                            Message::NightLightToggled,
+                        )),
+                )
+                .add(
+                    labeled_slider(
+                        &desc[2],
+                        TEMPERATURE,
+                        temperature,
+                        Message::TemperatureDragged,
+                    )
+                    .description(&desc[3])
+                    .step(100)
+                    .on_release(Message::TemperatureReleased)
+                    .readout(|temperature| fl!("night-light", "kelvin", value = temperature)),
+                );
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::NightLight)
+        })
+}
+
+fn schedule() -> Section {
+    Section::new()
+        .title(fl!("night-light-schedule"))
+        .descriptions(vec![
+            fl!("night-light-schedule", "start"),
+            fl!("night-light-schedule", "end"),
+            fl!("night-light-schedule", "locating"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("night light model is missing");
+
+            let desc = &section.descriptions;
+            let schedule = model.config.schedule();
+
+            let mut view = settings::view_section(&section.title).add(radio_list(
+                &model.schedules,
+                Some(&schedule),
+                Message::ScheduleChanged,
+            ));
+
+            let mut manual = schedule == Schedule::Manual;
+
+            if schedule == Schedule::SunsetToSunrise {
+                if model.locating {
+                    view = view.add(text(&desc[2]));
+                } else if model.location.is_some() {
+                    let (sunset, sunrise) = model.times();
+
+                    view = view.add(text(fl!(
+                        "night-light-schedule",
+                        "sun-times",
+                        sunset = minutes_to_time(sunset).format("%H:%M").to_string(),
+                        sunrise = minutes_to_time(sunrise).format("%H:%M").to_string()
+                    )));
+                } else {
+                    // Without a location, the manual times are used in place of the sun.
+                    manual = true;
+
+                    view = view.add(text(fl!(
+                        "night-light-schedule",
+                        "no-location",
+                        why = model.location_error.as_deref().unwrap_or_default()
+                    )));
+                }
+            }
+
+            if manual {
+                view = view
+                    .add(settings::item(
+                        &desc[0],
+                        model.start.view().map(Message::Start),
+                    ))
+                    .add(settings::item(&desc[1], model.end.view().map(Message::End)));
+            }
+
+            view.apply(Element::from).map(crate::Message::NightLight)
+        })
+}
@@ -0,0 +1,192 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{horizontal_space, text_input},
+        Command, Length,
+    },
+    widget::{settings, text, toggler},
+    Element,
+};
+use cosmic_settings_input::{self as input, Repeat};
+use slotmap::SlotMap;
+
+use crate::{
+    page::{self, section, Content, Section},
+    widget::labeled_slider,
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// The key repeat while a slider is dragged, which is applied once it is released.
+    KeyboardRepeatChanged(Repeat),
+    /// The key repeat, and whether Num Lock is on at login if there is a numeric keypad.
+    Loaded(Repeat, Option<bool>),
+    NumLockDefaultChanged(bool),
+    RepeatApply,
+    TestInput(String),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub repeat: Repeat,
+    /// Whether Num Lock is on at login, which is not offered without a numeric keypad.
+    pub numlock: Option<bool>,
+    /// Typed into while holding a key, to try the key repeat out.
+    pub test_input: String,
+    pub error: Option<String>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::KeyboardRepeatChanged(repeat) => self.repeat = repeat.clamp(),
+
+            Message::Loaded(repeat, numlock) => {
+                self.repeat = repeat;
+                self.numlock = numlock;
+            }
+
+            Message::NumLockDefaultChanged(enabled) => {
+                self.numlock = Some(enabled);
+
+                return Command::perform(
+                    page::background(move || input::set_numlock_on_login(enabled)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Keyboard(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::RepeatApply => {
+                let repeat = self.repeat;
+
+                return Command::perform(
+                    page::background(move || input::set_repeat(repeat)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Keyboard(Message::Applied(result))
+                    },
+                );
+            }
+
+            Message::TestInput(text) => self.test_input = text,
+        }
+
+        Command::none()
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("keyboard", "input-keyboard-symbolic")
+            .title(fl!("keyboard"))
+            .description(fl!("keyboard", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(repeat()), sections.insert(numlock())])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let loaded = page::background(|| {
+                let numlock = input::has_keypad().then(input::numlock_on_login);
+                (input::repeat(), numlock)
+            })
+            .await;
+
+            let (repeat, numlock) = loaded.unwrap_or_default();
+            crate::Message::Keyboard(Message::Loaded(repeat, numlock))
+        })
+    }
+}
+
+fn repeat() -> Section {
+    Section::new()
+        .title(fl!("keyboard-repeat"))
+        .descriptions(vec![
+            fl!("keyboard-repeat", "delay"),
+            fl!("keyboard-repeat", "rate"),
+            fl!("keyboard-repeat", "test"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("keyboard model is missing");
+
+            let desc = &section.descriptions;
+            let repeat = model.repeat;
+
+            let delay = labeled_slider(&desc[0], input::REPEAT_DELAY, repeat.delay, move |delay| {
+                Message::KeyboardRepeatChanged(Repeat { delay, ..repeat })
+            })
+            .step(50)
+            .on_release(Message::RepeatApply)
+            .readout(|delay| fl!("keyboard-repeat", "ms", value = delay));
+
+            let rate = labeled_slider(&desc[1], input::REPEAT_RATE, repeat.rate, move |rate| {
+                Message::KeyboardRepeatChanged(Repeat { rate, ..repeat })
+            })
+            .on_release(Message::RepeatApply)
+            .readout(|rate| fl!("keyboard-repeat", "per-second", value = rate));
+
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            view.add(delay)
+                .add(rate)
+                .add(
+                    text_input(&desc[2], &model.test_input, Message::TestInput)
+                        .padding(8)
+                        .width(Length::Fill),
+                )
+                .apply(Element::from)
+                .map(crate::Message::Keyboard)
+        })
+}
+
+fn numlock() -> Section {
+    Section::new()
+        .title(fl!("keyboard-numlock"))
+        .descriptions(vec![
+            fl!("keyboard-numlock", "login"),
+            fl!("keyboard-numlock", "next-login"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("keyboard model is missing");
+
+            // Hidden on keyboards which have no numeric keypad.
+            let Some(enabled) = model.numlock else {
+                return horizontal_space(Length::Units(0)).into();
+            };
+
+            let desc = &section.descriptions;
+
+            settings::view_section(&section.title)
+                .add(
+                    settings::item::builder(&desc[0])
+                        .description(&desc[1])
+                        .control(toggler(None, enabled, Message::NumLockDefaultChanged)),
+                )
+                .apply(Element::from)
+                .map(crate::Message::Keyboard)
+        })
+}
@@ -0,0 +1,26 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::page;
+
+pub mod keyboard;
+pub mod mouse;
+pub mod tablet;
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = ();
+
+    fn page() -> page::Meta {
+        page::Meta::new("input", "input-keyboard-symbolic")
+            .title(fl!("input"))
+            .description(fl!("input", "desc"))
+    }
+
+    fn sub_pages(page: page::Insert) -> page::Insert {
+        page.sub_page::<keyboard::Page>()
+            .sub_page::<mouse::Page>()
+            .sub_page::<tablet::Page>()
+    }
+}
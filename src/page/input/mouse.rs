@@ -0,0 +1,130 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{widget::horizontal_space, Command, Length},
+    widget::{settings, text, toggler},
+    Element,
+};
+use cosmic_settings_input::{self as input, DeviceClass};
+use slotmap::SlotMap;
+
+use crate::page::{self, section, Content, Section};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// The connected classes of pointing devices, with whether each scrolls naturally.
+    Loaded(Vec<(DeviceClass, bool)>),
+    ScrollDirectionChanged(DeviceClass, bool),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    /// Whether each connected class of pointing devices scrolls naturally.
+    pub natural_scroll: Vec<(DeviceClass, bool)>,
+    pub error: Option<String>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::Loaded(natural_scroll) => self.natural_scroll = natural_scroll,
+
+            Message::ScrollDirectionChanged(class, natural) => {
+                if let Some((_, value)) = self.natural_scroll.iter_mut().find(|(c, _)| *c == class)
+                {
+                    *value = natural;
+                }
+
+                return Command::perform(
+                    page::background(move || input::set_natural_scroll(class, natural)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Mouse(Message::Applied(result))
+                    },
+                );
+            }
+        }
+
+        Command::none()
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("mouse", "input-mouse-symbolic")
+            .title(fl!("mouse"))
+            .description(fl!("mouse", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(scrolling())])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let loaded = page::background(|| {
+                input::device_classes()
+                    .into_iter()
+                    .map(|class| (class, input::natural_scroll(class)))
+                    .collect()
+            })
+            .await;
+
+            crate::Message::Mouse(Message::Loaded(loaded.unwrap_or_default()))
+        })
+    }
+}
+
+fn scrolling() -> Section {
+    Section::new()
+        .title(fl!("mouse-scrolling"))
+        .descriptions(vec![
+            fl!("mouse-scrolling", "natural-desc"),
+            fl!("mouse-scrolling", "mouse"),
+            fl!("mouse-scrolling", "touchpad"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("mouse model is missing");
+
+            // Hidden until a mouse or touchpad is connected.
+            if model.natural_scroll.is_empty() {
+                return horizontal_space(Length::Units(0)).into();
+            }
+
+            let desc = &section.descriptions;
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            for &(class, natural) in &model.natural_scroll {
+                let title = match class {
+                    DeviceClass::Mouse => &desc[1],
+                    DeviceClass::Touchpad => &desc[2],
+                };
+
+                view = view.add(
+                    settings::item::builder(title)
+                        .description(&desc[0])
+                        .control(toggler(None, natural, move |natural| {
+                            Message::ScrollDirectionChanged(class, natural)
+                        })),
+                );
+            }
+
+            view.apply(Element::from).map(crate::Message::Mouse)
+        })
+}
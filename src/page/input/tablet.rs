@@ -0,0 +1,249 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::fmt;
+
+use apply::Apply;
+use cosmic::{
+    iced::{widget::pick_list, Command},
+    widget::{settings, text, toggler},
+    Element,
+};
+use cosmic_settings_display as display;
+use cosmic_settings_input::tablet::{self, ButtonAction, TabletConfig};
+use slotmap::SlotMap;
+
+use crate::page::{self, section, Content, Section};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// The connected tablets with their config, and the names of the enabled outputs.
+    Loaded(Vec<(String, TabletConfig)>, Vec<String>),
+    /// The config of a tablet was changed, by its name.
+    TabletMappingChanged(String, TabletConfig),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    /// The connected tablets, by their name, with their config.
+    pub tablets: Vec<(String, TabletConfig)>,
+    /// The names of the enabled outputs, which a tablet can be mapped to.
+    pub outputs: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::Loaded(tablets, outputs) => {
+                self.tablets = tablets;
+                self.outputs = outputs;
+            }
+
+            Message::TabletMappingChanged(name, config) => {
+                if let Some((_, current)) = self.tablets.iter_mut().find(|(n, _)| *n == name) {
+                    *current = config.clone();
+                }
+
+                return Command::perform(
+                    page::background(move || tablet::set_config(&name, &config)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Tablet(Message::Applied(result))
+                    },
+                );
+            }
+        }
+
+        Command::none()
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("tablet", "input-tablet-symbolic")
+            .title(fl!("tablet"))
+            .description(fl!("tablet", "desc"))
+            // Systems without a tablet have nothing to configure here.
+            .hidden(tablet::tablets().is_empty())
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(tablets())])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let loaded = page::background(|| {
+                let tablets = tablet::tablets()
+                    .into_iter()
+                    .map(|name| {
+                        let config = tablet::config(&name);
+                        (name, config)
+                    })
+                    .collect();
+
+                let outputs = display::outputs()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|output| output.enabled)
+                    .map(|output| output.name)
+                    .collect();
+
+                (tablets, outputs)
+            })
+            .await;
+
+            let (tablets, outputs) = loaded.unwrap_or_default();
+            crate::Message::Tablet(Message::Loaded(tablets, outputs))
+        })
+    }
+}
+
+/// An option of a pick list, shown by its label.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Choice<T> {
+    value: T,
+    label: String,
+}
+
+impl<T> fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)
+    }
+}
+
+fn button_label(action: ButtonAction) -> String {
+    match action {
+        ButtonAction::Default => fl!("tablet-stylus", "default"),
+        ButtonAction::MiddleClick => fl!("tablet-stylus", "middle-click"),
+        ButtonAction::RightClick => fl!("tablet-stylus", "right-click"),
+        ButtonAction::Back => fl!("tablet-stylus", "back"),
+        ButtonAction::Forward => fl!("tablet-stylus", "forward"),
+        ButtonAction::Disabled => fl!("tablet-stylus", "disabled"),
+    }
+}
+
+/// Each tablet is shown as a section of its own, titled by its name.
+fn tablets() -> Section {
+    Section::new()
+        .title(fl!("tablet"))
+        .descriptions(vec![
+            fl!("tablet", "display"),
+            fl!("tablet", "display-desc"),
+            fl!("tablet", "all-displays"),
+            fl!("tablet", "aspect-ratio"),
+            fl!("tablet", "aspect-ratio-desc"),
+            fl!("tablet-stylus", "lower"),
+            fl!("tablet-stylus", "upper"),
+        ])
+        .visible_when(|pages| {
+            pages
+                .resource::<Model>()
+                .map_or(false, |model| !model.tablets.is_empty())
+        })
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("tablet model is missing");
+
+            let desc = &section.descriptions;
+
+            let outputs: Vec<Choice<Option<String>>> = std::iter::once(Choice {
+                value: None,
+                label: desc[2].clone(),
+            })
+            .chain(model.outputs.iter().map(|output| Choice {
+                value: Some(output.clone()),
+                label: output.clone(),
+            }))
+            .collect();
+
+            let actions: Vec<Choice<ButtonAction>> = ButtonAction::ALL
+                .into_iter()
+                .map(|action| Choice {
+                    value: action,
+                    label: button_label(action),
+                })
+                .collect();
+
+            let mut views = Vec::with_capacity(model.tablets.len() + 1);
+
+            if let Some(why) = &model.error {
+                views.push(text(why).into());
+            }
+
+            for (name, config) in &model.tablets {
+                let selected = outputs
+                    .iter()
+                    .find(|choice| choice.value == config.output)
+                    .cloned();
+
+                let mut view = settings::view_section(name)
+                    .add(
+                        settings::item::builder(&desc[0])
+                            .description(&desc[1])
+                            .control(pick_list(outputs.clone(), selected, {
+                                let (name, config) = (name.clone(), config.clone());
+                                move |choice| {
+                                    Message::TabletMappingChanged(
+                                        name.clone(),
+                                        TabletConfig {
+                                            output: choice.value,
+                                            ..config.clone()
+                                        },
+                                    )
+                                }
+                            })),
+                    )
+                    .add(
+                        settings::item::builder(&desc[3])
+                            .description(&desc[4])
+                            .control(toggler(None, config.keep_aspect_ratio, {
+                                let (name, config) = (name.clone(), config.clone());
+                                move |keep_aspect_ratio| {
+                                    Message::TabletMappingChanged(
+                                        name.clone(),
+                                        TabletConfig {
+                                            keep_aspect_ratio,
+                                            ..config.clone()
+                                        },
+                                    )
+                                }
+                            })),
+                    );
+
+                for (button, title) in [&desc[5], &desc[6]].into_iter().enumerate() {
+                    let selected = actions
+                        .iter()
+                        .find(|choice| choice.value == config.buttons[button])
+                        .cloned();
+
+                    let (name, config) = (name.clone(), config.clone());
+
+                    view = view.add(settings::item(
+                        title,
+                        pick_list(actions.clone(), selected, move |choice| {
+                            let mut config = config.clone();
+                            config.buttons[button] = choice.value;
+                            Message::TabletMappingChanged(name.clone(), config)
+                        }),
+                    ));
+                }
+
+                views.push(view.into());
+            }
+
+            settings::view_column(views)
+                .apply(Element::from)
+                .map(crate::Message::Tablet)
+        })
+}
@@ -1,21 +1,28 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+pub mod accessibility;
+pub mod applications;
 pub mod desktop;
+pub mod display;
+pub mod input;
 pub mod networking;
+pub mod power;
 pub mod section;
 pub mod time;
 
-pub use section::Section;
+pub use section::{Section, SectionState};
 pub mod sound;
 pub mod system;
 
 mod model;
 
-pub use model::{Insert, Model, PageTask};
+pub use model::{DuplicateId, Expansion, Insert, Model, PageHook, PageTask};
 
+use cosmic::iced::{Command, Subscription};
 use derive_setters::Setters;
 use slotmap::SlotMap;
+use std::collections::HashSet;
 
 slotmap::new_key_type! {
     /// ID of a page
@@ -43,6 +50,34 @@ pub trait Page {
     fn load(page: Entity) -> PageTask {
         Box::pin(async move { crate::Message::None })
     }
+
+    /// Subscriptions which are only active while the page is being viewed.
+    #[must_use]
+    #[allow(unused)]
+    fn subscription(model: &Self::Model) -> Subscription<crate::Message> {
+        Subscription::none()
+    }
+
+    /// Applies the changes that a page has staged since marking itself as dirty.
+    #[allow(unused)]
+    fn apply(model: &mut Self::Model, page: Entity) -> Command<crate::Message> {
+        Command::none()
+    }
+
+    /// Invoked when another page is activated in place of the page, such as to stop what
+    /// was only needed while it was viewed.
+    #[allow(unused)]
+    fn on_leave(model: &mut Self::Model, page: Entity) -> Command<crate::Message> {
+        Command::none()
+    }
+
+    /// Resets the settings of a resettable page to their defaults.
+    ///
+    /// Defaults are applied the same way as changes made with the controls of the page.
+    #[allow(unused)]
+    fn reset_defaults(model: &mut Self::Model, page: Entity) -> Command<crate::Message> {
+        Command::none()
+    }
 }
 
 #[derive(Setters)]
@@ -56,6 +91,10 @@ pub struct Meta {
     #[setters(skip)]
     pub icon_name: &'static str,
 
+    /// The icon which is drawn, which is a fallback if the icon theme lacks its own icon.
+    #[setters(skip)]
+    icon: &'static str,
+
     /// The title of the page.
     #[setters(into)]
     pub title: String,
@@ -67,6 +106,16 @@ pub struct Meta {
     /// The parent of the page.
     #[setters(strip_option)]
     pub parent: Option<Entity>,
+
+    /// Whether the page offers to reset its settings to their defaults.
+    #[setters(bool)]
+    pub resettable: bool,
+
+    /// Whether the page is left out of the sub-pages of its parent, as it is only opened
+    /// from other pages with [`crate::Message::OpenPage`], or has nothing to configure on
+    /// this system.
+    #[setters(bool)]
+    pub hidden: bool,
 }
 
 impl Meta {
@@ -74,11 +123,40 @@ impl Meta {
         Self {
             title: String::new(),
             icon_name,
+            icon: icon_name,
             id,
             description: String::new(),
             parent: None,
+            resettable: false,
+            hidden: false,
         }
     }
+
+    /// The icon which is drawn for the page, as it was resolved in the icon theme.
+    #[must_use]
+    pub fn icon_handle(&self) -> &'static str {
+        self.icon
+    }
+
+    /// Falls back to a generic icon if the icon of the page is not among the icons of the theme.
+    pub fn resolve_icon(&mut self, icons: &HashSet<String>) {
+        self.icon = if icons.contains(self.icon_name) {
+            self.icon_name
+        } else {
+            crate::icon_theme::FALLBACK
+        };
+    }
 }
 
 pub type Content = Vec<section::Entity>;
+
+/// Tells the app whether a page has changes which have not been applied yet, which it asks
+/// to apply or discard before the page is left.
+pub fn mark_dirty(page: Entity, dirty: bool) -> Command<crate::Message> {
+    Command::perform(async {}, move |_| crate::Message::PageDirty(page, dirty))
+}
+
+/// Runs a blocking system query on a background thread so that it does not stall the UI.
+pub async fn background<T: Send + 'static>(func: impl FnOnce() -> T + Send + 'static) -> Option<T> {
+    tokio::task::spawn_blocking(func).await.ok()
+}
@@ -3,36 +3,75 @@
 
 use std::{
     any::{Any, TypeId},
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    fmt,
     future::Future,
     pin::Pin,
 };
 
-use crate::page::{self, section, Content, Meta, Page, Section};
+use crate::page::{self, section, Content, Meta, Page, Section, SectionState};
+use cosmic::iced::Subscription;
 use cosmic::iced_native::command::{Action, Command};
 use regex::Regex;
 use slotmap::{SecondaryMap, SlotMap, SparseSecondaryMap};
 
 pub type PageTask = Pin<Box<dyn Future<Output = crate::Message> + Send>>;
 
+pub type PageHook = fn(&mut Model, page::Entity) -> Command<crate::Message>;
+
+/// The state of a collapsible section, which animates towards being expanded or collapsed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Expansion {
+    pub expanded: bool,
+    /// How much of the section is revealed, from 0 to 1.
+    pub progress: f32,
+}
+
 pub struct Model {
     pub pages: SlotMap<page::Entity, Meta>,
+    pub busy: SecondaryMap<page::Entity, ()>,
+    pub dirty: SecondaryMap<page::Entity, ()>,
+    pub expanded: SecondaryMap<section::Entity, Expansion>,
+    pub page_apply: SecondaryMap<page::Entity, PageHook>,
+    pub page_leave: SecondaryMap<page::Entity, PageHook>,
+    pub page_localize: SecondaryMap<page::Entity, fn(&mut Model, page::Entity)>,
+    pub page_reset: SecondaryMap<page::Entity, PageHook>,
+    pub page_subscription: SecondaryMap<page::Entity, fn(&Model) -> Subscription<crate::Message>>,
     pub page_load: SecondaryMap<page::Entity, fn(page::Entity) -> PageTask>,
     pub resource: HashMap<TypeId, Box<dyn Any>>,
+    /// Pages whose titles were set with [`Model::set_title`], which are kept rather than
+    /// localized again.
+    pub retitled: SecondaryMap<page::Entity, ()>,
+    /// The names of the types of the resources, which are only kept to diagnose debug builds.
+    #[cfg(debug_assertions)]
+    resource_names: HashMap<TypeId, &'static str>,
     pub storage: HashMap<TypeId, SecondaryMap<page::Entity, Box<dyn Any>>>,
     pub sub_pages: SparseSecondaryMap<page::Entity, Vec<page::Entity>>,
     pub sections: SlotMap<section::Entity, Section>,
+    pub section_state: SecondaryMap<section::Entity, SectionState>,
     pub content: SparseSecondaryMap<page::Entity, Content>,
 }
 
 impl Default for Model {
     fn default() -> Self {
         Self {
+            busy: SecondaryMap::new(),
             content: SparseSecondaryMap::new(),
+            dirty: SecondaryMap::new(),
+            expanded: SecondaryMap::new(),
             pages: SlotMap::with_key(),
+            page_apply: SecondaryMap::new(),
+            page_leave: SecondaryMap::new(),
+            page_localize: SecondaryMap::new(),
+            page_reset: SecondaryMap::new(),
+            page_subscription: SecondaryMap::new(),
             page_load: SecondaryMap::new(),
             resource: HashMap::new(),
+            #[cfg(debug_assertions)]
+            resource_names: HashMap::new(),
+            retitled: SecondaryMap::new(),
             sections: SlotMap::with_key(),
+            section_state: SecondaryMap::new(),
             storage: HashMap::new(),
             sub_pages: SparseSecondaryMap::new(),
         }
@@ -40,12 +79,208 @@ impl Default for Model {
 }
 
 impl Model {
+    /// Applies the staged changes of a page, and marks it as clean.
+    pub fn apply(&mut self, id: page::Entity) -> Command<crate::Message> {
+        self.set_dirty(id, false);
+
+        match self.page_apply.get(id).copied() {
+            Some(func) => func(self, id),
+            None => Command::none(),
+        }
+    }
+
+    /// Tells a page that it is no longer being viewed.
+    pub fn leave(&mut self, id: page::Entity) -> Command<crate::Message> {
+        match self.page_leave.get(id).copied() {
+            Some(func) => func(self, id),
+            None => Command::none(),
+        }
+    }
+
+    /// Resets the settings of a page to their defaults, discarding its staged changes.
+    pub fn reset_defaults(&mut self, id: page::Entity) -> Command<crate::Message> {
+        self.set_dirty(id, false);
+
+        match self.page_reset.get(id).copied() {
+            Some(func) => func(self, id),
+            None => Command::none(),
+        }
+    }
+
+    /// Removes every page and section, returning the model to its default state.
+    ///
+    /// Every map is cleared in place along with the pages, so that no state outlives them.
+    /// The versions of their keys keep increasing, so entities of the removed pages which
+    /// are held outside of the model, such as by the nav bar or the history of the app, are
+    /// invalid from then on, and never refer to pages which are registered afterwards.
+    // Only tests clear the model, until pages can be reloaded.
+    #[allow(dead_code)]
+    pub fn clear(&mut self) {
+        self.busy.clear();
+        self.content.clear();
+        self.dirty.clear();
+        self.expanded.clear();
+        self.pages.clear();
+        self.page_apply.clear();
+        self.page_leave.clear();
+        self.page_localize.clear();
+        self.page_reset.clear();
+        self.page_subscription.clear();
+        self.page_load.clear();
+        self.resource.clear();
+        #[cfg(debug_assertions)]
+        self.resource_names.clear();
+        self.retitled.clear();
+        self.sections.clear();
+        self.section_state.clear();
+        self.storage.clear();
+        self.sub_pages.clear();
+    }
+
+    /// Changes the title of a page, such as to the name of what it found once it loaded,
+    /// which searches match from then on in place of its previous title.
+    ///
+    /// The title is kept when the language is changed, rather than localized again.
+    pub fn set_title(&mut self, id: page::Entity, title: String) {
+        if let Some(meta) = self.pages.get_mut(id) {
+            meta.title = title;
+            self.retitled.insert(id, ());
+        }
+    }
+
     /// Check if a page exists in the model.
     #[must_use]
     pub fn contains_item(&self, id: page::Entity) -> bool {
         self.pages.contains_key(id)
     }
 
+    /// Check if a page is running a background operation.
+    #[must_use]
+    pub fn is_busy(&self, id: page::Entity) -> bool {
+        self.busy.contains_key(id)
+    }
+
+    /// Marks a page as running, or no longer running, a background operation.
+    ///
+    /// The state is keyed to the page entity, so it is forgotten with the page.
+    pub fn set_busy(&mut self, id: page::Entity, busy: bool) {
+        if !busy {
+            self.busy.remove(id);
+        } else if self.contains_item(id) {
+            self.busy.insert(id, ());
+        }
+    }
+
+    /// Check if a page has changes which have not been applied yet.
+    #[must_use]
+    pub fn is_dirty(&self, id: page::Entity) -> bool {
+        self.dirty.contains_key(id)
+    }
+
+    /// Marks a page as having changes which have not been applied yet.
+    pub fn set_dirty(&mut self, id: page::Entity, dirty: bool) {
+        if !dirty {
+            self.dirty.remove(id);
+        } else if self.contains_item(id) {
+            self.dirty.insert(id, ());
+        }
+    }
+
+    /// Check if a collapsible section is expanded.
+    #[must_use]
+    pub fn is_expanded(&self, id: section::Entity) -> bool {
+        self.expanded
+            .get(id)
+            .map_or(false, |expansion| expansion.expanded)
+    }
+
+    /// How much of a collapsible section is revealed, from 0 to 1.
+    #[must_use]
+    pub fn expansion(&self, id: section::Entity) -> f32 {
+        self.expanded
+            .get(id)
+            .map_or(0.0, |expansion| expansion.progress)
+    }
+
+    /// Expands or collapses a section, animating the transition.
+    pub fn set_expanded(&mut self, id: section::Entity, expanded: bool) {
+        if !self.sections.contains_key(id) {
+            return;
+        }
+
+        if let Some(entry) = self.expanded.entry(id) {
+            entry.or_default().expanded = expanded;
+        }
+    }
+
+    /// Expands a section immediately, such as to reveal a search result within it.
+    pub fn expand(&mut self, id: section::Entity) {
+        if self.sections.contains_key(id) {
+            self.expanded.insert(
+                id,
+                Expansion {
+                    expanded: true,
+                    progress: 1.0,
+                },
+            );
+        }
+    }
+
+    /// Check if any section is being expanded or collapsed.
+    #[must_use]
+    pub fn is_animating(&self) -> bool {
+        self.expanded.values().any(|expansion| {
+            let target = if expansion.expanded { 1.0 } else { 0.0 };
+            (expansion.progress - target).abs() > f32::EPSILON
+        })
+    }
+
+    /// Advances the animations of expanding and collapsing sections.
+    pub fn animate(&mut self, step: f32) {
+        for expansion in self.expanded.values_mut() {
+            expansion.progress = if expansion.expanded {
+                (expansion.progress + step).min(1.0)
+            } else {
+                (expansion.progress - step).max(0.0)
+            };
+        }
+    }
+
+    /// Returns the state of a section, unless it is ready to be shown.
+    #[must_use]
+    pub fn section_state(&self, id: section::Entity) -> Option<&SectionState> {
+        self.section_state.get(id)
+    }
+
+    /// Marks a section as loading, as ready, or as having failed to load.
+    pub fn set_section_state(&mut self, id: section::Entity, state: SectionState) {
+        if state == SectionState::Ready {
+            self.section_state.remove(id);
+        } else if self.sections.contains_key(id) {
+            self.section_state.insert(id, state);
+        }
+    }
+
+    /// Marks every section of a page with the same state.
+    pub fn set_page_state(&mut self, page: page::Entity, state: &SectionState) {
+        let Some(content) = self.content.get(page) else {
+            return;
+        };
+
+        for id in content.clone() {
+            self.set_section_state(id, state.clone());
+        }
+    }
+
+    /// Returns the page which a section belongs to.
+    #[must_use]
+    pub fn section_page(&self, id: section::Entity) -> Option<page::Entity> {
+        self.content
+            .iter()
+            .find(|(_, content)| content.contains(&id))
+            .map(|(page, _)| page)
+    }
+
     /// Returns the content of a page, if it has any.
     #[must_use]
     pub fn content(&self, page: page::Entity) -> Option<&[section::Entity]> {
@@ -94,11 +329,26 @@ impl Model {
         None
     }
 
-    // Registers a new page in the settings panel.
-    pub fn register<P: Page>(&mut self) -> Insert {
-        let id = self.pages.insert(P::page());
+    /// Registers a new page in the settings panel.
+    ///
+    /// Ids of pages are unique, so a page whose id was already registered is not registered,
+    /// and the page which was registered first is kept.
+    ///
+    /// # Errors
+    ///
+    /// Fails if another page has registered the id of the page.
+    pub fn register<P: Page>(&mut self) -> Result<Insert, DuplicateId> {
+        let meta = P::page();
+        self.check_unique(&meta)?;
+
+        let id = self.pages.insert(meta);
 
         self.page_load.insert(id, P::load);
+        self.page_apply.insert(id, apply::<P>);
+        self.page_leave.insert(id, on_leave::<P>);
+        self.page_localize.insert(id, localize::<P>);
+        self.page_reset.insert(id, reset_defaults::<P>);
+        self.page_subscription.insert(id, subscription::<P>);
 
         if let Some(content) = P::content(&mut self.sections) {
             self.content.insert(id, content);
@@ -106,7 +356,34 @@ impl Model {
 
         self.resource_register::<P::Model>();
 
-        P::sub_pages(Insert { id, model: self })
+        Ok(P::sub_pages(Insert { id, model: self }))
+    }
+
+    /// Fails if a page has already registered the id of a page.
+    fn check_unique(&self, meta: &Meta) -> Result<(), DuplicateId> {
+        match self.find_page_by_id(meta.id) {
+            Some(existing) => Err(DuplicateId {
+                id: meta.id,
+                existing,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Resolves the icons of every page among the icons of the icon theme.
+    pub fn resolve_icons(&mut self, icons: &HashSet<String>) {
+        for meta in self.pages.values_mut() {
+            meta.resolve_icon(icons);
+        }
+    }
+
+    /// Finds a page by the id of its meta.
+    #[must_use]
+    pub fn find_page_by_id(&self, id: &str) -> Option<page::Entity> {
+        self.pages
+            .iter()
+            .find(|(_, meta)| meta.id == id)
+            .map(|(page, _)| page)
     }
 
     #[must_use]
@@ -128,17 +405,73 @@ impl Model {
         self.resource
             .entry(TypeId::of::<Resource>())
             .or_insert_with(|| Box::new(Resource::default()));
+
+        #[cfg(debug_assertions)]
+        self.resource_names
+            .insert(TypeId::of::<Resource>(), std::any::type_name::<Resource>());
+    }
+
+    /// The types of the resources which are registered, such as to find a page whose model
+    /// is missing.
+    pub fn resource_type_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.resource.keys().copied()
+    }
+
+    /// The names of the types of the resources which are registered, sorted, for a page
+    /// which diagnoses the state of the app in debug builds.
+    #[cfg(debug_assertions)]
+    #[must_use]
+    pub fn resource_debug(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .resource_type_ids()
+            .map(|id| match self.resource_names.get(&id) {
+                Some(name) => (*name).to_owned(),
+                None => format!("{id:?}"),
+            })
+            .collect();
+
+        names.sort();
+        names
+    }
+
+    /// Localizes the titles and descriptions of every page and section again, such as once the
+    /// language was changed, so that searches match them in the new language.
+    ///
+    /// Sections keep their IDs and state, and only their text is replaced. Titles which were
+    /// set with [`Model::set_title`] are kept as they were set.
+    pub fn reindex_search(&mut self) {
+        let pages: Vec<(page::Entity, fn(&mut Model, page::Entity))> = self
+            .page_localize
+            .iter()
+            .map(|(page, func)| (page, *func))
+            .collect();
+
+        for (page, func) in pages {
+            func(self, page);
+        }
     }
 
-    /// Finds content of panels that match the search.
+    /// Finds content of panels that match the search. Every section of a page matches when
+    /// the title of the page does.
     pub fn search<'a>(
         &'a self,
         rule: &'a Regex,
     ) -> impl Iterator<Item = (page::Entity, section::Entity)> + 'a {
         generator::Gn::new_scoped_local(|mut s| {
             for (page, sections) in self.content.iter() {
+                let title_matches = self.pages.get(page).map_or(false, |meta| {
+                    rule.is_match(&section::normalize(&meta.title))
+                });
+
                 for id in sections {
-                    if self.sections[*id].matches_search(rule) {
+                    let section = &self.sections[*id];
+
+                    // Hidden sections are not found, as they would show nothing.
+                    if !(section.visible)(self) {
+                        continue;
+                    }
+
+                    if (title_matches && !section.search_ignore) || section.matches_search(rule) {
                         s.yield_((page, *id));
                     }
                 }
@@ -148,12 +481,85 @@ impl Model {
         })
     }
 
+    /// Finds content of panels that match the search, collected so that the results may be
+    /// kept without borrowing the model.
+    #[must_use]
+    pub fn sections_matching(&self, rule: &Regex) -> Vec<(page::Entity, section::Entity)> {
+        self.search(rule).collect()
+    }
+
+    /// Finds content of panels that match the search, grouped by the page they belong to.
+    ///
+    /// Pages appear in the same order as they do in the search, and only when they have matches.
+    #[must_use]
+    pub fn search_grouped(&self, rule: &Regex) -> Vec<(page::Entity, Vec<section::Entity>)> {
+        let mut groups: Vec<(page::Entity, Vec<section::Entity>)> = Vec::new();
+
+        for (page, section) in self.search(rule) {
+            match groups.last_mut() {
+                Some((last, sections)) if *last == page => sections.push(section),
+                _ => groups.push((page, vec![section])),
+            }
+        }
+
+        groups
+    }
+
+    /// Returns the parent of a page, if it is a sub-page.
+    #[must_use]
+    pub fn parent(&self, id: page::Entity) -> Option<page::Entity> {
+        self.pages.get(id).and_then(|meta| meta.parent)
+    }
+
+    /// Returns the subscriptions of a page, which are active while it is being viewed.
+    #[must_use]
+    pub fn subscription(&self, id: page::Entity) -> Subscription<crate::Message> {
+        match self.page_subscription.get(id) {
+            Some(func) => func(self),
+            None => Subscription::none(),
+        }
+    }
+
     /// Returns the sub-pages of a page, if it has any.
     pub fn sub_pages(&self, page: page::Entity) -> Option<&[page::Entity]> {
         self.sub_pages.get(page).map(AsRef::as_ref)
     }
 }
 
+/// A page which was not registered, as another page has registered its id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuplicateId {
+    pub id: &'static str,
+    /// The page which registered the id first, and is kept.
+    pub existing: page::Entity,
+}
+
+impl fmt::Display for DuplicateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "page id {:?} is registered twice, and only the first is kept",
+            self.id
+        )
+    }
+}
+
+impl std::error::Error for DuplicateId {}
+
+impl DuplicateId {
+    /// Reports a page which was left out of the pages being built.
+    ///
+    /// Debug builds panic, as registering an id twice is a mistake of the pages, which
+    /// release builds only log and carry on from with the first page.
+    pub fn report(&self) {
+        if cfg!(debug_assertions) {
+            panic!("{self}");
+        }
+
+        eprintln!("{self}");
+    }
+}
+
 pub struct Insert<'a> {
     pub model: &'a mut Model,
     pub id: page::Entity,
@@ -175,12 +581,24 @@ impl<'a> Insert<'a> {
     #[allow(clippy::return_self_not_must_use)]
     #[allow(clippy::must_use_candidate)]
     pub fn sub_page<P: Page>(self) -> Self {
-        let page = self.model.pages.insert(Meta {
+        let meta = Meta {
             parent: Some(self.id),
             ..P::page()
-        });
+        };
+
+        if let Err(why) = self.model.check_unique(&meta) {
+            why.report();
+            return self;
+        }
+
+        let page = self.model.pages.insert(meta);
 
         self.model.page_load.insert(page, P::load);
+        self.model.page_apply.insert(page, apply::<P>);
+        self.model.page_leave.insert(page, on_leave::<P>);
+        self.model.page_localize.insert(page, localize::<P>);
+        self.model.page_reset.insert(page, reset_defaults::<P>);
+        self.model.page_subscription.insert(page, subscription::<P>);
 
         if let Some(content) = P::content(&mut self.model.sections) {
             self.model.content.insert(page, content);
@@ -195,6 +613,246 @@ impl<'a> Insert<'a> {
             .and_modify(|v| v.push(page))
             .or_insert_with(|| vec![page]);
 
-        self
+        // Sub-pages may have sub-pages of their own.
+        let model = P::sub_pages(Insert {
+            model: self.model,
+            id: page,
+        })
+        .model;
+
+        Insert { model, id: self.id }
+    }
+}
+
+/// Invokes the apply hook of a page with its model.
+fn apply<P: Page>(model: &mut Model, page: page::Entity) -> Command<crate::Message> {
+    match model.resource_mut::<P::Model>() {
+        Some(resource) => P::apply(resource, page),
+        None => Command::none(),
+    }
+}
+
+/// Invokes the leave hook of a page with its model.
+fn on_leave<P: Page>(model: &mut Model, page: page::Entity) -> Command<crate::Message> {
+    match model.resource_mut::<P::Model>() {
+        Some(resource) => P::on_leave(resource, page),
+        None => Command::none(),
+    }
+}
+
+/// Replaces the text of a page and of its sections with that of the current language.
+fn localize<P: Page>(model: &mut Model, page: page::Entity) {
+    let meta = P::page();
+
+    if let Some(current) = model.pages.get_mut(page) {
+        if !model.retitled.contains_key(page) {
+            current.title = meta.title;
+        }
+
+        current.description = meta.description;
+    }
+
+    // The sections are created anew aside, and matched to the current ones by their order.
+    let mut fresh = SlotMap::with_key();
+
+    let (Some(content), Some(ids)) = (model.content.get(page), P::content(&mut fresh)) else {
+        return;
+    };
+
+    for (id, localized_id) in content.iter().zip(ids) {
+        let (Some(section), Some(localized)) =
+            (model.sections.get_mut(*id), fresh.remove(localized_id))
+        else {
+            continue;
+        };
+
+        section.title = localized.title;
+        section.subtitle = localized.subtitle;
+        section.descriptions = localized.descriptions;
+        section.buttons = localized.buttons;
+    }
+}
+
+/// Invokes the reset hook of a page with its model.
+fn reset_defaults<P: Page>(model: &mut Model, page: page::Entity) -> Command<crate::Message> {
+    match model.resource_mut::<P::Model>() {
+        Some(resource) => P::reset_defaults(resource, page),
+        None => Command::none(),
+    }
+}
+
+/// Creates the subscriptions of a page from its model.
+fn subscription<P: Page>(model: &Model) -> Subscription<crate::Message> {
+    match model.resource::<P::Model>() {
+        Some(resource) => P::subscription(resource),
+        None => Subscription::none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Display;
+
+    impl Page for Display {
+        type Model = ();
+
+        fn page() -> Meta {
+            Meta::new("display", "preferences-desktop-display-symbolic").title("Display")
+        }
+
+        fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+            Some(vec![sections.insert(Section::new().title("Brightness"))])
+        }
+    }
+
+    /// A page which claims the id of the display page.
+    struct Monitors;
+
+    impl Page for Monitors {
+        type Model = ();
+
+        fn page() -> Meta {
+            Meta::new("display", "video-display-symbolic").title("Monitors")
+        }
+    }
+
+    /// A page whose title is localized.
+    struct Desktop;
+
+    impl Page for Desktop {
+        type Model = ();
+
+        fn page() -> Meta {
+            Meta::new("desktop", "video-display-symbolic").title(fl!("desktop"))
+        }
+
+        fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+            Some(vec![sections.insert(Section::new().title("Wallpaper"))])
+        }
+    }
+
+    /// A page with a section which is only shown on hardware which is missing.
+    struct Power;
+
+    impl Page for Power {
+        type Model = ();
+
+        fn page() -> Meta {
+            Meta::new("power", "preferences-system-power-symbolic").title("Power")
+        }
+
+        fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+            Some(vec![
+                sections.insert(Section::new().title("Battery")),
+                sections.insert(
+                    Section::new()
+                        .title("Battery Charge Limit")
+                        .visible_when(|_| false),
+                ),
+            ])
+        }
+    }
+
+    fn rule(pattern: &str) -> Regex {
+        Regex::new(pattern).unwrap()
+    }
+
+    fn select_language(language: &str) {
+        let language: i18n_embed::unic_langid::LanguageIdentifier = language.parse().unwrap();
+        crate::localize::localizer().select(&[language]).unwrap();
+    }
+
+    #[test]
+    fn reindex_search_matches_new_language() {
+        let mut model = Model::default();
+        let desktop = model.register::<Desktop>().map(Insert::id).unwrap();
+        let display = model.register::<Display>().map(Insert::id).unwrap();
+        model.set_title(display, String::from("Eizo"));
+
+        select_language("fr");
+        model.reindex_search();
+        let bureau = model.sections_matching(&rule("bureau"));
+        let eizo = model.sections_matching(&rule("eizo"));
+        select_language("en");
+
+        assert_eq!(model.pages[desktop].title, "Bureau");
+        assert_eq!(bureau.len(), 1);
+        assert_eq!(bureau[0].0, desktop);
+
+        // Titles which were set since registering are not localized again.
+        assert_eq!(model.pages[display].title, "Eizo");
+        assert_eq!(eizo.len(), 1);
+        assert_eq!(eizo[0].0, display);
+    }
+
+    #[test]
+    fn search_matches_updated_title() {
+        let mut model = Model::default();
+        let display = model.register::<Display>().map(Insert::id).unwrap();
+
+        model.set_title(display, String::from("Eizo FlexScan"));
+
+        assert_eq!(model.pages[display].title, "Eizo FlexScan");
+        assert_eq!(model.find_page_by_id("display"), Some(display));
+        assert_eq!(model.sections_matching(&rule("flexscan")).len(), 1);
+        assert!(model.sections_matching(&rule("display")).is_empty());
+    }
+
+    #[test]
+    fn search_skips_hidden_sections() {
+        let mut model = Model::default();
+        let power = model.register::<Power>().map(Insert::id).unwrap();
+        let battery = model.content(power).unwrap()[0];
+
+        assert_eq!(
+            model.sections_matching(&rule("battery")),
+            [(power, battery)]
+        );
+        assert!(model.search(&rule("charge limit")).next().is_none());
+
+        // Sections of a page which matches by its title are hidden all the same.
+        assert_eq!(model.sections_matching(&rule("power")), [(power, battery)]);
+    }
+
+    #[test]
+    fn clear_removes_every_page() {
+        let mut model = Model::default();
+        let display = model.register::<Display>().map(Insert::id).unwrap();
+        model.register::<Power>().unwrap();
+        assert!(!model.content.is_empty());
+
+        model.clear();
+
+        assert!(model.pages.is_empty());
+        assert!(model.content.is_empty());
+        assert!(model.sections.is_empty());
+        assert!(model.section_state.is_empty());
+        assert!(model.sub_pages.is_empty());
+        assert_eq!(model.find_page_by_id("display"), None);
+
+        // Entities of the removed pages do not refer to pages registered since.
+        let registered = model.register::<Display>().map(Insert::id).unwrap();
+        assert_ne!(registered, display);
+        assert!(!model.contains_item(display));
+    }
+
+    #[test]
+    fn duplicate_id_is_reported() {
+        let mut model = Model::default();
+        let display = model.register::<Display>().map(Insert::id).unwrap();
+
+        assert_eq!(
+            model.register::<Monitors>().map(Insert::id),
+            Err(DuplicateId {
+                id: "display",
+                existing: display,
+            })
+        );
+
+        assert_eq!(model.pages.len(), 1);
+        assert_eq!(model.pages[display].title, "Display");
+        assert_eq!(model.find_page_by_id("display"), Some(display));
     }
 }
@@ -1,10 +1,22 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::page;
+use slotmap::SlotMap;
 
-pub fn page() -> page::Meta {
-    page::Meta::new("online-accounts", "goa-panel-symbolic")
-        .title(fl!("online-accounts"))
-        .description(fl!("online-accounts", "desc"))
+use crate::page::{self, section, Content, Section};
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = ();
+
+    fn page() -> page::Meta {
+        page::Meta::new("online-accounts", "goa-panel-symbolic")
+            .title(fl!("online-accounts"))
+            .description(fl!("online-accounts", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(Section::new())])
+    }
 }
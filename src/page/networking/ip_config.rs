@@ -0,0 +1,600 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, container, horizontal_space, pick_list, row, text_input},
+        Alignment, Background, Command, Length,
+    },
+    theme,
+    widget::{icon, settings, text},
+    Element,
+};
+use cosmic_settings_network::ip::{self, Family, IpConfig, Method};
+use slotmap::SlotMap;
+use std::fmt;
+
+use crate::{
+    page::{self, section, Content, Section},
+    widget::{
+        segmented::{segmented, Segment},
+        toast::Toast,
+    },
+};
+
+/// A saved connection whose IP settings are edited.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IpConnection {
+    pub uuid: String,
+    pub name: String,
+    /// The device which the connection is active on, which the settings are applied to.
+    pub device: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    AddAddress,
+    AddDns,
+    AddressChanged(usize, String),
+    Applied(Result<(), String>),
+    DnsChanged(usize, String),
+    Discard,
+    GatewayChanged(String),
+    /// Applies the staged IPv4 and IPv6 settings of a connection at once.
+    IpConfigApplied(String, IpConfig, IpConfig),
+    Load(page::Entity),
+    Loaded(Result<(IpConfig, IpConfig), String>),
+    MethodChanged(Method),
+    NetmaskChanged(usize, String),
+    /// Edits the IP settings of a connection, once the page is opened with [`open`].
+    Open(IpConnection),
+    RemoveAddress(usize),
+    RemoveDns(usize),
+    Tab(Family),
+}
+
+/// An address of a draft, with its netmask typed separately.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressRow {
+    pub address: String,
+    pub netmask: String,
+}
+
+/// The settings of a family of addresses as they are edited, which never reach
+/// `NetworkManager` until both families are applied together.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Draft {
+    pub method: Method,
+    pub addresses: Vec<AddressRow>,
+    pub gateway: String,
+    pub dns: Vec<String>,
+}
+
+impl Draft {
+    fn new(family: Family, config: &IpConfig) -> Self {
+        let addresses = config
+            .addresses
+            .iter()
+            .map(|cidr| {
+                let (address, prefix) = cidr.split_once('/').unwrap_or((cidr, ""));
+
+                // IPv4 netmasks are shown the way that they are most often given.
+                let netmask = match (family, prefix.parse::<u8>()) {
+                    (Family::V4, Ok(prefix)) => ip::netmask(prefix).to_string(),
+                    _ => prefix.to_owned(),
+                };
+
+                AddressRow {
+                    address: address.to_owned(),
+                    netmask,
+                }
+            })
+            .collect();
+
+        Self {
+            method: config.method,
+            addresses,
+            gateway: config.gateway.clone(),
+            dns: config.dns.clone(),
+        }
+    }
+
+    /// Whether an address row is valid, which empty rows are, as they are left out.
+    fn is_valid_row(family: Family, row: &AddressRow) -> bool {
+        if row.address.trim().is_empty() && row.netmask.trim().is_empty() {
+            return true;
+        }
+
+        ip::is_valid_address(family, &row.address) && ip::prefix_len(family, &row.netmask).is_some()
+    }
+
+    /// The config which the draft is applied as, if every row of it is valid.
+    fn config(&self, family: Family) -> Option<IpConfig> {
+        let mut addresses = Vec::with_capacity(self.addresses.len());
+
+        for row in &self.addresses {
+            if row.address.trim().is_empty() && row.netmask.trim().is_empty() {
+                continue;
+            }
+
+            let prefix = ip::prefix_len(family, &row.netmask)?;
+            addresses.push(format!("{}/{prefix}", row.address.trim()));
+        }
+
+        let config = IpConfig {
+            method: self.method,
+            addresses,
+            gateway: self.gateway.trim().to_owned(),
+            dns: self
+                .dns
+                .iter()
+                .map(|server| server.trim())
+                .filter(|server| !server.is_empty())
+                .map(String::from)
+                .collect(),
+        };
+
+        config.validate(family).ok().map(|_| config)
+    }
+}
+
+pub struct Model {
+    pub connection: Option<IpConnection>,
+    /// The settings as they were loaded, and the drafts of them, by family.
+    pub saved: [Draft; 2],
+    pub drafts: [Draft; 2],
+    pub tab: Family,
+    pub tabs: Vec<Segment<Family>>,
+    /// The drafts which are being applied, which are saved once they were.
+    pub applying: Option<[Draft; 2]>,
+    pub error: Option<String>,
+    pub page: page::Entity,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            connection: None,
+            saved: Default::default(),
+            drafts: Default::default(),
+            tab: Family::V4,
+            tabs: vec![
+                Segment::new(Family::V4, fl!("ip-config", "ipv4")),
+                Segment::new(Family::V6, fl!("ip-config", "ipv6")),
+            ],
+            applying: None,
+            error: None,
+            page: page::Entity::default(),
+        }
+    }
+}
+
+/// Opens the IP settings of a connection, such as from the details of a wired or Wi-Fi network.
+pub fn open(connection: IpConnection) -> Command<crate::Message> {
+    Command::batch(vec![
+        Command::perform(async move { connection }, |connection| {
+            crate::Message::IpConfig(Message::Open(connection))
+        }),
+        Command::perform(async {}, |_| crate::Message::OpenPage("ip-config")),
+    ])
+}
+
+impl Model {
+    fn draft(&mut self) -> &mut Draft {
+        &mut self.drafts[self.tab as usize]
+    }
+
+    /// Handles a message, and then tells the app whether the drafts differ from the settings.
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        let command = self.handle(message);
+        Command::batch(vec![
+            command,
+            page::mark_dirty(self.page, self.drafts != self.saved),
+        ])
+    }
+
+    fn handle(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::AddAddress => self.draft().addresses.push(AddressRow::default()),
+
+            Message::AddDns => self.draft().dns.push(String::new()),
+
+            Message::AddressChanged(id, address) => {
+                if let Some(row) = self.draft().addresses.get_mut(id) {
+                    row.address = address;
+                }
+            }
+
+            Message::Applied(Ok(())) => {
+                if let Some(applied) = self.applying.take() {
+                    self.saved = applied;
+                }
+
+                let toast = Toast::success(fl!("ip-config", "applied"));
+                return Command::perform(async move { toast }, crate::Message::ShowToast);
+            }
+
+            Message::Applied(Err(why)) => {
+                self.applying = None;
+                self.error = Some(why);
+            }
+
+            Message::DnsChanged(id, server) => {
+                if let Some(dns) = self.draft().dns.get_mut(id) {
+                    *dns = server;
+                }
+            }
+
+            Message::Discard => {
+                self.drafts = self.saved.clone();
+                self.error = None;
+            }
+
+            Message::GatewayChanged(gateway) => self.draft().gateway = gateway,
+
+            Message::IpConfigApplied(uuid, ipv4, ipv6) => {
+                return self.apply_configs(uuid, ipv4, ipv6);
+            }
+
+            Message::Load(page) => self.page = page,
+
+            Message::Loaded(Ok((ipv4, ipv6))) => {
+                self.saved = [Draft::new(Family::V4, &ipv4), Draft::new(Family::V6, &ipv6)];
+                self.drafts = self.saved.clone();
+            }
+
+            Message::Loaded(Err(why)) => self.error = Some(why),
+
+            Message::MethodChanged(method) => self.draft().method = method,
+
+            Message::NetmaskChanged(id, netmask) => {
+                if let Some(row) = self.draft().addresses.get_mut(id) {
+                    row.netmask = netmask;
+                }
+            }
+
+            Message::Open(connection) => {
+                let uuid = connection.uuid.clone();
+
+                self.connection = Some(connection);
+                self.saved = Default::default();
+                self.drafts = Default::default();
+                self.tab = Family::V4;
+                self.error = None;
+
+                return Command::perform(page::background(move || ip::config(&uuid)), |result| {
+                    let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                    crate::Message::IpConfig(Message::Loaded(result))
+                });
+            }
+
+            Message::RemoveAddress(id) => {
+                let addresses = &mut self.draft().addresses;
+
+                if id < addresses.len() {
+                    addresses.remove(id);
+                }
+            }
+
+            Message::RemoveDns(id) => {
+                let dns = &mut self.draft().dns;
+
+                if id < dns.len() {
+                    dns.remove(id);
+                }
+            }
+
+            Message::Tab(family) => self.tab = family,
+        }
+
+        Command::none()
+    }
+
+    /// Applies the IPv4 and IPv6 settings of a connection, as the drafts are.
+    fn apply_configs(
+        &mut self,
+        uuid: String,
+        ipv4: IpConfig,
+        ipv6: IpConfig,
+    ) -> Command<crate::Message> {
+        let device = self
+            .connection
+            .as_ref()
+            .and_then(|connection| connection.device.clone());
+
+        self.applying = Some(self.drafts.clone());
+        self.error = None;
+
+        Command::perform(
+            page::background(move || ip::set_config(&uuid, device.as_deref(), &ipv4, &ipv6)),
+            |result| {
+                let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                crate::Message::IpConfig(Message::Applied(result))
+            },
+        )
+    }
+
+    /// Applies the drafts, unless a row of them is invalid, which is shown instead.
+    fn apply_drafts(&mut self) -> Command<crate::Message> {
+        let configs = (
+            self.connection.as_ref(),
+            self.drafts[0].config(Family::V4),
+            self.drafts[1].config(Family::V6),
+        );
+
+        match configs {
+            (Some(connection), Some(ipv4), Some(ipv6)) => {
+                let uuid = connection.uuid.clone();
+                self.update(Message::IpConfigApplied(uuid, ipv4, ipv6))
+            }
+
+            _ => {
+                let toast = Toast::error(fl!("ip-config", "not-applied"));
+                Command::perform(async move { toast }, crate::Message::ShowToast)
+            }
+        }
+    }
+}
+
+/// A method listed in a dropdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct MethodOption(pub Method);
+
+impl fmt::Display for MethodOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.0 {
+            Method::Auto => fl!("wired-ip", "auto"),
+            Method::Manual => fl!("wired-ip", "manual"),
+            Method::LinkLocal => fl!("wired-ip", "link-local"),
+            Method::Disabled => fl!("wired-ip", "disabled"),
+        };
+
+        f.write_str(&label)
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("ip-config", "preferences-system-network-symbolic")
+            .title(fl!("ip-config"))
+            .description(fl!("ip-config", "desc"))
+            .hidden(true)
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(settings())])
+    }
+
+    fn load(page: page::Entity) -> page::PageTask {
+        Box::pin(async move { crate::Message::IpConfig(Message::Load(page)) })
+    }
+
+    fn apply(model: &mut Self::Model, _page: page::Entity) -> Command<crate::Message> {
+        model.apply_drafts()
+    }
+
+    /// Drafts which were neither applied nor discarded are dropped when the page is left,
+    /// while those which are being applied are kept until they were.
+    fn on_leave(model: &mut Self::Model, _page: page::Entity) -> Command<crate::Message> {
+        model.drafts = model
+            .applying
+            .clone()
+            .unwrap_or_else(|| model.saved.clone());
+        model.error = None;
+        page::mark_dirty(model.page, model.drafts != model.saved)
+    }
+}
+
+fn settings() -> Section {
+    Section::new()
+        .title(fl!("ip-config"))
+        .descriptions(vec![
+            fl!("ip-config", "none"),
+            fl!("wired-ip", "method"),
+            fl!("ip-config", "addresses"),
+            fl!("ip-config", "address"),
+            fl!("ip-config", "netmask"),
+            fl!("ip-config", "prefix"),
+            fl!("wired-ip", "gateway"),
+            fl!("wired-ip", "dns"),
+            fl!("ip-config", "add"),
+            fl!("ip-config", "remove"),
+            fl!("wired-ip", "apply"),
+            fl!("ip-config", "discard"),
+            fl!("ip-config", "invalid"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("ip config model is missing");
+
+            let desc = &section.descriptions;
+
+            let Some(connection) = &model.connection else {
+                return settings::view_section(&section.title)
+                    .add(text(&desc[0]))
+                    .apply(Element::from)
+                    .map(crate::Message::IpConfig);
+            };
+
+            let family = model.tab;
+            let draft = &model.drafts[family as usize];
+
+            let mut methods: Vec<MethodOption> = Method::ALL.map(MethodOption).to_vec();
+
+            // A method chosen elsewhere is listed, so that the dropdown shows it.
+            if !Method::ALL.contains(&draft.method) {
+                methods.push(MethodOption(draft.method));
+            }
+
+            let mut view = settings::view_section(&connection.name)
+                .add(segmented(&model.tabs, Some(&model.tab), Message::Tab))
+                .add(settings::item(
+                    &desc[1],
+                    pick_list(methods, Some(MethodOption(draft.method)), |method| {
+                        Message::MethodChanged(method.0)
+                    }),
+                ));
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            // Addresses are only given by hand with the manual method.
+            if draft.method == Method::Manual {
+                let netmask = match family {
+                    Family::V4 => desc[4].as_str(),
+                    Family::V6 => desc[5].as_str(),
+                };
+
+                view = view.add(text(&desc[2]).size(14));
+
+                for (id, row) in draft.addresses.iter().enumerate() {
+                    let inputs = row!(
+                        text_input(&desc[3], &row.address, move |address| {
+                            Message::AddressChanged(id, address)
+                        })
+                        .padding(8)
+                        .width(Length::FillPortion(3)),
+                        text_input(netmask, &row.netmask, move |netmask| {
+                            Message::NetmaskChanged(id, netmask)
+                        })
+                        .padding(8)
+                        .width(Length::FillPortion(2)),
+                        remove_button(&desc[9], Message::RemoveAddress(id)),
+                    )
+                    .spacing(8)
+                    .align_items(Alignment::Center);
+
+                    view = view.add(highlight(inputs, Draft::is_valid_row(family, row)));
+                }
+
+                view = view
+                    .add(add_button(&desc[8], Message::AddAddress))
+                    .add(highlight(
+                        settings::item(
+                            &desc[6],
+                            text_input("", &draft.gateway, Message::GatewayChanged)
+                                .padding(8)
+                                .width(Length::Units(250)),
+                        ),
+                        draft.gateway.trim().is_empty()
+                            || ip::is_valid_address(family, &draft.gateway),
+                    ));
+            }
+
+            view = view.add(text(&desc[7]).size(14));
+
+            for (id, server) in draft.dns.iter().enumerate() {
+                let input = row!(
+                    text_input("", server, move |server| Message::DnsChanged(id, server))
+                        .padding(8)
+                        .width(Length::Fill),
+                    remove_button(&desc[9], Message::RemoveDns(id)),
+                )
+                .spacing(8)
+                .align_items(Alignment::Center);
+
+                let valid = server.trim().is_empty() || ip::is_valid_address(family, server);
+                view = view.add(highlight(input, valid));
+            }
+
+            view = view.add(add_button(&desc[8], Message::AddDns));
+
+            let configs = (
+                model.drafts[0].config(Family::V4),
+                model.drafts[1].config(Family::V6),
+            );
+
+            let mut buttons = row!(horizontal_space(Length::Fill)).spacing(8);
+
+            if model.drafts != model.saved {
+                buttons = buttons.push(
+                    button(text(&desc[11]))
+                        .style(theme::Button::Secondary)
+                        .on_press(Message::Discard),
+                );
+            }
+
+            let mut apply = button(text(&desc[10])).style(theme::Button::Primary);
+
+            // Both families are applied together, once neither of them has an invalid row.
+            match configs {
+                (Some(ipv4), Some(ipv6))
+                    if model.applying.is_none() && model.drafts != model.saved =>
+                {
+                    apply = apply.on_press(Message::IpConfigApplied(
+                        connection.uuid.clone(),
+                        ipv4,
+                        ipv6,
+                    ));
+                }
+
+                (None, _) | (_, None) => view = view.add(text(&desc[12]).size(12)),
+
+                _ => (),
+            }
+
+            view.add(buttons.push(apply))
+                .apply(Element::from)
+                .map(crate::Message::IpConfig)
+        })
+}
+
+fn add_button(label: &str, message: Message) -> Element<Message> {
+    row!(
+        icon("list-add-symbolic", 16).style(theme::Svg::Symbolic),
+        text(label)
+    )
+    .spacing(8)
+    .align_items(Alignment::Center)
+    .apply(button)
+    .style(theme::Button::Secondary)
+    .on_press(message)
+    .into()
+}
+
+fn remove_button(label: &str, message: Message) -> Element<Message> {
+    cosmic::iced::widget::tooltip(
+        icon("list-remove-symbolic", 16)
+            .style(theme::Svg::Symbolic)
+            .apply(button)
+            .padding(8)
+            .style(theme::Button::Transparent)
+            .on_press(message),
+        label,
+        cosmic::iced::widget::tooltip::Position::Top,
+    )
+    .style(theme::Container::Box)
+    .into()
+}
+
+/// Outlines a row which is invalid, so that it is found before the settings are applied.
+fn highlight<'a>(content: impl Into<Element<'a, Message>>, valid: bool) -> Element<'a, Message> {
+    if valid {
+        return content.into();
+    }
+
+    container(content)
+        .padding(4)
+        .style(theme::Container::Custom(invalid_style))
+        .into()
+}
+
+fn invalid_style(theme: &cosmic::Theme) -> container::Appearance {
+    let danger = theme.palette().danger;
+
+    container::Appearance {
+        background: Some(Background::Color(cosmic::iced::Color { a: 0.1, ..danger })),
+        border_radius: 8.0,
+        border_width: 1.0,
+        border_color: danger,
+        ..container::Appearance::default()
+    }
+}
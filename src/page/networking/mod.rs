@@ -2,4 +2,242 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 pub mod accounts;
+pub mod ip_config;
+pub mod wifi;
 pub mod wired;
+
+use apply::Apply;
+use bytecheck::CheckBytes;
+use cosmic::{
+    iced::{Command, Subscription},
+    widget::{settings, text, toggler},
+    Element,
+};
+use cosmic_settings_network::rfkill::{self, Radio, RadioState};
+use rkyv::{Archive, Deserialize, Serialize};
+use slotmap::SlotMap;
+
+use crate::{
+    config,
+    page::{self, section, Content, Section},
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    AirplaneModeChanged(bool),
+    Applied(Result<(), String>),
+    Loaded(Config),
+    RadioToggled(Radio, bool),
+    Radios(Result<Vec<RadioState>, String>),
+    Refresh,
+}
+
+/// Radios which were enabled before airplane mode was turned on, which are kept between
+/// sessions so that they are restored even after a restart.
+#[must_use]
+#[derive(Archive, Clone, Debug, Default, Deserialize, Serialize)]
+#[archive_attr(derive(CheckBytes, Debug))]
+pub struct Config {
+    pub restore: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub config: Config,
+    pub radios: Vec<RadioState>,
+    pub error: Option<String>,
+}
+
+impl Model {
+    /// Whether every radio is blocked, whether by the switch here or by a hardware switch.
+    #[must_use]
+    pub fn airplane_mode(&self) -> bool {
+        !self.radios.is_empty() && self.radios.iter().all(RadioState::blocked)
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::AirplaneModeChanged(true) => {
+                let radios: Vec<Radio> = self.radios.iter().map(|state| state.radio).collect();
+
+                self.config.restore = self
+                    .radios
+                    .iter()
+                    .filter(|state| !state.soft_blocked)
+                    .map(|state| String::from(state.radio.kind()))
+                    .collect();
+
+                let config = self.config.clone();
+
+                return apply(move || {
+                    config::store("networking", &config);
+                    radios
+                        .into_iter()
+                        .try_for_each(|radio| rfkill::set_blocked(radio, true))
+                });
+            }
+
+            Message::AirplaneModeChanged(false) => {
+                let restore: Vec<Radio> = self
+                    .config
+                    .restore
+                    .iter()
+                    .filter_map(|kind| Radio::from_kind(kind))
+                    .collect();
+
+                // Without radios to restore, such as when airplane mode was turned on by a
+                // hardware switch, every radio is enabled.
+                let radios: Vec<Radio> = if restore.is_empty() {
+                    self.radios.iter().map(|state| state.radio).collect()
+                } else {
+                    restore
+                };
+
+                self.config.restore.clear();
+                let config = self.config.clone();
+
+                return apply(move || {
+                    config::store("networking", &config);
+                    radios
+                        .into_iter()
+                        .try_for_each(|radio| rfkill::set_blocked(radio, false))
+                });
+            }
+
+            Message::Applied(result) => {
+                self.error = result.err();
+                return refresh();
+            }
+
+            Message::Loaded(config) => {
+                self.config = config;
+                return refresh();
+            }
+
+            Message::RadioToggled(radio, enabled) => {
+                if let Some(state) = self.radios.iter_mut().find(|state| state.radio == radio) {
+                    state.soft_blocked = !enabled;
+                }
+
+                return apply(move || rfkill::set_blocked(radio, !enabled));
+            }
+
+            Message::Radios(Ok(radios)) => self.radios = radios,
+
+            Message::Radios(Err(why)) => self.error = Some(why),
+
+            Message::Refresh => return refresh(),
+        }
+
+        Command::none()
+    }
+}
+
+fn apply(func: impl FnOnce() -> Result<(), String> + Send + 'static) -> Command<crate::Message> {
+    Command::perform(page::background(func), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Networking(Message::Applied(result))
+    })
+}
+
+fn refresh() -> Command<crate::Message> {
+    Command::perform(page::background(rfkill::radios), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Networking(Message::Radios(result))
+    })
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("networking", "preferences-system-network-symbolic")
+            .title(fl!("networking"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(airplane_mode())])
+    }
+
+    fn sub_pages(page: page::Insert) -> page::Insert {
+        page.sub_page::<wifi::Page>()
+            .sub_page::<wired::Page>()
+            .sub_page::<accounts::Page>()
+            .sub_page::<ip_config::Page>()
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let config = page::background(|| config::load::<Config>("networking")).await;
+            crate::Message::Networking(Message::Loaded(config.unwrap_or_default()))
+        })
+    }
+
+    fn subscription(_model: &Model) -> Subscription<crate::Message> {
+        // Radios are added, removed, and blocked by hardware switches at any time. The
+        // current radios are also printed as soon as the command starts.
+        crate::subscription::lines("rfkill-events", rfkill::events_command(), |_| {
+            Some(crate::Message::Networking(Message::Refresh))
+        })
+    }
+}
+
+fn airplane_mode() -> Section {
+    Section::new()
+        .title(fl!("airplane-mode"))
+        .descriptions(vec![
+            fl!("airplane-mode", "desc"),
+            fl!("airplane-mode", "wlan"),
+            fl!("airplane-mode", "bluetooth"),
+            fl!("airplane-mode", "wwan"),
+            fl!("airplane-mode", "hardware"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("networking model is missing");
+
+            let desc = &section.descriptions;
+            let airplane_mode = model.airplane_mode();
+
+            let mut view = settings::view_section(&section.title).add(
+                settings::item::builder(&section.title)
+                    .description(&desc[0])
+                    .control(toggler(None, airplane_mode, Message::AirplaneModeChanged)),
+            );
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            // Radios are only switched on their own while airplane mode is off.
+            if !airplane_mode {
+                for state in &model.radios {
+                    let radio = state.radio;
+
+                    let title = match radio {
+                        Radio::Wlan => &desc[1],
+                        Radio::Bluetooth => &desc[2],
+                        Radio::Wwan => &desc[3],
+                    };
+
+                    let mut item = settings::item::builder(title);
+
+                    if state.hard_blocked {
+                        item = item.description(&desc[4]);
+                    }
+
+                    view = view.add(item.control(toggler(
+                        None,
+                        !state.blocked(),
+                        move |enabled| Message::RadioToggled(radio, enabled),
+                    )));
+                }
+            }
+
+            view.apply(Element::from).map(crate::Message::Networking)
+        })
+}
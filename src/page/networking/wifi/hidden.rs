@@ -0,0 +1,267 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, container, horizontal_space, pick_list, row, text_input},
+        Alignment, Command, Length,
+    },
+    theme,
+    widget::{icon, settings, text},
+    Element,
+};
+use cosmic_settings_network::wifi::{self, ConnectError, HiddenNetwork, Security};
+use slotmap::SlotMap;
+use std::fmt;
+
+use crate::{
+    page::{self, section, Content, Section},
+    widget::{
+        secure_input::{self, SecureInput},
+        Toast,
+    },
+};
+
+/// SSIDs are at most 32 bytes long.
+const SSID_MAX_LEN: usize = 32;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    /// Creates a connection to a hidden network, and connects to it.
+    ConnectHidden(HiddenNetwork),
+    Connected(Result<(), ConnectError>),
+    Password(secure_input::Message),
+    SecurityChanged(Security),
+    SsidChanged(String),
+}
+
+pub struct Model {
+    pub ssid: String,
+    pub security: Security,
+    pub password: SecureInput,
+    pub connecting: bool,
+    pub error: Option<String>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            ssid: String::new(),
+            security: Security::default(),
+            password: secure_input::secure_input(""),
+            connecting: false,
+            error: None,
+        }
+    }
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::ConnectHidden(network) => {
+                if self.connecting {
+                    return Command::none();
+                }
+
+                self.connecting = true;
+                self.error = None;
+
+                return Command::perform(
+                    page::background(move || wifi::connect_hidden(&network)),
+                    |result| {
+                        let result =
+                            result.unwrap_or_else(|| Err(ConnectError::Failed(fl!("unknown"))));
+                        crate::Message::WifiHidden(Message::Connected(result))
+                    },
+                );
+            }
+
+            Message::Connected(result) => {
+                self.connecting = false;
+
+                match result {
+                    Ok(()) => {
+                        let toast = Toast::success(fl!(
+                            "wifi-hidden",
+                            "connected",
+                            ssid = self.ssid.as_str()
+                        ));
+                        self.ssid.clear();
+                        self.password.clear();
+
+                        return Command::perform(async move { toast }, crate::Message::ShowToast);
+                    }
+
+                    Err(ConnectError::Authentication) => {
+                        self.error = Some(fl!("wifi-hidden", "auth-failed"));
+                        return self.password.focus();
+                    }
+
+                    Err(ConnectError::Failed(why)) => self.error = Some(why),
+                }
+            }
+
+            Message::Password(message) => {
+                if let Some(secure_input::Output::Submit) = self.password.update(message) {
+                    if let Some(network) = self.network() {
+                        return self.update(Message::ConnectHidden(network));
+                    }
+                }
+            }
+
+            Message::SecurityChanged(security) => self.security = security,
+
+            Message::SsidChanged(ssid) => self.ssid = ssid,
+        }
+
+        Command::none()
+    }
+
+    /// The network which is connected to, once its SSID and password are valid.
+    fn network(&self) -> Option<HiddenNetwork> {
+        let password = self.password.value().expose();
+
+        let valid = !self.ssid.trim().is_empty()
+            && self.ssid.len() <= SSID_MAX_LEN
+            && self.security.accepts(password);
+
+        valid.then(|| HiddenNetwork {
+            ssid: self.ssid.clone(),
+            security: self.security,
+            password: if self.security == Security::None {
+                String::new()
+            } else {
+                password.to_owned()
+            },
+        })
+    }
+}
+
+/// A security type listed in a dropdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SecurityOption(Security);
+
+impl fmt::Display for SecurityOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.0 {
+            Security::None => fl!("wifi-hidden", "security-none"),
+            Security::Wep => fl!("wifi-hidden", "security-wep"),
+            Security::WpaPersonal => fl!("wifi-hidden", "security-wpa"),
+            Security::Wpa3Personal => fl!("wifi-hidden", "security-wpa3"),
+        };
+
+        f.write_str(&label)
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("wifi-hidden", "network-wireless-hidden-symbolic")
+            .title(fl!("wifi-hidden"))
+            .description(fl!("wifi-hidden", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(hidden_network())])
+    }
+}
+
+fn hidden_network() -> Section {
+    Section::new()
+        .title(fl!("wifi-hidden", "network"))
+        .descriptions(vec![
+            fl!("wifi-hidden", "ssid"),
+            fl!("wifi-hidden", "security"),
+            fl!("wifi-hidden", "password"),
+            fl!("wifi-hidden", "connect"),
+            fl!("wifi-hidden", "connecting"),
+            fl!("wifi-hidden", "wep-hint"),
+            fl!("wifi-hidden", "wpa-hint"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("hidden network model is missing");
+
+            let desc = &section.descriptions;
+
+            let ssid = text_input("", &model.ssid, Message::SsidChanged)
+                .padding(8)
+                .width(Length::Units(240));
+
+            let security = pick_list(
+                Security::ALL.map(SecurityOption).to_vec(),
+                Some(SecurityOption(model.security)),
+                |security| Message::SecurityChanged(security.0),
+            );
+
+            let mut view = settings::view_section(&section.title)
+                .add(settings::item(&desc[0], ssid))
+                .add(settings::item(&desc[1], security));
+
+            if model.security != Security::None {
+                let password = model.password.value().expose();
+                let mut item = settings::item::builder(&desc[2]);
+
+                // Only hints at the expected password once one was typed.
+                if !password.is_empty() && !model.security.accepts(password) {
+                    item = item.description(if model.security == Security::Wep {
+                        &desc[5]
+                    } else {
+                        &desc[6]
+                    });
+                }
+
+                view = view.add(
+                    item.control(
+                        model
+                            .password
+                            .view()
+                            .map(Message::Password)
+                            .apply(container)
+                            .width(Length::Units(240)),
+                    ),
+                );
+            }
+
+            let status: Element<_> = if model.connecting {
+                row!(
+                    icon("process-working-symbolic", 16).style(theme::Svg::Symbolic),
+                    text(&desc[4]),
+                )
+                .spacing(8)
+                .align_items(Alignment::Center)
+                .into()
+            } else if let Some(why) = &model.error {
+                row!(
+                    icon("dialog-error-symbolic", 16).style(theme::Svg::Symbolic),
+                    text(why),
+                )
+                .spacing(8)
+                .align_items(Alignment::Center)
+                .into()
+            } else {
+                horizontal_space(Length::Shrink).into()
+            };
+
+            let mut connect = button(text(&desc[3])).style(theme::Button::Primary);
+
+            if let Some(network) = model.network().filter(|_| !model.connecting) {
+                connect = connect.on_press(Message::ConnectHidden(network));
+            }
+
+            view.add(
+                row!(status, horizontal_space(Length::Fill), connect)
+                    .spacing(16)
+                    .align_items(Alignment::Center),
+            )
+            .apply(Element::from)
+            .map(crate::Message::WifiHidden)
+        })
+}
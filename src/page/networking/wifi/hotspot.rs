@@ -0,0 +1,376 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, container, horizontal_space, image, pick_list, row, text_input},
+        Alignment, Command, Length,
+    },
+    theme,
+    widget::{settings, text, toggler},
+    Element,
+};
+use cosmic_settings_network::hotspot::{self, Band, Hotspot, HotspotConfig, Invalid};
+use slotmap::SlotMap;
+use std::fmt;
+
+use crate::{
+    page::{self, section, Content, Section},
+    widget::{
+        dialog::{self, dialog},
+        qr_code,
+        secure_input::{self, SecureInput},
+    },
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    BandChanged(Band),
+    /// Disconnecting from the current network to start the hotspot was confirmed, or not.
+    ConfirmEnable(dialog::Response),
+    /// Saves the config of the hotspot, restarting it if it is broadcasting.
+    HotspotConfigChanged(HotspotConfig),
+    HotspotToggled(bool),
+    Loaded(Result<Hotspot, String>),
+    Password(secure_input::Message),
+    QrCode(Result<image::Handle, String>),
+    SsidChanged(String),
+}
+
+pub struct Model {
+    pub hotspot: Hotspot,
+    /// The config as it is edited, which is only saved once it is valid.
+    pub ssid: String,
+    pub password: SecureInput,
+    pub band: Band,
+    /// Whether disconnecting from the current network is waiting to be confirmed.
+    pub confirming: bool,
+    pub applying: bool,
+    /// The code which phones join the hotspot with, while it is broadcasting.
+    pub qr_code: Option<image::Handle>,
+    pub error: Option<String>,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        Self {
+            hotspot: Hotspot::default(),
+            ssid: String::new(),
+            password: secure_input::secure_input(""),
+            band: Band::default(),
+            confirming: false,
+            applying: false,
+            qr_code: None,
+            error: None,
+        }
+    }
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => {
+                self.applying = false;
+                self.error = result.err();
+                return load();
+            }
+
+            Message::BandChanged(band) => self.band = band,
+
+            Message::ConfirmEnable(response) => {
+                self.confirming = false;
+
+                if response.action == dialog::Action::Primary {
+                    return self.enable();
+                }
+            }
+
+            Message::HotspotConfigChanged(config) => {
+                let restart = self.hotspot.active;
+                return self.apply(move || hotspot::set_config(&config, restart));
+            }
+
+            Message::HotspotToggled(true) => {
+                if self.config().is_none() {
+                    self.error = Some(fl!("hotspot", "incomplete"));
+                    return Command::none();
+                }
+
+                // The Wi-Fi device can't be a client and an access point at the same time.
+                if self.hotspot.client.is_some() {
+                    self.confirming = true;
+                    return Command::none();
+                }
+
+                return self.enable();
+            }
+
+            Message::HotspotToggled(false) => {
+                self.hotspot.active = false;
+                self.qr_code = None;
+                return self.apply(|| hotspot::set_enabled(false));
+            }
+
+            Message::Loaded(Ok(hotspot)) => {
+                // The fields are only replaced once the saved config changes, so that edits
+                // which were not yet saved are kept.
+                if hotspot.config != self.hotspot.config {
+                    if let Some(config) = &hotspot.config {
+                        self.ssid = config.ssid.clone();
+                        self.password.set_value(config.password.clone());
+                        self.band = config.band;
+                    }
+                }
+
+                let payload = hotspot
+                    .config
+                    .as_ref()
+                    .filter(|_| hotspot.active)
+                    .map(HotspotConfig::qr_payload);
+
+                self.hotspot = hotspot;
+
+                let Some(payload) = payload else {
+                    self.qr_code = None;
+                    return Command::none();
+                };
+
+                return Command::perform(
+                    page::background(move || qr_code::encode(&payload)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::WifiHotspot(Message::QrCode(result))
+                    },
+                );
+            }
+
+            Message::Loaded(Err(why)) => self.error = Some(why),
+
+            Message::Password(message) => {
+                if let Some(secure_input::Output::Submit) = self.password.update(message) {
+                    if let Some(config) = self.config().filter(|_| self.changed()) {
+                        return self.update(Message::HotspotConfigChanged(config));
+                    }
+                }
+            }
+
+            // Without `qrencode`, the hotspot is still joined by typing its password.
+            Message::QrCode(result) => self.qr_code = result.ok(),
+
+            Message::SsidChanged(ssid) => self.ssid = ssid,
+        }
+
+        Command::none()
+    }
+
+    /// The config as it is edited, once it is valid.
+    fn config(&self) -> Option<HotspotConfig> {
+        let config = HotspotConfig {
+            ssid: self.ssid.clone(),
+            password: self.password.value().expose().to_owned(),
+            band: self.band,
+        };
+
+        config.validate().is_ok().then_some(config)
+    }
+
+    /// Whether the config was edited since it was saved.
+    fn changed(&self) -> bool {
+        self.hotspot.config.as_ref().map_or(true, |config| {
+            config.ssid != self.ssid
+                || config.password != self.password.value().expose()
+                || config.band != self.band
+        })
+    }
+
+    /// Saves the config as it is edited, and starts the hotspot with it.
+    fn enable(&mut self) -> Command<crate::Message> {
+        let Some(config) = self.config() else {
+            return Command::none();
+        };
+
+        self.applying = true;
+        self.apply(move || hotspot::set_config(&config, true))
+    }
+
+    fn apply(
+        &mut self,
+        func: impl FnOnce() -> Result<(), String> + Send + 'static,
+    ) -> Command<crate::Message> {
+        self.error = None;
+
+        Command::perform(page::background(func), |result| {
+            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+            crate::Message::WifiHotspot(Message::Applied(result))
+        })
+    }
+}
+
+fn load() -> Command<crate::Message> {
+    Command::perform(page::background(hotspot::hotspot), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::WifiHotspot(Message::Loaded(result))
+    })
+}
+
+/// A band listed in a dropdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct BandOption(Band);
+
+impl fmt::Display for BandOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.0 {
+            Band::Automatic => fl!("hotspot", "band-auto"),
+            Band::Ghz2 => fl!("hotspot", "band-2ghz"),
+            Band::Ghz5 => fl!("hotspot", "band-5ghz"),
+        };
+
+        f.write_str(&label)
+    }
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("wifi-hotspot", "network-wireless-hotspot-symbolic")
+            .title(fl!("hotspot"))
+            .description(fl!("hotspot", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(hotspot())])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let result = page::background(hotspot::hotspot).await;
+            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+            crate::Message::WifiHotspot(Message::Loaded(result))
+        })
+    }
+}
+
+fn hotspot() -> Section {
+    Section::new()
+        .title(fl!("hotspot"))
+        .descriptions(vec![
+            fl!("hotspot", "toggle"),
+            fl!("hotspot", "ssid"),
+            fl!("hotspot", "password"),
+            fl!("hotspot", "band"),
+            fl!("hotspot", "save"),
+            fl!("hotspot", "starting"),
+            fl!("hotspot", "qr-code"),
+            fl!("wifi-hidden", "wpa-hint"),
+            fl!("hotspot", "ssid-hint"),
+            fl!("hotspot-confirm"),
+            fl!("hotspot-confirm", "enable"),
+            fl!("hotspot-confirm", "cancel"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("hotspot model is missing");
+
+            let desc = &section.descriptions;
+
+            if model.confirming {
+                let network = model.hotspot.client.clone().unwrap_or_default();
+
+                return dialog(&desc[9], Message::ConfirmEnable)
+                    .body(fl!("hotspot-confirm", "body", network = network))
+                    .primary(&desc[10])
+                    .cancel(&desc[11])
+                    .apply(Element::from)
+                    .map(crate::Message::WifiHotspot);
+            }
+
+            let toggle = settings::item::builder(&desc[0]).control(toggler(
+                None,
+                model.hotspot.active || model.applying,
+                Message::HotspotToggled,
+            ));
+
+            let mut view = settings::view_section(&section.title).add(if model.applying {
+                toggle.description(&desc[5])
+            } else {
+                toggle
+            });
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            let draft = HotspotConfig {
+                ssid: model.ssid.clone(),
+                password: model.password.value().expose().to_owned(),
+                band: model.band,
+            };
+
+            // Only hints at what is wrong once something was typed.
+            let invalid = draft.validate().err();
+
+            let mut ssid = settings::item::builder(&desc[1]);
+
+            if invalid == Some(Invalid::Ssid) && !model.ssid.is_empty() {
+                ssid = ssid.description(&desc[8]);
+            }
+
+            let mut password = settings::item::builder(&desc[2]);
+
+            if invalid == Some(Invalid::Password) && !draft.password.is_empty() {
+                password = password.description(&desc[7]);
+            }
+
+            view = view
+                .add(
+                    ssid.control(
+                        text_input("", &model.ssid, Message::SsidChanged)
+                            .padding(8)
+                            .width(Length::Units(240)),
+                    ),
+                )
+                .add(
+                    password.control(
+                        model
+                            .password
+                            .view()
+                            .map(Message::Password)
+                            .apply(container)
+                            .width(Length::Units(240)),
+                    ),
+                )
+                .add(settings::item(
+                    &desc[3],
+                    pick_list(
+                        Band::ALL.map(BandOption).to_vec(),
+                        Some(BandOption(model.band)),
+                        |band| Message::BandChanged(band.0),
+                    ),
+                ));
+
+            if model.changed() {
+                let mut save = button(text(&desc[4])).style(theme::Button::Primary);
+
+                if let Some(config) = model.config().filter(|_| !model.applying) {
+                    save = save.on_press(Message::HotspotConfigChanged(config));
+                }
+
+                view = view.add(row!(horizontal_space(Length::Fill), save));
+            } else if let Some(code) = &model.qr_code {
+                view = view.add(
+                    row!(qr_code(code, 160), text(&desc[6]))
+                        .spacing(24)
+                        .align_items(Alignment::Center),
+                );
+            }
+
+            view.apply(Element::from).map(crate::Message::WifiHotspot)
+        })
+}
@@ -0,0 +1,384 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+pub mod hidden;
+pub mod hotspot;
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, horizontal_space, pick_list, row},
+        Command, Length,
+    },
+    theme,
+    widget::{icon, settings, text, toggler},
+    Element,
+};
+use cosmic_settings_network::wifi::{self, AccessPoint, KnownNetwork, Metered};
+use slotmap::SlotMap;
+use std::fmt;
+
+use super::ip_config::{self, IpConnection};
+use crate::{
+    page::{self, section, Content, Section},
+    widget::{expander, Confirm},
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    AutoConnectToggled(String, bool),
+    /// Asks to confirm forgetting a known network.
+    ConfirmForget(String),
+    ForgetNetwork(String),
+    Known(Result<Vec<KnownNetwork>, String>),
+    KnownChanged(Result<(), String>),
+    /// Shows or hides the details of a known network.
+    KnownExpanded(String, bool),
+    MeteredChanged(String, Metered),
+    /// Opens every IP setting of a known network on a page of its own.
+    OpenIpConfig(String),
+    Scan(page::Entity),
+    Scanned(Result<Vec<AccessPoint>, String>),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub page: page::Entity,
+    pub scanning: bool,
+    pub access_points: Vec<AccessPoint>,
+    pub error: Option<String>,
+    /// Saved connections, identified by their UUIDs.
+    pub known: Vec<KnownNetwork>,
+    /// The known network whose details are shown.
+    pub known_expanded: Option<String>,
+    pub known_error: Option<String>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::AutoConnectToggled(uuid, auto_connect) => {
+                if let Some(network) = self.known.iter_mut().find(|n| n.uuid == uuid) {
+                    network.auto_connect = auto_connect;
+                    return change(move || wifi::set_auto_connect(&uuid, auto_connect));
+                }
+            }
+
+            Message::ConfirmForget(uuid) => {
+                let Some(network) = self.known.iter().find(|n| n.uuid == uuid) else {
+                    return Command::none();
+                };
+
+                let body = if network.active {
+                    fl!("wifi-known", "forget-active")
+                } else {
+                    fl!("wifi-known", "forget-desc")
+                };
+
+                let confirm = Confirm::new(
+                    fl!(
+                        "wifi-known",
+                        "forget-title",
+                        network = network.name.as_str()
+                    ),
+                    body,
+                    fl!("wifi-known", "forget"),
+                    crate::Message::Wifi(Message::ForgetNetwork(uuid)),
+                )
+                .destructive();
+
+                return Command::perform(async {}, move |_| crate::Message::ShowConfirm(confirm));
+            }
+
+            Message::ForgetNetwork(uuid) => {
+                if let Some(network) = self.known.iter().find(|n| n.uuid == uuid).cloned() {
+                    return change(move || wifi::forget(&network));
+                }
+            }
+
+            Message::Known(result) => match result {
+                Ok(known) => {
+                    self.known = known;
+                    self.known_error = None;
+                }
+                Err(why) => self.known_error = Some(why),
+            },
+
+            Message::KnownChanged(result) => {
+                self.known_error = result.err();
+
+                // Forgetting the active network disconnects from it, so networks are rescanned.
+                return if self.scanning {
+                    load_known()
+                } else {
+                    self.update(Message::Scan(self.page))
+                };
+            }
+
+            Message::KnownExpanded(uuid, expanded) => {
+                self.known_expanded = expanded.then_some(uuid);
+            }
+
+            Message::MeteredChanged(uuid, metered) => {
+                if let Some(network) = self.known.iter_mut().find(|n| n.uuid == uuid) {
+                    network.metered = metered;
+                    let network = network.clone();
+                    return change(move || wifi::set_metered(&network, metered));
+                }
+            }
+
+            Message::OpenIpConfig(uuid) => {
+                if let Some(network) = self.known.iter().find(|n| n.uuid == uuid) {
+                    return ip_config::open(IpConnection {
+                        uuid,
+                        name: network.name.clone(),
+                        device: network.device.clone(),
+                    });
+                }
+            }
+
+            Message::Scan(page) => {
+                self.page = page;
+
+                if !self.scanning {
+                    self.scanning = true;
+
+                    return Command::batch(vec![
+                        load_known(),
+                        Command::perform(page::background(wifi::scan), |result| {
+                            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                            crate::Message::Wifi(Message::Scanned(result))
+                        }),
+                    ]);
+                }
+            }
+
+            Message::Scanned(result) => {
+                self.scanning = false;
+
+                match result {
+                    Ok(access_points) => {
+                        self.access_points = access_points;
+                        self.error = None;
+                    }
+                    Err(why) => self.error = Some(why),
+                }
+            }
+        }
+
+        Command::none()
+    }
+}
+
+fn load_known() -> Command<crate::Message> {
+    Command::perform(page::background(wifi::known), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Wifi(Message::Known(result))
+    })
+}
+
+fn change(func: impl FnOnce() -> Result<(), String> + Send + 'static) -> Command<crate::Message> {
+    Command::perform(page::background(func), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Wifi(Message::KnownChanged(result))
+    })
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("wifi", "network-wireless-symbolic")
+            .title(fl!("wifi"))
+            .description(fl!("wifi", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![
+            sections.insert(visible_networks()),
+            sections.insert(known_networks()),
+        ])
+    }
+
+    fn load(page: page::Entity) -> page::PageTask {
+        Box::pin(async move { crate::Message::Wifi(Message::Scan(page)) })
+    }
+
+    fn sub_pages(page: page::Insert) -> page::Insert {
+        page.sub_page::<hidden::Page>().sub_page::<hotspot::Page>()
+    }
+}
+
+fn visible_networks() -> Section {
+    Section::new()
+        .title(fl!("wifi-networks"))
+        .descriptions(vec![
+            fl!("wifi-networks", "scan"),
+            fl!("wifi-networks", "scanning"),
+            fl!("wifi-networks", "connected"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("wifi model is missing");
+
+            let desc = &section.descriptions;
+
+            let scan: Element<_> = if model.scanning {
+                text(&desc[1]).into()
+            } else {
+                button(text(&desc[0]))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::Scan(model.page))
+                    .into()
+            };
+
+            let mut scan_item = settings::item::builder(&desc[0]);
+
+            if let Some(ref why) = model.error {
+                scan_item = scan_item.description(why);
+            }
+
+            let mut view = settings::view_section(&section.title).add(scan_item.control(scan));
+
+            for access_point in &model.access_points {
+                let signal = text(format!("{}%", access_point.signal));
+
+                let control: Element<_> = if access_point.active {
+                    row!(text(&desc[2]), signal).spacing(16).into()
+                } else {
+                    signal.into()
+                };
+
+                let icon_name = if access_point.security.is_empty() {
+                    "network-wireless-symbolic"
+                } else {
+                    "network-wireless-encrypted-symbolic"
+                };
+
+                view = view.add(
+                    settings::item::builder(&access_point.ssid)
+                        .icon(icon(icon_name, 16).style(theme::Svg::Symbolic))
+                        .control(control),
+                );
+            }
+
+            view.apply(Element::from).map(crate::Message::Wifi)
+        })
+}
+
+fn known_networks() -> Section {
+    Section::new()
+        .title(fl!("wifi-known"))
+        .descriptions(vec![
+            fl!("wifi-known", "auto-connect"),
+            fl!("wifi-known", "forget"),
+            fl!("wifi-known", "none"),
+            fl!("wifi-networks", "connected"),
+            fl!("wifi-known", "metered"),
+            fl!("wifi-known", "metered-desc"),
+            fl!("wifi-known", "detected-metered"),
+            fl!("wifi-known", "detected-unmetered"),
+            fl!("ip-config", "open"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("wifi model is missing");
+
+            let desc = &section.descriptions;
+
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.known_error {
+                view = view.add(text(why));
+            }
+
+            if model.known.is_empty() {
+                view = view.add(text(&desc[2]));
+            }
+
+            for network in &model.known {
+                let expanded = model.known_expanded.as_ref() == Some(&network.uuid);
+                let subtitle = if network.active { desc[3].as_str() } else { "" };
+                let uuid = network.uuid.clone();
+
+                let mut details = expander(&network.name, subtitle)
+                    .expanded(expanded)
+                    .on_toggle(move |expanded| Message::KnownExpanded(uuid.clone(), expanded));
+
+                if expanded {
+                    details = details.content(known_network_details(network, desc));
+                }
+
+                view = view.add(details);
+            }
+
+            view.apply(Element::from).map(crate::Message::Wifi)
+        })
+}
+
+/// The settings of a known network, shown once it is expanded.
+fn known_network_details<'a>(
+    network: &'a KnownNetwork,
+    desc: &'a [String],
+) -> Element<'a, Message> {
+    let uuid = network.uuid.clone();
+    let auto_connect = toggler(None, network.auto_connect, move |enabled| {
+        Message::AutoConnectToggled(uuid.clone(), enabled)
+    });
+
+    // What was detected is only known while the network is connected to.
+    let metered_desc = match network.detected_metered {
+        Some(true) if network.metered == Metered::Automatic => &desc[6],
+        Some(false) if network.metered == Metered::Automatic => &desc[7],
+        _ => &desc[5],
+    };
+
+    let uuid = network.uuid.clone();
+    let metered = pick_list(
+        Metered::ALL.map(MeteredOption).to_vec(),
+        Some(MeteredOption(network.metered)),
+        move |metered| Message::MeteredChanged(uuid.clone(), metered.0),
+    );
+
+    let forget = button(text(&desc[1]))
+        .style(theme::Button::Destructive)
+        .on_press(Message::ConfirmForget(network.uuid.clone()));
+
+    column!(
+        settings::item(&desc[0], auto_connect),
+        settings::item::builder(&desc[4])
+            .description(metered_desc)
+            .control(metered),
+        row!(
+            button(text(&desc[8]))
+                .style(theme::Button::Secondary)
+                .on_press(Message::OpenIpConfig(network.uuid.clone())),
+            horizontal_space(Length::Fill),
+            forget,
+        ),
+    )
+    .spacing(8)
+    .into()
+}
+
+/// A metered setting listed in a dropdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct MeteredOption(Metered);
+
+impl fmt::Display for MeteredOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.0 {
+            Metered::Automatic => fl!("wifi-known", "metered-auto"),
+            Metered::Yes => fl!("wifi-known", "metered-yes"),
+            Metered::No => fl!("wifi-known", "metered-no"),
+        };
+
+        f.write_str(&label)
+    }
+}
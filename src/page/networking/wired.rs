@@ -1,10 +1,503 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::page;
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, horizontal_space, pick_list, row, text_input},
+        Alignment, Command, Length,
+    },
+    theme,
+    widget::{settings, text, toggler},
+    Element,
+};
+use cosmic_settings_network::{
+    ip::{self, Family, Invalid, IpConfig, Method},
+    wired::{self, LinkState, WiredDevice},
+};
+use slotmap::SlotMap;
+use std::collections::HashMap;
 
-pub fn page() -> page::Meta {
-    page::Meta::new("wired", "network-workgroup-symbolic")
-        .title(fl!("wired"))
-        .description(fl!("wired", "desc"))
+use super::ip_config::{self, IpConnection, MethodOption};
+use crate::{
+    page::{self, section, Content, Section},
+    widget::copy_row,
+};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// Copies a value to the clipboard, such as the addresses of a device.
+    Copy(String),
+    Devices(Result<Vec<WiredDevice>, String>),
+    /// A setting of a connection was edited, which is staged until it is applied.
+    Edit(String, Family, Field),
+    EnabledToggled(String, bool),
+    Load(page::Entity),
+    /// Opens every IP setting of a connection on a page of its own.
+    OpenIpConfig(String),
+    Refresh,
+    /// Applies the staged IPv4 and IPv6 settings of a connection.
+    WiredConfigChanged(String, IpConfig, IpConfig),
+}
+
+/// A setting of a family of addresses, as it is edited.
+#[derive(Clone, Debug)]
+pub enum Field {
+    Method(Method),
+    Addresses(String),
+    Gateway(String),
+    Dns(String),
+}
+
+/// The IP settings of a connection as they are typed, which are only parsed once applied.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Form {
+    pub method: Method,
+    pub addresses: String,
+    pub gateway: String,
+    pub dns: String,
+}
+
+impl From<&IpConfig> for Form {
+    fn from(config: &IpConfig) -> Self {
+        Self {
+            method: config.method,
+            addresses: config.addresses.join(", "),
+            gateway: config.gateway.clone(),
+            dns: config.dns.join(", "),
+        }
+    }
+}
+
+impl Form {
+    #[must_use]
+    pub fn config(&self) -> IpConfig {
+        let list = |value: &str| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+                .collect()
+        };
+
+        IpConfig {
+            method: self.method,
+            addresses: list(&self.addresses),
+            gateway: self.gateway.trim().to_owned(),
+            dns: list(&self.dns),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub devices: Vec<WiredDevice>,
+    /// The IPv4 and IPv6 settings being edited, by the UUID of their connection.
+    pub drafts: HashMap<String, [Form; 2]>,
+    pub error: Option<String>,
+    pub page: page::Entity,
+}
+
+impl Model {
+    /// Handles a message, and then tells the app whether any draft is left to apply.
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        let command = self.handle(message);
+        Command::batch(vec![command, page::mark_dirty(self.page, self.is_dirty())])
+    }
+
+    fn handle(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => {
+                self.error = result.err();
+                return refresh();
+            }
+
+            Message::Devices(Ok(devices)) => {
+                self.devices = devices;
+                self.error = None;
+
+                // Drafts of connections which are no longer active are discarded.
+                let active: Vec<&str> = self
+                    .devices
+                    .iter()
+                    .filter_map(|device| device.connection.as_ref())
+                    .map(|connection| connection.uuid.as_str())
+                    .collect();
+
+                self.drafts
+                    .retain(|uuid, _| active.contains(&uuid.as_str()));
+            }
+
+            Message::Devices(Err(why)) => self.error = Some(why),
+
+            Message::Edit(uuid, family, field) => {
+                let Some(connection) = self
+                    .devices
+                    .iter()
+                    .filter_map(|device| device.connection.as_ref())
+                    .find(|connection| connection.uuid == uuid)
+                else {
+                    return Command::none();
+                };
+
+                let forms = self.drafts.entry(uuid).or_insert_with(|| {
+                    [Form::from(&connection.ipv4), Form::from(&connection.ipv6)]
+                });
+
+                let form = &mut forms[family as usize];
+
+                match field {
+                    Field::Method(method) => form.method = method,
+                    Field::Addresses(addresses) => form.addresses = addresses,
+                    Field::Gateway(gateway) => form.gateway = gateway,
+                    Field::Dns(dns) => form.dns = dns,
+                }
+            }
+
+            Message::EnabledToggled(interface, enabled) => {
+                return apply(move || wired::set_enabled(&interface, enabled));
+            }
+
+            Message::Load(page) => {
+                self.page = page;
+                return refresh();
+            }
+
+            Message::Copy(value) => {
+                return Command::perform(async {}, move |_| crate::Message::CopyToClipboard(value));
+            }
+
+            Message::OpenIpConfig(uuid) => {
+                let connection = self.devices.iter().find_map(|device| {
+                    let connection = device.connection.as_ref()?;
+
+                    (connection.uuid == uuid).then(|| IpConnection {
+                        uuid: connection.uuid.clone(),
+                        name: connection.name.clone(),
+                        device: Some(device.interface.clone()),
+                    })
+                });
+
+                if let Some(connection) = connection {
+                    return ip_config::open(connection);
+                }
+            }
+
+            Message::Refresh => return refresh(),
+
+            Message::WiredConfigChanged(uuid, ipv4, ipv6) => {
+                let device = self
+                    .devices
+                    .iter()
+                    .find(|device| {
+                        device
+                            .connection
+                            .as_ref()
+                            .map_or(false, |connection| connection.uuid == uuid)
+                    })
+                    .map(|device| device.interface.clone());
+
+                self.drafts.remove(&uuid);
+
+                return apply(move || ip::set_config(&uuid, device.as_deref(), &ipv4, &ipv6));
+            }
+        }
+
+        Command::none()
+    }
+
+    /// Whether the settings of any connection were edited, and not yet applied.
+    fn is_dirty(&self) -> bool {
+        self.changed().next().is_some()
+    }
+
+    /// The UUIDs, interfaces, and drafts of the connections whose drafts differ from their
+    /// settings.
+    fn changed(&self) -> impl Iterator<Item = (&str, &str, &[Form; 2])> + '_ {
+        self.devices.iter().filter_map(|device| {
+            let connection = device.connection.as_ref()?;
+            let forms = self.drafts.get(&connection.uuid)?;
+            let saved = [Form::from(&connection.ipv4), Form::from(&connection.ipv6)];
+
+            (forms != &saved).then_some((
+                connection.uuid.as_str(),
+                device.interface.as_str(),
+                forms,
+            ))
+        })
+    }
+
+    /// Applies the drafts which are valid, and discards the rest.
+    fn apply_drafts(&mut self) -> Command<crate::Message> {
+        let commands: Vec<_> = self
+            .changed()
+            .filter(|(_, _, forms)| is_valid(forms))
+            .map(|(uuid, interface, forms)| {
+                let (uuid, interface) = (uuid.to_owned(), interface.to_owned());
+                let (ipv4, ipv6) = (forms[0].config(), forms[1].config());
+                apply(move || ip::set_config(&uuid, Some(&interface), &ipv4, &ipv6))
+            })
+            .collect();
+
+        self.drafts.clear();
+        Command::batch(commands)
+    }
+}
+
+/// Whether both families of a draft can be applied, of which only manual settings are checked.
+fn is_valid(forms: &[Form; 2]) -> bool {
+    Family::ALL.into_iter().all(|family| {
+        let form = &forms[family as usize];
+        form.method != Method::Manual || form.config().validate(family).is_ok()
+    })
+}
+
+fn apply(func: impl FnOnce() -> Result<(), String> + Send + 'static) -> Command<crate::Message> {
+    Command::perform(page::background(func), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Wired(Message::Applied(result))
+    })
+}
+
+fn refresh() -> Command<crate::Message> {
+    Command::perform(page::background(wired::devices), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Wired(Message::Devices(result))
+    })
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("wired", "network-workgroup-symbolic")
+            .title(fl!("wired"))
+            .description(fl!("wired", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![sections.insert(devices())])
+    }
+
+    fn load(page: page::Entity) -> page::PageTask {
+        Box::pin(async move { crate::Message::Wired(Message::Load(page)) })
+    }
+
+    fn apply(model: &mut Self::Model, _page: page::Entity) -> Command<crate::Message> {
+        model.apply_drafts()
+    }
+
+    /// Drafts which were neither applied nor discarded are dropped when the page is left.
+    fn on_leave(model: &mut Self::Model, _page: page::Entity) -> Command<crate::Message> {
+        model.drafts.clear();
+        Command::none()
+    }
+}
+
+fn devices() -> Section {
+    Section::new()
+        .title(fl!("wired-devices"))
+        .descriptions(vec![
+            fl!("wired-devices", "status"),
+            fl!("wired-devices", "speed"),
+            fl!("wired-devices", "addresses"),
+            fl!("wired-devices", "none"),
+            fl!("wired-ip", "ipv4"),
+            fl!("wired-ip", "ipv6"),
+            fl!("wired-ip", "method"),
+            fl!("wired-ip", "addresses"),
+            fl!("wired-ip", "gateway"),
+            fl!("wired-ip", "dns"),
+            fl!("wired-ip", "apply"),
+            fl!("wired-devices", "connected"),
+            fl!("wired-devices", "connecting"),
+            fl!("wired-devices", "disconnected"),
+            fl!("wired-devices", "unplugged"),
+            fl!("wired-devices", "connection"),
+            fl!("ip-config", "open"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("wired model is missing");
+
+            let desc = &section.descriptions;
+
+            if model.devices.is_empty() {
+                let mut view = settings::view_section(&section.title).add(text(&desc[3]));
+
+                if let Some(why) = &model.error {
+                    view = view.add(text(why));
+                }
+
+                return view.apply(Element::from).map(crate::Message::Wired);
+            }
+
+            let mut sections = Vec::with_capacity(model.devices.len() + 1);
+
+            if let Some(why) = &model.error {
+                sections.push(text(why).into());
+            }
+
+            // Each device is shown as a section of its own.
+            for device in &model.devices {
+                sections.push(device_view(model, device, desc));
+            }
+
+            settings::view_column(sections)
+                .apply(Element::from)
+                .map(crate::Message::Wired)
+        })
+}
+
+fn device_view<'a>(
+    model: &'a Model,
+    device: &'a WiredDevice,
+    desc: &'a [String],
+) -> Element<'a, Message> {
+    let mut view = settings::view_section(&device.interface);
+
+    // A device without a cable has nothing to connect to.
+    if device.state == LinkState::Unplugged {
+        view = view.add(settings::item(&desc[0], text(&desc[14])));
+    } else {
+        let state = match device.state {
+            LinkState::Connected => &desc[11],
+            LinkState::Connecting => &desc[12],
+            _ => &desc[13],
+        };
+
+        let interface = device.interface.clone();
+        let enabled = matches!(device.state, LinkState::Connected | LinkState::Connecting);
+
+        view = view.add(
+            settings::item::builder(&desc[0])
+                .description(state)
+                .control(toggler(None, enabled, move |enabled| {
+                    Message::EnabledToggled(interface.clone(), enabled)
+                })),
+        );
+    }
+
+    if let Some(connection) = &device.connection {
+        view = view.add(settings::item(
+            &desc[15],
+            row!(
+                text(&connection.name),
+                button(text(&desc[16]))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::OpenIpConfig(connection.uuid.clone())),
+            )
+            .spacing(16)
+            .align_items(Alignment::Center),
+        ));
+    }
+
+    if let Some(speed) = device.speed {
+        view = view.add(settings::item(
+            &desc[1],
+            text(fl!("wired-devices", "mbps", speed = speed)),
+        ));
+    }
+
+    if !device.addresses.is_empty() {
+        view = view.add(copy_row(
+            &desc[2],
+            device.addresses.join("\n"),
+            Message::Copy,
+        ));
+    }
+
+    let Some(connection) = &device.connection else {
+        return view.into();
+    };
+
+    let saved = [Form::from(&connection.ipv4), Form::from(&connection.ipv6)];
+
+    let forms = model.drafts.get(&connection.uuid).unwrap_or(&saved);
+    let mut valid = true;
+
+    for family in Family::ALL {
+        let form = &forms[family as usize];
+        let uuid = connection.uuid.clone();
+
+        let mut methods: Vec<MethodOption> = Method::ALL.map(MethodOption).to_vec();
+
+        // A method chosen elsewhere is listed, so that the dropdown shows it.
+        if !Method::ALL.contains(&form.method) {
+            methods.push(MethodOption(form.method));
+        }
+
+        let family_title = match family {
+            Family::V4 => &desc[4],
+            Family::V6 => &desc[5],
+        };
+
+        view = view.add(
+            settings::item::builder(family_title)
+                .description(&desc[6])
+                .control(pick_list(
+                    methods,
+                    Some(MethodOption(form.method)),
+                    move |method| Message::Edit(uuid.clone(), family, Field::Method(method.0)),
+                )),
+        );
+
+        if form.method != Method::Manual {
+            continue;
+        }
+
+        let invalid = form.config().validate(family).err();
+        valid &= invalid.is_none();
+
+        let input = |label: &'a String, value: &str, field: fn(String) -> Field| {
+            let uuid = connection.uuid.clone();
+            settings::item(
+                label,
+                text_input("", value, move |value| {
+                    Message::Edit(uuid.clone(), family, field(value))
+                })
+                .padding(8)
+                .width(Length::Units(250)),
+            )
+        };
+
+        view = view
+            .add(input(&desc[7], &form.addresses, Field::Addresses))
+            .add(input(&desc[8], &form.gateway, Field::Gateway))
+            .add(input(&desc[9], &form.dns, Field::Dns));
+
+        if let Some(invalid) = invalid {
+            let why = match invalid {
+                Invalid::NoAddress => fl!("wired-ip", "no-address"),
+                Invalid::Address(_) => fl!("wired-ip", "invalid-address"),
+                Invalid::Gateway => fl!("wired-ip", "invalid-gateway"),
+                Invalid::Dns(_) => fl!("wired-ip", "invalid-dns"),
+            };
+
+            view = view.add(text(why).size(12));
+        }
+    }
+
+    // Only changed settings are applied, once every one of them is valid.
+    if forms != &saved {
+        let mut apply = button(text(&desc[10])).style(theme::Button::Primary);
+
+        if valid {
+            apply = apply.on_press(Message::WiredConfigChanged(
+                connection.uuid.clone(),
+                forms[0].config(),
+                forms[1].config(),
+            ));
+        }
+
+        view = view.add(row!(horizontal_space(Length::Fill), apply));
+    }
+
+    view.into()
 }
@@ -0,0 +1,366 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Batteries which power the system, and those of connected devices, as UPower reports them.
+
+use cosmic::{iced::Subscription, iced_native::subscription};
+use futures_util::stream::SelectAll;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zbus::zvariant::OwnedObjectPath;
+
+use super::Message;
+
+const DESTINATION: &str = "org.freedesktop.UPower";
+
+/// The type of UPower devices which are batteries.
+const TYPE_BATTERY: u32 = 2;
+
+/// The type of UPower devices which are power adapters, which have no battery.
+const TYPE_LINE_POWER: u32 = 1;
+
+/// Points which the history of a battery is read at, at most.
+const HISTORY_RESOLUTION: u32 = 200;
+
+/// Time after which a device which has stopped reporting its battery is no longer listed.
+pub const DEVICE_STALE: Duration = Duration::from_secs(30 * 60);
+
+/// Whether a battery is being charged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Charging,
+    Discharging,
+    Empty,
+    Full,
+    /// Plugged in, but held at its charge, such as by a charge limit.
+    NotCharging,
+    Unknown,
+}
+
+impl State {
+    fn from_upower(state: u32) -> Self {
+        match state {
+            1 => Self::Charging,
+            2 | 6 => Self::Discharging,
+            3 => Self::Empty,
+            4 => Self::Full,
+            5 => Self::NotCharging,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Battery {
+    /// The name of the battery, which tells batteries apart if there are several.
+    pub name: String,
+    pub percentage: f64,
+    pub state: State,
+    /// The time until the battery is empty or full, while UPower can estimate it.
+    pub estimate: Option<Duration>,
+    /// The capacity which the battery holds now, as a share of its design in percent.
+    ///
+    /// Some batteries report more than they were designed for, which is kept as it was
+    /// reported.
+    pub health: Option<f64>,
+}
+
+impl Battery {
+    /// The health of the battery, which never exceeds 100%.
+    #[must_use]
+    pub fn clamped_health(&self) -> Option<f64> {
+        self.health.map(|health| health.min(100.0))
+    }
+}
+
+/// A charge of a battery which UPower recorded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    pub time: SystemTime,
+    pub percentage: f64,
+    pub charging: bool,
+}
+
+/// A connected device which reports its battery, such as a wireless mouse or headset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Device {
+    pub name: String,
+    /// A symbolic icon of the kind of device.
+    pub icon: &'static str,
+    pub percentage: f64,
+    pub state: State,
+    /// When UPower last heard from the device.
+    pub updated: SystemTime,
+}
+
+impl Device {
+    /// Whether the device has not reported its battery for so long that it is likely gone.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.updated
+            .elapsed()
+            .map_or(false, |elapsed| elapsed > DEVICE_STALE)
+    }
+}
+
+/// Reads the batteries which power the system, and those of connected devices.
+///
+/// # Errors
+///
+/// Fails if UPower is not running.
+pub async fn load() -> zbus::Result<(Vec<Battery>, Vec<Device>)> {
+    let connection = zbus::Connection::system().await?;
+    let mut batteries = Vec::new();
+    let mut devices = Vec::new();
+
+    for path in battery_paths(&connection).await? {
+        let device = device(&connection, path).await?;
+
+        let kind: u32 = device.get_property("Type").await?;
+        let power_supply: bool = device.get_property("PowerSupply").await?;
+
+        if !power_supply {
+            if let Some(peripheral) = peripheral(&device, kind).await? {
+                devices.push(peripheral);
+            }
+
+            continue;
+        }
+
+        if kind != TYPE_BATTERY {
+            continue;
+        }
+
+        let state = State::from_upower(device.get_property("State").await?);
+
+        let estimate: i64 = match state {
+            State::Charging => device.get_property("TimeToFull").await?,
+            State::Discharging => device.get_property("TimeToEmpty").await?,
+            _ => 0,
+        };
+
+        let full: f64 = device.get_property("EnergyFull").await?;
+        let design: f64 = device.get_property("EnergyFullDesign").await?;
+        let model: String = device.get_property("Model").await?;
+
+        batteries.push(Battery {
+            name: model.trim().to_owned(),
+            percentage: device.get_property("Percentage").await?,
+            state,
+            estimate: u64::try_from(estimate)
+                .ok()
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            health: (design > 0.0).then(|| full / design * 100.0),
+        });
+    }
+
+    // A single battery is simply the battery, while several are told apart by their models.
+    let count = batteries.len();
+
+    for (index, battery) in batteries.iter_mut().enumerate() {
+        if count == 1 {
+            battery.name = fl!("power-battery");
+        } else if battery.name.is_empty() {
+            battery.name = fl!("power-battery", "numbered", number = index + 1);
+        }
+    }
+
+    devices.retain(|device| !device.is_stale());
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok((batteries, devices))
+}
+
+/// The charge of the battery which powers the system over a span until now, oldest first.
+///
+/// The history is empty if UPower keeps none for the battery.
+///
+/// # Errors
+///
+/// Fails if UPower is not running.
+pub async fn history(span: Duration) -> zbus::Result<Vec<Sample>> {
+    let connection = zbus::Connection::system().await?;
+    let span = u32::try_from(span.as_secs()).unwrap_or(u32::MAX);
+
+    for path in battery_paths(&connection).await? {
+        let device = device(&connection, path).await?;
+
+        let kind: u32 = device.get_property("Type").await?;
+        let power_supply: bool = device.get_property("PowerSupply").await?;
+        let has_history: bool = device.get_property("HasHistory").await?;
+
+        if kind != TYPE_BATTERY || !power_supply || !has_history {
+            continue;
+        }
+
+        let records: Vec<(u32, f64, u32)> = device
+            .call("GetHistory", &("charge", span, HISTORY_RESOLUTION))
+            .await?;
+
+        // UPower lists the newest records first.
+        let mut samples: Vec<Sample> = records
+            .into_iter()
+            .filter(|(time, ..)| *time > 0)
+            .map(|(time, percentage, state)| Sample {
+                time: UNIX_EPOCH + Duration::from_secs(time.into()),
+                percentage,
+                charging: State::from_upower(state) == State::Charging,
+            })
+            .collect();
+
+        samples.sort_by_key(|sample| sample.time);
+        return Ok(samples);
+    }
+
+    Ok(Vec::new())
+}
+
+/// Reads a device which does not power the system, unless it has no battery or is gone.
+async fn peripheral(device: &zbus::Proxy<'_>, kind: u32) -> zbus::Result<Option<Device>> {
+    let present: bool = device.get_property("IsPresent").await?;
+
+    if kind == TYPE_LINE_POWER || !present {
+        return Ok(None);
+    }
+
+    let model: String = device.get_property("Model").await?;
+    let updated: u64 = device.get_property("UpdateTime").await?;
+
+    let name = match model.trim() {
+        "" => fl!("power-devices", "unnamed"),
+        model => model.to_owned(),
+    };
+
+    Ok(Some(Device {
+        name,
+        icon: device_icon(kind),
+        percentage: device.get_property("Percentage").await?,
+        state: State::from_upower(device.get_property("State").await?),
+        updated: UNIX_EPOCH + Duration::from_secs(updated),
+    }))
+}
+
+/// The symbolic icon of a kind of UPower device.
+fn device_icon(kind: u32) -> &'static str {
+    match kind {
+        5 => "input-mouse-symbolic",
+        6 => "input-keyboard-symbolic",
+        8 => "phone-symbolic",
+        9 => "multimedia-player-symbolic",
+        10 | 13 => "input-tablet-symbolic",
+        12 => "input-gaming-symbolic",
+        14 => "input-touchpad-symbolic",
+        17 => "audio-headset-symbolic",
+        18 => "audio-speakers-symbolic",
+        19 => "audio-headphones-symbolic",
+        _ => "battery-symbolic",
+    }
+}
+
+async fn battery_paths(connection: &zbus::Connection) -> zbus::Result<Vec<OwnedObjectPath>> {
+    upower(connection)
+        .await?
+        .call("EnumerateDevices", &())
+        .await
+}
+
+async fn upower(connection: &zbus::Connection) -> zbus::Result<zbus::Proxy<'static>> {
+    zbus::Proxy::new(
+        connection,
+        DESTINATION,
+        "/org/freedesktop/UPower",
+        DESTINATION,
+    )
+    .await
+}
+
+async fn device(
+    connection: &zbus::Connection,
+    path: OwnedObjectPath,
+) -> zbus::Result<zbus::Proxy<'static>> {
+    zbus::Proxy::new(
+        connection,
+        DESTINATION,
+        path,
+        "org.freedesktop.UPower.Device",
+    )
+    .await
+}
+
+enum Watch {
+    Starting,
+    Watching(SelectAll<zbus::SignalStream<'static>>),
+    Finished,
+}
+
+/// The batteries as they are first watched, and whenever UPower reports a change to them, or
+/// a device was added or removed.
+///
+/// Devices which report their batteries are watched as well.
+pub fn changes() -> Subscription<crate::Message> {
+    use futures_util::StreamExt;
+
+    subscription::unfold("power-batteries", Watch::Starting, |state| async move {
+        match state {
+            Watch::Starting => {
+                let watch = async {
+                    let connection = zbus::Connection::system().await?;
+                    let upower = upower(&connection).await?;
+
+                    let mut signals = futures_util::stream::select_all([
+                        upower.receive_signal("DeviceAdded").await?,
+                        upower.receive_signal("DeviceRemoved").await?,
+                    ]);
+
+                    for path in battery_paths(&connection).await? {
+                        let properties = zbus::Proxy::new(
+                            &connection,
+                            DESTINATION,
+                            path,
+                            "org.freedesktop.DBus.Properties",
+                        )
+                        .await?;
+
+                        signals.push(properties.receive_signal("PropertiesChanged").await?);
+                    }
+
+                    zbus::Result::Ok(signals)
+                };
+
+                let Ok(signals) = watch.await else {
+                    return (None, Watch::Finished);
+                };
+
+                // Batteries are read once watched, so that no change is missed in between.
+                let message = load().await.ok().map(|(batteries, devices)| {
+                    crate::Message::Power(Message::BatteriesChanged(batteries, devices))
+                });
+
+                (message, Watch::Watching(signals))
+            }
+
+            Watch::Watching(mut signals) => {
+                let Some(signal) = signals.next().await else {
+                    return (None, Watch::Finished);
+                };
+
+                let message = load().await.ok().map(|(batteries, devices)| {
+                    crate::Message::Power(Message::BatteriesChanged(batteries, devices))
+                });
+
+                // The devices which are watched are listed anew once they changed.
+                let added_or_removed = signal.member().map_or(false, |member| {
+                    matches!(member.as_str(), "DeviceAdded" | "DeviceRemoved")
+                });
+
+                if added_or_removed {
+                    (message, Watch::Starting)
+                } else {
+                    (message, Watch::Watching(signals))
+                }
+            }
+
+            Watch::Finished => std::future::pending().await,
+        }
+    })
+}
@@ -0,0 +1,1696 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+pub mod battery;
+pub mod profiles;
+
+use apply::Apply;
+use bytecheck::CheckBytes;
+use cosmic::{
+    iced::{
+        widget::{button, column, horizontal_space, pick_list, row, tooltip, vertical_space},
+        Alignment, Command, Length, Subscription,
+    },
+    iced_native::subscription,
+    theme,
+    widget::{icon, settings, text, toggler},
+    Element,
+};
+use cosmic_settings_power::{
+    self as power, Backlight, ChargeLimit, ChargeLimitError, Hardware, LidAction, Managed,
+    PowerButtonAction, PowerSource, ScreenOff, Suspend,
+};
+use rkyv::{Archive, Deserialize, Serialize};
+use slotmap::SlotMap;
+use std::{
+    fmt,
+    time::{Duration, Instant, SystemTime},
+};
+
+use crate::{
+    config,
+    page::{self, section, Content, Section},
+    widget::{
+        battery_graph::{self, battery_graph},
+        info_popover, labeled_slider,
+        radio_list::{radio_list, Entry},
+        settings_row,
+        spin_button::{self, spin_button, SpinButton},
+        Confirm,
+    },
+};
+
+use self::{
+    battery::{Battery, Device, State},
+    profiles::{Profile, Profiles},
+};
+
+/// Times after which the screen may be turned off, in minutes.
+const SCREEN_OFF_MINUTES: [u64; 5] = [1, 2, 5, 10, 15];
+
+/// Times after which the screen may be locked once it was turned off, in seconds.
+const LOCK_DELAY_SECONDS: [u64; 4] = [0, 30, 60, 300];
+
+/// Times after which the system may be suspended, in minutes.
+const SUSPEND_MINUTES: [u64; 5] = [15, 30, 45, 60, 90];
+
+/// The span of time which the usage of the battery is shown over.
+const HISTORY_SPAN: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The height of the usage graph, in pixels.
+const GRAPH_HEIGHT: u16 = 120;
+
+/// Interval between reads of the usage of the battery, while the page is shown.
+const HISTORY_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// The longest time between recorded charges which the usage graph joins, as the system was
+/// likely asleep if no charge was recorded for longer.
+const HISTORY_MAX_GAP: Duration = Duration::from_secs(30 * 60);
+
+/// The charge below which a connected device is warned of, in percent, unless it is charging.
+const DEVICE_LOW_PERCENT: f64 = 20.0;
+
+/// Interval between checks of whether connected devices have stopped reporting their batteries.
+const DEVICE_AGE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long automatic brightness is paused for once the brightness was changed by hand.
+const MANUAL_GRACE: Duration = Duration::from_secs(5 * 60);
+
+/// Changes of brightness which are ignored, as a share of the maximum, so that the
+/// backlight does not flicker while the ambient light wavers.
+const BRIGHTNESS_THRESHOLD: f64 = 0.03;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Applied(Result<(), String>),
+    /// Automatic brightness was turned on or off, or its sensitivity was released.
+    AutoBrightnessChanged(AutoBrightness),
+    /// The batteries which power the system, and those of connected devices.
+    BatteriesChanged(Vec<Battery>, Vec<Device>),
+    /// The backlight was adjusted for the ambient light, or found changed by hand.
+    BrightnessAdjusted(Result<Adjustment, String>),
+    /// Sets the custom charge limit which was entered.
+    ChargeLimitApply,
+    /// The limit which the firmware holds once it was set, and why setting it failed if it
+    /// did.
+    ChargeLimitApplied(Option<ChargeLimit>, Option<String>),
+    ChargeLimitCustom(spin_button::Message),
+    ChargeLimitSelected(Limit),
+    /// Asks to confirm doing nothing when the lid is closed on battery.
+    ConfirmLidNothing(Suspend),
+    /// Drops the devices which have stopped reporting their batteries.
+    DevicesAged,
+    DimScreenChanged(bool),
+    /// The recorded charges of the battery, oldest first.
+    HistoryLoaded(Vec<battery::Sample>),
+    /// The pointer moved over a charge in the usage graph, or off of the graph.
+    HistoryHovered(Option<usize>),
+    HistoryRefresh,
+    /// The explanation of why hibernating is not offered was opened or closed.
+    HibernateInfo(bool),
+    /// The names of the applications which keep the screen from blanking.
+    IdleInhibited(Vec<String>),
+    /// The ambient light which the sensor measures, in lux.
+    LightLevel(f64),
+    Loaded(Loaded),
+    LockAfterBlankChanged(bool),
+    LockDelayChanged(Duration),
+    PowerButtonChanged(PowerButtonAction),
+    ProfileApplied(Result<Profiles, String>),
+    ProfileSelected(Profile),
+    /// The daemon changed the profiles, or went away.
+    ProfilesChanged(Option<Profiles>),
+    ScreenTimeoutChanged(PowerSource, Option<Duration>),
+    /// The sensitivity of automatic brightness while its slider is dragged.
+    SensitivityDragged(u32),
+    SuspendConfigChanged(Suspend),
+}
+
+/// The settings and hardware of the system, as they are loaded with the page.
+#[derive(Clone, Debug, Default)]
+pub struct Loaded {
+    pub screen_off: ScreenOff,
+    pub suspend: Suspend,
+    pub power_button: PowerButtonAction,
+    pub managed: Managed,
+    pub hardware: Hardware,
+    pub auto_brightness: AutoBrightness,
+    /// The profiles are `None` if power-profiles-daemon is not running.
+    pub profiles: Option<Profiles>,
+    pub idle_inhibitors: Vec<String>,
+    pub docked: bool,
+    pub history: Vec<battery::Sample>,
+    /// The charge limit is `None` if the firmware does not support one.
+    pub charge_limit: Option<ChargeLimit>,
+}
+
+/// Whether the brightness follows the ambient light, which is kept between sessions.
+#[must_use]
+#[derive(Archive, Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[archive_attr(derive(CheckBytes, Debug))]
+pub struct AutoBrightness {
+    pub enabled: bool,
+    pub sensitivity: u32,
+}
+
+impl Default for AutoBrightness {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sensitivity: 50,
+        }
+    }
+}
+
+/// A charge limit which may be chosen, of which the presets stop charging at a fixed charge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    Full,
+    Balanced,
+    Lifespan,
+    Custom,
+}
+
+impl Limit {
+    const ALL: [Self; 4] = [Self::Full, Self::Balanced, Self::Lifespan, Self::Custom];
+
+    /// The charge at which a preset stops charging, in percent.
+    fn percent(self) -> Option<u8> {
+        match self {
+            Self::Full => Some(100),
+            Self::Balanced => Some(80),
+            Self::Lifespan => Some(60),
+            Self::Custom => None,
+        }
+    }
+
+    /// The preset which stops charging at a charge, or custom if none does.
+    fn of(end: u8) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|limit| limit.percent() == Some(end))
+            .unwrap_or(Self::Custom)
+    }
+}
+
+/// How the backlight was found when it was to be adjusted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Adjustment {
+    /// The backlight was set to a brightness, in its steps.
+    Set(u32),
+    /// The brightness differed from the last which was set, as it was changed by hand.
+    Manual,
+    /// The brightness was close enough to the ambient light to be kept.
+    Unchanged,
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub screen_off: ScreenOff,
+    pub suspend: Suspend,
+    pub power_button: PowerButtonAction,
+    /// Whether the explanation of why hibernating is not offered is open.
+    pub hibernate_info: bool,
+    /// The names of the applications which keep the screen from blanking.
+    pub idle_inhibitors: Vec<String>,
+    /// Whether an external display is connected, while which closing the lid does nothing.
+    pub docked: bool,
+    /// Settings which an administrator manages are shown, but cannot be changed.
+    pub managed: Managed,
+    /// Settings which do not apply to the hardware of the system are hidden.
+    pub hardware: Hardware,
+    pub error: Option<String>,
+    /// The batteries which power the system, as UPower last reported them.
+    pub batteries: Vec<Battery>,
+    /// The connected devices which report their batteries, such as wireless mice.
+    pub devices: Vec<Device>,
+    /// The charges of the battery which UPower recorded, oldest first.
+    pub history: Vec<battery::Sample>,
+    /// The recorded charges placed on the usage graph, as of when they were read.
+    pub history_graph: Vec<battery_graph::Sample>,
+    /// The charge in the usage graph which the pointer is over.
+    pub history_hovered: Option<usize>,
+    /// The power profiles, unless power-profiles-daemon is not running.
+    pub profiles: Option<Profiles>,
+    /// The profiles which the daemon offers, listed with their descriptions.
+    pub profile_entries: Vec<Entry<Profile>>,
+    pub profile_error: Option<String>,
+    /// The charge limit which the firmware holds, unless it does not support one.
+    pub charge_limit: Option<ChargeLimit>,
+    /// The charge limits which may be chosen, listed with their descriptions.
+    pub charge_limit_entries: Vec<Entry<Limit>>,
+    /// The custom charge limit, which is entered before it is set.
+    pub charge_limit_custom: Option<SpinButton<u8>>,
+    /// Whether a custom limit was chosen, even if it equals a preset.
+    pub custom_limit: bool,
+    pub charge_limit_error: Option<String>,
+    pub auto_brightness: AutoBrightness,
+    /// The sensitivity of automatic brightness while its slider is dragged.
+    pub sensitivity: Option<u32>,
+    /// The brightness which was last set automatically, to tell when it is changed by hand.
+    last_brightness: Option<u32>,
+    /// When automatic brightness resumes, after the brightness was changed by hand.
+    pub paused_until: Option<Instant>,
+    /// Whether an adjustment of the backlight is in progress.
+    adjusting: bool,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Applied(result) => self.error = result.err(),
+
+            Message::AutoBrightnessChanged(auto_brightness) => {
+                self.sensitivity = None;
+                self.paused_until = None;
+                self.last_brightness = None;
+                self.auto_brightness = auto_brightness;
+
+                return Command::perform(
+                    page::background(move || {
+                        config::store("power-auto-brightness", &auto_brightness);
+                    }),
+                    |_| crate::Message::None,
+                );
+            }
+
+            Message::BatteriesChanged(batteries, devices) => {
+                self.batteries = batteries;
+                self.devices = devices;
+            }
+
+            Message::BrightnessAdjusted(result) => {
+                self.adjusting = false;
+
+                match result {
+                    Ok(Adjustment::Set(brightness)) => self.last_brightness = Some(brightness),
+
+                    // The brightness chosen by hand is kept for a while, and then followed
+                    // from the brightness which it was left at.
+                    Ok(Adjustment::Manual) => {
+                        self.paused_until = Some(Instant::now() + MANUAL_GRACE);
+                        self.last_brightness = None;
+                    }
+
+                    Ok(Adjustment::Unchanged) => (),
+
+                    Err(why) => self.error = Some(why),
+                }
+            }
+
+            Message::ChargeLimitApply => {
+                if let Some(custom) = &self.charge_limit_custom {
+                    return self.set_charge_limit(custom.value());
+                }
+            }
+
+            Message::ChargeLimitApplied(limit, error) => {
+                self.charge_limit_error = error;
+                self.set_charge_limit_entries(limit);
+            }
+
+            Message::ChargeLimitCustom(message) => {
+                if let Some(custom) = &mut self.charge_limit_custom {
+                    custom.update(message);
+                }
+            }
+
+            Message::ChargeLimitSelected(limit) => {
+                self.custom_limit = limit == Limit::Custom;
+
+                if let Some(end) = limit.percent() {
+                    return self.set_charge_limit(end);
+                }
+            }
+
+            Message::ConfirmLidNothing(suspend) => {
+                let confirm = Confirm::new(
+                    fl!("power-lid-nothing"),
+                    fl!("power-lid-nothing", "desc"),
+                    fl!("power-lid-nothing", "confirm"),
+                    crate::Message::Power(Message::SuspendConfigChanged(suspend)),
+                )
+                .dont_ask_again("power-lid-nothing");
+
+                return Command::perform(async {}, move |_| crate::Message::ShowConfirm(confirm));
+            }
+
+            Message::DevicesAged => self.devices.retain(|device| !device.is_stale()),
+
+            Message::HistoryLoaded(history) => self.set_history(history),
+
+            Message::HistoryHovered(index) => self.history_hovered = index,
+
+            Message::HistoryRefresh => {
+                return Command::perform(battery::history(HISTORY_SPAN), |history| {
+                    crate::Message::Power(Message::HistoryLoaded(history.unwrap_or_default()))
+                });
+            }
+
+            Message::DimScreenChanged(dim) => {
+                self.screen_off.dim = dim;
+                return apply(move || power::set_dim_screen(dim));
+            }
+
+            Message::HibernateInfo(open) => self.hibernate_info = open,
+
+            Message::IdleInhibited(inhibitors) => self.idle_inhibitors = inhibitors,
+
+            Message::LightLevel(lux) => {
+                if !self.auto_brightness.enabled || self.adjusting {
+                    return Command::none();
+                }
+
+                if let Some(until) = self.paused_until {
+                    if Instant::now() < until {
+                        return Command::none();
+                    }
+
+                    self.paused_until = None;
+                }
+
+                self.adjusting = true;
+                let sensitivity = self.auto_brightness.sensitivity;
+                let last = self.last_brightness;
+
+                return Command::perform(adjust_brightness(lux, sensitivity, last), |result| {
+                    crate::Message::Power(Message::BrightnessAdjusted(result))
+                });
+            }
+
+            Message::Loaded(loaded) => {
+                self.screen_off = loaded.screen_off;
+                self.suspend = loaded.suspend;
+                self.power_button = loaded.power_button;
+                self.managed = loaded.managed;
+                self.hardware = loaded.hardware;
+                self.auto_brightness = loaded.auto_brightness;
+                self.set_profiles(loaded.profiles);
+                self.idle_inhibitors = loaded.idle_inhibitors;
+                self.docked = loaded.docked;
+                self.set_charge_limit_entries(loaded.charge_limit);
+                self.set_history(loaded.history);
+            }
+
+            Message::LockAfterBlankChanged(lock) => {
+                self.screen_off.lock = lock;
+                let delay = self.screen_off.lock_delay;
+                return apply(move || power::set_lock_on_screen_off(lock, delay));
+            }
+
+            Message::LockDelayChanged(delay) => {
+                self.screen_off.lock_delay = delay;
+                let lock = self.screen_off.lock;
+                return apply(move || power::set_lock_on_screen_off(lock, delay));
+            }
+
+            Message::PowerButtonChanged(action) => {
+                self.power_button = action;
+                return apply(move || power::set_power_button(action));
+            }
+
+            Message::ProfileApplied(result) => match result {
+                Ok(profiles) => self.set_profiles(Some(profiles)),
+
+                // The selection goes back to the profile which remains active.
+                Err(why) => {
+                    self.profile_error = Some(why);
+
+                    return Command::perform(profiles::load(), |profiles| {
+                        crate::Message::Power(Message::ProfilesChanged(profiles.ok()))
+                    });
+                }
+            },
+
+            Message::ProfileSelected(profile) => {
+                let Some(current) = &mut self.profiles else {
+                    return Command::none();
+                };
+
+                current.active = profile;
+                self.profile_error = None;
+
+                return Command::perform(profiles::set(profile), |result| {
+                    crate::Message::Power(Message::ProfileApplied(
+                        result.map_err(|why| why.to_string()),
+                    ))
+                });
+            }
+
+            Message::ProfilesChanged(profiles) => self.set_profiles(profiles),
+
+            Message::ScreenTimeoutChanged(source, timeout) => {
+                match source {
+                    PowerSource::Ac => self.screen_off.on_ac = timeout,
+                    PowerSource::Battery => self.screen_off.on_battery = timeout,
+                }
+
+                // The time is kept whether or not an application inhibits blanking, which is
+                // checked again so that the note of it is current.
+                return Command::batch(vec![
+                    apply(move || power::set_screen_off_time(source, timeout)),
+                    Command::perform(idle_inhibitors(), |inhibitors| {
+                        crate::Message::Power(Message::IdleInhibited(inhibitors))
+                    }),
+                ]);
+            }
+
+            Message::SensitivityDragged(sensitivity) => self.sensitivity = Some(sensitivity),
+
+            Message::SuspendConfigChanged(suspend) => {
+                self.suspend = suspend;
+                return apply(move || power::set_suspend(suspend));
+            }
+        }
+
+        Command::none()
+    }
+
+    /// Places the recorded charges on the usage graph, which ends at the time they were read.
+    fn set_history(&mut self, history: Vec<battery::Sample>) {
+        let now = SystemTime::now();
+        let span = HISTORY_SPAN.as_secs_f32();
+
+        self.history_graph = history
+            .iter()
+            .map(|sample| {
+                let age = now.duration_since(sample.time).unwrap_or_default();
+
+                battery_graph::Sample {
+                    at: 1.0 - age.as_secs_f32() / span,
+                    level: sample.percentage as f32 / 100.0,
+                    charging: sample.charging,
+                }
+            })
+            .collect();
+
+        self.history = history;
+        self.history_hovered = None;
+    }
+
+    /// Asks the firmware to stop charging at a charge, which it is shown to already while
+    /// the user authenticates.
+    fn set_charge_limit(&mut self, end: u8) -> Command<crate::Message> {
+        let Some(limit) = &mut self.charge_limit else {
+            return Command::none();
+        };
+
+        if limit.end == end {
+            return Command::none();
+        }
+
+        limit.end = end;
+        self.charge_limit_error = None;
+        let battery = limit.battery.clone();
+
+        Command::perform(
+            page::background(move || match power::set_charge_limit(&battery, end) {
+                Ok(limit) => (Some(limit), None),
+                // The limit goes back to that which the firmware kept.
+                Err(why) => (power::charge_limit(), Some(charge_limit_error(why))),
+            }),
+            |result| {
+                let (limit, error) = result.unwrap_or_else(|| (None, Some(fl!("unknown"))));
+                crate::Message::Power(Message::ChargeLimitApplied(limit, error))
+            },
+        )
+    }
+
+    /// Shows the charge limit which the firmware holds, and offers the presets for it.
+    fn set_charge_limit_entries(&mut self, limit: Option<ChargeLimit>) {
+        let Some(limit) = limit else {
+            self.charge_limit = None;
+            self.charge_limit_entries.clear();
+            self.charge_limit_custom = None;
+            return;
+        };
+
+        if self.charge_limit_entries.is_empty() {
+            self.charge_limit_entries = Limit::ALL
+                .into_iter()
+                .map(|preset| {
+                    let (title, description) = match preset {
+                        Limit::Full => (
+                            fl!("power-charge-limit", "full"),
+                            fl!("power-charge-limit", "full-desc"),
+                        ),
+                        Limit::Balanced => (
+                            fl!("power-charge-limit", "balanced"),
+                            fl!("power-charge-limit", "balanced-desc"),
+                        ),
+                        Limit::Lifespan => (
+                            fl!("power-charge-limit", "lifespan"),
+                            fl!("power-charge-limit", "lifespan-desc"),
+                        ),
+                        Limit::Custom => (
+                            fl!("power-charge-limit", "custom"),
+                            fl!("power-charge-limit", "custom-desc"),
+                        ),
+                    };
+
+                    Entry::new(preset, title, description)
+                })
+                .collect();
+        }
+
+        match &mut self.charge_limit_custom {
+            Some(custom) => custom.set_value(limit.end),
+            None => {
+                self.charge_limit_custom = Some(
+                    spin_button(limit.end, power::CHARGE_LIMIT, 5)
+                        .suffix(fl!("power-charge-limit", "percent-suffix")),
+                );
+            }
+        }
+
+        self.charge_limit = Some(limit);
+    }
+
+    /// Lists the profiles which the daemon offers, by the performance which they allow.
+    fn set_profiles(&mut self, profiles: Option<Profiles>) {
+        self.profile_entries = profiles
+            .iter()
+            .flat_map(|profiles| {
+                Profile::ALL
+                    .into_iter()
+                    .filter(move |profile| profiles.available.contains(profile))
+            })
+            .map(|profile| {
+                let (title, description) = match profile {
+                    Profile::Performance => (
+                        fl!("power-profile", "performance"),
+                        fl!("power-profile", "performance-desc"),
+                    ),
+                    Profile::Balanced => (
+                        fl!("power-profile", "balanced"),
+                        fl!("power-profile", "balanced-desc"),
+                    ),
+                    Profile::PowerSaver => (
+                        fl!("power-profile", "power-saver"),
+                        fl!("power-profile", "power-saver-desc"),
+                    ),
+                };
+
+                Entry::new(profile, title, description)
+            })
+            .collect();
+
+        self.profiles = profiles;
+    }
+}
+
+fn apply(func: impl FnOnce() -> Result<(), String> + Send + 'static) -> Command<crate::Message> {
+    Command::perform(page::background(func), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Power(Message::Applied(result))
+    })
+}
+
+/// Describes why the charge limit could not be set.
+fn charge_limit_error(why: ChargeLimitError) -> String {
+    match why {
+        ChargeLimitError::Denied => fl!("power-charge-limit", "denied"),
+        ChargeLimitError::Rejected => fl!("power-charge-limit", "rejected"),
+        ChargeLimitError::Failed(why) => fl!("power-charge-limit", "failed", why = why),
+    }
+}
+
+/// Sets the backlight for the ambient light, unless its brightness was changed by hand
+/// since it was last set automatically.
+async fn adjust_brightness(
+    lux: f64,
+    sensitivity: u32,
+    last: Option<u32>,
+) -> Result<Adjustment, String> {
+    let Some(Backlight {
+        name,
+        brightness,
+        max,
+    }) = page::background(power::backlight).await.flatten()
+    else {
+        return Err(fl!("power-brightness", "no-backlight"));
+    };
+
+    let threshold = (f64::from(max) * BRIGHTNESS_THRESHOLD).round() as u32;
+
+    if matches!(last, Some(last) if last.abs_diff(brightness) > threshold) {
+        return Ok(Adjustment::Manual);
+    }
+
+    let target = power::auto_brightness(lux, sensitivity, max);
+
+    if target.abs_diff(brightness) <= threshold {
+        return Ok(Adjustment::Unchanged);
+    }
+
+    set_brightness(&name, target)
+        .await
+        .map(|_| Adjustment::Set(target))
+        .map_err(|why| why.to_string())
+}
+
+/// Sets the brightness of a backlight through logind, which permits the session to do so
+/// without privileges.
+async fn set_brightness(name: &str, brightness: u32) -> zbus::Result<()> {
+    let connection = zbus::Connection::system().await?;
+
+    let session = zbus::Proxy::new(
+        &connection,
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1/session/auto",
+        "org.freedesktop.login1.Session",
+    )
+    .await?;
+
+    session
+        .call("SetBrightness", &("backlight", name, brightness))
+        .await
+}
+
+/// Whether logind permits hibernating, which it only does once it found swap space large
+/// enough to hold the memory of the system.
+async fn logind_can_hibernate() -> bool {
+    let can = async {
+        let connection = zbus::Connection::system().await?;
+
+        let manager = zbus::Proxy::new(
+            &connection,
+            "org.freedesktop.login1",
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )
+        .await?;
+
+        zbus::Result::<String>::Ok(manager.call("CanHibernate", &()).await?)
+    };
+
+    matches!(can.await.as_deref(), Ok("yes"))
+}
+
+/// The names of the applications which keep the system from idling, such as to present,
+/// as logind lists them.
+async fn idle_inhibitors() -> Vec<String> {
+    let list = async {
+        let connection = zbus::Connection::system().await?;
+
+        let manager = zbus::Proxy::new(
+            &connection,
+            "org.freedesktop.login1",
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )
+        .await?;
+
+        let inhibitors: Vec<(String, String, String, String, u32, u32)> =
+            manager.call("ListInhibitors", &()).await?;
+
+        zbus::Result::Ok(inhibitors)
+    };
+
+    let mut names: Vec<String> = list
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(what, _, _, mode, ..)| mode == "block" && what.split(':').any(|w| w == "idle"))
+        .map(|(_, who, ..)| who)
+        .collect();
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+enum LightSensor {
+    Starting,
+    /// The light is claimed while the proxy is kept, until the subscription is dropped.
+    Claimed(zbus::Proxy<'static>, zbus::PropertyStream<'static, f64>),
+    Finished,
+}
+
+/// The ambient light which iio-sensor-proxy measures, in lux.
+///
+/// The sensor is only claimed while the power page is shown, so that it is not woken
+/// otherwise. Its claim is released with the connection once the subscription is dropped.
+fn light_level() -> Subscription<crate::Message> {
+    use futures_util::StreamExt;
+
+    subscription::unfold("ambient-light", LightSensor::Starting, |state| async move {
+        match state {
+            LightSensor::Starting => {
+                let claim = async {
+                    let connection = zbus::Connection::system().await?;
+
+                    let proxy = zbus::Proxy::new(
+                        &connection,
+                        "net.hadess.SensorProxy",
+                        "/net/hadess/SensorProxy",
+                        "net.hadess.SensorProxy",
+                    )
+                    .await?;
+
+                    proxy.call::<_, _, ()>("ClaimLight", &()).await?;
+
+                    let level = proxy.get_property::<f64>("LightLevel").await?;
+                    let changes = proxy.receive_property_changed::<f64>("LightLevel").await;
+                    zbus::Result::Ok((proxy, changes, level))
+                };
+
+                match claim.await {
+                    Ok((proxy, changes, level)) => (
+                        Some(crate::Message::Power(Message::LightLevel(level))),
+                        LightSensor::Claimed(proxy, changes),
+                    ),
+                    Err(_) => (None, LightSensor::Finished),
+                }
+            }
+
+            LightSensor::Claimed(proxy, mut changes) => {
+                let Some(change) = changes.next().await else {
+                    return (None, LightSensor::Finished);
+                };
+
+                let message = change
+                    .get()
+                    .await
+                    .ok()
+                    .map(|level| crate::Message::Power(Message::LightLevel(level)));
+
+                (message, LightSensor::Claimed(proxy, changes))
+            }
+
+            LightSensor::Finished => std::future::pending().await,
+        }
+    })
+}
+
+/// A lid action listed in a dropdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Lid(LidAction);
+
+impl fmt::Display for Lid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.0 {
+            LidAction::Suspend => fl!("power-suspend", "lid-suspend"),
+            LidAction::Hibernate => fl!("power-suspend", "lid-hibernate"),
+            LidAction::Lock => fl!("power-suspend", "lid-lock"),
+            LidAction::Nothing => fl!("power-suspend", "lid-nothing"),
+        };
+
+        f.write_str(&label)
+    }
+}
+
+/// A power button action listed in a dropdown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PowerButton(PowerButtonAction);
+
+impl fmt::Display for PowerButton {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.0 {
+            PowerButtonAction::Suspend => fl!("power-button", "suspend"),
+            PowerButtonAction::PowerOff => fl!("power-button", "power-off"),
+            PowerButtonAction::Hibernate => fl!("power-button", "hibernate"),
+            PowerButtonAction::Nothing => fl!("power-button", "nothing"),
+        };
+
+        f.write_str(&label)
+    }
+}
+
+/// A time listed in a dropdown, which is never if it is `None`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Timeout(Option<Duration>);
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.0 {
+            None => fl!("power-screen-off", "never"),
+            Some(time) if time.is_zero() => fl!("power-screen-off", "immediately"),
+            Some(time) if time.as_secs() < 60 => {
+                fl!("power-screen-off", "seconds", value = time.as_secs())
+            }
+            Some(time) => fl!("power-screen-off", "minutes", value = time.as_secs() / 60),
+        };
+
+        f.write_str(&label)
+    }
+}
+
+/// Whether the system is suspended before the screen would be turned off, which then never
+/// is while idle.
+fn suspends_first(screen_off: &ScreenOff, suspend: &Suspend, source: PowerSource) -> bool {
+    match (screen_off.timeout(source), suspend.timeout(source)) {
+        (Some(blank), Some(suspend)) => blank > suspend,
+        (None, Some(_)) => true,
+        _ => false,
+    }
+}
+
+/// Times which may be chosen, in minutes, followed by never.
+///
+/// The current time is included among them if it was set to another, such as by an
+/// earlier version, so that it is shown as it is.
+fn timeout_choices(minutes: &[u64], current: Timeout) -> Vec<Timeout> {
+    let mut choices: Vec<Timeout> = minutes
+        .iter()
+        .map(|minutes| Timeout(Some(Duration::from_secs(minutes * 60))))
+        .collect();
+
+    if let Timeout(Some(time)) = current {
+        if !choices.contains(&current) {
+            let index = choices.partition_point(|choice| choice.0 < Some(time));
+            choices.insert(index, current);
+        }
+    }
+
+    choices.push(Timeout(None));
+    choices
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Model;
+
+    fn page() -> page::Meta {
+        page::Meta::new("power", "preferences-system-power-symbolic")
+            .title(fl!("power"))
+            .description(fl!("power", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![
+            sections.insert(battery()),
+            sections.insert(usage()),
+            sections.insert(devices()),
+            sections.insert(charge_limit()),
+            sections.insert(profile()),
+            sections.insert(brightness()),
+            sections.insert(screen_off()),
+            sections.insert(suspend()),
+            sections.insert(power_button()),
+        ])
+    }
+
+    fn load(_page: page::Entity) -> page::PageTask {
+        Box::pin(async move {
+            let loaded = page::background(|| Loaded {
+                screen_off: power::screen_off(),
+                suspend: power::suspend(),
+                power_button: power::power_button(),
+                managed: power::managed(),
+                hardware: Hardware::probe(),
+                docked: power::has_external_display(),
+                auto_brightness: config::load::<AutoBrightness>("power-auto-brightness"),
+                charge_limit: power::charge_limit(),
+                profiles: None,
+                idle_inhibitors: Vec::new(),
+            })
+            .await;
+
+            let mut loaded = loaded.unwrap_or_default();
+            loaded.hardware.hibernate &= logind_can_hibernate().await;
+
+            if loaded.hardware.battery {
+                loaded.history = battery::history(HISTORY_SPAN).await.unwrap_or_default();
+            }
+            loaded.profiles = profiles::load().await.ok();
+            loaded.idle_inhibitors = idle_inhibitors().await;
+
+            crate::Message::Power(Message::Loaded(loaded))
+        })
+    }
+
+    fn subscription(model: &Model) -> Subscription<crate::Message> {
+        let mut subscriptions = Vec::new();
+
+        if model.hardware.light_sensor && model.auto_brightness.enabled {
+            subscriptions.push(light_level());
+        }
+
+        if model.hardware.battery {
+            subscriptions.push(
+                cosmic::iced::time::every(HISTORY_INTERVAL)
+                    .map(|_| crate::Message::Power(Message::HistoryRefresh)),
+            );
+        }
+
+        // Devices which report their batteries are watched even on systems without one.
+        subscriptions.push(battery::changes());
+
+        if !model.devices.is_empty() {
+            subscriptions.push(
+                cosmic::iced::time::every(DEVICE_AGE_INTERVAL)
+                    .map(|_| crate::Message::Power(Message::DevicesAged)),
+            );
+        }
+
+        if model.profiles.is_some() {
+            subscriptions.push(profiles::changes());
+        }
+
+        if let Some(custom) = &model.charge_limit_custom {
+            subscriptions.push(
+                custom
+                    .subscription()
+                    .map(|message| crate::Message::Power(Message::ChargeLimitCustom(message))),
+            );
+        }
+
+        Subscription::batch(subscriptions)
+    }
+}
+
+fn battery() -> Section {
+    Section::new()
+        .title(fl!("power-battery"))
+        .descriptions(vec![
+            fl!("power-battery", "health"),
+            fl!("power-battery", "charging"),
+            fl!("power-battery", "discharging"),
+            fl!("power-battery", "full"),
+        ])
+        // Hidden on desktops, and while UPower reports no battery.
+        .visible_when(|pages| {
+            pages
+                .resource::<Model>()
+                .map_or(false, |model| !model.batteries.is_empty())
+        })
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+
+            model
+                .batteries
+                .iter()
+                .fold(settings::view_section(&section.title), |view, battery| {
+                    let charge = row!(
+                        text(battery_status(battery)),
+                        text(fl!(
+                            "power-battery",
+                            "percent",
+                            value = battery.percentage.round() as u32
+                        )),
+                    )
+                    .spacing(16)
+                    .align_items(Alignment::Center);
+
+                    let view = view.add(settings::item(&battery.name, charge));
+
+                    let (Some(health), Some(raw)) = (battery.clamped_health(), battery.health)
+                    else {
+                        return view;
+                    };
+
+                    let health = text(fl!(
+                        "power-battery",
+                        "percent",
+                        value = health.round() as u32
+                    ));
+
+                    // Readings above the design capacity are implausible, so the reading is
+                    // only given as it was reported in a tooltip.
+                    let health: Element<_> = if raw > 100.0 {
+                        tooltip(
+                            health,
+                            fl!("power-battery", "health-raw", value = raw.round() as u32),
+                            tooltip::Position::Bottom,
+                        )
+                        .style(theme::Container::Box)
+                        .into()
+                    } else {
+                        health.into()
+                    };
+
+                    view.add(settings::item(&desc[0], health))
+                })
+                .apply(Element::from)
+                .map(crate::Message::Power)
+        })
+}
+
+fn usage() -> Section {
+    Section::new()
+        .title(fl!("power-usage"))
+        .subtitle(fl!("power-usage", "subtitle"))
+        .descriptions(vec![
+            fl!("power-usage", "full"),
+            fl!("power-usage", "empty"),
+            fl!("power-usage", "start"),
+            fl!("power-usage", "middle"),
+            fl!("power-usage", "now"),
+            fl!("power-usage", "charging"),
+        ])
+        .collapsible()
+        // Hidden on desktops, and while UPower reports no battery.
+        .visible_when(|pages| {
+            pages
+                .resource::<Model>()
+                .map_or(false, |model| !model.batteries.is_empty())
+        })
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+            let view = settings::view_section(&section.title);
+
+            // Without a history, the charge is summarized as it is now.
+            if model.history_graph.is_empty() {
+                let summary = match model.batteries.first() {
+                    Some(battery) => fl!(
+                        "power-usage",
+                        "no-history",
+                        percent = battery.percentage.round() as u32,
+                        status = battery_status(battery)
+                    ),
+                    None => fl!("power-usage", "no-history-unknown"),
+                };
+
+                return view
+                    .add(text(summary))
+                    .apply(Element::from)
+                    .map(crate::Message::Power);
+            }
+
+            let max_gap = HISTORY_MAX_GAP.as_secs_f32() / HISTORY_SPAN.as_secs_f32();
+
+            let graph = battery_graph(&model.history_graph, Message::HistoryHovered)
+                .max_gap(max_gap)
+                .height(GRAPH_HEIGHT);
+
+            // The charge under the pointer is told in a tooltip which follows it.
+            let hovered = model
+                .history_hovered
+                .and_then(|index| model.history.get(index))
+                .map(|sample| {
+                    fl!(
+                        "power-usage",
+                        "sample",
+                        time = chrono::DateTime::<chrono::Local>::from(sample.time)
+                            .format("%H:%M")
+                            .to_string(),
+                        percent = sample.percentage.round() as u32
+                    )
+                });
+
+            let graph: Element<_> = match hovered {
+                Some(label) => tooltip(graph, label, tooltip::Position::FollowCursor)
+                    .style(theme::Container::Box)
+                    .into(),
+                None => graph.into(),
+            };
+
+            let levels = column!(
+                text(&desc[0]).size(12),
+                vertical_space(Length::Fill),
+                text(&desc[1]).size(12),
+            )
+            .height(Length::Units(GRAPH_HEIGHT));
+
+            let times = row!(
+                text(&desc[2]).size(12),
+                horizontal_space(Length::Fill),
+                text(&desc[3]).size(12),
+                horizontal_space(Length::Fill),
+                text(&desc[4]).size(12),
+            );
+
+            view.add(
+                column!(
+                    row!(levels, graph).spacing(8),
+                    times,
+                    text(&desc[5]).size(12),
+                )
+                .spacing(8),
+            )
+            .apply(Element::from)
+            .map(crate::Message::Power)
+        })
+}
+
+fn devices() -> Section {
+    Section::new()
+        .title(fl!("power-devices"))
+        .descriptions(vec![
+            fl!("power-devices", "desc"),
+            fl!("power-devices", "low"),
+            fl!("power-battery", "charging"),
+            fl!("power-battery", "discharging"),
+            fl!("power-battery", "full"),
+            fl!("power-battery", "not-charging"),
+        ])
+        // Hidden while no connected device reports its battery.
+        .visible_when(|pages| {
+            pages
+                .resource::<Model>()
+                .map_or(false, |model| !model.devices.is_empty())
+        })
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+
+            model
+                .devices
+                .iter()
+                .fold(settings::view_section(&section.title), |view, device| {
+                    let percent = text(fl!(
+                        "power-battery",
+                        "percent",
+                        value = device.percentage.round() as u32
+                    ));
+
+                    let low = device.percentage < DEVICE_LOW_PERCENT
+                        && !matches!(device.state, State::Charging | State::Full);
+
+                    // A low battery is marked beside its charge, with a tooltip which says so.
+                    let charge: Element<_> = if low {
+                        let warning = tooltip(
+                            icon("battery-caution-symbolic", 16).style(theme::Svg::Symbolic),
+                            &desc[1],
+                            tooltip::Position::Bottom,
+                        )
+                        .style(theme::Container::Box);
+
+                        row!(warning, percent)
+                            .spacing(8)
+                            .align_items(Alignment::Center)
+                            .into()
+                    } else {
+                        percent.into()
+                    };
+
+                    view.add(
+                        settings_row(&device.name, device_status(desc, device.state))
+                            .icon(device.icon)
+                            .control(charge),
+                    )
+                })
+                .into()
+        })
+}
+
+/// Whether a connected device is charging, from the descriptions of its section.
+///
+/// UPower gives no estimate of the time which devices take to charge or discharge.
+fn device_status(desc: &[String], state: State) -> &str {
+    match state {
+        State::Charging => &desc[2],
+        State::Discharging => &desc[3],
+        State::Full => &desc[4],
+        State::NotCharging => &desc[5],
+        State::Empty | State::Unknown => "",
+    }
+}
+
+/// Whether a battery is charging, and the time which it is estimated to take.
+fn battery_status(battery: &Battery) -> String {
+    let estimate = battery.estimate.map(duration);
+
+    match (battery.state, estimate) {
+        (State::Charging, Some(time)) => fl!("power-battery", "until-full", time = time),
+        (State::Charging, None) => fl!("power-battery", "charging"),
+        (State::Discharging, Some(time)) => fl!("power-battery", "until-empty", time = time),
+        (State::Discharging, None) => fl!("power-battery", "discharging"),
+        (State::Full, _) => fl!("power-battery", "full"),
+        (State::NotCharging, _) => fl!("power-battery", "not-charging"),
+        (State::Empty, _) => fl!("power-battery", "empty"),
+        (State::Unknown, _) => String::new(),
+    }
+}
+
+/// A time in hours and minutes, such as `2 h 15 min`.
+fn duration(time: Duration) -> String {
+    let minutes = time.as_secs() / 60;
+
+    if minutes < 60 {
+        fl!("power-battery", "minutes", minutes = minutes)
+    } else {
+        fl!(
+            "power-battery",
+            "hours",
+            hours = minutes / 60,
+            minutes = minutes % 60
+        )
+    }
+}
+
+fn charge_limit() -> Section {
+    Section::new()
+        .title(fl!("power-charge-limit"))
+        .descriptions(vec![
+            fl!("power-charge-limit", "desc"),
+            fl!("power-charge-limit", "full"),
+            fl!("power-charge-limit", "balanced"),
+            fl!("power-charge-limit", "lifespan"),
+            fl!("power-charge-limit", "custom"),
+            fl!("power-charge-limit", "custom-value"),
+            fl!("power-charge-limit", "apply"),
+        ])
+        // Hidden unless the firmware can stop charging at a limit.
+        .visible_when(|pages| {
+            pages
+                .resource::<Model>()
+                .map_or(false, |model| model.charge_limit.is_some())
+        })
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+
+            let mut view = settings::view_section(&section.title).add(text(&desc[0]).size(12));
+
+            let (Some(limit), Some(custom)) = (&model.charge_limit, &model.charge_limit_custom)
+            else {
+                return view.apply(Element::from).map(crate::Message::Power);
+            };
+
+            let selected = if model.custom_limit {
+                Limit::Custom
+            } else {
+                Limit::of(limit.end)
+            };
+
+            view = view.add(radio_list(
+                &model.charge_limit_entries,
+                Some(&selected),
+                Message::ChargeLimitSelected,
+            ));
+
+            if selected == Limit::Custom {
+                let mut apply = button(text(&desc[6])).style(theme::Button::Secondary);
+
+                if custom.value() != limit.end {
+                    apply = apply.on_press(Message::ChargeLimitApply);
+                }
+
+                view = view.add(settings::item(
+                    &desc[5],
+                    row!(custom.view().map(Message::ChargeLimitCustom), apply)
+                        .spacing(8)
+                        .align_items(Alignment::Center),
+                ));
+            }
+
+            if let Some(why) = &model.charge_limit_error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Power)
+        })
+}
+
+fn profile() -> Section {
+    Section::new()
+        .title(fl!("power-profile"))
+        .descriptions(vec![
+            fl!("power-profile", "performance"),
+            fl!("power-profile", "balanced"),
+            fl!("power-profile", "power-saver"),
+            fl!("power-profile", "unavailable"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let mut view = settings::view_section(&section.title);
+
+            let Some(profiles) = &model.profiles else {
+                return view
+                    .add(text(&section.descriptions[3]))
+                    .apply(Element::from)
+                    .map(crate::Message::Power);
+            };
+
+            view = view.add(radio_list(
+                &model.profile_entries,
+                Some(&profiles.active),
+                Message::ProfileSelected,
+            ));
+
+            // Performance is only reduced while its profile is active.
+            if let (Profile::Performance, Some(reasons)) = (profiles.active, &profiles.degraded) {
+                view = view.add(text(profiles::degraded_reason(reasons)).size(12));
+            }
+
+            if let Some(why) = &model.profile_error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Power)
+        })
+}
+
+fn brightness() -> Section {
+    Section::new()
+        .title(fl!("power-brightness"))
+        .descriptions(vec![
+            fl!("power-brightness", "auto"),
+            fl!("power-brightness", "auto-desc"),
+            fl!("power-brightness", "sensitivity"),
+            fl!("power-brightness", "paused"),
+        ])
+        // Hidden unless an ambient light sensor and a backlight are present.
+        .visible_when(|pages| {
+            pages
+                .resource::<Model>()
+                .map_or(false, |model| model.hardware.light_sensor)
+        })
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+            let auto_brightness = model.auto_brightness;
+
+            let mut view = settings::view_section(&section.title).add(
+                settings::item::builder(&desc[0])
+                    .description(&desc[1])
+                    .control(toggler(None, auto_brightness.enabled, move |enabled| {
+                        Message::AutoBrightnessChanged(AutoBrightness {
+                            enabled,
+                            ..auto_brightness
+                        })
+                    })),
+            );
+
+            if auto_brightness.enabled {
+                let sensitivity = model.sensitivity.unwrap_or(auto_brightness.sensitivity);
+
+                view = view.add(
+                    labeled_slider(
+                        &desc[2],
+                        power::SENSITIVITY,
+                        sensitivity,
+                        Message::SensitivityDragged,
+                    )
+                    .step(5)
+                    .on_release(Message::AutoBrightnessChanged(AutoBrightness {
+                        sensitivity,
+                        ..auto_brightness
+                    }))
+                    .readout(|sensitivity| fl!("power-brightness", "percent", value = sensitivity)),
+                );
+
+                if matches!(model.paused_until, Some(until) if Instant::now() < until) {
+                    view = view.add(text(&desc[3]).size(12));
+                }
+            }
+
+            view.apply(Element::from).map(crate::Message::Power)
+        })
+}
+
+fn screen_off() -> Section {
+    Section::new()
+        .title(fl!("power-screen-off"))
+        .descriptions(vec![
+            fl!("power-screen-off", "after"),
+            fl!("power-screen-off", "on-ac"),
+            fl!("power-screen-off", "on-battery"),
+            fl!("power-screen-off", "dim"),
+            fl!("power-screen-off", "lock"),
+            fl!("power-screen-off", "lock-delay"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+            let screen_off = &model.screen_off;
+
+            let timeout = |source| {
+                let current = Timeout(screen_off.timeout(source));
+
+                pick_list(
+                    timeout_choices(&SCREEN_OFF_MINUTES, current),
+                    Some(current),
+                    move |timeout: Timeout| Message::ScreenTimeoutChanged(source, timeout.0),
+                )
+            };
+
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &model.error {
+                view = view.add(text(why));
+            }
+
+            // Only the time while plugged in applies to systems without a battery.
+            view = if model.hardware.battery {
+                view.add(settings::item(&desc[1], timeout(PowerSource::Ac)))
+                    .add(settings::item(&desc[2], timeout(PowerSource::Battery)))
+            } else {
+                view.add(settings::item(&desc[0], timeout(PowerSource::Ac)))
+            };
+
+            let sources: &[PowerSource] = if model.hardware.battery {
+                &[PowerSource::Ac, PowerSource::Battery]
+            } else {
+                &[PowerSource::Ac]
+            };
+
+            if sources
+                .iter()
+                .any(|source| suspends_first(screen_off, &model.suspend, *source))
+            {
+                view = view.add(text(fl!("power-screen-off", "after-suspend")).size(12));
+            }
+
+            if !model.idle_inhibitors.is_empty() {
+                view = view.add(
+                    text(fl!(
+                        "power-screen-off",
+                        "inhibited",
+                        apps = model.idle_inhibitors.join(", ")
+                    ))
+                    .size(12),
+                );
+            }
+
+            view = view
+                .add(settings::item(
+                    &desc[3],
+                    toggler(None, screen_off.dim, Message::DimScreenChanged),
+                ))
+                .add(settings::item(
+                    &desc[4],
+                    toggler(None, screen_off.lock, Message::LockAfterBlankChanged),
+                ));
+
+            if screen_off.lock {
+                let delays: Vec<Timeout> = LOCK_DELAY_SECONDS
+                    .iter()
+                    .map(|seconds| Timeout(Some(Duration::from_secs(*seconds))))
+                    .collect();
+
+                view = view.add(settings::item(
+                    &desc[5],
+                    pick_list(
+                        delays,
+                        Some(Timeout(Some(screen_off.lock_delay))),
+                        |delay: Timeout| Message::LockDelayChanged(delay.0.unwrap_or_default()),
+                    ),
+                ));
+            }
+
+            view.apply(Element::from).map(crate::Message::Power)
+        })
+}
+
+fn suspend() -> Section {
+    Section::new()
+        .title(fl!("power-suspend"))
+        .descriptions(vec![
+            fl!("power-suspend", "after"),
+            fl!("power-suspend", "on-ac"),
+            fl!("power-suspend", "on-battery"),
+            fl!("power-suspend", "lid"),
+            fl!("power-suspend", "managed"),
+            fl!("power-suspend", "lid-on-ac"),
+            fl!("power-suspend", "lid-on-battery"),
+            fl!("power-suspend", "docked"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+            let suspend = model.suspend;
+
+            let timeout = |title, source| {
+                let mut item = settings::item::builder(title);
+
+                if model.managed.suspend(source) {
+                    item = item.description(&desc[4]);
+                }
+
+                item.control(suspend_timeout(model, source))
+            };
+
+            let mut view = settings::view_section(&section.title);
+
+            // Only the time while plugged in applies to systems without a battery.
+            view = if model.hardware.battery {
+                view.add(timeout(&desc[1], PowerSource::Ac))
+                    .add(timeout(&desc[2], PowerSource::Battery))
+            } else {
+                view.add(timeout(&desc[0], PowerSource::Ac))
+            };
+
+            if model.hardware.lid {
+                let lid = |source| lid_action(model, source);
+
+                view = if model.hardware.battery {
+                    view.add(settings::item(&desc[5], lid(PowerSource::Ac)))
+                        .add(settings::item(&desc[6], lid(PowerSource::Battery)))
+                } else {
+                    view.add(settings::item(&desc[3], lid(PowerSource::Ac)))
+                };
+
+                if model.docked {
+                    view = view.add(text(&desc[7]).size(12));
+                }
+            }
+
+            view.apply(Element::from).map(crate::Message::Power)
+        })
+}
+
+fn power_button() -> Section {
+    Section::new()
+        .title(fl!("power-button"))
+        .descriptions(vec![
+            fl!("power-button", "action"),
+            fl!("power-button", "no-hibernate"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("power model is missing");
+
+            let desc = &section.descriptions;
+            let current = model.power_button;
+
+            // Hibernating is only offered if logind permits it, though it is kept while it
+            // is the current action, so that it is shown as it is.
+            let actions: Vec<PowerButton> = PowerButtonAction::ALL
+                .into_iter()
+                .filter(|action| {
+                    *action != PowerButtonAction::Hibernate
+                        || model.hardware.hibernate
+                        || *action == current
+                })
+                .map(PowerButton)
+                .collect();
+
+            let dropdown = pick_list(
+                actions,
+                Some(PowerButton(current)),
+                |action: PowerButton| Message::PowerButtonChanged(action.0),
+            );
+
+            let control: Element<_> = if model.hardware.hibernate {
+                dropdown.into()
+            } else {
+                row!(
+                    info_popover(&desc[1], model.hibernate_info, Message::HibernateInfo),
+                    dropdown,
+                )
+                .spacing(8)
+                .align_items(Alignment::Center)
+                .into()
+            };
+
+            settings::view_section(&section.title)
+                .add(settings::item(&desc[0], control))
+                .apply(Element::from)
+                .map(crate::Message::Power)
+        })
+}
+
+/// A dropdown of what happens when the lid is closed.
+///
+/// Hibernating is only offered if the system is able to resume from it. Doing nothing on
+/// battery is confirmed first, as a laptop which is put away while running may overheat.
+fn lid_action(model: &Model, source: PowerSource) -> Element<'static, Message> {
+    let suspend = model.suspend;
+
+    let actions: Vec<Lid> = LidAction::ALL
+        .into_iter()
+        .filter(|action| *action != LidAction::Hibernate || model.hardware.hibernate)
+        .map(Lid)
+        .collect();
+
+    pick_list(actions, Some(Lid(suspend.lid(source))), move |lid: Lid| {
+        let mut changed = suspend;
+
+        match source {
+            PowerSource::Ac => changed.lid_on_ac = lid.0,
+            PowerSource::Battery => changed.lid_on_battery = lid.0,
+        }
+
+        if source == PowerSource::Battery && lid.0 == LidAction::Nothing {
+            Message::ConfirmLidNothing(changed)
+        } else {
+            Message::SuspendConfigChanged(changed)
+        }
+    })
+    .into()
+}
+
+/// A dropdown of the time after which the system is suspended.
+///
+/// The effective time is shown whatever it is, but cannot be changed while an administrator
+/// manages it.
+fn suspend_timeout(model: &Model, source: PowerSource) -> Element<'static, Message> {
+    let suspend = model.suspend;
+    let current = Timeout(suspend.timeout(source));
+
+    if model.managed.suspend(source) {
+        return row!(
+            icon("changes-prevent-symbolic", 16).style(theme::Svg::Symbolic),
+            text(current.to_string()),
+        )
+        .spacing(8)
+        .align_items(Alignment::Center)
+        .into();
+    }
+
+    pick_list(
+        timeout_choices(&SUSPEND_MINUTES, current),
+        Some(current),
+        move |timeout: Timeout| {
+            let mut suspend = suspend;
+
+            match source {
+                PowerSource::Ac => suspend.on_ac = timeout.0,
+                PowerSource::Battery => suspend.on_battery = timeout.0,
+            }
+
+            Message::SuspendConfigChanged(suspend)
+        },
+    )
+    .into()
+}
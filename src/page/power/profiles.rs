@@ -0,0 +1,171 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Power profiles of power-profiles-daemon, which trade performance for battery life.
+
+use cosmic::{iced::Subscription, iced_native::subscription};
+use std::collections::HashMap;
+use zbus::zvariant::OwnedValue;
+
+use super::Message;
+
+const DESTINATION: &str = "net.hadess.PowerProfiles";
+const PATH: &str = "/net/hadess/PowerProfiles";
+
+/// A power profile, listed from the fastest to the most frugal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Performance,
+    Balanced,
+    PowerSaver,
+}
+
+impl Profile {
+    pub const ALL: [Self; 3] = [Self::Performance, Self::Balanced, Self::PowerSaver];
+
+    /// The name of the profile as the daemon knows it.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Performance => "performance",
+            Self::Balanced => "balanced",
+            Self::PowerSaver => "power-saver",
+        }
+    }
+
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.name() == name)
+    }
+}
+
+/// The profiles which the daemon offers, and the one which is active.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profiles {
+    pub active: Profile,
+    pub available: Vec<Profile>,
+    /// Why performance is reduced while its profile is active, such as `lap-detected`.
+    pub degraded: Option<String>,
+}
+
+async fn proxy(connection: &zbus::Connection) -> zbus::Result<zbus::Proxy<'static>> {
+    zbus::Proxy::new(connection, DESTINATION, PATH, DESTINATION).await
+}
+
+/// Reads the profiles from the daemon.
+///
+/// # Errors
+///
+/// Fails if the daemon is not running.
+pub async fn load() -> zbus::Result<Profiles> {
+    let connection = zbus::Connection::system().await?;
+    read(&proxy(&connection).await?).await
+}
+
+async fn read(proxy: &zbus::Proxy<'_>) -> zbus::Result<Profiles> {
+    let active: String = proxy.get_property("ActiveProfile").await?;
+    let profiles: Vec<HashMap<String, OwnedValue>> = proxy.get_property("Profiles").await?;
+    let degraded: String = proxy.get_property("PerformanceDegraded").await?;
+
+    let available = profiles
+        .iter()
+        .filter_map(|profile| {
+            let name: &str = profile.get("Profile")?.downcast_ref()?;
+            Profile::from_name(name)
+        })
+        .collect();
+
+    Ok(Profiles {
+        active: Profile::from_name(&active).unwrap_or(Profile::Balanced),
+        available,
+        degraded: Some(degraded).filter(|reason| !reason.is_empty()),
+    })
+}
+
+/// Activates a profile, and reads the profiles back as the daemon applied it.
+///
+/// # Errors
+///
+/// Fails if the daemon is not running, or refused the profile.
+pub async fn set(profile: Profile) -> zbus::Result<Profiles> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = proxy(&connection).await?;
+
+    proxy
+        .set_property("ActiveProfile", profile.name())
+        .await
+        .map_err(zbus::Error::from)?;
+
+    read(&proxy).await
+}
+
+enum Watch {
+    Starting,
+    Watching(zbus::Proxy<'static>, zbus::SignalStream<'static>),
+    Finished,
+}
+
+/// The profiles whenever the daemon changes them, such as by the function keys of a laptop,
+/// or `None` once the daemon has gone away.
+pub fn changes() -> Subscription<crate::Message> {
+    use futures_util::StreamExt;
+
+    subscription::unfold("power-profiles", Watch::Starting, |state| async move {
+        match state {
+            Watch::Starting => {
+                let watch = async {
+                    let connection = zbus::Connection::system().await?;
+                    let proxy = proxy(&connection).await?;
+
+                    let properties = zbus::Proxy::new(
+                        &connection,
+                        DESTINATION,
+                        PATH,
+                        "org.freedesktop.DBus.Properties",
+                    )
+                    .await?;
+
+                    let signals = properties.receive_signal("PropertiesChanged").await?;
+                    zbus::Result::Ok((proxy, signals))
+                };
+
+                match watch.await {
+                    Ok((proxy, signals)) => (None, Watch::Watching(proxy, signals)),
+                    Err(_) => (None, Watch::Finished),
+                }
+            }
+
+            Watch::Watching(proxy, mut signals) => {
+                if signals.next().await.is_none() {
+                    return (
+                        Some(crate::Message::Power(Message::ProfilesChanged(None))),
+                        Watch::Finished,
+                    );
+                }
+
+                // The profiles are read back whole, rather than pieced together from the
+                // properties which changed.
+                let profiles = read(&proxy).await.ok();
+                let message = crate::Message::Power(Message::ProfilesChanged(profiles));
+
+                (Some(message), Watch::Watching(proxy, signals))
+            }
+
+            Watch::Finished => std::future::pending().await,
+        }
+    })
+}
+
+/// Describes why performance is reduced, of which the daemon may give several reasons.
+#[must_use]
+pub fn degraded_reason(reasons: &str) -> String {
+    reasons
+        .split(',')
+        .map(|reason| match reason.trim() {
+            "lap-detected" => fl!("power-profile", "degraded-lap"),
+            "high-operating-temperature" => fl!("power-profile", "degraded-temperature"),
+            other => fl!("power-profile", "degraded-other", reason = other),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -4,7 +4,7 @@
 use derive_setters::Setters;
 use regex::Regex;
 
-use crate::SettingsApp;
+use crate::{widget::Confirm, SettingsApp};
 
 slotmap::new_key_type! {
     /// ID of a page section
@@ -17,39 +17,141 @@ slotmap::new_key_type! {
 pub struct Section {
     #[setters(into)]
     pub title: String,
+    /// Shown beneath the title of a collapsible section.
+    #[setters(into)]
+    pub subtitle: String,
     pub descriptions: Vec<String>,
     pub view_fn: for<'a> fn(&'a SettingsApp, &'a Section) -> cosmic::Element<'a, crate::Message>,
     #[setters(bool)]
     pub search_ignore: bool,
+    /// Whether the section collapses beneath its title, which is shown by the page.
+    #[setters(bool)]
+    pub collapsible: bool,
+    /// Whether the section is shown, such as only while the hardware which it configures is
+    /// present. Hidden sections are left out of their page and of search results.
+    #[setters(skip)]
+    pub visible: for<'a> fn(&'a super::Model) -> bool,
+    /// Buttons shown beneath the section, which perform actions rather than show values.
+    #[setters(skip)]
+    pub buttons: Vec<Button>,
+}
+
+/// A button of a section which performs an action when pressed, such as to run a test.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Button {
+    pub label: String,
+    pub message: crate::Message,
+    /// Asks to confirm the action before its message is emitted.
+    pub confirm: Option<Confirm>,
+    /// Whether the button is dimmed, and cannot be pressed.
+    pub disabled: for<'a> fn(&'a super::Model) -> bool,
+}
+
+impl Button {
+    pub fn new(label: impl Into<String>, message: crate::Message) -> Self {
+        Self {
+            label: label.into(),
+            message,
+            confirm: None,
+            disabled: |_| false,
+        }
+    }
+
+    /// Asks to confirm the action with a dialog before it is taken.
+    pub fn confirm(
+        mut self,
+        title: impl Into<String>,
+        body: impl Into<String>,
+        confirm_label: impl Into<String>,
+    ) -> Self {
+        let confirm = Confirm::new(title, body, confirm_label, self.message.clone());
+        self.confirm = Some(confirm);
+        self
+    }
+
+    /// Disables the button while the predicate holds for the models of the pages.
+    pub fn disabled_when(mut self, disabled: for<'a> fn(&'a super::Model) -> bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// The message emitted when the button is pressed, which asks for confirmation first if
+    /// the action is to be confirmed.
+    #[must_use]
+    pub fn on_press(&self) -> crate::Message {
+        match &self.confirm {
+            Some(confirm) => crate::Message::ShowConfirm(confirm.clone()),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// The state of the data shown by a section, which a page may be fetching in the background.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SectionState {
+    /// A spinner is shown in place of the section.
+    Loading,
+    #[default]
+    Ready,
+    /// The error is shown in place of the section, with a button to fetch it again.
+    Error(String),
 }
 
 impl Section {
     pub const fn new() -> Self {
         Self {
             title: String::new(),
+            subtitle: String::new(),
             descriptions: Vec::new(),
             view_fn: Self::unimplemented,
             search_ignore: false,
+            collapsible: false,
+            visible: Self::always,
+            buttons: Vec::new(),
         }
     }
 
+    /// Adds a button beneath the section, which emits the message when pressed.
+    pub fn button(self, label: impl Into<String>, message: crate::Message) -> Self {
+        self.button_with(Button::new(label, message))
+    }
+
+    /// Adds a button beneath the section, such as one which is confirmed or disabled.
+    pub fn button_with(mut self, button: Button) -> Self {
+        self.buttons.push(button);
+        self
+    }
+
+    /// Shows the section only while the predicate holds for the models of the pages.
+    pub fn visible_when(mut self, visible: for<'a> fn(&'a super::Model) -> bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    fn always(_pages: &super::Model) -> bool {
+        true
+    }
+
     #[must_use]
     pub fn matches_search(&self, rule: &Regex) -> bool {
         if self.search_ignore {
             return false;
         }
 
-        if rule.is_match(self.title.as_str()) {
+        if rule.is_match(&normalize(&self.title)) {
             return true;
         }
 
         for description in &self.descriptions {
-            if rule.is_match(description.as_str()) {
+            if rule.is_match(&normalize(description)) {
                 return true;
             }
         }
 
-        false
+        self.buttons
+            .iter()
+            .any(|button| rule.is_match(&normalize(&button.label)))
     }
 
     #[must_use]
@@ -63,3 +165,63 @@ impl Section {
         .into()
     }
 }
+
+/// Normalizes text for searching, by lowercasing it and folding accented latin letters to
+/// their base letters, so that "sao paulo" matches "São Paulo", whether its accents are
+/// composed into the letters or follow them as combining marks.
+#[must_use]
+pub fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match c {
+            'à'..='å' | 'ā' | 'ă' | 'ą' => normalized.push('a'),
+            'æ' => normalized.push_str("ae"),
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => normalized.push('c'),
+            'ď' | 'đ' => normalized.push('d'),
+            'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => normalized.push('e'),
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => normalized.push('g'),
+            'ĥ' | 'ħ' => normalized.push('h'),
+            'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => normalized.push('i'),
+            'ĵ' => normalized.push('j'),
+            'ķ' => normalized.push('k'),
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => normalized.push('l'),
+            'ñ' | 'ń' | 'ņ' | 'ň' => normalized.push('n'),
+            'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => normalized.push('o'),
+            'œ' => normalized.push_str("oe"),
+            'ŕ' | 'ŗ' | 'ř' => normalized.push('r'),
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => normalized.push('s'),
+            'ß' => normalized.push_str("ss"),
+            'ţ' | 'ť' | 'ŧ' | 'ț' => normalized.push('t'),
+            'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => normalized.push('u'),
+            'ŵ' => normalized.push('w'),
+            'ý' | 'ÿ' | 'ŷ' => normalized.push('y'),
+            'ź' | 'ż' | 'ž' => normalized.push('z'),
+            // Combining diacritical marks, as in decomposed text.
+            '\u{300}'..='\u{36f}' => (),
+            c => normalized.push(c),
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_folds_combining_accents() {
+        // "São Paulo" with its tilde composed into the letter, and following it.
+        assert_eq!(normalize("S\u{e3}o Paulo"), "sao paulo");
+        assert_eq!(normalize("Sa\u{303}o Paulo"), "sao paulo");
+        assert_eq!(normalize("Zu\u{308}rich"), "zurich");
+        assert_eq!(normalize("Re\u{301}seau"), "reseau");
+    }
+
+    #[test]
+    fn normalize_keeps_other_scripts() {
+        assert_eq!(normalize("Ελληνικά"), "ελληνικά");
+        assert_eq!(normalize("日本語"), "日本語");
+    }
+}
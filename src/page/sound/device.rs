@@ -0,0 +1,410 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        alignment,
+        widget::{button, pick_list, tooltip, Column, Row},
+        Alignment, Command, Length, Subscription,
+    },
+    theme,
+    widget::{icon, settings, text},
+    Element,
+};
+use cosmic_settings_sound::{channels, Device};
+use slotmap::SlotMap;
+use std::fmt;
+
+use super::{Message, Sound};
+use crate::{
+    page::{self, section, Content, Section},
+    widget::{debounce, dialog, marked_slider, radio_list::radio_list},
+};
+
+/// The width of the button of each speaker, so that the grid lines up.
+const SPEAKER_WIDTH: u16 = 120;
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Sound;
+
+    fn page() -> page::Meta {
+        page::Meta::new("sound-device", "audio-card-symbolic")
+            .title(fl!("sound-device"))
+            .description(fl!("sound-device", "desc"))
+            .hidden(true)
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![
+            sections.insert(configuration()),
+            sections.insert(balance()),
+            sections.insert(fade()),
+        ])
+    }
+
+    fn load(page: page::Entity) -> page::PageTask {
+        Box::pin(async move { crate::Message::Sound(Message::LoadDevice(page)) })
+    }
+
+    fn on_leave(model: &mut Sound, _page: page::Entity) -> Command<crate::Message> {
+        if !model.speaker_test {
+            return Command::none();
+        }
+
+        model.update(Message::SpeakerTest(false))
+    }
+
+    fn subscription(model: &Sound) -> Subscription<crate::Message> {
+        let sliders = Subscription::batch(vec![
+            model.balance.subscription().map(Message::Balance),
+            model.fade.subscription().map(Message::Fade),
+        ])
+        .map(crate::Message::Sound);
+
+        Subscription::batch(vec![super::events(), sliders])
+    }
+}
+
+/// Where the speakers of common layouts are placed around the listener, from the front left
+/// to the rear right, with the subwoofer in the middle.
+const SPEAKER_GRID: [[&str; 3]; 3] = [
+    ["front-left", "front-center", "front-right"],
+    ["side-left", "lfe", "side-right"],
+    ["rear-left", "rear-center", "rear-right"],
+];
+
+/// A profile listed by its description in a dropdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ProfileChoice {
+    name: String,
+    description: String,
+}
+
+impl fmt::Display for ProfileChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+/// Sums up the positions of channels, such as `Stereo (front-left, front-right)`.
+fn channels(channel_map: &[String]) -> String {
+    let layout = match channel_map.len() {
+        1 => fl!("sound-device", "mono"),
+        2 => fl!("sound-device", "stereo"),
+        6 => fl!("sound-device", "surround-51"),
+        8 => fl!("sound-device", "surround-71"),
+        count => fl!("sound-device", "channel-count", count = count),
+    };
+
+    format!("{layout} ({})", channel_map.join(", "))
+}
+
+fn configuration() -> Section {
+    Section::new()
+        .title(fl!("sound-device"))
+        .descriptions(vec![
+            fl!("sound-device", "profile"),
+            fl!("sound-device", "port"),
+            fl!("sound-device", "channels"),
+            fl!("sound-device", "switching"),
+            fl!("sound-device", "no-output"),
+            fl!("sound-device", "disconnected"),
+            fl!("sound-speaker-test"),
+            fl!("sound-speaker-test", "desc"),
+            fl!("sound-speaker-test", "close"),
+            fl!("sound-speaker-test", "test"),
+        ])
+        .view_fn(|app, section| {
+            let sound = app
+                .pages
+                .resource::<Sound>()
+                .expect("sound model is missing");
+
+            let desc = &section.descriptions;
+            let card = sound.configured_card();
+            let sink = sound.configured_sink();
+
+            if let (true, Some(sink)) = (sound.speaker_test, sink) {
+                return dialog(&desc[6], |_| Message::SpeakerTest(false))
+                    .body(&desc[7])
+                    .content(speaker_test_view(sound, sink))
+                    .cancel(&desc[8])
+                    .apply(Element::from)
+                    .map(crate::Message::Sound);
+            }
+
+            let mut view = settings::view_section(&section.title);
+
+            if let Some(why) = &sound.device_error {
+                view = view.add(text(why));
+            }
+
+            if let Some(card) = card {
+                // Profiles which cannot be used are left out, unless the card is set to one.
+                let profiles: Vec<ProfileChoice> = card
+                    .profiles
+                    .iter()
+                    .filter(|profile| profile.available || profile.name == card.active_profile)
+                    .map(|profile| ProfileChoice {
+                        name: profile.name.clone(),
+                        description: profile.description.clone(),
+                    })
+                    .collect();
+
+                let profile = sound
+                    .switching_profile
+                    .as_ref()
+                    .unwrap_or(&card.active_profile);
+
+                let selected = profiles.iter().find(|p| &p.name == profile).cloned();
+
+                view = view.add(settings::item(
+                    &desc[0],
+                    pick_list(profiles, selected, |choice| {
+                        Message::ProfileChanged(choice.name)
+                    }),
+                ));
+            }
+
+            match sink {
+                // The sink of the previous profile may linger until the new one replaces it.
+                _ if sound.switching_profile.is_some() => view = view.add(text(&desc[3])),
+
+                Some(sink) => {
+                    if !sound.ports.is_empty() {
+                        view = view.add(text(&desc[1])).add(radio_list(
+                            &sound.ports,
+                            Some(&sink.active_port),
+                            Message::PortChanged,
+                        ));
+                    }
+
+                    if !sink.channel_map.is_empty() {
+                        view = view
+                            .add(settings::item(&desc[2], text(channels(&sink.channel_map))))
+                            .add(settings::item(
+                                &desc[6],
+                                button(text(&desc[9]))
+                                    .style(theme::Button::Secondary)
+                                    .on_press(Message::SpeakerTest(true)),
+                            ));
+                    }
+                }
+
+                None if card.is_some() => view = view.add(text(&desc[4])),
+
+                None => view = view.add(text(&desc[5])),
+            }
+
+            view.apply(Element::from).map(crate::Message::Sound)
+        })
+}
+
+/// A button for each channel of the sink, which plays its test, placed where its speaker is
+/// around the listener.
+///
+/// Layouts which do not fit around the listener are listed instead. Speakers of the grid which
+/// the sink does not have in its current profile are disabled.
+fn speaker_test_view<'a>(sound: &'a Sound, sink: &'a Device) -> Element<'a, Message> {
+    let speaker = |position: &str| {
+        let mut speaker = button(
+            text(speaker_name(position))
+                .width(Length::Fill)
+                .horizontal_alignment(alignment::Horizontal::Center),
+        )
+        .width(Length::Units(SPEAKER_WIDTH))
+        .style(theme::Button::Secondary);
+
+        if sink.channel_map.iter().any(|channel| channel == position) {
+            speaker = speaker.on_press(Message::SpeakerTestPlay(position.to_owned()));
+        }
+
+        speaker
+    };
+
+    let fits_grid = sink.channel_map.iter().all(|channel| {
+        SPEAKER_GRID
+            .iter()
+            .flatten()
+            .any(|position| position == channel)
+    });
+
+    let mut content = if fits_grid {
+        let rows = SPEAKER_GRID.iter().map(|row| {
+            row.iter()
+                .fold(Row::new().spacing(8), |row, position| {
+                    row.push(speaker(position))
+                })
+                .into()
+        });
+
+        Column::with_children(rows.collect())
+            .spacing(8)
+            .align_items(Alignment::Center)
+    } else {
+        let rows = sink
+            .channel_map
+            .iter()
+            .map(|position| speaker(position).into());
+        Column::with_children(rows.collect()).spacing(8)
+    };
+
+    if let Some(why) = &sound.speaker_test_error {
+        content = content.push(text(why));
+    }
+
+    content.width(Length::Fill).into()
+}
+
+/// The name of the speaker of a channel, or its position as the sound server names it.
+fn speaker_name(position: &str) -> String {
+    match position {
+        "mono" => fl!("sound-speaker-test", "mono"),
+        "front-left" => fl!("sound-speaker-test", "front-left"),
+        "front-center" => fl!("sound-speaker-test", "front-center"),
+        "front-right" => fl!("sound-speaker-test", "front-right"),
+        "side-left" => fl!("sound-speaker-test", "side-left"),
+        "lfe" => fl!("sound-speaker-test", "lfe"),
+        "side-right" => fl!("sound-speaker-test", "side-right"),
+        "rear-left" => fl!("sound-speaker-test", "rear-left"),
+        "rear-center" => fl!("sound-speaker-test", "rear-center"),
+        "rear-right" => fl!("sound-speaker-test", "rear-right"),
+        other => other.to_owned(),
+    }
+}
+
+/// Whether the configured sink is found, and has channels which a slider may lean between,
+/// while its speakers are not being tested.
+fn configured_has(pages: &page::Model, has: fn(&[String]) -> bool) -> bool {
+    pages
+        .resource::<Sound>()
+        .filter(|sound| !sound.speaker_test)
+        .and_then(Sound::configured_sink)
+        .map_or(false, |sink| has(&sink.channel_map))
+}
+
+fn balance() -> Section {
+    Section::new()
+        .title(fl!("sound-balance"))
+        .descriptions(vec![
+            fl!("sound-balance", "left"),
+            fl!("sound-balance", "center"),
+            fl!("sound-balance", "right"),
+            fl!("sound-balance", "reset"),
+        ])
+        .visible_when(|pages| configured_has(pages, channels::has_balance))
+        .view_fn(|app, section| {
+            let sound = app
+                .pages
+                .resource::<Sound>()
+                .expect("sound model is missing");
+
+            let Some(sink) = sound.configured_sink() else {
+                return Element::from(text(""));
+            };
+
+            lean_view(
+                &section.title,
+                &section.descriptions,
+                sound.balance(sink),
+                Message::Balance,
+                Message::BalanceCentered,
+            )
+        })
+}
+
+/// Fading is left out for stereo devices, which have no rear channels.
+fn fade() -> Section {
+    Section::new()
+        .title(fl!("sound-fade"))
+        .descriptions(vec![
+            fl!("sound-fade", "rear"),
+            fl!("sound-fade", "center"),
+            fl!("sound-fade", "front"),
+            fl!("sound-fade", "reset"),
+        ])
+        .visible_when(|pages| configured_has(pages, channels::has_fade))
+        .view_fn(|app, section| {
+            let sound = app
+                .pages
+                .resource::<Sound>()
+                .expect("sound model is missing");
+
+            let Some(sink) = sound.configured_sink() else {
+                return Element::from(text(""));
+            };
+
+            lean_view(
+                &section.title,
+                &section.descriptions,
+                sound.fade(sink),
+                Message::Fade,
+                Message::FadeCentered,
+            )
+        })
+}
+
+/// A slider which leans between two ends, such as the left and right, which snaps to the
+/// center and has a button to go back to it.
+///
+/// The descriptions are of the start, center, and end, and of the button as its tooltip.
+fn lean_view<'a>(
+    title: &'a str,
+    descriptions: &'a [String],
+    value: f32,
+    on_change: fn(debounce::Message<f32>) -> Message,
+    on_center: Message,
+) -> Element<'a, crate::Message> {
+    let (start, center, end) = (&descriptions[0], &descriptions[1], &descriptions[2]);
+
+    let slider = marked_slider(-1.0..=1.0, f64::from(value), move |value| {
+        on_change(debounce::Message::SliderPreview(value as f32))
+    })
+    .step(0.05)
+    .snap(0.05)
+    .mark(-1.0, start.as_str())
+    .mark(0.0, center.as_str())
+    .mark(1.0, end.as_str())
+    .readout(move |value| lean(value, start, center, end))
+    .on_release(on_change(debounce::Message::SliderRelease));
+
+    let mut reset = icon("edit-undo-symbolic", 16)
+        .style(theme::Svg::Symbolic)
+        .apply(button)
+        .style(theme::Button::Transparent);
+
+    // Only a device which leans to either end can be brought back to the center.
+    if value.abs() > f32::EPSILON {
+        reset = reset.on_press(on_center);
+    }
+
+    let control = Row::new()
+        .spacing(16)
+        .align_items(Alignment::Center)
+        .push(slider)
+        .push(
+            tooltip(reset, &descriptions[3], tooltip::Position::Bottom)
+                .style(theme::Container::Box),
+        );
+
+    settings::view_section(title)
+        .add(control)
+        .apply(Element::from)
+        .map(crate::Message::Sound)
+}
+
+/// The end which a value leans to, and by how much, such as `Left 20%`.
+fn lean(value: f64, start: &str, center: &str, end: &str) -> String {
+    let percent = (value.abs() * 100.0).round();
+
+    if percent < 1.0 {
+        center.to_owned()
+    } else if value < 0.0 {
+        format!("{start} {percent}%")
+    } else {
+        format!("{end} {percent}%")
+    }
+}
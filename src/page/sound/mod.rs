@@ -0,0 +1,1210 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+pub mod device;
+
+use crate::{
+    page::{self, Content, Section, SectionState},
+    widget::{
+        debounce::{self, Debounce},
+        expander, info_popover, labeled_slider, level_meter,
+        radio_list::{radio_list, radio_list_with_action, Entry},
+    },
+};
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, slider, Row},
+        Alignment, Command, Length, Subscription,
+    },
+    theme,
+    widget::{icon, settings, text, toggler},
+    Element,
+};
+use cosmic_settings_sound::{
+    self as sound,
+    alerts::{self, AlertConfig, AlertSound},
+    Card, Device, Devices, Stream,
+};
+use slotmap::SlotMap;
+use std::path::PathBuf;
+
+use super::section;
+
+/// Updates of the input level per second, which the meter is redrawn with.
+const LEVEL_UPDATES: usize = 30;
+
+/// Bytes of 16-bit samples captured for each level update, whatever the capture rate.
+const LEVEL_CHUNK_SIZE: usize = sound::CAPTURE_RATE * 2 / LEVEL_UPDATES;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    AlertsApplied(Result<(), String>),
+    AlertsLoaded(AlertConfig, Vec<AlertSound>),
+    /// Plays an alert sound once, without choosing it.
+    AlertPreview(Option<PathBuf>),
+    AlertSoundChanged(Option<PathBuf>),
+    AlertSoundsExpanded(bool),
+    AlertVolume(debounce::Message<u32>),
+    /// Allows volumes above 100%, or clamps them back to 100%.
+    Amplification(bool),
+    AmplificationApplied(Result<(), String>),
+    AmplificationInfo(bool),
+    AmplificationLoaded(bool),
+    /// Shows or hides the individual streams of an application.
+    ApplicationExpanded(String, bool),
+    /// The balance of the configured sink, from -1 for the left to 1 for the right.
+    Balance(debounce::Message<f32>),
+    BalanceCentered,
+    /// Opens the configuration of a sink, by its name.
+    Configure(String),
+    DefaultSinkChanged(String),
+    DefaultSourceChanged(String),
+    Devices(Result<Devices, String>),
+    /// The fade of the configured sink, from -1 for the rear to 1 for the front.
+    Fade(debounce::Message<f32>),
+    FadeCentered,
+    /// The capture of the input level stopped, as the source is missing or busy.
+    InputCaptureFailed,
+    InputLevel(f32),
+    Load(page::Entity),
+    /// The configuration of a sink was opened, as the page of the entity.
+    LoadDevice(page::Entity),
+    PortChanged(String),
+    /// The profile of the card of the configured sink was switched, or failed to be.
+    ProfileApplied(Result<(), String>),
+    ProfileChanged(String),
+    Refresh,
+    /// Lists the monitors of sinks among the input devices, or leaves them out.
+    ShowMonitors(bool),
+    SinkMute(bool),
+    SinkVolume(debounce::Message<u32>),
+    SourceMute(bool),
+    SourceVolume(debounce::Message<u32>),
+    /// Opens or closes the test of the speakers of the configured sink.
+    SpeakerTest(bool),
+    /// Plays the test of a channel of the configured sink, by its position.
+    SpeakerTestPlay(String),
+    SpeakerTestPlayed(Result<(), String>),
+    StreamMute(Vec<u32>, bool),
+    /// The volume of the streams of a slider, by their indices.
+    StreamVolume(debounce::Message<(Vec<u32>, u32)>),
+}
+
+/// The sink whose configuration is shown, and the card which it belongs to.
+///
+/// Switching the profile of the card replaces its sink by one of a new name, so the sink is
+/// found again among those of the card by [`Sound::resolve_configured`].
+#[derive(Clone, Debug, Default)]
+pub struct Configured {
+    pub card: String,
+    pub sink: String,
+}
+
+#[derive(Default)]
+pub struct Sound {
+    pub devices: Devices,
+    pub input_level: f32,
+    /// Whether the input level could not be captured from the default source.
+    pub input_capture_failed: bool,
+    pub page: page::Entity,
+    /// The sinks which the default sink is chosen from, by name.
+    pub sinks: Vec<Entry<String>>,
+    /// The sources which the default source is chosen from, by name.
+    pub sources: Vec<Entry<String>>,
+    /// Whether the monitors of sinks, which capture what they play, are listed as sources.
+    pub show_monitors: bool,
+    /// The application whose individual streams are shown.
+    pub expanded_application: Option<String>,
+    /// The volume of the default sink while its slider is dragged.
+    pub sink_volume: Debounce<u32>,
+    pub source_volume: Debounce<u32>,
+    pub stream_volume: Debounce<(Vec<u32>, u32)>,
+    pub alerts: AlertConfig,
+    /// The sounds which alerts are played with, by path, after the choice of no sound.
+    pub alert_sounds: Vec<Entry<Option<PathBuf>>>,
+    pub alert_sounds_expanded: bool,
+    pub alert_volume: Debounce<u32>,
+    pub alert_error: Option<String>,
+    /// Whether volumes may be amplified above 100%.
+    pub amplification: bool,
+    pub amplification_info: bool,
+    pub amplification_error: Option<String>,
+    pub configured: Option<Configured>,
+    pub balance: Debounce<f32>,
+    pub fade: Debounce<f32>,
+    /// The ports which the port of the configured sink is chosen from, by name.
+    pub ports: Vec<Entry<String>>,
+    /// The profile which the card of the configured sink is being switched to, while its
+    /// sink is missing.
+    pub switching_profile: Option<String>,
+    pub device_error: Option<String>,
+    /// Whether the dialog testing the speakers of the configured sink is open.
+    pub speaker_test: bool,
+    pub speaker_test_error: Option<String>,
+    /// The state of the sections of the page, which are loading until devices are found.
+    pub state: SectionState,
+}
+
+impl Sound {
+    #[must_use]
+    pub fn default_sink(&self) -> Option<&Device> {
+        let name = &self.devices.default_sink;
+        self.devices.sinks.iter().find(|sink| &sink.name == name)
+    }
+
+    #[must_use]
+    pub fn default_source(&self) -> Option<&Device> {
+        let name = &self.devices.default_source;
+        self.devices
+            .sources
+            .iter()
+            .find(|source| &source.name == name)
+    }
+
+    fn default_sink_mut(&mut self) -> Option<&mut Device> {
+        let name = &self.devices.default_sink;
+        self.devices
+            .sinks
+            .iter_mut()
+            .find(|sink| &sink.name == name)
+    }
+
+    fn default_source_mut(&mut self) -> Option<&mut Device> {
+        let name = &self.devices.default_source;
+        self.devices
+            .sources
+            .iter_mut()
+            .find(|source| &source.name == name)
+    }
+
+    /// The configured sink, or none while it is missing, such as during a switch of profile.
+    #[must_use]
+    pub fn configured_sink(&self) -> Option<&Device> {
+        let configured = self.configured.as_ref()?;
+        self.devices
+            .sinks
+            .iter()
+            .find(|sink| sink.name == configured.sink)
+    }
+
+    fn configured_sink_mut(&mut self) -> Option<&mut Device> {
+        let configured = self.configured.as_ref()?;
+        self.devices
+            .sinks
+            .iter_mut()
+            .find(|sink| sink.name == configured.sink)
+    }
+
+    /// The balance of the configured sink, which is the one that its slider is dragged to
+    /// while it is dragged.
+    #[must_use]
+    pub fn balance(&self, sink: &Device) -> f32 {
+        self.balance
+            .preview()
+            .copied()
+            .unwrap_or_else(|| sound::channels::balance(&sink.channel_map, &sink.channel_volumes))
+    }
+
+    #[must_use]
+    pub fn fade(&self, sink: &Device) -> f32 {
+        self.fade
+            .preview()
+            .copied()
+            .unwrap_or_else(|| sound::channels::fade(&sink.channel_map, &sink.channel_volumes))
+    }
+
+    /// Changes the volumes of the channels of the configured sink.
+    fn set_channel_volumes(
+        &mut self,
+        change: impl FnOnce(&[String], &[u32]) -> Vec<u32>,
+    ) -> Command<crate::Message> {
+        let Some(sink) = self.configured_sink_mut() else {
+            return Command::none();
+        };
+
+        sink.channel_volumes = change(&sink.channel_map, &sink.channel_volumes);
+
+        let name = sink.name.clone();
+        let volumes = sink.channel_volumes.clone();
+        apply(move || sound::set_sink_channel_volumes(&name, &volumes))
+    }
+
+    /// The card of the configured sink, which is none for sinks that are not of a card.
+    #[must_use]
+    pub fn configured_card(&self) -> Option<&Card> {
+        let configured = self.configured.as_ref()?;
+        self.devices
+            .cards
+            .iter()
+            .find(|card| !card.name.is_empty() && card.name == configured.card)
+    }
+
+    /// Finds the configured sink by its name, or else among the sinks of its card, whose
+    /// names change with its profile, and lists its ports.
+    fn resolve_configured(&mut self) {
+        let Some(configured) = &mut self.configured else {
+            return;
+        };
+
+        let sink = self
+            .devices
+            .sinks
+            .iter()
+            .find(|sink| sink.name == configured.sink)
+            .or_else(|| {
+                self.devices
+                    .sinks
+                    .iter()
+                    .find(|sink| !configured.card.is_empty() && sink.card == configured.card)
+            });
+
+        let Some(sink) = sink else {
+            self.ports.clear();
+            return;
+        };
+
+        sink.name.clone_into(&mut configured.sink);
+
+        self.ports = sink
+            .ports
+            .iter()
+            .map(|port| {
+                let description = if port.available {
+                    String::new()
+                } else {
+                    fl!("sound-device", "unplugged")
+                };
+
+                // Ports which are unplugged stay selectable while they are in use.
+                Entry::new(port.name.clone(), port.description.clone(), description)
+                    .disabled(!port.available && port.name != sink.active_port)
+            })
+            .collect();
+    }
+
+    /// Lists the sources, leaving out monitors unless they are shown, or are the default.
+    fn list_sources(&mut self) {
+        self.sources = self
+            .devices
+            .sources
+            .iter()
+            .filter(|source| {
+                !source.monitor || self.show_monitors || source.name == self.devices.default_source
+            })
+            .map(|source| {
+                Entry::new(source.name.clone(), source.to_string(), source.port.clone())
+                    .icon(source.icon())
+            })
+            .collect();
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::AlertsApplied(result) => self.alert_error = result.err(),
+
+            Message::AlertsLoaded(config, sounds) => {
+                self.alert_sounds =
+                    std::iter::once(Entry::new(None, fl!("sound-alerts", "none"), String::new()))
+                        .chain(sounds.into_iter().map(|sound| {
+                            Entry::new(Some(sound.path), sound.name, String::new())
+                                .action("media-playback-start-symbolic")
+                        }))
+                        .collect();
+
+                self.alerts = config;
+            }
+
+            Message::AlertPreview(Some(path)) => {
+                let volume = self.alert_volume();
+
+                // Errors of a preview are only shown for as long as the next one succeeds.
+                return Command::perform(
+                    page::background(move || alerts::preview(&path, volume)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Sound(Message::AlertsApplied(result))
+                    },
+                );
+            }
+
+            Message::AlertPreview(None) => (),
+
+            Message::AlertSoundChanged(sound) => {
+                self.alerts.sound = sound;
+                return self.apply_alerts();
+            }
+
+            Message::AlertSoundsExpanded(expanded) => self.alert_sounds_expanded = expanded,
+
+            Message::AlertVolume(message) => {
+                if let Some(volume) = self.alert_volume.update(message) {
+                    self.alerts.volume = volume;
+                    return self.apply_alerts();
+                }
+            }
+
+            Message::Amplification(amplification) => {
+                self.amplification = amplification;
+
+                let mut commands = vec![Command::perform(
+                    page::background(move || sound::set_amplification(amplification)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Sound(Message::AmplificationApplied(result))
+                    },
+                )];
+
+                if !amplification {
+                    commands.push(self.clamp_volumes());
+                }
+
+                return Command::batch(commands);
+            }
+
+            Message::AmplificationApplied(result) => self.amplification_error = result.err(),
+
+            Message::AmplificationInfo(open) => self.amplification_info = open,
+
+            Message::AmplificationLoaded(amplification) => self.amplification = amplification,
+
+            Message::ApplicationExpanded(application, expanded) => {
+                self.expanded_application = expanded.then_some(application);
+            }
+
+            Message::Balance(message) => {
+                if let Some(balance) = self.balance.update(message) {
+                    return self.set_channel_volumes(|channel_map, volumes| {
+                        sound::channels::with_balance(channel_map, volumes, balance)
+                    });
+                }
+            }
+
+            Message::BalanceCentered => {
+                self.balance.clear();
+                return self.set_channel_volumes(|channel_map, volumes| {
+                    sound::channels::with_balance(channel_map, volumes, 0.0)
+                });
+            }
+
+            Message::Configure(sink) => {
+                let card = self
+                    .devices
+                    .sinks
+                    .iter()
+                    .find(|device| device.name == sink)
+                    .map(|device| device.card.clone())
+                    .unwrap_or_default();
+
+                self.configured = Some(Configured { card, sink });
+                self.switching_profile = None;
+                self.device_error = None;
+                self.speaker_test = false;
+                self.balance.clear();
+                self.fade.clear();
+                self.resolve_configured();
+
+                return Command::perform(async {}, |_| crate::Message::OpenPage("sound-device"));
+            }
+
+            Message::DefaultSinkChanged(name) => {
+                self.devices.default_sink = name.clone();
+                self.sink_volume.clear();
+                return apply(move || sound::set_default_sink(&name));
+            }
+
+            Message::DefaultSourceChanged(name) => {
+                self.devices.default_source = name.clone();
+                self.source_volume.clear();
+                self.input_level = 0.0;
+                self.input_capture_failed = false;
+                return apply(move || sound::set_default_source(&name));
+            }
+
+            Message::Devices(Ok(devices)) => {
+                self.sinks = devices
+                    .sinks
+                    .iter()
+                    .map(|sink| {
+                        Entry::new(sink.name.clone(), sink.to_string(), sink.port.clone())
+                            .icon(sink.icon())
+                            .action("emblem-system-symbolic")
+                    })
+                    .collect();
+
+                // Streams may end while their slider is dragged, whose rows are then removed.
+                let ended = |indices: &[u32]| {
+                    !devices
+                        .streams
+                        .iter()
+                        .any(|stream| indices.contains(&stream.index))
+                };
+
+                if matches!(self.stream_volume.preview(), Some((indices, _)) if ended(indices)) {
+                    self.stream_volume.clear();
+                }
+
+                self.devices = devices;
+                self.state = SectionState::Ready;
+                self.list_sources();
+                self.resolve_configured();
+
+                // The switch is over once the card reports the profile, whether or not the
+                // profile has a sink.
+                let switched = match (&self.switching_profile, self.configured_card()) {
+                    (Some(profile), Some(card)) => &card.active_profile == profile,
+                    _ => true,
+                };
+
+                if switched {
+                    self.switching_profile = None;
+                }
+            }
+
+            Message::Devices(Err(why)) => self.state = SectionState::Error(why),
+
+            Message::Fade(message) => {
+                if let Some(fade) = self.fade.update(message) {
+                    return self.set_channel_volumes(|channel_map, volumes| {
+                        sound::channels::with_fade(channel_map, volumes, fade)
+                    });
+                }
+            }
+
+            Message::FadeCentered => {
+                self.fade.clear();
+                return self.set_channel_volumes(|channel_map, volumes| {
+                    sound::channels::with_fade(channel_map, volumes, 0.0)
+                });
+            }
+
+            Message::InputCaptureFailed => {
+                self.input_level = 0.0;
+                self.input_capture_failed = true;
+            }
+
+            Message::InputLevel(level) => self.input_level = level,
+
+            Message::Load(page) => {
+                self.page = page;
+
+                // Devices already shown are kept in view while they are refreshed.
+                if self.state != SectionState::Ready || self.devices.sinks.is_empty() {
+                    self.state = SectionState::Loading;
+                }
+
+                return Command::batch(vec![refresh(), load_alerts(), load_amplification()]);
+            }
+
+            Message::LoadDevice(page) => {
+                let title = self
+                    .configured_card()
+                    .map(|card| card.description.clone())
+                    .filter(|description| !description.is_empty())
+                    .or_else(|| self.configured_sink().map(Device::to_string));
+
+                let mut commands = vec![refresh()];
+
+                if let Some(title) = title {
+                    commands.push(Command::perform(async move { title }, move |title| {
+                        crate::Message::PageTitle(page, title)
+                    }));
+                }
+
+                return Command::batch(commands);
+            }
+
+            Message::PortChanged(port) => {
+                if let Some(sink) = self.configured_sink() {
+                    let name = sink.name.clone();
+                    return apply(move || sound::set_sink_port(&name, &port));
+                }
+            }
+
+            Message::ProfileApplied(result) => {
+                if let Err(why) = result {
+                    self.switching_profile = None;
+                    self.device_error = Some(why);
+                }
+
+                return refresh();
+            }
+
+            Message::ProfileChanged(profile) => {
+                let Some(card) = self.configured_card() else {
+                    return Command::none();
+                };
+
+                let card = card.name.clone();
+                self.switching_profile = Some(profile.clone());
+                self.device_error = None;
+
+                return Command::perform(
+                    page::background(move || sound::set_card_profile(&card, &profile)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Sound(Message::ProfileApplied(result))
+                    },
+                );
+            }
+
+            Message::Refresh => return refresh(),
+
+            Message::ShowMonitors(show) => {
+                self.show_monitors = show;
+                self.list_sources();
+            }
+
+            Message::SinkMute(mute) => {
+                if let Some(sink) = self.default_sink_mut() {
+                    sink.mute = mute;
+                    let name = sink.name.clone();
+                    return apply(move || sound::set_sink_mute(&name, mute));
+                }
+            }
+
+            Message::SinkVolume(message) => {
+                if let Some(volume) = self.sink_volume.update(message) {
+                    return self.set_sink_volume(volume);
+                }
+            }
+
+            Message::SourceMute(mute) => {
+                if let Some(source) = self.default_source_mut() {
+                    source.mute = mute;
+                    let name = source.name.clone();
+                    return apply(move || sound::set_source_mute(&name, mute));
+                }
+            }
+
+            Message::SourceVolume(message) => {
+                let Some(volume) = self.source_volume.update(message) else {
+                    return Command::none();
+                };
+
+                if let Some(source) = self.default_source_mut() {
+                    source.volume = volume;
+                    let name = source.name.clone();
+                    return apply(move || sound::set_source_volume(&name, volume));
+                }
+            }
+
+            Message::SpeakerTest(open) => {
+                self.speaker_test = open;
+                self.speaker_test_error = None;
+
+                if !open {
+                    return stop_speaker_test();
+                }
+            }
+
+            Message::SpeakerTestPlay(position) => {
+                let Some(sink) = self.configured_sink() else {
+                    return Command::none();
+                };
+
+                let name = sink.name.clone();
+
+                return Command::perform(
+                    page::background(move || sound::speaker_test::play(&name, &position)),
+                    |result| {
+                        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                        crate::Message::Sound(Message::SpeakerTestPlayed(result))
+                    },
+                );
+            }
+
+            Message::SpeakerTestPlayed(result) => self.speaker_test_error = result.err(),
+
+            Message::StreamMute(indices, mute) => {
+                for stream in &mut self.devices.streams {
+                    if indices.contains(&stream.index) {
+                        stream.mute = mute;
+                    }
+                }
+
+                return apply(move || {
+                    indices
+                        .iter()
+                        .try_for_each(|&index| sound::set_stream_mute(index, mute))
+                });
+            }
+
+            Message::StreamVolume(message) => {
+                let Some((indices, volume)) = self.stream_volume.update(message) else {
+                    return Command::none();
+                };
+
+                for stream in &mut self.devices.streams {
+                    if indices.contains(&stream.index) {
+                        stream.volume = volume;
+                    }
+                }
+
+                // Streams which ended meanwhile are left alone, as are their errors.
+                return apply(move || {
+                    for index in indices {
+                        let _res = sound::set_stream_volume(index, volume);
+                    }
+
+                    Ok(())
+                });
+            }
+        }
+
+        Command::none()
+    }
+
+    /// The volume of alerts, which is the one that its slider is dragged to while it is dragged.
+    fn alert_volume(&self) -> u32 {
+        self.alert_volume
+            .preview()
+            .copied()
+            .unwrap_or(self.alerts.volume)
+    }
+
+    /// Brings the default sink and the streams which play above 100% back to 100%, once
+    /// amplification is forbidden.
+    fn clamp_volumes(&mut self) -> Command<crate::Message> {
+        self.sink_volume.clear();
+        self.stream_volume.clear();
+
+        let mut commands = Vec::new();
+
+        if matches!(self.default_sink(), Some(sink) if sink.volume > sound::MAX_VOLUME) {
+            commands.push(self.set_sink_volume(sound::MAX_VOLUME));
+        }
+
+        let indices: Vec<u32> = self
+            .devices
+            .streams
+            .iter_mut()
+            .filter(|stream| stream.volume > sound::MAX_VOLUME)
+            .map(|stream| {
+                stream.volume = sound::MAX_VOLUME;
+                stream.index
+            })
+            .collect();
+
+        if !indices.is_empty() {
+            commands.push(apply(move || {
+                for index in indices {
+                    let _res = sound::set_stream_volume(index, sound::MAX_VOLUME);
+                }
+
+                Ok(())
+            }));
+        }
+
+        Command::batch(commands)
+    }
+
+    /// The loudest volume which sliders may be dragged to.
+    fn max_volume(&self) -> u32 {
+        sound::max_volume(self.amplification)
+    }
+
+    /// Sets the volume of the default sink, as a percentage of its loudest channel.
+    fn set_sink_volume(&mut self, volume: u32) -> Command<crate::Message> {
+        let Some(sink) = self.default_sink_mut() else {
+            return Command::none();
+        };
+
+        sink.volume = volume;
+        let name = sink.name.clone();
+
+        if sink.channel_volumes.is_empty() {
+            return apply(move || sound::set_sink_volume(&name, volume));
+        }
+
+        // The channels are scaled together, so that the balance and fade are kept.
+        sink.channel_volumes = sound::channels::scaled(&sink.channel_volumes, volume);
+        let volumes = sink.channel_volumes.clone();
+        apply(move || sound::set_sink_channel_volumes(&name, &volumes))
+    }
+
+    /// Writes the config of alerts, which the notification daemon plays its next alert by.
+    fn apply_alerts(&self) -> Command<crate::Message> {
+        let config = self.alerts.clone();
+
+        Command::perform(
+            page::background(move || alerts::set_config(&config)),
+            |result| {
+                let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                crate::Message::Sound(Message::AlertsApplied(result))
+            },
+        )
+    }
+
+    /// The streams which are played, grouped by the application which plays them.
+    fn applications(&self) -> Vec<(&str, Vec<&Stream>)> {
+        let mut applications: Vec<(&str, Vec<&Stream>)> = Vec::new();
+
+        for stream in &self.devices.streams {
+            match applications
+                .iter_mut()
+                .find(|(name, _)| *name == stream.application)
+            {
+                Some((_, streams)) => streams.push(stream),
+                None => applications.push((stream.application.as_str(), vec![stream])),
+            }
+        }
+
+        applications
+    }
+
+    /// The volume of streams, which is the one that they are dragged to while it is dragged.
+    fn stream_volume(&self, streams: &[&Stream]) -> u32 {
+        match self.stream_volume.preview() {
+            Some((indices, volume))
+                if indices.len() == streams.len()
+                    && streams.iter().all(|stream| indices.contains(&stream.index)) =>
+            {
+                *volume
+            }
+            _ => streams
+                .iter()
+                .map(|stream| stream.volume)
+                .max()
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Applies a change to the sound server, and then refreshes its devices.
+fn apply(func: impl FnOnce() -> Result<(), String> + Send + 'static) -> Command<crate::Message> {
+    Command::perform(page::background(func), |_| {
+        crate::Message::Sound(Message::Refresh)
+    })
+}
+
+/// Stops the test of a speaker which is playing, such as when its dialog is closed.
+fn stop_speaker_test() -> Command<crate::Message> {
+    Command::perform(page::background(sound::speaker_test::stop), |_| {
+        crate::Message::None
+    })
+}
+
+fn load_alerts() -> Command<crate::Message> {
+    Command::perform(
+        page::background(|| (alerts::config(), alerts::sounds())),
+        |loaded| {
+            let (config, sounds) = loaded.unwrap_or_default();
+            crate::Message::Sound(Message::AlertsLoaded(config, sounds))
+        },
+    )
+}
+
+fn load_amplification() -> Command<crate::Message> {
+    Command::perform(page::background(sound::amplification), |amplification| {
+        crate::Message::Sound(Message::AmplificationLoaded(
+            amplification.unwrap_or_default(),
+        ))
+    })
+}
+
+fn refresh() -> Command<crate::Message> {
+    Command::perform(page::background(sound::devices), |result| {
+        let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+        crate::Message::Sound(Message::Devices(result))
+    })
+}
+
+pub struct Page;
+
+impl page::Page for Page {
+    type Model = Sound;
+
+    fn page() -> page::Meta {
+        page::Meta::new("sound", "multimedia-volume-control-symbolic")
+            .title(fl!("sound"))
+            .description(fl!("sound", "desc"))
+    }
+
+    fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
+        Some(vec![
+            sections.insert(output()),
+            sections.insert(input()),
+            sections.insert(alerts()),
+            sections.insert(applications()),
+        ])
+    }
+
+    fn load(page: page::Entity) -> page::PageTask {
+        Box::pin(async move { crate::Message::Sound(Message::Load(page)) })
+    }
+
+    fn on_leave(model: &mut Sound, _page: page::Entity) -> Command<crate::Message> {
+        // The capture stops along with the subscriptions of the page, and is retried once
+        // the page is viewed again.
+        model.input_level = 0.0;
+        model.input_capture_failed = false;
+        Command::none()
+    }
+
+    fn sub_pages(page: page::Insert) -> page::Insert {
+        page.sub_page::<device::Page>()
+    }
+
+    fn subscription(model: &Sound) -> Subscription<crate::Message> {
+        let events = events();
+
+        let sliders = Subscription::batch(vec![
+            model.alert_volume.subscription().map(Message::AlertVolume),
+            model.sink_volume.subscription().map(Message::SinkVolume),
+            model
+                .source_volume
+                .subscription()
+                .map(Message::SourceVolume),
+            model
+                .stream_volume
+                .subscription()
+                .map(Message::StreamVolume),
+        ])
+        .map(crate::Message::Sound);
+
+        let Some(source) = model.default_source() else {
+            return Subscription::batch(vec![events, sliders]);
+        };
+
+        // Restarted with the new source whenever the default source changes, which kills the
+        // capture of the previous source as its subscription is dropped.
+        let level = crate::subscription::chunks(
+            ("sound-input-level", source.name.clone()),
+            sound::capture_command(&source.name),
+            LEVEL_CHUNK_SIZE,
+            |samples| {
+                Some(crate::Message::Sound(Message::InputLevel(sound::peak(
+                    samples,
+                ))))
+            },
+            crate::Message::Sound(Message::InputCaptureFailed),
+        );
+
+        Subscription::batch(vec![events, sliders, level])
+    }
+}
+
+/// Refreshes the devices whenever they are added, removed, or changed.
+fn events() -> Subscription<crate::Message> {
+    crate::subscription::lines("sound-events", sound::events_command(), |line| {
+        let changed = [
+            " on sink ",
+            " on sink-input ",
+            " on source ",
+            " on card ",
+            " on server",
+        ]
+        .iter()
+        .any(|kind| line.contains(kind));
+
+        changed.then_some(crate::Message::Sound(Message::Refresh))
+    })
+}
+
+fn alerts() -> Section {
+    Section::new()
+        .title(fl!("sound-alerts"))
+        .descriptions(vec![
+            fl!("sound-alerts", "volume"),
+            fl!("sound-alerts", "sound"),
+            fl!("sound-alerts", "none"),
+        ])
+        .view_fn(|app, section| {
+            let sound = app
+                .pages
+                .resource::<Sound>()
+                .expect("sound model is missing");
+
+            let desc = &section.descriptions;
+
+            let selected = sound
+                .alert_sounds
+                .iter()
+                .find(|entry| entry.id == sound.alerts.sound)
+                .map_or(desc[2].as_str(), |entry| entry.title.as_str());
+
+            let mut view = settings::view_section(&section.title)
+                .add(
+                    labeled_slider(&desc[0], 0..=100, sound.alert_volume(), |volume| {
+                        Message::AlertVolume(debounce::Message::SliderPreview(volume))
+                    })
+                    .on_release(Message::AlertVolume(debounce::Message::SliderRelease))
+                    .unit("%"),
+                )
+                .add(
+                    expander(&desc[1], selected)
+                        .expanded(sound.alert_sounds_expanded)
+                        .on_toggle(Message::AlertSoundsExpanded)
+                        .content(radio_list_with_action(
+                            &sound.alert_sounds,
+                            Some(&sound.alerts.sound),
+                            Message::AlertSoundChanged,
+                            Message::AlertPreview,
+                        )),
+                );
+
+            if let Some(why) = &sound.alert_error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Sound)
+        })
+}
+
+fn applications() -> Section {
+    Section::new()
+        .title(fl!("sound-applications"))
+        .descriptions(vec![
+            fl!("sound-applications", "desc"),
+            fl!("sound-applications", "streams"),
+            fl!("sound-applications", "none"),
+        ])
+        .view_fn(|app, section| {
+            let sound = app
+                .pages
+                .resource::<Sound>()
+                .expect("sound model is missing");
+
+            let desc = &section.descriptions;
+            let applications = sound.applications();
+
+            let mut view = settings::view_section(&section.title);
+
+            if applications.is_empty() {
+                view = view.add(text(&desc[2]));
+            }
+
+            for (application, streams) in applications {
+                view = view.add(stream_row(
+                    Some(&streams[0].icon_name),
+                    application,
+                    &streams,
+                    sound.stream_volume(&streams),
+                    sound.max_volume(),
+                ));
+
+                // Applications which play several streams at once may set each of them apart.
+                if streams.len() > 1 {
+                    let expanded = sound.expanded_application.as_deref() == Some(application);
+                    let name = application.to_owned();
+
+                    let mut details =
+                        expander(&desc[1], "")
+                            .expanded(expanded)
+                            .on_toggle(move |expanded| {
+                                Message::ApplicationExpanded(name.clone(), expanded)
+                            });
+
+                    if expanded {
+                        let rows = streams.iter().map(|stream| {
+                            stream_row(
+                                None,
+                                &stream.media,
+                                &[stream],
+                                sound.stream_volume(&[stream]),
+                                sound.max_volume(),
+                            )
+                        });
+
+                        details = details.content(column(rows.collect()).spacing(8));
+                    }
+
+                    view = view.add(details);
+                }
+            }
+
+            view.apply(Element::from).map(crate::Message::Sound)
+        })
+}
+
+/// The volume and mute of streams, which are changed together.
+fn stream_row<'a>(
+    icon_name: Option<&'a str>,
+    title: &'a str,
+    streams: &[&Stream],
+    volume: u32,
+    max_volume: u32,
+) -> Element<'a, Message> {
+    let indices: Vec<u32> = streams.iter().map(|stream| stream.index).collect();
+    let mute = streams.iter().all(|stream| stream.mute);
+
+    let mute_icon = if mute {
+        "audio-volume-muted-symbolic"
+    } else {
+        "audio-volume-high-symbolic"
+    };
+
+    let mute_button = icon(mute_icon, 16)
+        .style(theme::Svg::Symbolic)
+        .apply(button)
+        .style(theme::Button::Transparent)
+        .on_press(Message::StreamMute(indices.clone(), !mute));
+
+    let volume = slider(0..=max_volume, volume, move |volume| {
+        Message::StreamVolume(debounce::Message::SliderPreview((indices.clone(), volume)))
+    })
+    .on_release(Message::StreamVolume(debounce::Message::SliderRelease))
+    .width(Length::Units(250));
+
+    let mut content = Row::new().spacing(16).align_items(Alignment::Center);
+
+    if let Some(name) = icon_name {
+        content = content.push(icon(name, 24));
+    }
+
+    content
+        .push(text(title).width(Length::Fill))
+        .push(mute_button)
+        .push(volume)
+        .into()
+}
+
+fn input() -> Section {
+    Section::new()
+        .title(fl!("sound-input"))
+        .descriptions(vec![
+            fl!("sound-input", "volume"),
+            fl!("sound-input", "device"),
+            fl!("sound-input", "level"),
+            fl!("sound-input", "mute"),
+            fl!("sound-input", "level-unavailable"),
+            fl!("sound-input", "show-monitors"),
+            fl!("sound-input", "show-monitors-desc"),
+        ])
+        .view_fn(|app, section| {
+            let sound = app
+                .pages
+                .resource::<Sound>()
+                .expect("sound model is missing");
+
+            let desc = &section.descriptions;
+            let source = sound.default_source();
+
+            let mut view = settings::view_section(&section.title)
+                .add(text(&desc[1]))
+                .add(radio_list(
+                    &sound.sources,
+                    Some(&sound.devices.default_source),
+                    Message::DefaultSourceChanged,
+                ))
+                .add(
+                    settings::item::builder(&desc[5])
+                        .description(&desc[6])
+                        .control(toggler(None, sound.show_monitors, Message::ShowMonitors)),
+                );
+
+            if let Some(source) = source {
+                view = view
+                    .add(
+                        labeled_slider(
+                            &desc[0],
+                            0..=100,
+                            sound
+                                .source_volume
+                                .preview()
+                                .copied()
+                                .unwrap_or(source.volume),
+                            |volume| {
+                                Message::SourceVolume(debounce::Message::SliderPreview(volume))
+                            },
+                        )
+                        .on_release(Message::SourceVolume(debounce::Message::SliderRelease))
+                        .unit("%"),
+                    )
+                    .add(settings::item(
+                        &desc[3],
+                        toggler(None, source.mute, Message::SourceMute),
+                    ))
+                    .add(settings::item(&desc[2], {
+                        let meter = level_meter(sound.input_level);
+
+                        if sound.input_capture_failed {
+                            meter.disabled(&desc[4])
+                        } else {
+                            meter
+                        }
+                    }));
+            }
+
+            view.apply(Element::from).map(crate::Message::Sound)
+        })
+}
+
+fn output() -> Section {
+    Section::new()
+        .title(fl!("sound-output"))
+        .descriptions(vec![
+            fl!("sound-output", "volume"),
+            fl!("sound-output", "device"),
+            fl!("sound-output", "level"),
+            fl!("sound-output", "config"),
+            fl!("sound-output", "balance"),
+            fl!("sound-output", "mute"),
+            fl!("sound-output", "amplification"),
+            fl!("sound-output", "amplification-desc"),
+            fl!("sound-output", "amplification-warning"),
+        ])
+        .view_fn(|app, section| {
+            let sound = app
+                .pages
+                .resource::<Sound>()
+                .expect("sound model is missing");
+
+            let desc = &section.descriptions;
+            let sink = sound.default_sink();
+
+            let mut view = settings::view_section(&section.title)
+                .add(text(&desc[1]))
+                .add(radio_list_with_action(
+                    &sound.sinks,
+                    Some(&sound.devices.default_sink),
+                    Message::DefaultSinkChanged,
+                    Message::Configure,
+                ));
+
+            if let Some(sink) = sink {
+                // Changes made elsewhere, such as by media keys, are shown unless it is dragged.
+                let volume = sound.sink_volume.preview().copied().unwrap_or(sink.volume);
+
+                let mut slider =
+                    labeled_slider(&desc[0], 0..=sound.max_volume(), volume, |volume| {
+                        Message::SinkVolume(debounce::Message::SliderPreview(volume))
+                    })
+                    .on_release(Message::SinkVolume(debounce::Message::SliderRelease))
+                    .unit("%");
+
+                // Marks where amplification begins, past which sounds are likely to distort.
+                if sound.amplification {
+                    slider = slider.ticks([sound::MAX_VOLUME]);
+                }
+
+                view = view.add(slider).add(settings::item(
+                    &desc[5],
+                    toggler(None, sink.mute, Message::SinkMute),
+                ));
+            }
+
+            view = view.add(
+                settings::item::builder(&desc[6])
+                    .description(&desc[7])
+                    .control(
+                        Row::new()
+                            .spacing(8)
+                            .align_items(Alignment::Center)
+                            .push(info_popover(
+                                &desc[8],
+                                sound.amplification_info,
+                                Message::AmplificationInfo,
+                            ))
+                            .push(toggler(None, sound.amplification, Message::Amplification)),
+                    ),
+            );
+
+            if let Some(why) = &sound.amplification_error {
+                view = view.add(text(why));
+            }
+
+            view.apply(Element::from).map(crate::Message::Sound)
+        })
+}
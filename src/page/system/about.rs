@@ -3,6 +3,7 @@
 
 use crate::{
     page::{self, desktop::wallpaper::settings, Content, Section},
+    widget::copy_row,
     SettingsApp,
 };
 use cosmic::{
@@ -103,17 +104,16 @@ fn hardware() -> Section {
             let model = model(app);
 
             let mut sections = settings::view_section(&section.title)
-                .add(settings::item(&desc[0], text(&model.info.hardware_model)))
-                .add(settings::item(&desc[1], text(&model.info.memory)))
-                .add(settings::item(&desc[2], text(&model.info.processor)));
+                .add(copy(&desc[0], &model.info.hardware_model))
+                .add(copy(&desc[1], &model.info.memory))
+                .add(copy(&desc[2], &model.info.processor));
 
             for card in &model.info.graphics {
-                sections = sections.add(settings::item(&desc[3], text(card.as_str())));
+                sections = sections.add(copy(&desc[3], card));
             }
 
-            // .add(settings::item(&desc[3], text(&model.info.graphics)))
             sections
-                .add(settings::item(&desc[4], text(&model.info.disk_capacity)))
+                .add(copy(&desc[4], &model.info.disk_capacity))
                 .into()
         })
 }
@@ -132,13 +132,10 @@ fn os() -> Section {
 
             let desc = &section.descriptions;
             settings::view_section(&section.title)
-                .add(settings::item(&desc[0], text(&model.info.operating_system)))
-                .add(settings::item(&desc[1], text(&model.info.os_architecture)))
-                .add(settings::item(
-                    &desc[2],
-                    text(&model.info.desktop_environment),
-                ))
-                .add(settings::item(&desc[3], text(&model.info.windowing_system)))
+                .add(copy(&desc[0], &model.info.operating_system))
+                .add(copy(&desc[1], &model.info.os_architecture))
+                .add(copy(&desc[2], &model.info.desktop_environment))
+                .add(copy(&desc[3], &model.info.windowing_system))
                 .into()
         })
 }
@@ -154,6 +151,11 @@ fn related() -> Section {
         })
 }
 
+/// An item of the info of the system, which may be copied into a bug report.
+fn copy<'a>(title: &'a str, value: &'a str) -> cosmic::Element<'a, crate::Message> {
+    copy_row(title, value, crate::Message::CopyToClipboard)
+}
+
 fn model(app: &SettingsApp) -> &Model {
     app.pages
         .resource::<Model>()
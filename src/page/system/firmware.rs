@@ -1,14 +1,67 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use apply::Apply;
+use cosmic::{
+    iced::{widget::button, Command},
+    theme,
+    widget::{settings, text},
+    Element,
+};
 use slotmap::SlotMap;
 
 use crate::page::{self, section, Content, Section};
 
+#[derive(Clone, Debug)]
+pub enum Message {
+    /// The page was opened, which only checks for updates once asked to.
+    Loaded(page::Entity),
+    Refresh(page::Entity),
+    Refreshed(Result<(), String>),
+}
+
+#[derive(Debug, Default)]
+pub struct Model {
+    pub page: page::Entity,
+    pub refreshing: bool,
+    pub status: Option<Result<(), String>>,
+}
+
+impl Model {
+    pub fn update(&mut self, message: Message) -> Command<crate::Message> {
+        match message {
+            Message::Loaded(page) => self.page = page,
+
+            Message::Refresh(page) => {
+                self.page = page;
+
+                if !self.refreshing {
+                    self.refreshing = true;
+
+                    return Command::perform(
+                        page::background(cosmic_settings_system::firmware::refresh),
+                        |result| {
+                            let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                            crate::Message::Firmware(Message::Refreshed(result))
+                        },
+                    );
+                }
+            }
+
+            Message::Refreshed(result) => {
+                self.refreshing = false;
+                self.status = Some(result);
+            }
+        }
+
+        Command::none()
+    }
+}
+
 pub struct Page;
 
 impl page::Page for Page {
-    type Model = super::Model;
+    type Model = Model;
 
     fn page() -> page::Meta {
         page::Meta::new("firmware", "firmware-manager-symbolic")
@@ -17,6 +70,50 @@ impl page::Page for Page {
     }
 
     fn content(sections: &mut SlotMap<section::Entity, Section>) -> Option<Content> {
-        Some(vec![sections.insert(Section::new())])
+        Some(vec![sections.insert(updates())])
+    }
+
+    fn load(page: page::Entity) -> page::PageTask {
+        Box::pin(async move { crate::Message::Firmware(Message::Loaded(page)) })
     }
 }
+
+fn updates() -> Section {
+    Section::new()
+        .title(fl!("firmware-updates"))
+        .descriptions(vec![
+            fl!("firmware-updates", "check"),
+            fl!("firmware-updates", "checking"),
+            fl!("firmware-updates", "refreshed"),
+        ])
+        .view_fn(|app, section| {
+            let model = app
+                .pages
+                .resource::<Model>()
+                .expect("firmware model is missing");
+
+            let desc = &section.descriptions;
+
+            let control: Element<_> = if model.refreshing {
+                text(&desc[1]).into()
+            } else {
+                button(text(&desc[0]))
+                    .style(theme::Button::Secondary)
+                    .on_press(Message::Refresh(model.page))
+                    .into()
+            };
+
+            let mut item = settings::item::builder(&desc[0]);
+
+            match model.status {
+                Some(Ok(())) => item = item.description(&desc[2]),
+                Some(Err(ref why)) => item = item.description(why),
+                None => (),
+            }
+
+            settings::view_section(&section.title)
+                .add(item.control(control))
+                .apply(Element::from)
+                .map(crate::Message::Firmware)
+        })
+}
@@ -0,0 +1,276 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Exports of the settings which the pages manage, to be imported on another install.
+
+use crate::{file_chooser, page};
+use cosmic_settings_config as config;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// Whether a value of a key is well-formed, as parsed into the type which its component
+/// reads it as.
+type Check = fn(&str) -> bool;
+
+/// The cosmic config keys which the pages manage, by the component which they configure,
+/// each with the check of its values.
+///
+/// Keys which refer to other files, such as color profiles, are left out, as the files are
+/// not carried along with an export.
+const SETTINGS_KEYS: &[(&str, &[(&str, Check)])] = &[
+    (
+        "com.system76.CosmicComp",
+        &[
+            ("autotile", parses::<bool>),
+            ("focus_follows_cursor", parses::<bool>),
+            ("focus_follows_cursor_delay", parses::<u32>),
+            ("input_default", parses::<ron::Value>),
+            ("input_devices", parses::<ron::Value>),
+            ("input_touchpad", parses::<ron::Value>),
+            ("keyboard_a11y", parses::<ron::Value>),
+            ("keyboard_config", parses::<ron::Value>),
+            ("workspaces", parses::<ron::Value>),
+            ("xkb_config", parses::<ron::Value>),
+        ],
+    ),
+    (
+        "com.system76.CosmicTk",
+        &[
+            ("interface_font", parses::<ron::Value>),
+            ("monospace_font", parses::<ron::Value>),
+        ],
+    ),
+    (
+        "com.system76.CosmicIdle",
+        &[
+            ("dim_screen", parses::<bool>),
+            ("lid_close_action", parses::<LidAction>),
+            ("lid_close_on_ac_action", parses::<LidAction>),
+            ("lid_close_on_battery_action", parses::<LidAction>),
+            ("lock_delay", parses::<u64>),
+            ("lock_on_screen_off", parses::<bool>),
+            ("power_button_action", parses::<PowerButtonAction>),
+            ("screen_off_on_ac_time", parses::<Option<u64>>),
+            ("screen_off_on_battery_time", parses::<Option<u64>>),
+            ("suspend_on_ac_time", parses::<Option<u64>>),
+            ("suspend_on_battery_time", parses::<Option<u64>>),
+        ],
+    ),
+    ("com.system76.CosmicPanel.Panel", PANEL_KEYS),
+    ("com.system76.CosmicPanel.Dock", PANEL_KEYS),
+    (
+        "com.system76.CosmicNotifications",
+        &[
+            ("alert_sound", parses::<Option<String>>),
+            ("alert_volume", parses::<u32>),
+        ],
+    ),
+    (
+        "com.system76.CosmicAudio",
+        &[("amplification", parses::<bool>)],
+    ),
+];
+
+/// The keys of both the panel and the dock.
+const PANEL_KEYS: &[(&str, Check)] = &[
+    ("anchor", parses::<Anchor>),
+    ("autohide", parses::<Option<AutoHide>>),
+    ("plugins_center", parses::<Option<Vec<String>>>),
+    (
+        "plugins_wings",
+        parses::<Option<(Vec<String>, Vec<String>)>>,
+    ),
+    ("size", parses::<PanelSize>),
+];
+
+/// What happens when the lid of a laptop is closed, as the idle daemon reads it.
+#[derive(Deserialize)]
+enum LidAction {
+    Suspend,
+    Hibernate,
+    Lock,
+    Nothing,
+}
+
+/// What happens when the power button is pressed, as the idle daemon reads it.
+#[derive(Deserialize)]
+enum PowerButtonAction {
+    Suspend,
+    PowerOff,
+    Hibernate,
+    Nothing,
+}
+
+/// The edge of the screen which a panel is placed along.
+#[derive(Deserialize)]
+enum Anchor {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// The size of a panel.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Deserialize)]
+enum PanelSize {
+    XS,
+    S,
+    M,
+    L,
+    XL,
+}
+
+/// How a panel hides itself, which is never hidden when it is not set.
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct AutoHide {
+    wait_time: u32,
+    transition_time: u32,
+    handle_size: u32,
+}
+
+/// Asks where to export the settings to, and then exports them there.
+///
+/// Resolves to `None` if the user cancelled.
+pub async fn choose_export() -> Result<Option<usize>, String> {
+    let chosen = file_chooser::save_file(
+        &crate::fl!("settings-file", "export-title"),
+        "cosmic-settings.ron",
+        filter(),
+        "cosmic_settings_export",
+    )
+    .await
+    .map_err(|why| why.to_string())?;
+
+    let Some(path) = chosen else {
+        return Ok(None);
+    };
+
+    page::background(move || export(&path))
+        .await
+        .unwrap_or_else(|| Err(crate::fl!("unknown")))
+        .map(Some)
+}
+
+/// Asks for an export, and then imports its settings.
+///
+/// Resolves to `None` if the user cancelled.
+pub async fn choose_import() -> Result<Option<ImportReport>, String> {
+    let chosen = file_chooser::open_file(
+        &crate::fl!("settings-file", "import-title"),
+        filter(),
+        "cosmic_settings_import",
+    )
+    .await
+    .map_err(|why| why.to_string())?;
+
+    let Some(path) = chosen else {
+        return Ok(None);
+    };
+
+    page::background(move || import(&path))
+        .await
+        .unwrap_or_else(|| Err(crate::fl!("unknown")))
+        .map(Some)
+}
+
+/// Exports are RON files, which may also be chosen by their extension.
+fn filter() -> file_chooser::Filter {
+    file_chooser::Filter {
+        name: crate::fl!("settings-file", "filter"),
+        globs: &["*.ron"],
+        mime_types: &[],
+    }
+}
+
+/// The first line of an export, by which files of other kinds are refused.
+const HEADER: &str = "// COSMIC Settings";
+
+/// The keys of an import which were applied, and those which were skipped as unknown or
+/// invalid, as `component/key`.
+#[derive(Clone, Debug, Default)]
+pub struct ImportReport {
+    pub applied: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Writes the settings which are set to a file, as a RON map from `component/key` to the
+/// value of the key, to be imported on another install. Returns how many were written.
+///
+/// # Errors
+///
+/// Fails if the file could not be written.
+pub fn export(path: &Path) -> Result<usize, String> {
+    let settings: BTreeMap<String, String> = keys()
+        .filter_map(|(component, key, _)| {
+            let value = config::read(component, key)?;
+            Some((format!("{component}/{key}"), value))
+        })
+        .collect();
+
+    let map = ron::ser::to_string_pretty(&settings, ron::ser::PrettyConfig::new())
+        .map_err(|why| why.to_string())?;
+
+    std::fs::write(path, format!("{HEADER}\n{map}\n")).map_err(|why| why.to_string())?;
+    Ok(settings.len())
+}
+
+/// Applies the settings of an exported file, skipping those which are unknown, or whose
+/// values do not parse as the type of their key, rather than failing on them.
+///
+/// Every value is checked before any is written, so that a file which is not an export
+/// changes nothing.
+///
+/// # Errors
+///
+/// Fails if the file could not be read, or is not an export of settings.
+pub fn import(path: &Path) -> Result<ImportReport, String> {
+    let contents = std::fs::read_to_string(path).map_err(|why| why.to_string())?;
+
+    if !contents.starts_with(HEADER) {
+        return Err(crate::fl!("settings-file", "not-an-export"));
+    }
+
+    let settings: BTreeMap<String, String> =
+        ron::from_str(&contents).map_err(|_| crate::fl!("settings-file", "not-an-export"))?;
+
+    let mut report = ImportReport::default();
+    let mut valid = Vec::new();
+
+    for (name, value) in settings {
+        let known = name.split_once('/').and_then(|(component, key)| {
+            keys().find(|&(known_component, known_key, _)| {
+                (known_component, known_key) == (component, key)
+            })
+        });
+
+        match known {
+            Some((component, key, check)) if check(&value) => {
+                valid.push((component, key, name, value));
+            }
+            _ => report.skipped.push(name),
+        }
+    }
+
+    for (component, key, name, value) in valid {
+        match config::write(component, key, &value) {
+            Ok(()) => report.applied.push(name),
+            Err(_) => report.skipped.push(name),
+        }
+    }
+
+    Ok(report)
+}
+
+fn keys() -> impl Iterator<Item = (&'static str, &'static str, Check)> {
+    SETTINGS_KEYS.iter().flat_map(|(component, keys)| {
+        keys.iter()
+            .map(move |&(key, check)| (*component, key, check))
+    })
+}
+
+/// Whether a value parses as a `T`.
+fn parses<T: DeserializeOwned>(value: &str) -> bool {
+    ron::from_str::<T>(value).is_ok()
+}
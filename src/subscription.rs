@@ -0,0 +1,92 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Subscriptions to the output of long-running system commands.
+//!
+//! Commands are spawned when the subscription is first requested, and killed once it
+//! is no longer requested, such as when navigating away from the page which asked for it.
+
+use cosmic::{iced::Subscription, iced_native::subscription};
+use std::{hash::Hash, process::Stdio};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, BufReader},
+    process::{Child, ChildStdout},
+};
+
+enum State<Output> {
+    Starting(std::process::Command),
+    Running(Child, Output),
+    Finished,
+}
+
+/// Runs a command while subscribed, mapping each line that it prints to a message.
+pub fn lines<I: Hash + 'static>(
+    id: I,
+    command: std::process::Command,
+    map: fn(String) -> Option<crate::Message>,
+) -> Subscription<crate::Message> {
+    subscription::unfold(id, State::Starting(command), move |state| async move {
+        match state {
+            State::Starting(command) => match spawn(command) {
+                Some((child, stdout)) => {
+                    (None, State::Running(child, BufReader::new(stdout).lines()))
+                }
+                None => (None, State::Finished),
+            },
+
+            State::Running(child, mut lines) => match lines.next_line().await {
+                Ok(Some(line)) => (map(line), State::Running(child, lines)),
+                _ => (None, State::Finished),
+            },
+
+            State::Finished => std::future::pending().await,
+        }
+    })
+}
+
+/// Runs a command while subscribed, mapping each chunk of `size` bytes that it outputs to a message.
+///
+/// `on_exit` is emitted if the command could not be run, or once it stops outputting, such
+/// as when the device that it reads from is missing or busy.
+pub fn chunks<I: Hash + 'static>(
+    id: I,
+    command: std::process::Command,
+    size: usize,
+    map: fn(&[u8]) -> Option<crate::Message>,
+    on_exit: crate::Message,
+) -> Subscription<crate::Message> {
+    subscription::unfold(id, State::Starting(command), move |state| {
+        let on_exit = on_exit.clone();
+
+        async move {
+            match state {
+                State::Starting(command) => match spawn(command) {
+                    Some((child, stdout)) => (None, State::Running(child, (stdout, vec![0; size]))),
+                    None => (Some(on_exit), State::Finished),
+                },
+
+                State::Running(child, (mut stdout, mut buffer)) => {
+                    match stdout.read_exact(&mut buffer).await {
+                        Ok(_) => (map(&buffer), State::Running(child, (stdout, buffer))),
+                        Err(_) => (Some(on_exit), State::Finished),
+                    }
+                }
+
+                State::Finished => std::future::pending().await,
+            }
+        }
+    })
+}
+
+fn spawn(command: std::process::Command) -> Option<(Child, ChildStdout)> {
+    let mut child = tokio::process::Command::from(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .ok()?;
+
+    let stdout = child.stdout.take()?;
+    Some((child, stdout))
+}
@@ -0,0 +1,235 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{Color, Length},
+    iced_native::{
+        event, layout, mouse, renderer,
+        widget::{tree, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    Element, Renderer,
+};
+
+/// Levels at which guide lines are drawn across the graph.
+const GUIDES: [f32; 3] = [0.0, 0.5, 1.0];
+
+/// A charge level of a battery, at a time within the span which the graph shows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    /// How far into the span the sample was taken, from 0 at its start to 1 at its end.
+    pub at: f32,
+    /// The charge, from 0 to 1.
+    pub level: f32,
+    pub charging: bool,
+}
+
+/// A graph of the charge of a battery over a span of time, whose charging periods are shaded.
+///
+/// Each level is held until the next sample, rather than interpolated towards it. Samples
+/// which are further apart than the largest gap are not joined, as the system was likely
+/// asleep between them, unless the level held steady, as it does while full.
+///
+/// The graph fills the width which it is given.
+#[must_use]
+pub struct BatteryGraph<'a, Message> {
+    samples: &'a [Sample],
+    max_gap: f32,
+    height: u16,
+    on_hover: Box<dyn Fn(Option<usize>) -> Message + 'a>,
+}
+
+/// Creates a graph of samples, which are sorted by the time that they were taken.
+///
+/// The sample under the pointer is emitted by its index whenever it changes, and `None`
+/// once the pointer is over no sample.
+pub fn battery_graph<'a, Message>(
+    samples: &'a [Sample],
+    on_hover: impl Fn(Option<usize>) -> Message + 'a,
+) -> BatteryGraph<'a, Message> {
+    BatteryGraph {
+        samples,
+        max_gap: 1.0,
+        height: 120,
+        on_hover: Box::new(on_hover),
+    }
+}
+
+impl<'a, Message> BatteryGraph<'a, Message> {
+    /// The largest share of the span between samples across which they are joined.
+    pub fn max_gap(mut self, max_gap: f32) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    pub fn height(mut self, height: u16) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// The span which each sample is held across, from it to the next, or to the end of the
+    /// graph for the last, unless it is followed by a gap.
+    fn extent(&self, index: usize) -> Option<(f32, f32)> {
+        let sample = self.samples.get(index)?;
+
+        let Some(next) = self.samples.get(index + 1) else {
+            return Some((sample.at, 1.0));
+        };
+
+        let steady = (next.level - sample.level).abs() < 0.01;
+
+        (next.at - sample.at <= self.max_gap || steady).then_some((sample.at, next.at))
+    }
+
+    /// The sample held at a share of the span, unless it falls in a gap.
+    fn sample_at(&self, at: f32) -> Option<usize> {
+        let index = self
+            .samples
+            .partition_point(|sample| sample.at <= at)
+            .checked_sub(1)?;
+
+        self.extent(index)
+            .filter(|(_, end)| at <= *end)
+            .map(|_| index)
+    }
+}
+
+/// The sample which the pointer was last over.
+#[derive(Default)]
+struct Hover {
+    index: Option<usize>,
+}
+
+impl<'a, Message> Widget<Message, Renderer> for BatteryGraph<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Hover>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(Hover::default())
+    }
+
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(self.height)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits
+            .width(Length::Fill)
+            .height(Length::Units(self.height));
+
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if !matches!(event, Event::Mouse(mouse::Event::CursorMoved { .. })) {
+            return event::Status::Ignored;
+        }
+
+        let bounds = layout.bounds();
+
+        let index = if bounds.contains(cursor_position) && bounds.width > 0.0 {
+            self.sample_at((cursor_position.x - bounds.x) / bounds.width)
+        } else {
+            None
+        };
+
+        let hover = tree.state.downcast_mut::<Hover>();
+
+        if hover.index != index {
+            hover.index = index;
+            shell.publish((self.on_hover)(index));
+        }
+
+        event::Status::Ignored
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let bounds = layout.bounds();
+        let palette = theme.palette();
+        let hovered = tree.state.downcast_ref::<Hover>().index;
+
+        let quad = |bounds| renderer::Quad {
+            bounds,
+            border_radius: 0.0,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        };
+
+        let mut guide = palette.text;
+        guide.a = 0.1;
+
+        for level in GUIDES {
+            let y = bounds.y + (1.0 - level) * (bounds.height - 1.0);
+            renderer.fill_quad(
+                quad(Rectangle {
+                    y,
+                    height: 1.0,
+                    ..bounds
+                }),
+                guide,
+            );
+        }
+
+        for (index, sample) in self.samples.iter().enumerate() {
+            let Some((start, end)) = self.extent(index) else {
+                continue;
+            };
+
+            let x = bounds.x + start * bounds.width;
+            let width = ((end - start) * bounds.width).max(1.0);
+
+            if sample.charging {
+                let mut shade = palette.success;
+                shade.a = 0.1;
+
+                renderer.fill_quad(quad(Rectangle { x, width, ..bounds }), shade);
+            }
+
+            let height = sample.level.clamp(0.0, 1.0) * bounds.height;
+
+            let mut color = palette.primary;
+            color.a = if hovered == Some(index) { 1.0 } else { 0.6 };
+
+            renderer.fill_quad(
+                quad(Rectangle {
+                    x,
+                    y: bounds.y + bounds.height - height,
+                    width,
+                    height,
+                }),
+                color,
+            );
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<BatteryGraph<'a, Message>> for Element<'a, Message> {
+    fn from(graph: BatteryGraph<'a, Message>) -> Self {
+        Element::new(graph)
+    }
+}
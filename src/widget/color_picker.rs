@@ -0,0 +1,682 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, row, slider, text_input, Row},
+        Alignment, Color, Length,
+    },
+    iced_native::{
+        event, layout, mouse, renderer,
+        widget::{tree, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    theme,
+    widget::{icon, text},
+    Element, Renderer,
+};
+use std::collections::HashMap;
+
+/// The number of recently chosen colors which are remembered.
+pub const RECENT_COLORS: usize = 8;
+
+/// Cells drawn along each axis of the saturation and value area.
+const AREA_CELLS: (u16, u16) = (32, 20);
+
+/// A color in the sRGB color space, with an alpha channel.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Srgba {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    pub alpha: f32,
+}
+
+impl Default for Srgba {
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 1.0, 1.0)
+    }
+}
+
+impl From<Srgba> for Color {
+    fn from(color: Srgba) -> Self {
+        Color::from_rgba(color.red, color.green, color.blue, color.alpha)
+    }
+}
+
+impl Srgba {
+    #[must_use]
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Parses a `#rgb`, `#rrggbb`, or `#rrggbbaa` color, with an optional leading `#`.
+    #[must_use]
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim();
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+        if !hex.is_ascii() {
+            return None;
+        }
+
+        let channel = |digits: &str| {
+            u8::from_str_radix(digits, 16)
+                .ok()
+                .map(|v| v as f32 / 255.0)
+        };
+
+        match hex.len() {
+            3 => {
+                let short = |index: usize| channel(&hex[index..=index].repeat(2));
+                Some(Self::new(short(0)?, short(1)?, short(2)?, 1.0))
+            }
+            6 | 8 => Some(Self::new(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+                if hex.len() == 8 {
+                    channel(&hex[6..8])?
+                } else {
+                    1.0
+                },
+            )),
+            _ => None,
+        }
+    }
+
+    /// Formats the color as `#rrggbb`, or `#rrggbbaa` if `alpha` is set.
+    #[must_use]
+    pub fn to_hex(self, alpha: bool) -> String {
+        let byte = |channel: f32| (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        let mut hex = format!(
+            "#{:02x}{:02x}{:02x}",
+            byte(self.red),
+            byte(self.green),
+            byte(self.blue)
+        );
+
+        if alpha {
+            hex.push_str(&format!("{:02x}", byte(self.alpha)));
+        }
+
+        hex
+    }
+
+    /// Converts from a hue in degrees, and a saturation and value between 0 and 1.
+    #[must_use]
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        let chroma = value * saturation;
+        let sector = (hue.rem_euclid(360.0)) / 60.0;
+        let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+        let (red, green, blue) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let m = value - chroma;
+        Self::new(red + m, green + m, blue + m, alpha)
+    }
+
+    /// Converts to a hue in degrees, and a saturation and value between 0 and 1.
+    #[must_use]
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let max = self.red.max(self.green).max(self.blue);
+        let min = self.red.min(self.green).min(self.blue);
+        let delta = max - min;
+
+        let hue = if delta <= f32::EPSILON {
+            0.0
+        } else if (max - self.red).abs() <= f32::EPSILON {
+            60.0 * ((self.green - self.blue) / delta).rem_euclid(6.0)
+        } else if (max - self.green).abs() <= f32::EPSILON {
+            60.0 * ((self.blue - self.red) / delta + 2.0)
+        } else {
+            60.0 * ((self.red - self.green) / delta + 4.0)
+        };
+
+        let saturation = if max <= f32::EPSILON {
+            0.0
+        } else {
+            delta / max
+        };
+
+        (hue, saturation, max)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Alpha(f32),
+    Area(f32, f32),
+    Commit,
+    Eyedropper,
+    Hex(String),
+    Hue(f32),
+    Picked(Result<Option<Srgba>, String>),
+    Recent(Srgba),
+}
+
+/// Changes made by the color picker, which its page should act upon.
+#[derive(Clone, Debug)]
+pub enum Output {
+    /// The color is being changed, and may be previewed.
+    Changed(Srgba),
+    /// The color was chosen, and should be saved along with the recent colors.
+    Committed(Srgba),
+    /// A color should be picked from the screen with [`pick_from_screen`].
+    PickFromScreen,
+}
+
+/// The state of a color picker, with its hue kept separately so that it survives greys.
+#[derive(Debug)]
+pub struct ColorPicker {
+    hue: f32,
+    saturation: f32,
+    value: f32,
+    alpha: f32,
+    alpha_enabled: bool,
+    hex: String,
+    hex_valid: bool,
+    recent: Vec<Srgba>,
+    error: Option<String>,
+}
+
+impl Default for ColorPicker {
+    fn default() -> Self {
+        Self::new(Srgba::default())
+    }
+}
+
+impl ColorPicker {
+    #[must_use]
+    pub fn new(color: Srgba) -> Self {
+        let mut picker = Self {
+            hue: 0.0,
+            saturation: 0.0,
+            value: 1.0,
+            alpha: 1.0,
+            alpha_enabled: true,
+            hex: String::new(),
+            hex_valid: true,
+            recent: Vec::new(),
+            error: None,
+        };
+
+        picker.set_color(color);
+        picker
+    }
+
+    /// Hides the alpha slider, and keeps colors fully opaque.
+    #[must_use]
+    pub fn without_alpha(mut self) -> Self {
+        self.alpha_enabled = false;
+        self.alpha = 1.0;
+        self.hex = self.color().to_hex(false);
+        self
+    }
+
+    #[must_use]
+    pub fn color(&self) -> Srgba {
+        Srgba::from_hsv(self.hue, self.saturation, self.value, self.alpha)
+    }
+
+    pub fn set_color(&mut self, color: Srgba) {
+        let (hue, saturation, value) = color.to_hsv();
+
+        // Greys have no hue, so the previous hue is kept for them.
+        if saturation > 0.0 {
+            self.hue = hue;
+        }
+
+        self.saturation = saturation;
+        self.value = value;
+        self.alpha = if self.alpha_enabled { color.alpha } else { 1.0 };
+        self.hex = self.color().to_hex(self.alpha_enabled);
+        self.hex_valid = true;
+    }
+
+    /// The recently chosen colors, from most to least recent.
+    #[must_use]
+    pub fn recent(&self) -> &[Srgba] {
+        &self.recent
+    }
+
+    pub fn set_recent(&mut self, mut recent: Vec<Srgba>) {
+        recent.truncate(RECENT_COLORS);
+        self.recent = recent;
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Output> {
+        match message {
+            Message::Alpha(alpha) => {
+                self.alpha = alpha;
+                self.hex = self.color().to_hex(self.alpha_enabled);
+                self.hex_valid = true;
+            }
+
+            Message::Area(saturation, value) => {
+                self.saturation = saturation;
+                self.value = value;
+                self.hex = self.color().to_hex(self.alpha_enabled);
+                self.hex_valid = true;
+            }
+
+            Message::Commit => {
+                if !self.hex_valid {
+                    return None;
+                }
+
+                let color = self.color();
+                self.remember(color);
+                return Some(Output::Committed(color));
+            }
+
+            Message::Eyedropper => {
+                self.error = None;
+                return Some(Output::PickFromScreen);
+            }
+
+            Message::Hex(hex) => {
+                // Invalid input is kept as entered, so that it may be corrected.
+                let color = Srgba::from_hex(&hex);
+                self.hex_valid = color.is_some();
+                self.hex = hex;
+
+                let color = color?;
+                let hex = std::mem::take(&mut self.hex);
+                self.set_color(color);
+                self.hex = hex;
+            }
+
+            Message::Hue(hue) => {
+                self.hue = hue;
+                self.hex = self.color().to_hex(self.alpha_enabled);
+                self.hex_valid = true;
+            }
+
+            Message::Picked(Ok(Some(color))) => {
+                self.set_color(color);
+                return self.update(Message::Commit);
+            }
+
+            Message::Picked(Ok(None)) => return None,
+
+            Message::Picked(Err(why)) => {
+                self.error = Some(why);
+                return None;
+            }
+
+            Message::Recent(color) => {
+                self.set_color(color);
+                return self.update(Message::Commit);
+            }
+        }
+
+        Some(Output::Changed(self.color()))
+    }
+
+    fn remember(&mut self, color: Srgba) {
+        self.recent
+            .retain(|recent| recent.to_hex(true) != color.to_hex(true));
+        self.recent.insert(0, color);
+        self.recent.truncate(RECENT_COLORS);
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        let color = self.color();
+
+        let area = Element::new(Area {
+            hue: self.hue,
+            saturation: self.saturation,
+            value: self.value,
+        });
+
+        let hue = row!(
+            text(fl!("color-picker", "hue")).width(Length::Units(80)),
+            slider(0.0..=359.0, self.hue, Message::Hue).on_release(Message::Commit),
+        )
+        .spacing(12)
+        .align_items(Alignment::Center);
+
+        let mut hex = column!(row!(
+            Swatch::new(color, 32),
+            text_input("#rrggbb", &self.hex, Message::Hex)
+                .on_submit(Message::Commit)
+                .width(Length::Units(120)),
+            button(icon("color-select-symbolic", 16).style(theme::Svg::Symbolic))
+                .style(theme::Button::Secondary)
+                .on_press(Message::Eyedropper),
+        )
+        .spacing(12)
+        .align_items(Alignment::Center))
+        .spacing(4);
+
+        if !self.hex_valid {
+            hex = hex.push(text(fl!("color-picker", "invalid")).size(12));
+        }
+
+        if let Some(why) = &self.error {
+            hex = hex.push(text(why).size(12));
+        }
+
+        let mut view = column!(area, hue).spacing(12);
+
+        if self.alpha_enabled {
+            view = view.push(
+                row!(
+                    text(fl!("color-picker", "alpha")).width(Length::Units(80)),
+                    slider(0.0..=1.0, self.alpha, Message::Alpha)
+                        .step(0.01)
+                        .on_release(Message::Commit),
+                )
+                .spacing(12)
+                .align_items(Alignment::Center),
+            );
+        }
+
+        view = view.push(hex);
+
+        if !self.recent.is_empty() {
+            let recent = self
+                .recent
+                .iter()
+                .fold(Row::new().spacing(8), |row, &color| {
+                    row.push(
+                        Swatch::new(color, 24)
+                            .apply(button)
+                            .padding(0)
+                            .style(theme::Button::Transparent)
+                            .on_press(Message::Recent(color)),
+                    )
+                });
+
+            view =
+                view.push(column!(text(fl!("color-picker", "recent")).size(12), recent).spacing(4));
+        }
+
+        view.into()
+    }
+}
+
+/// Requests a color from the screenshot portal, which lets the user pick one from the screen.
+///
+/// Resolves to `None` if the user cancelled.
+///
+/// # Errors
+///
+/// Fails if the portal is not available.
+pub async fn pick_from_screen() -> Result<Option<Srgba>, String> {
+    pick_color().await.map_err(|why| why.to_string())
+}
+
+async fn pick_color() -> zbus::Result<Option<Srgba>> {
+    use futures_util::StreamExt;
+    use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+
+    const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+    const TOKEN: &str = "cosmic_settings_color";
+
+    let connection = zbus::Connection::session().await?;
+
+    let sender = connection
+        .unique_name()
+        .map(|name| name.as_str().trim_start_matches(':').replace('.', "_"))
+        .unwrap_or_default();
+
+    // Subscribed before the request is made, so that an early response is not missed.
+    let request = zbus::Proxy::new(
+        &connection,
+        DESTINATION,
+        format!("/org/freedesktop/portal/desktop/request/{sender}/{TOKEN}"),
+        "org.freedesktop.portal.Request",
+    )
+    .await?;
+
+    let mut responses = request.receive_signal("Response").await?;
+
+    let screenshot = zbus::Proxy::new(
+        &connection,
+        DESTINATION,
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.Screenshot",
+    )
+    .await?;
+
+    let options = HashMap::from([("handle_token", Value::from(TOKEN))]);
+    let _handle: OwnedObjectPath = screenshot.call("PickColor", &("", options)).await?;
+
+    let Some(response) = responses.next().await else {
+        return Ok(None);
+    };
+
+    let (code, results): (u32, HashMap<String, OwnedValue>) = response.body()?;
+
+    let Some(color) = results.get("color").filter(|_| code == 0) else {
+        return Ok(None);
+    };
+
+    let (red, green, blue): (f64, f64, f64) = Value::clone(color).try_into()?;
+
+    Ok(Some(Srgba::new(red as f32, green as f32, blue as f32, 1.0)))
+}
+
+/// A square filled with a color.
+struct Swatch {
+    color: Srgba,
+    size: u16,
+}
+
+impl Swatch {
+    fn new<'a, Message: 'a>(color: Srgba, size: u16) -> Element<'a, Message> {
+        Element::new(Self { color, size })
+    }
+}
+
+impl<Message> Widget<Message, Renderer> for Swatch {
+    fn width(&self) -> Length {
+        Length::Units(self.size)
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(self.size)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(self.width()).height(self.height());
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: layout.bounds(),
+                border_radius: 4.0,
+                border_width: 1.0,
+                border_color: theme.palette().text,
+            },
+            Color::from(self.color),
+        );
+    }
+}
+
+#[derive(Default)]
+struct AreaState {
+    dragging: bool,
+}
+
+/// The saturation and value of a hue, with saturation increasing to the right and value upwards.
+struct Area {
+    hue: f32,
+    saturation: f32,
+    value: f32,
+}
+
+impl Area {
+    fn publish(bounds: Rectangle, cursor_position: Point, shell: &mut Shell<'_, Message>) {
+        let saturation = ((cursor_position.x - bounds.x) / bounds.width).clamp(0.0, 1.0);
+        let value = 1.0 - ((cursor_position.y - bounds.y) / bounds.height).clamp(0.0, 1.0);
+        shell.publish(Message::Area(saturation, value));
+    }
+}
+
+impl Widget<Message, Renderer> for Area {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<AreaState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(AreaState::default())
+    }
+
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(160)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(self.width()).height(self.height());
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<AreaState>();
+        let bounds = layout.bounds();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+                if bounds.contains(cursor_position) =>
+            {
+                state.dragging = true;
+                Self::publish(bounds, cursor_position, shell);
+                event::Status::Captured
+            }
+
+            Event::Mouse(mouse::Event::CursorMoved { .. }) if state.dragging => {
+                Self::publish(bounds, cursor_position, shell);
+                event::Status::Captured
+            }
+
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) if state.dragging => {
+                state.dragging = false;
+                shell.publish(Message::Commit);
+                event::Status::Captured
+            }
+
+            _ => event::Status::Ignored,
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if layout.bounds().contains(cursor_position) {
+            mouse::Interaction::Crosshair
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let bounds = layout.bounds();
+        let (columns, rows) = AREA_CELLS;
+        let cell = Size::new(
+            bounds.width / f32::from(columns),
+            bounds.height / f32::from(rows),
+        );
+
+        for column in 0..columns {
+            for row in 0..rows {
+                let saturation = f32::from(column) / f32::from(columns - 1);
+                let value = 1.0 - f32::from(row) / f32::from(rows - 1);
+
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: Rectangle {
+                            x: bounds.x + cell.width * f32::from(column),
+                            y: bounds.y + cell.height * f32::from(row),
+                            // Slightly enlarged to avoid seams between cells.
+                            width: cell.width + 0.5,
+                            height: cell.height + 0.5,
+                        },
+                        border_radius: 0.0,
+                        border_width: 0.0,
+                        border_color: Color::TRANSPARENT,
+                    },
+                    Color::from(Srgba::from_hsv(self.hue, saturation, value, 1.0)),
+                );
+            }
+        }
+
+        let marker = Point::new(
+            bounds.x + self.saturation * bounds.width,
+            bounds.y + (1.0 - self.value) * bounds.height,
+        );
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    x: marker.x - 6.0,
+                    y: marker.y - 6.0,
+                    width: 12.0,
+                    height: 12.0,
+                },
+                border_radius: 6.0,
+                border_width: 2.0,
+                border_color: Color::WHITE,
+            },
+            Color::TRANSPARENT,
+        );
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use super::dialog::{dialog, Dialog, Response};
+
+/// An action which is confirmed before it is taken, such as one which removes something.
+///
+/// Pages ask for it with `Message::ShowConfirm`, and the app shows it in place of the page
+/// until it is answered.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Confirm<Message = crate::Message> {
+    pub title: String,
+    pub body: String,
+    pub confirm_label: String,
+    /// Whether the action destroys something, which styles its button as dangerous.
+    pub destructive: bool,
+    /// The message emitted once the action was confirmed.
+    pub on_confirm: Box<Message>,
+    /// Identifies an action of low risk, whose confirmation may be skipped from then on.
+    pub skip_key: Option<&'static str>,
+}
+
+impl<Message> Confirm<Message> {
+    pub fn new(
+        title: impl Into<String>,
+        body: impl Into<String>,
+        confirm_label: impl Into<String>,
+        on_confirm: Message,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            confirm_label: confirm_label.into(),
+            destructive: false,
+            on_confirm: Box::new(on_confirm),
+            skip_key: None,
+        }
+    }
+
+    pub fn destructive(mut self) -> Self {
+        self.destructive = true;
+        self
+    }
+
+    /// Offers a "don't ask again" checkbox, which skips this confirmation by its key once it
+    /// was checked.
+    pub fn dont_ask_again(mut self, key: &'static str) -> Self {
+        self.skip_key = Some(key);
+        self
+    }
+}
+
+/// A dialog asking to confirm an action, whose response is emitted however it was closed.
+///
+/// Cancelling is the default, which Enter takes as well as Escape, so that an action is
+/// never taken by pressing a key by habit.
+pub fn confirm_dialog<'a, Message: Clone + 'static>(
+    title: impl Into<String>,
+    body: impl Into<String>,
+    confirm_label: impl Into<String>,
+    on_confirm: impl Fn(Response) -> Message + 'a,
+) -> Dialog<'a, Message> {
+    dialog(title, on_confirm)
+        .body(body)
+        .primary(confirm_label)
+        .cancel_on_enter()
+}
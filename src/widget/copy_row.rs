@@ -0,0 +1,42 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, row, tooltip},
+        Alignment,
+    },
+    theme,
+    widget::{icon, settings, text},
+    Element,
+};
+
+/// A settings item of a read-only value, such as a version or an address, with a button
+/// which copies the value to the clipboard.
+///
+/// The value is copied as it is shown, so that the lines of a value of several lines are
+/// kept.
+#[must_use]
+pub fn copy_row<'a, Message: Clone + 'static>(
+    title: &'a str,
+    value: impl Into<String>,
+    on_copy: impl Fn(String) -> Message,
+) -> Element<'a, Message> {
+    let value = value.into();
+
+    let copy = icon("edit-copy-symbolic", 16)
+        .style(theme::Svg::Symbolic)
+        .apply(button)
+        .style(theme::Button::Transparent)
+        .on_press(on_copy(value.clone()));
+
+    let control = row!(
+        text(value),
+        tooltip(copy, fl!("copy-row"), tooltip::Position::Bottom).style(theme::Container::Box),
+    )
+    .spacing(8)
+    .align_items(Alignment::Center);
+
+    settings::item(title, control).into()
+}
@@ -0,0 +1,263 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use cosmic::{
+    iced::{
+        alignment::Horizontal,
+        widget::{button, column, horizontal_space, row, Column, Row},
+        Alignment, Length,
+    },
+    theme,
+    widget::{icon, text},
+    Element,
+};
+
+/// Width of the cells of the month grid.
+const CELL: u16 = 40;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Message {
+    Next,
+    Previous,
+    /// A day of the month which is shown was selected.
+    Select(u32),
+}
+
+/// A calendar which shows a month at a time, whose days are selected from a grid.
+///
+/// Only days which exist are shown, so that dates such as February 30 cannot be selected.
+#[derive(Debug)]
+pub struct DatePicker {
+    selected: NaiveDate,
+    /// The first day of the month which is shown.
+    shown: NaiveDate,
+    first_day: Weekday,
+    today: NaiveDate,
+}
+
+/// Creates a date picker which shows the month of the date, with weeks beginning on the
+/// first day of the week of the locale.
+#[must_use]
+pub fn date_picker(selected: NaiveDate) -> DatePicker {
+    DatePicker {
+        selected,
+        shown: first_of_month(selected),
+        first_day: locale_first_day(),
+        today: Local::now().naive_local().date(),
+    }
+}
+
+impl DatePicker {
+    /// The day which weeks begin with, in place of that of the locale.
+    #[must_use]
+    pub fn first_day_of_week(mut self, first_day: Weekday) -> Self {
+        self.first_day = first_day;
+        self
+    }
+
+    #[must_use]
+    pub fn value(&self) -> NaiveDate {
+        self.selected
+    }
+
+    /// Sets the date, such as when it is loaded, and shows its month.
+    pub fn set_value(&mut self, date: NaiveDate) {
+        self.selected = date;
+        self.shown = first_of_month(date);
+    }
+
+    /// Emits the new date whenever it changes.
+    pub fn update(&mut self, message: Message) -> Option<NaiveDate> {
+        match message {
+            Message::Next => {
+                let (year, month) = match self.shown.month() {
+                    12 => (self.shown.year() + 1, 1),
+                    month => (self.shown.year(), month + 1),
+                };
+
+                // Beyond the dates which are representable, the month is kept.
+                if let Some(shown) = NaiveDate::from_ymd_opt(year, month, 1) {
+                    self.shown = shown;
+                }
+
+                None
+            }
+
+            Message::Previous => {
+                let (year, month) = match self.shown.month() {
+                    1 => (self.shown.year() - 1, 12),
+                    month => (self.shown.year(), month - 1),
+                };
+
+                if let Some(shown) = NaiveDate::from_ymd_opt(year, month, 1) {
+                    self.shown = shown;
+                }
+
+                None
+            }
+
+            Message::Select(day) => {
+                let date = self.shown.with_day(day)?;
+                let changed = date != self.selected;
+                self.selected = date;
+                changed.then_some(date)
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn view(&self) -> Element<Message> {
+        let header = row!(
+            nav_button("go-previous-symbolic", Message::Previous),
+            text(fl!(
+                "date-picker",
+                "header",
+                month = month_name(self.shown.month()),
+                year = self.shown.year().to_string()
+            ))
+            .horizontal_alignment(Horizontal::Center)
+            .width(Length::Fill),
+            nav_button("go-next-symbolic", Message::Next),
+        )
+        .align_items(Alignment::Center)
+        .width(Length::Units(CELL * 7));
+
+        let mut weekday = self.first_day;
+        let mut weekdays = Row::new();
+
+        for _ in 0..7 {
+            weekdays = weekdays.push(
+                text(weekday_name(weekday))
+                    .size(12)
+                    .horizontal_alignment(Horizontal::Center)
+                    .width(Length::Units(CELL)),
+            );
+
+            weekday = weekday.succ();
+        }
+
+        // Days of the previous month which lead the first week are left blank.
+        let leading = (self.shown.weekday().num_days_from_monday() + 7
+            - self.first_day.num_days_from_monday())
+            % 7;
+
+        let mut cells: Vec<Element<Message>> = (0..leading)
+            .map(|_| horizontal_space(Length::Units(CELL)).into())
+            .collect();
+
+        cells.extend(
+            (1..=31)
+                .filter_map(|day| self.shown.with_day(day))
+                .map(|date| self.day_button(date)),
+        );
+
+        let mut weeks = Column::new().spacing(4);
+        let mut cells = cells.into_iter().peekable();
+
+        while cells.peek().is_some() {
+            weeks = weeks.push(Row::with_children(cells.by_ref().take(7).collect()));
+        }
+
+        column!(header, weekdays, weeks).spacing(8).into()
+    }
+
+    fn day_button(&self, date: NaiveDate) -> Element<Message> {
+        let style = if date == self.selected {
+            theme::Button::Primary
+        } else if date == self.today {
+            theme::Button::Secondary
+        } else {
+            theme::Button::Transparent
+        };
+
+        text(date.day().to_string())
+            .horizontal_alignment(Horizontal::Center)
+            .width(Length::Fill)
+            .apply(button)
+            .width(Length::Units(CELL))
+            .style(style)
+            .on_press(Message::Select(date.day()))
+            .into()
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn nav_button(name: &'static str, message: Message) -> Element<'static, Message> {
+    icon(name, 16)
+        .style(theme::Svg::Symbolic)
+        .apply(button)
+        .padding(8)
+        .style(theme::Button::Transparent)
+        .on_press(message)
+        .into()
+}
+
+/// The day which weeks begin with in the region of the locale, which is Monday in most.
+#[must_use]
+pub fn locale_first_day() -> Weekday {
+    const SATURDAY: &[&str] = &[
+        "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD", "SY",
+    ];
+
+    const SUNDAY: &[&str] = &[
+        "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CN", "CO", "DM", "DO", "ET", "GT",
+        "GU", "HK", "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH", "MM", "MO",
+        "MT", "MX", "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY", "SA", "SG", "SV",
+        "TH", "TT", "TW", "UM", "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+    ];
+
+    // Such as `en_US.UTF-8`, of which the region is `US`.
+    let locale = ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+        .unwrap_or_default();
+
+    let region = locale
+        .split(|c| c == '.' || c == '@')
+        .next()
+        .and_then(|locale| locale.split('_').nth(1))
+        .unwrap_or_default();
+
+    if SATURDAY.contains(&region) {
+        Weekday::Sat
+    } else if SUNDAY.contains(&region) {
+        Weekday::Sun
+    } else {
+        Weekday::Mon
+    }
+}
+
+fn month_name(month: u32) -> String {
+    match month {
+        1 => fl!("date-picker", "january"),
+        2 => fl!("date-picker", "february"),
+        3 => fl!("date-picker", "march"),
+        4 => fl!("date-picker", "april"),
+        5 => fl!("date-picker", "may"),
+        6 => fl!("date-picker", "june"),
+        7 => fl!("date-picker", "july"),
+        8 => fl!("date-picker", "august"),
+        9 => fl!("date-picker", "september"),
+        10 => fl!("date-picker", "october"),
+        11 => fl!("date-picker", "november"),
+        _ => fl!("date-picker", "december"),
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> String {
+    match weekday {
+        Weekday::Mon => fl!("date-picker", "mon"),
+        Weekday::Tue => fl!("date-picker", "tue"),
+        Weekday::Wed => fl!("date-picker", "wed"),
+        Weekday::Thu => fl!("date-picker", "thu"),
+        Weekday::Fri => fl!("date-picker", "fri"),
+        Weekday::Sat => fl!("date-picker", "sat"),
+        Weekday::Sun => fl!("date-picker", "sun"),
+    }
+}
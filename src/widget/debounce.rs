@@ -0,0 +1,93 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::iced::{self, Subscription};
+use std::time::{Duration, Instant};
+
+/// How long a dragged slider rests before its value is committed.
+const DELAY: Duration = Duration::from_millis(150);
+
+/// How often a dragged slider is checked for having rested.
+const INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Debug)]
+pub enum Message<T> {
+    /// The provisional value while the slider is dragged, which is shown at once.
+    SliderPreview(T),
+    /// The slider was released, which commits its value unless it was already committed.
+    SliderRelease,
+    /// Emitted while the slider is dragged, which commits its value once it has rested.
+    SliderCommit,
+}
+
+/// Debounces the values of a dragged slider, so that the backend is only written to once
+/// the slider rests or is released, rather than on every step of the drag.
+///
+/// The provisional value is kept until the slider is released, and is shown in place of
+/// the value which the backend reports meanwhile, so that the slider does not snap back.
+pub struct Debounce<T> {
+    preview: Option<T>,
+    /// When the provisional value last changed, unless it was committed since.
+    changed: Option<Instant>,
+}
+
+impl<T> Default for Debounce<T> {
+    fn default() -> Self {
+        Self {
+            preview: None,
+            changed: None,
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> Debounce<T> {
+    /// The value which the slider is dragged to, if it is dragged.
+    #[must_use]
+    pub fn preview(&self) -> Option<&T> {
+        self.preview.as_ref()
+    }
+
+    /// Forgets the dragged value, such as when what the slider controls goes away.
+    pub fn clear(&mut self) {
+        self.preview = None;
+        self.changed = None;
+    }
+
+    /// Emits the value to commit, once the slider rests or is released.
+    pub fn update(&mut self, message: Message<T>) -> Option<T> {
+        match message {
+            Message::SliderPreview(value) => {
+                if self.preview.as_ref() != Some(&value) {
+                    self.preview = Some(value);
+                    self.changed = Some(Instant::now());
+                }
+
+                None
+            }
+
+            Message::SliderCommit => {
+                if self.changed?.elapsed() < DELAY {
+                    return None;
+                }
+
+                self.changed = None;
+                self.preview.clone()
+            }
+
+            Message::SliderRelease => {
+                let value = self.preview.take();
+                self.changed.take().and(value)
+            }
+        }
+    }
+
+    /// Checks whether the slider rested while it has a value which is not committed.
+    #[must_use]
+    pub fn subscription(&self) -> Subscription<Message<T>> {
+        if self.changed.is_some() {
+            iced::time::every(INTERVAL).map(|_| Message::SliderCommit)
+        } else {
+            Subscription::none()
+        }
+    }
+}
@@ -0,0 +1,360 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{
+        widget::{button, checkbox, horizontal_space, Row},
+        Length,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{operation, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    theme,
+    widget::{settings, text},
+    Element, Renderer,
+};
+
+/// The action which closed a dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Primary,
+    Secondary,
+    /// Escape was pressed, or the dialog was cancelled.
+    Cancel,
+}
+
+/// The result of a dialog, along with the state of its "don't ask again" checkbox.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub action: Action,
+    pub dont_ask_again: bool,
+}
+
+/// A dialog asking to confirm an action, which is shown in place of the content of a page.
+///
+/// The dialog holds no state of its own, so that it may be opened and closed by messages
+/// alone, such as once a background task completes. While it is shown, Tab only moves the
+/// focus between its own controls, and Escape cancels it.
+#[must_use]
+pub struct Dialog<'a, Message> {
+    title: String,
+    body: String,
+    content: Option<Element<'a, Message>>,
+    primary: Option<(String, bool)>,
+    secondary: Option<String>,
+    cancel: String,
+    /// Whether Enter cancels the dialog, unless a control of it takes the key.
+    cancel_on_enter: bool,
+    dont_ask_again: Option<(bool, Box<dyn Fn(bool) -> Message + 'a>)>,
+    on_response: Box<dyn Fn(Response) -> Message + 'a>,
+}
+
+/// Creates a dialog, which emits a response when it is closed by any of its actions.
+pub fn dialog<'a, Message>(
+    title: impl Into<String>,
+    on_response: impl Fn(Response) -> Message + 'a,
+) -> Dialog<'a, Message> {
+    Dialog {
+        title: title.into(),
+        body: String::new(),
+        content: None,
+        primary: None,
+        secondary: None,
+        cancel: fl!("dialog", "cancel"),
+        cancel_on_enter: false,
+        dont_ask_again: None,
+        on_response: Box::new(on_response),
+    }
+}
+
+impl<'a, Message: Clone + 'static> Dialog<'a, Message> {
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Controls shown beneath the body, such as those which the dialog is opened to use.
+    pub fn content(mut self, content: impl Into<Element<'a, Message>>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    pub fn primary(mut self, label: impl Into<String>) -> Self {
+        self.primary = Some((label.into(), false));
+        self
+    }
+
+    /// A primary action which destroys something, and is styled as dangerous.
+    pub fn destructive(mut self, label: impl Into<String>) -> Self {
+        self.primary = Some((label.into(), true));
+        self
+    }
+
+    /// An action offered alongside the primary one, placed before the cancel button.
+    pub fn secondary(mut self, label: impl Into<String>) -> Self {
+        self.secondary = Some(label.into());
+        self
+    }
+
+    /// Replaces the label of the cancel button, which is always shown.
+    pub fn cancel(mut self, label: impl Into<String>) -> Self {
+        self.cancel = label.into();
+        self
+    }
+
+    /// Makes cancelling the default action, which Enter takes, such as when confirming an
+    /// action which cannot be undone.
+    pub fn cancel_on_enter(mut self) -> Self {
+        self.cancel_on_enter = true;
+        self
+    }
+
+    /// Offers to skip the dialog in the future, with a checkbox which the caller keeps the state of.
+    pub fn dont_ask_again(
+        mut self,
+        is_checked: bool,
+        on_toggle: impl Fn(bool) -> Message + 'a,
+    ) -> Self {
+        self.dont_ask_again = Some((is_checked, Box::new(on_toggle)));
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<Dialog<'a, Message>> for Element<'a, Message> {
+    fn from(dialog: Dialog<'a, Message>) -> Self {
+        let dont_ask_again = dialog
+            .dont_ask_again
+            .as_ref()
+            .map_or(false, |(is_checked, _)| *is_checked);
+
+        let respond = |action| {
+            (dialog.on_response)(Response {
+                action,
+                dont_ask_again,
+            })
+        };
+
+        let cancel = respond(Action::Cancel);
+
+        let mut buttons = Row::new().spacing(8).push(horizontal_space(Length::Fill));
+
+        if let Some(label) = dialog.secondary {
+            buttons = buttons.push(
+                button(text(label))
+                    .style(theme::Button::Secondary)
+                    .on_press(respond(Action::Secondary)),
+            );
+        }
+
+        buttons = buttons.push(
+            button(text(dialog.cancel))
+                .style(theme::Button::Secondary)
+                .on_press(cancel.clone()),
+        );
+
+        if let Some((label, destructive)) = dialog.primary {
+            let style = if destructive {
+                theme::Button::Destructive
+            } else {
+                theme::Button::Primary
+            };
+
+            buttons = buttons.push(
+                button(text(label))
+                    .style(style)
+                    .on_press(respond(Action::Primary)),
+            );
+        }
+
+        let mut children = vec![text(dialog.title).size(24).into()];
+
+        if !dialog.body.is_empty() {
+            children.push(text(dialog.body).into());
+        }
+
+        if let Some(content) = dialog.content {
+            children.push(content);
+        }
+
+        if let Some((is_checked, on_toggle)) = dialog.dont_ask_again {
+            children.push(checkbox(fl!("dialog", "dont-ask-again"), is_checked, on_toggle).into());
+        }
+
+        children.push(buttons.into());
+
+        Element::new(Trap {
+            content: settings::view_column(children).into(),
+            cancel_on_enter: dialog.cancel_on_enter,
+            on_cancel: cancel,
+        })
+    }
+}
+
+/// Keeps the focus within the content, and cancels it when Escape, or Enter if it is the
+/// default, is pressed.
+struct Trap<'a, Message> {
+    content: Element<'a, Message>,
+    cancel_on_enter: bool,
+    on_cancel: Message,
+}
+
+impl<'a, Message: Clone> Trap<'a, Message> {
+    /// Moves the focus to the next or previous control of the content, wrapping around.
+    fn cycle_focus(&self, tree: &mut Tree, layout: Layout<'_>, backwards: bool) {
+        let mut focus: Box<dyn Operation<Message>> = if backwards {
+            Box::new(operation::focusable::focus_previous())
+        } else {
+            Box::new(operation::focusable::focus_next())
+        };
+
+        // Focus operations count the focusable widgets before focusing one of them.
+        loop {
+            self.content
+                .as_widget()
+                .operate(tree, layout, focus.as_mut());
+
+            match focus.finish() {
+                operation::Outcome::Chain(next) => focus = next,
+                _ => break,
+            }
+        }
+    }
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Trap<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if let Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code,
+            modifiers,
+        }) = &event
+        {
+            match key_code {
+                KeyCode::Escape => {
+                    shell.publish(self.on_cancel.clone());
+                    return event::Status::Captured;
+                }
+
+                // Captured, so that the app does not move the focus out of the dialog.
+                KeyCode::Tab => {
+                    self.cycle_focus(&mut tree.children[0], layout, modifiers.shift());
+                    return event::Status::Captured;
+                }
+
+                _ => (),
+            }
+        }
+
+        let is_enter = matches!(
+            &event,
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Enter | KeyCode::NumpadEnter,
+                ..
+            })
+        );
+
+        let status = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        );
+
+        if self.cancel_on_enter && is_enter && status == event::Status::Ignored {
+            shell.publish(self.on_cancel.clone());
+            return event::Status::Captured;
+        }
+
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
@@ -0,0 +1,429 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{alignment, Length},
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, renderer, text,
+        widget::{operation, tree, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    Element, Renderer,
+};
+use cosmic_settings_display::arrangement::{self, Rect};
+
+/// Space left around the layout, in pixels, so that outputs can be dragged past its edges.
+const PADDING: f32 = 24.0;
+
+/// Distance in pixels within which a dragged output snaps to the edges of the others.
+const SNAP_DISTANCE: f32 = 16.0;
+
+/// The largest scale at which outputs are drawn, so that small layouts are not enlarged
+/// to fill the canvas. Larger layouts are zoomed out until they fit.
+const MAX_SCALE: f32 = 0.125;
+
+/// Logical pixels by which the selected output is moved with each press of an arrow key.
+const NUDGE: i32 = 8;
+
+/// An output as it is drawn in the arrangement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputInfo {
+    /// Identifies the output in the messages of the arrangement.
+    pub id: String,
+    pub name: String,
+    /// Shown beneath the name, such as the resolution of the output.
+    pub details: String,
+    /// The primary output is marked with a star.
+    pub primary: bool,
+    /// The logical area of the output in the layout.
+    pub rect: Rect,
+}
+
+/// A canvas of outputs drawn as rectangles, scaled to their logical sizes, which are dragged
+/// to new positions.
+///
+/// A dragged output snaps to the edges of the others and never overlaps them. Pressing an
+/// output selects it, after which it is nudged with the arrow keys. New positions are only
+/// published on drop or nudge, and are for the owner to validate before applying them.
+///
+/// Outputs may be removed while one is dragged, such as when a display is disconnected. A
+/// drag of an output which was removed is cancelled.
+pub struct DisplayArrangement<'a, Message> {
+    outputs: Vec<OutputInfo>,
+    on_move: Box<dyn Fn(String, i32, i32) -> Message + 'a>,
+}
+
+/// Creates an arrangement of outputs, which publishes `on_move` with the id and new logical
+/// position of each output that is moved.
+pub fn display_arrangement<'a, Message: 'a>(
+    outputs: &[OutputInfo],
+    on_move: impl Fn(String, i32, i32) -> Message + 'a,
+) -> Element<'a, Message> {
+    Element::new(DisplayArrangement {
+        outputs: outputs.to_vec(),
+        on_move: Box::new(on_move),
+    })
+}
+
+#[derive(Default)]
+struct State {
+    drag: Option<Drag>,
+    focused: bool,
+    /// The id of the output which is nudged with the keyboard.
+    selected: Option<String>,
+}
+
+impl operation::Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+struct Drag {
+    /// The id of the dragged output, which is followed even as others are added or removed.
+    id: String,
+    /// The cursor's offset from the top-left corner of the output when it was grabbed.
+    grab: (f32, f32),
+    cursor: Point,
+}
+
+/// The mapping of logical positions to the bounds of the widget.
+struct Viewport {
+    origin: Point,
+    min: (i32, i32),
+    scale: f32,
+}
+
+impl Viewport {
+    fn new(rects: &[Rect], bounds: Rectangle) -> Self {
+        let min_x = rects.iter().map(|rect| rect.x).min().unwrap_or(0);
+        let min_y = rects.iter().map(|rect| rect.y).min().unwrap_or(0);
+        let max_x = rects.iter().map(Rect::right).max().unwrap_or(1);
+        let max_y = rects.iter().map(Rect::bottom).max().unwrap_or(1);
+
+        let (width, height) = ((max_x - min_x).max(1) as f32, (max_y - min_y).max(1) as f32);
+
+        let scale = ((bounds.width - PADDING * 2.0) / width)
+            .min((bounds.height - PADDING * 2.0) / height)
+            .min(MAX_SCALE)
+            .max(f32::EPSILON);
+
+        // The layout is centered within the widget.
+        let origin = Point::new(
+            bounds.x + (bounds.width - width * scale) / 2.0,
+            bounds.y + (bounds.height - height * scale) / 2.0,
+        );
+
+        Self {
+            origin,
+            min: (min_x, min_y),
+            scale,
+        }
+    }
+
+    fn to_screen(&self, rect: &Rect) -> Rectangle {
+        Rectangle {
+            x: self.origin.x + (rect.x - self.min.0) as f32 * self.scale,
+            y: self.origin.y + (rect.y - self.min.1) as f32 * self.scale,
+            width: rect.width as f32 * self.scale,
+            height: rect.height as f32 * self.scale,
+        }
+    }
+
+    fn to_logical(&self, point: Point) -> (i32, i32) {
+        (
+            ((point.x - self.origin.x) / self.scale).round() as i32 + self.min.0,
+            ((point.y - self.origin.y) / self.scale).round() as i32 + self.min.1,
+        )
+    }
+}
+
+impl<'a, Message> DisplayArrangement<'a, Message> {
+    fn rects(&self) -> Vec<Rect> {
+        self.outputs.iter().map(|output| output.rect).collect()
+    }
+
+    /// Where the dragged output would be dropped, once snapped to the edges of the others,
+    /// unless it was removed.
+    fn drop_position(&self, viewport: &Viewport, drag: &Drag) -> Option<(i32, i32)> {
+        let moving = self
+            .outputs
+            .iter()
+            .find(|output| output.id == drag.id)?
+            .rect;
+
+        let (x, y) = viewport.to_logical(Point::new(
+            drag.cursor.x - drag.grab.0,
+            drag.cursor.y - drag.grab.1,
+        ));
+
+        let others: Vec<Rect> = self
+            .outputs
+            .iter()
+            .filter(|output| output.id != drag.id)
+            .map(|output| output.rect)
+            .collect();
+
+        let threshold = (SNAP_DISTANCE / viewport.scale) as i32;
+        Some(arrangement::snap(
+            &others,
+            Rect { x, y, ..moving },
+            threshold,
+        ))
+    }
+}
+
+impl<'a, Message> Widget<Message, Renderer> for DisplayArrangement<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(240)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(self.width()).height(self.height());
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        _layout: Layout<'_>,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        operation.focusable(tree.state.downcast_mut::<State>(), None);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+        let viewport = Viewport::new(&self.rects(), layout.bounds());
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.focused = layout.bounds().contains(cursor_position);
+
+                let grabbed = self.outputs.iter().find_map(|output| {
+                    let bounds = viewport.to_screen(&output.rect);
+                    bounds.contains(cursor_position).then_some((output, bounds))
+                });
+
+                if let Some((output, bounds)) = grabbed {
+                    state.selected = Some(output.id.clone());
+                    state.drag = Some(Drag {
+                        id: output.id.clone(),
+                        grab: (cursor_position.x - bounds.x, cursor_position.y - bounds.y),
+                        cursor: cursor_position,
+                    });
+
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                state.focused = layout.bounds().contains(cursor_position);
+            }
+
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                if let Some(drag) = state.drag.as_mut() {
+                    drag.cursor = cursor_position;
+
+                    if !self.outputs.iter().any(|output| output.id == drag.id) {
+                        state.drag = None;
+                    }
+
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some(drag) = state.drag.take() {
+                    let output = self.outputs.iter().find(|output| output.id == drag.id);
+
+                    if let (Some(output), Some((x, y))) =
+                        (output, self.drop_position(&viewport, &drag))
+                    {
+                        if (x, y) != (output.rect.x, output.rect.y) {
+                            shell.publish((self.on_move)(output.id.clone(), x, y));
+                        }
+                    }
+
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Mouse(mouse::Event::CursorLeft) => state.drag = None,
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Escape,
+                ..
+            }) if state.drag.is_some() => {
+                state.drag = None;
+                return event::Status::Captured;
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. })
+                if state.focused && state.drag.is_none() =>
+            {
+                let (dx, dy) = match key_code {
+                    KeyCode::Left => (-NUDGE, 0),
+                    KeyCode::Right => (NUDGE, 0),
+                    KeyCode::Up => (0, -NUDGE),
+                    KeyCode::Down => (0, NUDGE),
+                    _ => return event::Status::Ignored,
+                };
+
+                let selected = state
+                    .selected
+                    .as_ref()
+                    .and_then(|id| self.outputs.iter().find(|output| &output.id == id));
+
+                if let Some(output) = selected {
+                    let (x, y) = (output.rect.x + dx, output.rect.y + dy);
+                    shell.publish((self.on_move)(output.id.clone(), x, y));
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let dragging = tree
+            .state
+            .downcast_ref::<State>()
+            .drag
+            .as_ref()
+            .map_or(false, |drag| self.outputs.iter().any(|o| o.id == drag.id));
+
+        if dragging {
+            return mouse::Interaction::Grabbing;
+        }
+
+        let viewport = Viewport::new(&self.rects(), layout.bounds());
+
+        let hovered = self
+            .outputs
+            .iter()
+            .any(|output| viewport.to_screen(&output.rect).contains(cursor_position));
+
+        if hovered {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::{text::Renderer as _, Renderer as _};
+
+        let state = tree.state.downcast_ref::<State>();
+        let viewport = Viewport::new(&self.rects(), layout.bounds());
+        let palette = theme.palette();
+
+        for output in &self.outputs {
+            let mut bounds = viewport.to_screen(&output.rect);
+
+            let dragged = state.drag.as_ref().filter(|drag| drag.id == output.id);
+
+            // The dragged output is drawn where it would snap to if it were dropped.
+            if let Some(drag) = dragged {
+                if let Some((x, y)) = self.drop_position(&viewport, drag) {
+                    bounds = viewport.to_screen(&Rect {
+                        x,
+                        y,
+                        ..output.rect
+                    });
+                }
+            }
+
+            let selected = state.selected.as_ref() == Some(&output.id);
+
+            let mut background = palette.primary;
+            background.a = if dragged.is_some() || selected {
+                0.5
+            } else {
+                0.25
+            };
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds,
+                    border_radius: 4.0,
+                    border_width: if selected && state.focused { 3.0 } else { 1.0 },
+                    border_color: palette.primary,
+                },
+                background,
+            );
+
+            let star = if output.primary { "★ " } else { "" };
+
+            let label = if output.details.is_empty() {
+                format!("{star}{}", output.name)
+            } else {
+                format!("{star}{}\n{}", output.name, output.details)
+            };
+
+            renderer.fill_text(text::Text {
+                content: &label,
+                bounds: Rectangle {
+                    x: bounds.center_x(),
+                    y: bounds.center_y(),
+                    ..bounds
+                },
+                size: 14.0,
+                color: palette.text,
+                font: Default::default(),
+                horizontal_alignment: alignment::Horizontal::Center,
+                vertical_alignment: alignment::Vertical::Center,
+            });
+        }
+    }
+}
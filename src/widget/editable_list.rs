@@ -0,0 +1,230 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, row, text_input},
+        Alignment, Command, Length,
+    },
+    theme,
+    widget::{icon, text},
+    Element,
+};
+
+use super::reorder_list;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    /// Begins to add an entry after the last one.
+    Add,
+    Cancel,
+    Edit(usize),
+    Input(String),
+    Move(usize, usize),
+    Remove(usize),
+    Submit,
+}
+
+/// A change to the entries of a list, which its owner applies to them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    Added(usize, String),
+    Edited(usize, String),
+    Removed(usize),
+    /// An entry was dragged from the first position to the second.
+    Moved(usize, usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Target {
+    New,
+    Entry(usize),
+}
+
+#[derive(Debug)]
+struct Editing {
+    target: Target,
+    text: String,
+    error: Option<String>,
+    id: text_input::Id,
+}
+
+/// A list of strings whose entries are added, edited, removed, and dragged into place.
+///
+/// The entries are kept by the owner of the list, which is told of every change to them,
+/// so that the list only keeps the entry which is being edited.
+#[derive(Debug, Default)]
+pub struct EditableList {
+    editing: Option<Editing>,
+    validate: Option<fn(&str) -> Result<(), String>>,
+}
+
+impl EditableList {
+    /// Rejects entries which are not valid, with the reason shown beneath the input.
+    #[must_use]
+    pub fn validator(mut self, validate: fn(&str) -> Result<(), String>) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
+    /// Whether an entry is being added or edited.
+    #[must_use]
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// Handles a message of the list, given its entries, along with the command which focuses
+    /// the input once an entry is being added or edited.
+    pub fn update(
+        &mut self,
+        message: Message,
+        entries: &[String],
+    ) -> (Option<Change>, Command<Message>) {
+        match message {
+            Message::Add => return (None, self.begin(Target::New, String::new())),
+
+            Message::Cancel => self.editing = None,
+
+            Message::Edit(index) => {
+                if let Some(entry) = entries.get(index) {
+                    return (None, self.begin(Target::Entry(index), entry.clone()));
+                }
+            }
+
+            Message::Input(text) => {
+                if let Some(editing) = self.editing.as_mut() {
+                    editing.text = text;
+                    editing.error = None;
+                }
+            }
+
+            Message::Move(from, to) => {
+                if from != to && from < entries.len() && to < entries.len() {
+                    return (Some(Change::Moved(from, to)), Command::none());
+                }
+            }
+
+            Message::Remove(index) => {
+                if index < entries.len() {
+                    self.editing = None;
+                    return (Some(Change::Removed(index)), Command::none());
+                }
+            }
+
+            Message::Submit => return (self.submit(entries.len()), Command::none()),
+        }
+
+        (None, Command::none())
+    }
+
+    /// Begins to edit an entry, with the input focused so that it may be typed into at once.
+    fn begin(&mut self, target: Target, text: String) -> Command<Message> {
+        let id = text_input::Id::unique();
+
+        self.editing = Some(Editing {
+            target,
+            text,
+            error: None,
+            id: id.clone(),
+        });
+
+        text_input::focus(id)
+    }
+
+    fn submit(&mut self, len: usize) -> Option<Change> {
+        let editing = self.editing.as_mut()?;
+        let text = editing.text.trim();
+
+        if text.is_empty() {
+            return None;
+        }
+
+        if let Some(validate) = self.validate {
+            if let Err(why) = validate(text) {
+                editing.error = Some(why);
+                return None;
+            }
+        }
+
+        let text = text.to_owned();
+
+        match self.editing.take()?.target {
+            Target::New => Some(Change::Added(len, text)),
+            Target::Entry(index) if index < len => Some(Change::Edited(index, text)),
+            Target::Entry(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn view<'a>(&'a self, entries: &'a [String]) -> Element<'a, Message> {
+        let editing = self.editing.as_ref();
+
+        let rows = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| match editing {
+                Some(editing) if editing.target == Target::Entry(index) => editor(editing),
+
+                _ => row!(
+                    text(entry.as_str()).width(Length::Fill),
+                    icon_button("edit-symbolic", Message::Edit(index)),
+                    icon_button("edit-delete-symbolic", Message::Remove(index)),
+                )
+                .spacing(8)
+                .align_items(Alignment::Center)
+                .into(),
+            })
+            .collect();
+
+        let add = match editing {
+            Some(editing) if editing.target == Target::New => editor(editing),
+            _ => button(text(fl!("editable-list", "add")))
+                .style(theme::Button::Secondary)
+                .on_press(Message::Add)
+                .into(),
+        };
+
+        column!(reorder_list(rows, Message::Move), add)
+            .spacing(8)
+            .into()
+    }
+}
+
+fn editor(editing: &Editing) -> Element<Message> {
+    let input = text_input("", &editing.text, Message::Input)
+        .id(editing.id.clone())
+        .on_submit(Message::Submit)
+        .padding(8)
+        .width(Length::Fill);
+
+    let input = row!(
+        input,
+        button(text(fl!("editable-list", "cancel")))
+            .style(theme::Button::Secondary)
+            .on_press(Message::Cancel),
+        button(text(fl!("editable-list", "save")))
+            .style(theme::Button::Primary)
+            .on_press(Message::Submit),
+    )
+    .spacing(8)
+    .align_items(Alignment::Center);
+
+    match &editing.error {
+        Some(why) => column!(input, text(why).size(12))
+            .spacing(4)
+            .width(Length::Fill)
+            .into(),
+        None => input.into(),
+    }
+}
+
+fn icon_button(name: &'static str, message: Message) -> Element<'static, Message> {
+    icon(name, 16)
+        .style(theme::Svg::Symbolic)
+        .apply(button)
+        .padding(8)
+        .style(theme::Button::Transparent)
+        .on_press(message)
+        .into()
+}
@@ -0,0 +1,381 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, horizontal_space, row},
+        Alignment, Length,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{tree, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    theme,
+    widget::{icon, text},
+    Element, Renderer,
+};
+
+use super::Focus;
+
+/// A header which collapses and expands the content beneath it.
+#[must_use]
+pub struct Expander<'a, Message> {
+    title: &'a str,
+    subtitle: &'a str,
+    expanded: bool,
+    progress: Option<f32>,
+    on_toggle: Option<Box<dyn Fn(bool) -> Message + 'a>>,
+    content: Option<Element<'a, Message>>,
+}
+
+/// Creates an expander, which omits its subtitle if it is empty.
+pub fn expander<'a, Message>(title: &'a str, subtitle: &'a str) -> Expander<'a, Message> {
+    Expander {
+        title,
+        subtitle,
+        expanded: false,
+        progress: None,
+        on_toggle: None,
+        content: None,
+    }
+}
+
+impl<'a, Message: Clone + 'static> Expander<'a, Message> {
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// How much of the content is revealed while animating, from 0 to 1.
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.progress = Some(progress.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Emitted by pressing the header, or by pressing Enter or Space while it is focused.
+    pub fn on_toggle(mut self, on_toggle: impl Fn(bool) -> Message + 'a) -> Self {
+        self.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    pub fn content(mut self, content: impl Into<Element<'a, Message>>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<Expander<'a, Message>> for Element<'a, Message> {
+    fn from(expander: Expander<'a, Message>) -> Self {
+        let mut labels = column!(text(expander.title).size(18)).spacing(2);
+
+        if !expander.subtitle.is_empty() {
+            labels = labels.push(text(expander.subtitle).size(12));
+        }
+
+        let chevron = if expander.expanded {
+            "go-down-symbolic"
+        } else {
+            "go-next-symbolic"
+        };
+
+        let mut header = row!(
+            labels,
+            horizontal_space(Length::Fill),
+            icon(chevron, 16).style(theme::Svg::Symbolic),
+        )
+        .align_items(Alignment::Center)
+        .apply(button)
+        .padding([8, 0])
+        .width(Length::Fill)
+        .style(theme::Button::Transparent);
+
+        let toggle = expander
+            .on_toggle
+            .as_ref()
+            .map(|on_toggle| on_toggle(!expander.expanded));
+
+        if let Some(message) = toggle.clone() {
+            header = header.on_press(message);
+        }
+
+        let header = Element::new(Activate {
+            content: header.into(),
+            on_activate: toggle,
+        });
+
+        let progress = expander
+            .progress
+            .unwrap_or(if expander.expanded { 1.0 } else { 0.0 });
+
+        match expander.content {
+            Some(content) if progress > 0.0 => {
+                column!(header, Element::new(Reveal { content, progress }))
+                    .spacing(8)
+                    .into()
+            }
+            _ => header,
+        }
+    }
+}
+
+/// Publishes a message when Enter or Space is pressed while the content is focused.
+struct Activate<'a, Message> {
+    content: Element<'a, Message>,
+    on_activate: Option<Message>,
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Activate<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Focus>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(Focus::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        operation.focusable(tree.state.downcast_mut::<Focus>(), None);
+
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let focus = tree.state.downcast_mut::<Focus>();
+
+        match &event {
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                focus.focused = layout.bounds().contains(cursor_position);
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space,
+                ..
+            }) if focus.focused => {
+                if let Some(message) = &self.on_activate {
+                    shell.publish(message.clone());
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+/// Reveals a fraction of the height of its content, which is clipped to the rest.
+///
+/// The content only receives events once it is fully revealed.
+struct Reveal<'a, Message> {
+    content: Element<'a, Message>,
+    progress: f32,
+}
+
+impl<'a, Message> Widget<Message, Renderer> for Reveal<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let content = self.content.as_widget().layout(renderer, limits);
+        let size = content.size();
+
+        layout::Node::with_children(
+            Size::new(size.width, size.height * self.progress),
+            vec![content],
+        )
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        if let Some(layout) = layout.children().next() {
+            self.content
+                .as_widget()
+                .operate(&mut tree.children[0], layout, operation);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let Some(content_layout) = layout.children().next() else {
+            return event::Status::Ignored;
+        };
+
+        if self.progress < 1.0 {
+            return event::Status::Ignored;
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            content_layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        match layout.children().next() {
+            Some(content_layout) if self.progress >= 1.0 => {
+                self.content.as_widget().mouse_interaction(
+                    &tree.children[0],
+                    content_layout,
+                    cursor_position,
+                    viewport,
+                    renderer,
+                )
+            }
+            _ => mouse::Interaction::default(),
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let Some(content_layout) = layout.children().next() else {
+            return;
+        };
+
+        renderer.with_layer(layout.bounds(), |renderer| {
+            self.content.as_widget().draw(
+                &tree.children[0],
+                renderer,
+                theme,
+                style,
+                content_layout,
+                cursor_position,
+                viewport,
+            );
+        });
+    }
+}
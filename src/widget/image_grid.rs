@@ -0,0 +1,430 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{column, container, image, scrollable, vertical_space, Column, Row},
+        Alignment, Command, Length,
+    },
+    theme,
+    widget::icon,
+    Element,
+};
+use once_cell::sync::Lazy;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::page;
+
+const COLUMNS: usize = 4;
+const TILE_WIDTH: u16 = 150;
+const TILE_HEIGHT: u16 = 94;
+const SPACING: u16 = 16;
+
+/// The height of a row of tiles, including the checkmark beneath them and their padding.
+const ROW_HEIGHT: u16 = TILE_HEIGHT + 28 + SPACING;
+
+/// The number of rows shown at once, beyond which the grid scrolls.
+const VISIBLE_ROWS: usize = 3;
+
+/// The number of thumbnails which are decoded at the same time.
+const MAX_TASKS: usize = 4;
+
+/// The largest dimension of a thumbnail, which is twice that of a tile for scaled displays.
+const THUMBNAIL_SIZE: u32 = 2 * TILE_WIDTH as u32;
+
+/// The number of decoded thumbnails which are kept in memory, of about 200 KiB each.
+const MEMORY_CAPACITY: usize = 128;
+
+/// Thumbnails which were decoded recently, shared by every grid so that they are shown again
+/// without reading them from the disk.
+static MEMORY: Lazy<Mutex<Memory>> = Lazy::new(Mutex::default);
+
+/// The least recently used thumbnails are forgotten beyond [`MEMORY_CAPACITY`].
+#[derive(Default)]
+struct Memory {
+    /// The thumbnail of each image, with the time that the image was modified, and when the
+    /// thumbnail was last used.
+    entries: HashMap<PathBuf, (SystemTime, image::Handle, u64)>,
+    uses: u64,
+}
+
+impl Memory {
+    fn get(&mut self, path: &Path, modified: SystemTime) -> Option<image::Handle> {
+        self.uses += 1;
+
+        let (entry_modified, handle, used) = self.entries.get_mut(path)?;
+
+        if *entry_modified != modified {
+            return None;
+        }
+
+        *used = self.uses;
+        Some(handle.clone())
+    }
+
+    fn insert(&mut self, path: PathBuf, modified: SystemTime, handle: image::Handle) {
+        self.uses += 1;
+        self.entries.insert(path, (modified, handle, self.uses));
+
+        if self.entries.len() > MEMORY_CAPACITY {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (.., used))| *used)
+                .map(|(path, _)| path.clone());
+
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message<Id> {
+    /// A thumbnail of the images set by a generation of the grid was decoded.
+    Loaded(u64, usize, Result<image::Handle, String>),
+    /// The relative offset of the grid, from 0 to 1.
+    Scrolled(f32),
+    /// Handled by the owner of the grid, which decides what is selected.
+    Select(Id),
+}
+
+#[derive(Clone, Debug)]
+enum Thumbnail {
+    Pending,
+    Loading,
+    Loaded(image::Handle),
+    Failed,
+}
+
+/// A grid of image thumbnails, which are decoded in the background once scrolled into view.
+///
+/// Thumbnails are cached beneath the cache directory, and the most recent of them in memory,
+/// so that they are only decoded from their images again once those images are modified.
+pub struct ImageGrid<Id> {
+    images: Vec<(Id, PathBuf)>,
+    thumbnails: Vec<Thumbnail>,
+    /// Incremented whenever the images are set, so that stale thumbnails are discarded.
+    generation: u64,
+    in_flight: usize,
+    first_row: usize,
+}
+
+impl<Id> Default for ImageGrid<Id> {
+    fn default() -> Self {
+        Self {
+            images: Vec::new(),
+            thumbnails: Vec::new(),
+            generation: 0,
+            in_flight: 0,
+            first_row: 0,
+        }
+    }
+}
+
+impl<Id: Clone + PartialEq + std::fmt::Debug + Send + 'static> ImageGrid<Id> {
+    /// Replaces the images of the grid, and begins to decode those in view.
+    pub fn set_images(
+        &mut self,
+        images: impl IntoIterator<Item = (Id, PathBuf)>,
+    ) -> Command<Message<Id>> {
+        self.images = images.into_iter().collect();
+        self.thumbnails = vec![Thumbnail::Pending; self.images.len()];
+        self.generation += 1;
+        self.first_row = 0;
+        self.load_visible()
+    }
+
+    pub fn update(&mut self, message: Message<Id>) -> Command<Message<Id>> {
+        match message {
+            Message::Loaded(generation, index, result) => {
+                self.in_flight = self.in_flight.saturating_sub(1);
+
+                if generation == self.generation {
+                    self.thumbnails[index] = match result {
+                        Ok(handle) => Thumbnail::Loaded(handle),
+                        Err(_) => Thumbnail::Failed,
+                    };
+                }
+            }
+
+            Message::Scrolled(offset) => {
+                let rows = self.rows();
+                let hidden = rows.saturating_sub(VISIBLE_ROWS) as f32;
+                self.first_row = (offset.clamp(0.0, 1.0) * hidden).floor() as usize;
+            }
+
+            Message::Select(_) => return Command::none(),
+        }
+
+        self.load_visible()
+    }
+
+    fn rows(&self) -> usize {
+        (self.images.len() + COLUMNS - 1) / COLUMNS
+    }
+
+    /// The images of the rows in view, along with those of the row after them.
+    fn visible(&self) -> std::ops::Range<usize> {
+        let start = self.first_row * COLUMNS;
+        let end = (start + (VISIBLE_ROWS + 1) * COLUMNS).min(self.images.len());
+        start..end
+    }
+
+    /// Decodes the thumbnails in view, without exceeding the limit of tasks.
+    fn load_visible(&mut self) -> Command<Message<Id>> {
+        let mut commands = Vec::new();
+
+        for index in self.visible() {
+            if self.in_flight >= MAX_TASKS {
+                break;
+            }
+
+            if !matches!(self.thumbnails[index], Thumbnail::Pending) {
+                continue;
+            }
+
+            self.thumbnails[index] = Thumbnail::Loading;
+            self.in_flight += 1;
+
+            let path = self.images[index].1.clone();
+            let generation = self.generation;
+
+            commands.push(Command::perform(
+                page::background(move || thumbnail(&path)),
+                move |result| {
+                    let result = result.unwrap_or_else(|| Err(fl!("unknown")));
+                    Message::Loaded(generation, index, result)
+                },
+            ));
+        }
+
+        Command::batch(commands)
+    }
+
+    /// Displays the rows in view, between spaces taking the place of the others.
+    #[must_use]
+    pub fn view(&self, selected: Option<&Id>) -> Element<Message<Id>> {
+        let rows = self.rows();
+        let range = self.visible();
+        let last_row = (range.end + COLUMNS - 1) / COLUMNS;
+
+        let mut grid = space(Column::new(), self.first_row);
+
+        for (offset, chunk) in self.images[range.clone()].chunks(COLUMNS).enumerate() {
+            let start = range.start + offset * COLUMNS;
+
+            let tiles = chunk.iter().enumerate().fold(
+                Row::new().spacing(SPACING),
+                |tiles, (column, (id, _))| {
+                    let thumbnail = &self.thumbnails[start + column];
+                    tiles.push(tile(id, thumbnail, selected == Some(id)))
+                },
+            );
+
+            grid = grid.push(tiles.apply(container).padding([0, 0, SPACING, 0]));
+        }
+
+        grid = space(grid, rows.saturating_sub(last_row));
+
+        grid.apply(scrollable)
+            .height(Length::Units(VISIBLE_ROWS.min(rows) as u16 * ROW_HEIGHT))
+            .on_scroll(Message::Scrolled)
+            .into()
+    }
+}
+
+/// Takes the place of rows which are not in view, with as many spaces as their height needs.
+fn space<Message>(
+    mut grid: Column<Message, cosmic::Renderer>,
+    rows: usize,
+) -> Column<Message, cosmic::Renderer> {
+    let mut height = rows * ROW_HEIGHT as usize;
+
+    while height > 0 {
+        let space = height.min(u16::MAX as usize);
+        grid = grid.push(vertical_space(Length::Units(space as u16)));
+        height -= space;
+    }
+
+    grid
+}
+
+fn tile<'a, Id: Clone + 'static>(
+    id: &Id,
+    thumbnail: &Thumbnail,
+    is_selected: bool,
+) -> Element<'a, Message<Id>> {
+    let preview: Element<_> = match thumbnail {
+        Thumbnail::Loaded(handle) => image(handle.clone())
+            .width(Length::Units(TILE_WIDTH))
+            .height(Length::Units(TILE_HEIGHT))
+            .into(),
+
+        Thumbnail::Failed => icon("image-missing-symbolic", 32)
+            .style(theme::Svg::Symbolic)
+            .apply(placeholder),
+
+        Thumbnail::Pending | Thumbnail::Loading => {
+            vertical_space(Length::Units(0)).apply(placeholder)
+        }
+    };
+
+    let mut content = column!(preview).spacing(4).align_items(Alignment::End);
+
+    content = if is_selected {
+        content.push(icon("object-select-symbolic", 16).style(theme::Svg::Symbolic))
+    } else {
+        content.push(vertical_space(Length::Units(16)))
+    };
+
+    let style: fn(&cosmic::Theme) -> container::Appearance = if is_selected {
+        selected_style
+    } else {
+        tile_style
+    };
+
+    content
+        .apply(container)
+        .padding(4)
+        .style(theme::Container::Custom(style))
+        .apply(cosmic::iced::widget::button)
+        .padding(0)
+        .style(theme::Button::Transparent)
+        .on_press(Message::Select(id.clone()))
+        .into()
+}
+
+/// Takes the place of a thumbnail which has not been decoded.
+fn placeholder<'a, Message: 'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    container(content)
+        .width(Length::Units(TILE_WIDTH))
+        .height(Length::Units(TILE_HEIGHT))
+        .center_x()
+        .center_y()
+        .style(theme::Container::Box)
+        .into()
+}
+
+fn tile_style(_theme: &cosmic::Theme) -> container::Appearance {
+    container::Appearance {
+        border_radius: 8.0,
+        ..container::Appearance::default()
+    }
+}
+
+fn selected_style(theme: &cosmic::Theme) -> container::Appearance {
+    container::Appearance {
+        border_color: theme.palette().primary,
+        border_radius: 8.0,
+        border_width: 2.0,
+        ..container::Appearance::default()
+    }
+}
+
+/// Decodes and downscales an image, or loads its cached thumbnail.
+fn thumbnail(path: &Path) -> Result<image::Handle, String> {
+    let modified = fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .map_err(|why| why.to_string())?;
+
+    // A poisoned cache is only skipped, as the thumbnail is loaded from the disk instead.
+    let remembered = MEMORY
+        .lock()
+        .ok()
+        .and_then(|mut memory| memory.get(path, modified));
+
+    if let Some(handle) = remembered {
+        return Ok(handle);
+    }
+
+    let cache = cache_path(path, modified);
+    let cached = cache.as_deref().and_then(|cache| ::image::open(cache).ok());
+
+    let thumbnail = match cached {
+        Some(cached) => cached.to_rgba8(),
+        None => {
+            let thumbnail = ::image::open(path)
+                .map_err(|why| why.to_string())?
+                .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+                .to_rgba8();
+
+            // Failing to cache a thumbnail only means that it will be decoded again.
+            if let Some(cache) = cache {
+                if let Some(parent) = cache.parent() {
+                    let _res = fs::create_dir_all(parent);
+                    remove_stale(parent, &cache);
+                }
+
+                let _res = thumbnail.save(&cache);
+            }
+
+            thumbnail
+        }
+    };
+
+    let (width, height) = thumbnail.dimensions();
+    let handle = image::Handle::from_pixels(width, height, thumbnail.into_raw());
+
+    if let Ok(mut memory) = MEMORY.lock() {
+        memory.insert(path.to_owned(), modified, handle.clone());
+    }
+
+    Ok(handle)
+}
+
+/// The cached thumbnail of an image, named after its path and the time it was modified, as
+/// in `<path hash>-<modified>.png`.
+fn cache_path(path: &Path, modified: SystemTime) -> Option<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+
+    let modified = modified.duration_since(UNIX_EPOCH).ok()?;
+
+    let mut cache = dirs::cache_dir()?;
+    cache.push("cosmic-settings");
+    cache.push("thumbnails");
+    cache.push(format!(
+        "{:016x}-{}.{:09}.png",
+        hasher.finish(),
+        modified.as_secs(),
+        modified.subsec_nanos()
+    ));
+    Some(cache)
+}
+
+/// Removes the thumbnails of an image from before it was last modified.
+fn remove_stale(dir: &Path, current: &Path) {
+    let Some(prefix) = current
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split_once('-'))
+        .map(|(hash, _)| format!("{hash}-"))
+    else {
+        return;
+    };
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(Result::ok) {
+        let stale = entry
+            .file_name()
+            .to_str()
+            .map_or(false, |name| name.starts_with(&prefix));
+
+        if stale && entry.path() != current {
+            let _res = fs::remove_file(entry.path());
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, container, tooltip, Column},
+        Length,
+    },
+    theme,
+    widget::{icon, text},
+    Element,
+};
+
+use super::{link_row::activate, popover};
+
+/// A button beside a setting which explains it in a popover, rather than in a description.
+///
+/// The explanation is a single string, so that it is translated in full. Paragraphs are
+/// separated by blank lines. The popover is closed with Escape, or by pressing outside of it.
+#[must_use]
+pub struct InfoPopover<'a, Message> {
+    text: &'a str,
+    is_open: bool,
+    on_toggle: Box<dyn Fn(bool) -> Message + 'a>,
+    learn_more: Option<(&'a str, Message)>,
+}
+
+/// Creates an info popover, which is open while `is_open` is true.
+pub fn info_popover<'a, Message>(
+    text: &'a str,
+    is_open: bool,
+    on_toggle: impl Fn(bool) -> Message + 'a,
+) -> InfoPopover<'a, Message> {
+    InfoPopover {
+        text,
+        is_open,
+        on_toggle: Box::new(on_toggle),
+        learn_more: None,
+    }
+}
+
+impl<'a, Message> InfoPopover<'a, Message> {
+    /// Ends the explanation with a link to a web page which explains it further.
+    pub fn learn_more(mut self, url: &'a str, on_open: impl Fn(String) -> Message) -> Self {
+        self.learn_more = Some((url, on_open(url.to_owned())));
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<InfoPopover<'a, Message>> for Element<'a, Message> {
+    fn from(info: InfoPopover<'a, Message>) -> Self {
+        let toggle = (info.on_toggle)(!info.is_open);
+
+        let button = icon("help-info-symbolic", 16)
+            .style(theme::Svg::Symbolic)
+            .apply(button)
+            .padding(4)
+            .style(theme::Button::Transparent)
+            .on_press(toggle.clone());
+
+        let mut popover = popover(button)
+            .width(320)
+            .keep_in_window(true)
+            .on_dismiss((info.on_toggle)(false));
+
+        if info.is_open {
+            let mut content = info
+                .text
+                .split("\n\n")
+                .map(str::trim)
+                .filter(|paragraph| !paragraph.is_empty())
+                .fold(Column::new().spacing(8), |content, paragraph| {
+                    content.push(text(paragraph).width(Length::Fill))
+                });
+
+            if let Some((url, on_open)) = info.learn_more {
+                let link = button(text(fl!("info-popover", "learn-more")))
+                    .padding(0)
+                    .style(theme::Button::Link)
+                    .on_press(on_open);
+
+                content = content.push(tooltip(link, url, tooltip::Position::Bottom));
+            }
+
+            popover = popover.popup(content.apply(container).padding(16));
+        }
+
+        activate(popover, toggle)
+    }
+}
@@ -0,0 +1,376 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{alignment, Length},
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode, Modifiers},
+        layout, mouse, renderer, text,
+        widget::{operation, tree, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    Element, Renderer,
+};
+use std::fmt;
+
+/// A key combination, which is bound to modifiers alone if it has no key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Keybind {
+    pub modifiers: Modifiers,
+    pub key: Option<KeyCode>,
+}
+
+impl fmt::Display for Keybind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_modifiers(f, self.modifiers)?;
+
+        let Some(key) = self.key else {
+            return Ok(());
+        };
+
+        if !self.modifiers.is_empty() {
+            f.write_str("+")?;
+        }
+
+        write_key(f, key)
+    }
+}
+
+fn write_modifiers(f: &mut fmt::Formatter<'_>, modifiers: Modifiers) -> fmt::Result {
+    let names = [
+        (modifiers.logo(), "Super"),
+        (modifiers.control(), "Ctrl"),
+        (modifiers.alt(), "Alt"),
+        (modifiers.shift(), "Shift"),
+    ];
+
+    let mut first = true;
+
+    for (_, name) in names.iter().filter(|(held, _)| *held) {
+        if !first {
+            f.write_str("+")?;
+        }
+
+        f.write_str(name)?;
+        first = false;
+    }
+
+    Ok(())
+}
+
+fn write_key(f: &mut fmt::Formatter<'_>, key: KeyCode) -> fmt::Result {
+    let name = format!("{key:?}");
+
+    // Digits are named `Key1` through `Key0` by the key codes.
+    let name = name.strip_prefix("Key").unwrap_or(&name);
+
+    f.write_str(name)
+}
+
+/// Whether a key code is one of the modifier keys.
+fn is_modifier(key: KeyCode) -> bool {
+    matches!(
+        key,
+        KeyCode::LShift
+            | KeyCode::RShift
+            | KeyCode::LControl
+            | KeyCode::RControl
+            | KeyCode::LAlt
+            | KeyCode::RAlt
+            | KeyCode::LWin
+            | KeyCode::RWin
+    )
+}
+
+/// A control which, once clicked or focused, captures the next key combination.
+///
+/// While capturing, every keyboard event is captured by the widget, so that shortcuts of
+/// the app are not triggered. Escape cancels the capture, and Backspace clears the binding.
+pub struct KeybindCapture<'a, Message> {
+    binding: Option<Keybind>,
+    on_change: Box<dyn Fn(Option<Keybind>) -> Message + 'a>,
+    placeholder: String,
+    prompt: String,
+    width: Length,
+}
+
+/// Creates a capture for a binding, emitting `None` when the binding is cleared.
+pub fn keybind_capture<'a, Message>(
+    binding: Option<Keybind>,
+    on_change: impl Fn(Option<Keybind>) -> Message + 'a,
+) -> KeybindCapture<'a, Message> {
+    KeybindCapture {
+        binding,
+        on_change: Box::new(on_change),
+        placeholder: fl!("keybind-capture", "disabled"),
+        prompt: fl!("keybind-capture", "prompt"),
+        width: Length::Units(200),
+    }
+}
+
+impl<'a, Message> KeybindCapture<'a, Message> {
+    /// Shown while there is no binding.
+    #[must_use]
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Shown while capturing, before any key is held.
+    #[must_use]
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    #[must_use]
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    fn label(&self, state: &State) -> String {
+        if !state.capturing {
+            return match self.binding {
+                Some(binding) => binding.to_string(),
+                None => self.placeholder.clone(),
+            };
+        }
+
+        if state.modifiers.is_empty() && state.key.is_none() {
+            return self.prompt.clone();
+        }
+
+        let held = Keybind {
+            modifiers: state.modifiers,
+            key: state.key,
+        };
+
+        // Modifiers held alone are followed by an ellipsis, as a key may be added to them.
+        match held.key {
+            Some(_) => held.to_string(),
+            None => format!("{held}+…"),
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<KeybindCapture<'a, Message>> for Element<'a, Message> {
+    fn from(capture: KeybindCapture<'a, Message>) -> Self {
+        Element::new(capture)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    capturing: bool,
+    modifiers: Modifiers,
+    key: Option<KeyCode>,
+    /// Set once a key or modifier is held, so that releasing modifiers alone binds them.
+    held: bool,
+}
+
+impl State {
+    fn start(&mut self) {
+        *self = State {
+            capturing: true,
+            ..State::default()
+        };
+    }
+
+    fn stop(&mut self) {
+        *self = State::default();
+    }
+}
+
+impl operation::Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.capturing
+    }
+
+    fn focus(&mut self) {
+        self.start();
+    }
+
+    fn unfocus(&mut self) {
+        self.stop();
+    }
+}
+
+impl<'a, Message> Widget<Message, Renderer> for KeybindCapture<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn width(&self) -> Length {
+        self.width
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(32)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(self.width).height(self.height());
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        _layout: Layout<'_>,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        operation.focusable(tree.state.downcast_mut::<State>(), None);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            if layout.bounds().contains(cursor_position) {
+                state.start();
+                return event::Status::Captured;
+            }
+
+            state.stop();
+            return event::Status::Ignored;
+        }
+
+        if !state.capturing {
+            return event::Status::Ignored;
+        }
+
+        let Event::Keyboard(event) = event else {
+            return event::Status::Ignored;
+        };
+
+        match event {
+            keyboard::Event::ModifiersChanged(modifiers) => {
+                // Modifiers released without a key are bound on their own.
+                if modifiers.is_empty() && state.held && state.key.is_none() {
+                    shell.publish((self.on_change)(Some(Keybind {
+                        modifiers: state.modifiers,
+                        key: None,
+                    })));
+
+                    state.stop();
+                } else {
+                    state.held |= !modifiers.is_empty();
+                    state.modifiers = modifiers;
+                }
+            }
+
+            keyboard::Event::KeyPressed { key_code, .. } if is_modifier(key_code) => (),
+
+            keyboard::Event::KeyPressed {
+                key_code: KeyCode::Escape,
+                modifiers,
+            } if modifiers.is_empty() => state.stop(),
+
+            keyboard::Event::KeyPressed {
+                key_code: KeyCode::Backspace,
+                modifiers,
+            } if modifiers.is_empty() => {
+                shell.publish((self.on_change)(None));
+                state.stop();
+            }
+
+            keyboard::Event::KeyPressed { key_code, .. } => {
+                state.key = Some(key_code);
+                state.held = true;
+            }
+
+            keyboard::Event::KeyReleased { key_code, .. } if state.key == Some(key_code) => {
+                shell.publish((self.on_change)(Some(Keybind {
+                    modifiers: state.modifiers,
+                    key: Some(key_code),
+                })));
+
+                state.stop();
+            }
+
+            _ => (),
+        }
+
+        // Every keyboard event is captured while capturing, including the app's shortcuts.
+        event::Status::Captured
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if layout.bounds().contains(cursor_position) {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::{text::Renderer as _, Renderer as _};
+
+        let state = tree.state.downcast_ref::<State>();
+        let bounds = layout.bounds();
+        let palette = theme.palette();
+
+        let (border_width, border_color) = if state.capturing {
+            (2.0, palette.primary)
+        } else {
+            let mut color = palette.text;
+            color.a = 0.3;
+            (1.0, color)
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border_radius: 4.0,
+                border_width,
+                border_color,
+            },
+            palette.background,
+        );
+
+        renderer.fill_text(text::Text {
+            content: &self.label(state),
+            bounds: Rectangle {
+                x: bounds.center_x(),
+                y: bounds.center_y(),
+                ..bounds
+            },
+            size: 14.0,
+            color: palette.text,
+            font: Default::default(),
+            horizontal_alignment: alignment::Horizontal::Center,
+            vertical_alignment: alignment::Vertical::Center,
+        });
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{
+        alignment,
+        widget::{column, slider, Row},
+        Alignment, Color, Length,
+    },
+    iced_native::{layout, renderer, widget::Tree, Layout, Point, Rectangle, Size, Widget},
+    widget::{settings, text},
+    Element, Renderer,
+};
+use std::ops::RangeInclusive;
+
+/// Width which is kept for the readout, so that the slider does not move as its value changes.
+const READOUT_WIDTH: u16 = 96;
+
+/// Half the width of the handle of a slider, by which the center of the handle is kept from
+/// the ends of its track.
+const HANDLE_INSET: f32 = 8.0;
+
+const TICK_HEIGHT: u16 = 4;
+
+/// A value which a labeled slider is dragged between, by which its readout is formatted.
+pub trait Value: Copy + PartialOrd + 'static {
+    /// Decimals shown in the readout, unless they are set with [`LabeledSlider::decimals`].
+    const DECIMALS: usize;
+
+    fn to_f64(self) -> f64;
+
+    /// Integers are rounded to the nearest whole number within the bounds of their type.
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! integer {
+    ($($ty:ty),*) => {
+        $(impl Value for $ty {
+            const DECIMALS: usize = 0;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                value.round() as $ty
+            }
+        })*
+    };
+}
+
+integer!(u8, u16, u32, i32);
+
+impl Value for f32 {
+    const DECIMALS: usize = 2;
+
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Value for f64 {
+    const DECIMALS: usize = 2;
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+/// A settings item of a label and a slider, which is followed by a right-aligned readout of
+/// its value.
+///
+/// Integers are read out as whole numbers, and floats with two decimals by default.
+#[must_use]
+pub struct LabeledSlider<'a, T, Message> {
+    label: &'a str,
+    description: Option<&'a str>,
+    range: RangeInclusive<T>,
+    value: T,
+    step: Option<T>,
+    on_change: Box<dyn Fn(T) -> Message + 'a>,
+    on_release: Option<Message>,
+    decimals: usize,
+    unit: String,
+    readout: Option<Box<dyn Fn(T) -> String + 'a>>,
+    ticks: Vec<T>,
+    width: Length,
+}
+
+/// Creates a labeled slider which emits `on_change` continuously while it is dragged.
+pub fn labeled_slider<'a, T: Value, Message>(
+    label: &'a str,
+    range: RangeInclusive<T>,
+    value: T,
+    on_change: impl Fn(T) -> Message + 'a,
+) -> LabeledSlider<'a, T, Message> {
+    LabeledSlider {
+        label,
+        description: None,
+        range,
+        value,
+        step: None,
+        on_change: Box::new(on_change),
+        on_release: None,
+        decimals: T::DECIMALS,
+        unit: String::new(),
+        readout: None,
+        ticks: Vec::new(),
+        width: Length::Units(250),
+    }
+}
+
+impl<'a, T: Value, Message: Clone + 'static> LabeledSlider<'a, T, Message> {
+    pub fn description(mut self, description: &'a str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn step(mut self, step: T) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Emitted when the slider is released, to commit the value.
+    pub fn on_release(mut self, message: Message) -> Self {
+        self.on_release = Some(message);
+        self
+    }
+
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Appended to the readout as it is, such as `%`.
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// Formats the readout in place of its decimals and unit, such as with a translation.
+    pub fn readout(mut self, readout: impl Fn(T) -> String + 'a) -> Self {
+        self.readout = Some(Box::new(readout));
+        self
+    }
+
+    /// Marks values below the track, such as those of its steps.
+    pub fn ticks(mut self, ticks: impl IntoIterator<Item = T>) -> Self {
+        self.ticks = ticks.into_iter().collect();
+        self
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+}
+
+impl<'a, T: Value, Message: Clone + 'static> From<LabeledSlider<'a, T, Message>>
+    for Element<'a, Message>
+{
+    fn from(labeled: LabeledSlider<'a, T, Message>) -> Self {
+        let LabeledSlider {
+            label,
+            description,
+            range,
+            value,
+            step,
+            on_change,
+            on_release,
+            decimals,
+            unit,
+            readout,
+            ticks,
+            width,
+        } = labeled;
+
+        let readout = match readout {
+            Some(readout) => readout(value),
+            None => format!("{:.*}{unit}", decimals, value.to_f64()),
+        };
+
+        let (start, end) = (range.start().to_f64(), range.end().to_f64());
+
+        // Readouts without decimals are stepped by whole numbers unless a step is given.
+        let step = step.map_or(if decimals == 0 { 1.0 } else { 0.01 }, T::to_f64);
+
+        let mut track = slider(start..=end, value.to_f64(), move |value| {
+            on_change(T::from_f64(value))
+        })
+        .step(step)
+        .width(width);
+
+        if let Some(message) = on_release {
+            track = track.on_release(message);
+        }
+
+        let track: Element<'a, Message> = if ticks.is_empty() {
+            track.into()
+        } else {
+            let positions = ticks
+                .iter()
+                .filter(|tick| (start..=end).contains(&tick.to_f64()))
+                .map(|tick| ((tick.to_f64() - start) / (end - start)) as f32)
+                .collect();
+
+            column!(track, Element::new(Ticks { positions, width }))
+                .spacing(2)
+                .into()
+        };
+
+        let control = Row::new()
+            .spacing(16)
+            .align_items(Alignment::Center)
+            .push(track)
+            .push(
+                text(readout)
+                    .width(Length::Units(READOUT_WIDTH))
+                    .horizontal_alignment(alignment::Horizontal::Right),
+            );
+
+        let item = settings::item::builder(label);
+
+        let item = match description {
+            Some(description) => item.description(description).control(control),
+            None => item.control(control),
+        };
+
+        item.into()
+    }
+}
+
+/// Marks below the track of a slider, at portions of its width.
+struct Ticks {
+    positions: Vec<f32>,
+    width: Length,
+}
+
+impl<Message> Widget<Message, Renderer> for Ticks {
+    fn width(&self) -> Length {
+        self.width
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(TICK_HEIGHT)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(self.width).height(Length::Units(TICK_HEIGHT));
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let bounds = layout.bounds();
+        let track = bounds.width - HANDLE_INSET * 2.0;
+
+        let mut color = theme.palette().text;
+        color.a = 0.5;
+
+        for position in &self.positions {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle {
+                        x: bounds.x + HANDLE_INSET + position * track - 0.5,
+                        width: 1.0,
+                        ..bounds
+                    },
+                    border_radius: 0.0,
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                },
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{widget::tooltip, Color, Length},
+    iced_native::{
+        layout, renderer,
+        widget::{tree, Tree},
+        Layout, Point, Rectangle, Size, Widget,
+    },
+    theme, Element, Renderer,
+};
+use std::{cell::Cell, time::Instant};
+
+/// Segments which the bar is divided into.
+const SEGMENTS: u16 = 24;
+
+/// Space between segments, in pixels.
+const GAP: f32 = 2.0;
+
+/// Levels above which segments are yellow, and then red.
+const YELLOW_FROM: f32 = 0.7;
+const RED_FROM: f32 = 0.9;
+
+const YELLOW: Color = Color::from_rgb(0.96, 0.76, 0.07);
+
+/// How quickly the peak indicator falls back towards the level, per second.
+const PEAK_DECAY: f32 = 0.5;
+
+/// A meter showing a level from 0 to 1 as a bar of segments, such as that of a microphone.
+///
+/// Segments are green, then yellow, then red towards the top of the range. The highest
+/// recent level is marked by a peak indicator, which falls back towards the level over time.
+#[must_use]
+pub struct LevelMeter {
+    level: f32,
+    disabled: Option<String>,
+    width: Length,
+}
+
+/// Creates a meter showing `level`, from 0 to 1.
+pub fn level_meter(level: f32) -> LevelMeter {
+    LevelMeter {
+        level: level.clamp(0.0, 1.0),
+        disabled: None,
+        width: Length::Units(250),
+    }
+}
+
+impl LevelMeter {
+    /// Dims the meter, with a tooltip which explains why it shows no level.
+    pub fn disabled(mut self, reason: impl Into<String>) -> Self {
+        self.disabled = Some(reason.into());
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+}
+
+impl<'a, Message: 'a> From<LevelMeter> for Element<'a, Message> {
+    fn from(meter: LevelMeter) -> Self {
+        let reason = meter.disabled.clone();
+        let meter = Element::new(Meter {
+            level: meter.level,
+            enabled: reason.is_none(),
+            width: meter.width,
+        });
+
+        match reason {
+            Some(reason) => tooltip(meter, reason, tooltip::Position::Bottom)
+                .style(theme::Container::Box)
+                .into(),
+            None => meter,
+        }
+    }
+}
+
+struct Meter {
+    level: f32,
+    enabled: bool,
+    width: Length,
+}
+
+#[derive(Default)]
+struct Peak {
+    /// The level which the indicator is at, and when it was last drawn.
+    level: Cell<Option<(f32, Instant)>>,
+}
+
+impl<Message> Widget<Message, Renderer> for Meter {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Peak>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(Peak::default())
+    }
+
+    fn width(&self) -> Length {
+        self.width
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(8)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(self.width).height(Length::Units(8));
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let bounds = layout.bounds();
+        let palette = theme.palette();
+        let peak = tree.state.downcast_ref::<Peak>();
+
+        // The peak only falls as the meter is redrawn, which is whenever the level changes.
+        let now = Instant::now();
+        let peak_level = match peak.level.get() {
+            Some((level, drawn)) if self.enabled => {
+                let decayed = level - PEAK_DECAY * now.duration_since(drawn).as_secs_f32();
+                decayed.max(self.level)
+            }
+            _ => self.level,
+        };
+
+        peak.level.set(self.enabled.then_some((peak_level, now)));
+
+        let segments = f32::from(SEGMENTS);
+        let width = (bounds.width - GAP * (segments - 1.0)) / segments;
+        let lit = (self.level * segments).ceil() as u16;
+        let peak_segment = ((peak_level * segments).ceil() as u16).checked_sub(1);
+
+        for segment in 0..SEGMENTS {
+            let position = f32::from(segment) / segments;
+
+            let mut color = if position >= RED_FROM {
+                palette.danger
+            } else if position >= YELLOW_FROM {
+                YELLOW
+            } else {
+                palette.success
+            };
+
+            if !self.enabled {
+                color = palette.text;
+                color.a = 0.05;
+            } else if segment >= lit && Some(segment) != peak_segment {
+                color.a = 0.15;
+            }
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle {
+                        x: bounds.x + f32::from(segment) * (width + GAP),
+                        width,
+                        ..bounds
+                    },
+                    border_radius: 2.0,
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                },
+                color,
+            );
+        }
+    }
+}
@@ -0,0 +1,268 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, container, row, tooltip},
+        Alignment, Length,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{tree, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    theme,
+    widget::{icon, list, text},
+    Element, Renderer,
+};
+use std::collections::HashMap;
+
+use super::Focus;
+
+/// A row which opens a web page in the browser, such as a privacy policy.
+///
+/// The row is activated by pressing it with the left or middle button, or with Enter or
+/// Space once it is focused. Its URL is shown in a tooltip, so that it is known where it
+/// leads before it is opened.
+#[must_use]
+pub fn link_row<'a, Message: Clone + 'static>(
+    title: &'a str,
+    url: &'a str,
+    on_open: impl Fn(String) -> Message,
+) -> Element<'a, Message> {
+    let on_open = on_open(url.to_owned());
+
+    let content = row!(
+        text(title).width(Length::Fill),
+        icon("external-link-symbolic", 16).style(theme::Svg::Symbolic),
+    )
+    .spacing(16)
+    .align_items(Alignment::Center)
+    .apply(container)
+    .padding([16, 24])
+    .style(theme::Container::Custom(list::column::style))
+    .apply(button)
+    .padding(0)
+    .width(Length::Fill)
+    .style(theme::Button::Transparent)
+    .on_press(on_open.clone());
+
+    activate(
+        tooltip(content, url, tooltip::Position::Bottom).style(theme::Container::Box),
+        on_open,
+    )
+}
+
+/// Wraps content so that it is also activated with the middle button, and with Enter or
+/// Space once it is focused.
+pub(super) fn activate<'a, Message: Clone + 'static>(
+    content: impl Into<Element<'a, Message>>,
+    on_activate: Message,
+) -> Element<'a, Message> {
+    Element::new(Activate {
+        content: content.into(),
+        on_activate,
+    })
+}
+
+/// Opens a URL in the default browser, through the OpenURI portal so that it works within a
+/// sandbox, or with `xdg-open` where the portal is not available.
+///
+/// # Errors
+///
+/// Fails if neither the portal nor `xdg-open` could open it.
+pub async fn open_url(url: String) -> Result<(), String> {
+    if open_with_portal(&url).await.is_ok() {
+        return Ok(());
+    }
+
+    let status = tokio::process::Command::new("xdg-open")
+        .arg(&url)
+        .status()
+        .await
+        .map_err(|why| why.to_string())?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(fl!("link-row", "failed", url = url))
+    }
+}
+
+async fn open_with_portal(url: &str) -> zbus::Result<()> {
+    use zbus::zvariant::{OwnedObjectPath, Value};
+
+    let connection = zbus::Connection::session().await?;
+
+    let open_uri = zbus::Proxy::new(
+        &connection,
+        "org.freedesktop.portal.Desktop",
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.OpenURI",
+    )
+    .await?;
+
+    let options: HashMap<&str, Value> = HashMap::new();
+    let _handle: OwnedObjectPath = open_uri.call("OpenURI", &("", url, options)).await?;
+
+    Ok(())
+}
+
+/// Publishes a message when the middle button is pressed over the content, or when Enter
+/// or Space is pressed while it is focused.
+struct Activate<'a, Message> {
+    content: Element<'a, Message>,
+    on_activate: Message,
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Activate<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Focus>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(Focus::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        operation.focusable(tree.state.downcast_mut::<Focus>(), None);
+
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let focus = tree.state.downcast_mut::<Focus>();
+        let is_over = layout.bounds().contains(cursor_position);
+
+        match &event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Middle)) if is_over => {
+                focus.focused = true;
+                shell.publish(self.on_activate.clone());
+                return event::Status::Captured;
+            }
+
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => focus.focused = is_over,
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space,
+                ..
+            }) if focus.focused => {
+                shell.publish(self.on_activate.clone());
+                return event::Status::Captured;
+            }
+
+            _ => (),
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if layout.bounds().contains(cursor_position) {
+            return mouse::Interaction::Pointer;
+        }
+
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+
+        if tree.state.downcast_ref::<Focus>().focused {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: layout.bounds(),
+                    border_radius: 8.0,
+                    border_width: 2.0,
+                    border_color: theme.palette().primary,
+                },
+                cosmic::iced::Color::TRANSPARENT,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{
+        widget::{column, horizontal_space, slider, Row},
+        Length,
+    },
+    iced_native::{
+        event, keyboard, layout, mouse, overlay, renderer,
+        widget::{Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    widget::text,
+    Element, Renderer,
+};
+use std::ops::RangeInclusive;
+
+/// Resolution of the fill portions used to place labels along the slider.
+const PORTIONS: f64 = 1000.0;
+
+/// A slider with labeled detents, and a readout of its value above the thumb.
+///
+/// While hovered, the arrow keys step the value between detents.
+#[must_use]
+pub struct MarkedSlider<'a, Message> {
+    range: RangeInclusive<f64>,
+    value: f64,
+    marks: Vec<(f64, String)>,
+    snap_radius: f64,
+    step: f64,
+    on_change: Box<dyn Fn(f64) -> Message + 'a>,
+    on_release: Option<Message>,
+    readout: Box<dyn Fn(f64) -> String + 'a>,
+    width: Length,
+}
+
+/// Creates a slider which emits `on_change` continuously while it is dragged.
+pub fn marked_slider<'a, Message>(
+    range: RangeInclusive<f64>,
+    value: f64,
+    on_change: impl Fn(f64) -> Message + 'a,
+) -> MarkedSlider<'a, Message> {
+    MarkedSlider {
+        range,
+        value,
+        marks: Vec::new(),
+        snap_radius: 0.0,
+        step: 1.0,
+        on_change: Box::new(on_change),
+        on_release: None,
+        readout: Box::new(|value| format!("{}", (value * 100.0).round() / 100.0)),
+        width: Length::Units(250),
+    }
+}
+
+impl<'a, Message: Clone + 'static> MarkedSlider<'a, Message> {
+    /// Adds a labeled detent at the given value.
+    pub fn mark(mut self, value: f64, label: impl Into<String>) -> Self {
+        self.marks.push((value, label.into()));
+        self.marks.sort_by(|a, b| a.0.total_cmp(&b.0));
+        self
+    }
+
+    /// Snaps values within `radius` of a detent onto the detent.
+    pub fn snap(mut self, radius: f64) -> Self {
+        self.snap_radius = radius;
+        self
+    }
+
+    /// The granularity of the slider, and of arrow key steps when there are no detents.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Emitted when the slider is released, or stepped with the keyboard, to commit the value.
+    pub fn on_release(mut self, message: Message) -> Self {
+        self.on_release = Some(message);
+        self
+    }
+
+    /// Formats the value shown above the thumb.
+    pub fn readout(mut self, readout: impl Fn(f64) -> String + 'a) -> Self {
+        self.readout = Box::new(readout);
+        self
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The detent after the current value in the given direction, or the end of the range.
+    fn next(&self, forward: bool) -> f64 {
+        let (start, end) = (*self.range.start(), *self.range.end());
+        let value = self.value;
+
+        let next = if self.marks.is_empty() {
+            if forward {
+                value + self.step
+            } else {
+                value - self.step
+            }
+        } else if forward {
+            self.marks
+                .iter()
+                .map(|(mark, _)| *mark)
+                .find(|mark| *mark > value)
+                .unwrap_or(end)
+        } else {
+            self.marks
+                .iter()
+                .rev()
+                .map(|(mark, _)| *mark)
+                .find(|mark| *mark < value)
+                .unwrap_or(start)
+        };
+
+        next.clamp(start, end)
+    }
+
+    /// The fill portion of the track preceding a value.
+    fn portion(&self, value: f64) -> u16 {
+        let (start, end) = (*self.range.start(), *self.range.end());
+
+        if end <= start {
+            return 0;
+        }
+
+        (((value - start) / (end - start)).clamp(0.0, 1.0) * PORTIONS) as u16
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<MarkedSlider<'a, Message>> for Element<'a, Message> {
+    fn from(marked: MarkedSlider<'a, Message>) -> Self {
+        let decrement = (marked.on_change)(marked.next(false));
+        let increment = (marked.on_change)(marked.next(true));
+
+        let position = marked.portion(marked.value);
+
+        let readout = Row::new()
+            .width(marked.width)
+            .push(horizontal_space(Length::FillPortion(position.max(1))))
+            .push(text((marked.readout)(marked.value)).size(12))
+            .push(horizontal_space(Length::FillPortion(
+                (PORTIONS as u16 - position).max(1),
+            )));
+
+        let mut labels = Row::new().width(marked.width);
+        let mut previous = 0;
+
+        for (value, label) in &marked.marks {
+            let portion = marked.portion(*value);
+            labels = labels
+                .push(horizontal_space(Length::FillPortion(
+                    (portion - previous).max(1),
+                )))
+                .push(text(label).size(10));
+            previous = portion;
+        }
+
+        labels = labels.push(horizontal_space(Length::FillPortion(
+            (PORTIONS as u16 - previous).max(1),
+        )));
+
+        let MarkedSlider {
+            range,
+            value,
+            marks,
+            snap_radius,
+            step,
+            on_change,
+            on_release,
+            width,
+            ..
+        } = marked;
+
+        let detents: Vec<f64> = marks.iter().map(|(mark, _)| *mark).collect();
+
+        let mut slider = slider(range, value, move |value| {
+            on_change(snap(&detents, snap_radius, value))
+        })
+        .step(step)
+        .width(width);
+
+        if let Some(message) = on_release.clone() {
+            slider = slider.on_release(message);
+        }
+
+        Element::new(Stepper {
+            content: column!(readout, slider, labels).spacing(4).into(),
+            decrement,
+            increment,
+            on_release,
+        })
+    }
+}
+
+/// Moves a value onto the nearest detent within `radius` of it.
+fn snap(detents: &[f64], radius: f64, value: f64) -> f64 {
+    detents
+        .iter()
+        .copied()
+        .filter(|detent| (detent - value).abs() <= radius)
+        .min_by(|a, b| (a - value).abs().total_cmp(&(b - value).abs()))
+        .unwrap_or(value)
+}
+
+/// Publishes step messages for arrow keys pressed while the content is hovered.
+struct Stepper<'a, Message> {
+    content: Element<'a, Message>,
+    decrement: Message,
+    increment: Message,
+    on_release: Option<Message>,
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Stepper<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if let Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) = &event {
+            if layout.bounds().contains(cursor_position) {
+                let step = match key_code {
+                    keyboard::KeyCode::Left | keyboard::KeyCode::Down => Some(&self.decrement),
+                    keyboard::KeyCode::Right | keyboard::KeyCode::Up => Some(&self.increment),
+                    _ => None,
+                };
+
+                if let Some(message) = step {
+                    shell.publish(message.clone());
+
+                    if let Some(message) = &self.on_release {
+                        shell.publish(message.clone());
+                    }
+
+                    return event::Status::Captured;
+                }
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
@@ -7,11 +7,125 @@ use cosmic::iced::{
     widget::{button, column, container, horizontal_space, row, vertical_space, Button},
     Length,
 };
+use cosmic::iced_native::widget::operation;
 use cosmic::widget::{divider, icon, list, settings, text};
 use cosmic::{theme, Element};
 
 use crate::page::{self, Meta};
 
+pub mod battery_graph;
+pub use battery_graph::battery_graph;
+
+pub mod color_picker;
+pub use color_picker::ColorPicker;
+
+pub mod confirm_dialog;
+pub use confirm_dialog::{confirm_dialog, Confirm};
+
+pub mod copy_row;
+pub use copy_row::copy_row;
+
+pub mod date_picker;
+pub use date_picker::{date_picker, DatePicker};
+
+pub mod debounce;
+pub use debounce::Debounce;
+
+pub mod dialog;
+pub use dialog::dialog;
+
+pub mod display_arrangement;
+pub use display_arrangement::display_arrangement;
+
+pub mod editable_list;
+pub use editable_list::EditableList;
+
+pub mod expander;
+pub use expander::expander;
+
+pub mod image_grid;
+pub use image_grid::ImageGrid;
+
+pub mod info_popover;
+pub use info_popover::info_popover;
+
+pub mod keybind_capture;
+pub use keybind_capture::{keybind_capture, Keybind};
+
+pub mod labeled_slider;
+pub use labeled_slider::labeled_slider;
+
+pub mod level_meter;
+pub use level_meter::level_meter;
+
+pub mod link_row;
+pub use link_row::link_row;
+
+pub mod marked_slider;
+pub use marked_slider::marked_slider;
+
+pub mod operation_progress;
+pub use operation_progress::{operation_progress, OperationHandle};
+
+pub mod popover;
+pub use popover::popover;
+
+pub mod qr_code;
+pub use qr_code::qr_code;
+
+pub mod radio_list;
+pub use radio_list::radio_list;
+
+pub mod reorder_list;
+pub use reorder_list::reorder_list;
+
+pub mod search_input;
+pub use search_input::SearchInput;
+
+pub mod searchable_dropdown;
+pub use searchable_dropdown::SearchableDropdown;
+
+pub mod secure_input;
+pub use secure_input::{secure_input, SecureInput};
+
+pub mod segmented;
+pub use segmented::segmented;
+
+pub mod settings_row;
+pub use settings_row::settings_row;
+
+pub mod spin_button;
+pub use spin_button::{spin_button, SpinButton};
+
+pub mod spinner;
+pub use spinner::spinner;
+
+pub mod time_picker;
+pub use time_picker::{time_picker, TimePicker};
+
+pub mod toast;
+pub use toast::Toast;
+
+/// The focus of a widget which reacts to the keyboard while it is focused.
+#[derive(Default)]
+struct Focus {
+    focused: bool,
+}
+
+impl operation::Focusable for Focus {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
 #[must_use]
 pub fn search_header(pages: &page::Model, page: page::Entity) -> cosmic::Element<crate::Message> {
     let page_meta = &pages.pages[page];
@@ -88,7 +202,7 @@ pub fn parent_page_button<'a, Message: Clone + 'static>(
 pub fn sub_page_button(entity: page::Entity, page: &Meta) -> Element<page::Entity> {
     settings::item::builder(page.title.as_str())
         .description(page.description.as_str())
-        .icon(icon(page.icon_name, 20).style(theme::Svg::Symbolic))
+        .icon(icon(page.icon_handle(), 20).style(theme::Svg::Symbolic))
         .control(row!(
             horizontal_space(Length::Fill),
             icon("go-next-symbolic", 20).style(theme::Svg::Symbolic)
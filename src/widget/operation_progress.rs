@@ -0,0 +1,262 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{
+        self,
+        widget::{button, column, horizontal_space, progress_bar, row},
+        Alignment, Length, Subscription,
+    },
+    theme,
+    widget::{icon, text},
+    Element,
+};
+use std::time::Duration;
+
+use super::expander;
+
+/// Interval between the frames of the bar of an operation whose progress is unknown.
+const PULSE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Frames which the bar takes to fill and empty again.
+const PULSE_FRAMES: u32 = 40;
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Cancel,
+    Pulse,
+    ToggleDetails(bool),
+}
+
+/// What the owner of the operation is told of, once the handle has handled a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// The operation should be cancelled, which the owner reports once it has stopped.
+    Cancel,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum State {
+    Running {
+        step: String,
+        /// How much of the operation is done, from 0 to 1, if that is known.
+        fraction: Option<f32>,
+    },
+    Failed {
+        why: String,
+        details: String,
+    },
+    Finished,
+}
+
+/// The progress of an operation which runs in the background, such as installing firmware.
+///
+/// Progress is reported with sequence numbers, so that reports from tasks which raced each
+/// other are ignored if a later report was already received.
+#[derive(Debug)]
+pub struct OperationHandle {
+    label: String,
+    details_label: String,
+    state: State,
+    /// The sequence number of the latest report.
+    sequence: Option<u64>,
+    cancellable: bool,
+    cancelling: bool,
+    details_expanded: bool,
+    pulse: u32,
+}
+
+impl OperationHandle {
+    /// Creates the handle of an operation, which is running and has not reported progress.
+    #[must_use]
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            details_label: fl!("operation-progress", "details"),
+            state: State::Running {
+                step: String::new(),
+                fraction: None,
+            },
+            sequence: None,
+            cancellable: false,
+            cancelling: false,
+            details_expanded: false,
+            pulse: 0,
+        }
+    }
+
+    /// Offers to cancel the operation while it is running.
+    #[must_use]
+    pub fn cancellable(mut self, cancellable: bool) -> Self {
+        self.cancellable = cancellable;
+        self
+    }
+
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, State::Running { .. })
+    }
+
+    /// Whether cancelling the operation was asked for, while it has not stopped yet.
+    #[must_use]
+    pub fn is_cancelling(&self) -> bool {
+        self.cancelling && self.is_running()
+    }
+
+    /// Accepts a report, unless a later one was already received.
+    fn accept(&mut self, sequence: u64) -> bool {
+        if self.sequence.map_or(false, |latest| sequence <= latest) {
+            return false;
+        }
+
+        self.sequence = Some(sequence);
+        true
+    }
+
+    /// Reports the current step of the operation, and how much of it is done if that is known.
+    pub fn progress(&mut self, sequence: u64, step: impl Into<String>, fraction: Option<f32>) {
+        if self.accept(sequence) && self.is_running() {
+            self.state = State::Running {
+                step: step.into(),
+                fraction: fraction.map(|fraction| fraction.clamp(0.0, 1.0)),
+            };
+        }
+    }
+
+    /// Reports that the operation failed, with details which are shown on request.
+    pub fn fail(&mut self, sequence: u64, why: impl Into<String>, details: impl Into<String>) {
+        if self.accept(sequence) {
+            self.state = State::Failed {
+                why: why.into(),
+                details: details.into(),
+            };
+        }
+    }
+
+    /// Reports that the operation finished, or stopped after it was cancelled.
+    pub fn finish(&mut self, sequence: u64) {
+        if self.accept(sequence) {
+            self.state = State::Finished;
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Output> {
+        match message {
+            // Only asked for once, so that pressing it again does not cancel it twice.
+            Message::Cancel if self.cancellable && !self.cancelling && self.is_running() => {
+                self.cancelling = true;
+                return Some(Output::Cancel);
+            }
+
+            Message::Cancel => (),
+
+            Message::Pulse => self.pulse = (self.pulse + 1) % PULSE_FRAMES,
+
+            Message::ToggleDetails(expanded) => self.details_expanded = expanded,
+        }
+
+        None
+    }
+
+    /// Animates the bar while the operation runs without knowing how much of it is done.
+    #[must_use]
+    pub fn subscription(&self) -> Subscription<Message> {
+        match self.state {
+            State::Running { fraction: None, .. } => {
+                iced::time::every(PULSE_INTERVAL).map(|_| Message::Pulse)
+            }
+            _ => Subscription::none(),
+        }
+    }
+
+    /// Fills and empties the bar of an operation whose progress is unknown.
+    fn pulse(&self) -> f32 {
+        let half = PULSE_FRAMES / 2;
+        let frame = if self.pulse < half {
+            self.pulse
+        } else {
+            PULSE_FRAMES - self.pulse
+        };
+
+        frame as f32 / half as f32
+    }
+}
+
+/// Displays the progress of an operation, or why it failed.
+#[must_use]
+pub fn operation_progress(handle: &OperationHandle) -> Element<Message> {
+    match &handle.state {
+        State::Running { step, fraction } => {
+            let mut header = row!(text(handle.label.as_str()), horizontal_space(Length::Fill))
+                .spacing(8)
+                .align_items(Alignment::Center);
+
+            if let Some(fraction) = fraction {
+                header = header.push(text(format!("{:.0}%", fraction * 100.0)));
+            }
+
+            if handle.cancellable {
+                let mut cancel = button(text(fl!("operation-progress", "cancel")))
+                    .style(theme::Button::Secondary);
+
+                // Disabled once pressed, until the operation has stopped.
+                if !handle.cancelling {
+                    cancel = cancel.on_press(Message::Cancel);
+                }
+
+                header = header.push(cancel);
+            }
+
+            let mut content = column!(
+                header,
+                progress_bar(0.0..=1.0, fraction.unwrap_or_else(|| handle.pulse()))
+                    .height(Length::Units(8))
+                    .width(Length::Fill),
+            )
+            .spacing(8);
+
+            let step = if handle.cancelling {
+                fl!("operation-progress", "cancelling")
+            } else {
+                step.clone()
+            };
+
+            if !step.is_empty() {
+                content = content.push(text(step).size(12));
+            }
+
+            content.into()
+        }
+
+        State::Failed { why, details } => {
+            let summary = row!(
+                icon("dialog-error-symbolic", 16).style(theme::Svg::Symbolic),
+                text(why.as_str()),
+            )
+            .spacing(8)
+            .align_items(Alignment::Center);
+
+            if details.is_empty() {
+                return summary.into();
+            }
+
+            column!(
+                summary,
+                expander(handle.details_label.as_str(), "")
+                    .expanded(handle.details_expanded)
+                    .on_toggle(Message::ToggleDetails)
+                    .content(text(details.as_str()).size(12)),
+            )
+            .spacing(8)
+            .into()
+        }
+
+        State::Finished => row!(
+            icon("object-select-symbolic", 16).style(theme::Svg::Symbolic),
+            text(handle.label.as_str()),
+        )
+        .spacing(8)
+        .align_items(Alignment::Center)
+        .into(),
+    }
+}
@@ -0,0 +1,341 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::Length,
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    Element, Renderer,
+};
+
+/// Content with a popup floating beneath it, such as a dropdown menu.
+#[must_use]
+pub struct Popover<'a, Message> {
+    content: Element<'a, Message>,
+    popup: Option<Element<'a, Message>>,
+    on_dismiss: Option<Message>,
+    on_key: Option<Box<dyn Fn(KeyCode) -> Option<Message> + 'a>>,
+    width: Option<u16>,
+    keep_in_window: bool,
+}
+
+/// Creates a popover, which shows its popup while one is given.
+pub fn popover<'a, Message>(content: impl Into<Element<'a, Message>>) -> Popover<'a, Message> {
+    Popover {
+        content: content.into(),
+        popup: None,
+        on_dismiss: None,
+        on_key: None,
+        width: None,
+        keep_in_window: false,
+    }
+}
+
+impl<'a, Message: Clone + 'a> Popover<'a, Message> {
+    /// Shows a popup beneath the content.
+    pub fn popup(mut self, popup: impl Into<Element<'a, Message>>) -> Self {
+        self.popup = Some(popup.into());
+        self
+    }
+
+    /// Emitted when Escape is pressed, or the pointer is pressed outside of the popup.
+    pub fn on_dismiss(mut self, message: Message) -> Self {
+        self.on_dismiss = Some(message);
+        self
+    }
+
+    /// Maps keys pressed while the popup is shown, before the popup receives them.
+    pub fn on_key(mut self, on_key: impl Fn(KeyCode) -> Option<Message> + 'a) -> Self {
+        self.on_key = Some(Box::new(on_key));
+        self
+    }
+
+    /// The width of the popup, which otherwise matches the content.
+    pub fn width(mut self, width: u16) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Moves the popup within the window, above the content if there is no room beneath it,
+    /// rather than limiting its height to the room beneath the content.
+    pub fn keep_in_window(mut self, keep_in_window: bool) -> Self {
+        self.keep_in_window = keep_in_window;
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<Popover<'a, Message>> for Element<'a, Message> {
+    fn from(popover: Popover<'a, Message>) -> Self {
+        Element::new(popover)
+    }
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Popover<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        match &self.popup {
+            Some(popup) => vec![Tree::new(&self.content), Tree::new(popup)],
+            None => vec![Tree::new(&self.content)],
+        }
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        match &self.popup {
+            Some(popup) => tree.diff_children(&[&self.content, popup]),
+            None => tree.diff_children(std::slice::from_ref(&self.content)),
+        }
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        _renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let popup = self.popup.as_mut()?;
+        let bounds = layout.bounds();
+
+        let overlay = Popup {
+            tree: tree.children.get_mut(1)?,
+            content: popup,
+            width: self.width.map_or(bounds.width, f32::from),
+            on_dismiss: self.on_dismiss.clone(),
+            on_key: self.on_key.as_deref(),
+            anchor: self.keep_in_window.then_some(bounds),
+        };
+
+        Some(overlay::Element::new(
+            Point::new(bounds.x, bounds.y + bounds.height),
+            Box::new(overlay),
+        ))
+    }
+}
+
+struct Popup<'a, 'b, Message> {
+    tree: &'b mut Tree,
+    content: &'b mut Element<'a, Message>,
+    width: f32,
+    on_dismiss: Option<Message>,
+    on_key: Option<&'b (dyn Fn(KeyCode) -> Option<Message> + 'a)>,
+    /// The bounds of the content, if the popup is kept within the window.
+    anchor: Option<Rectangle>,
+}
+
+impl<'a, 'b, Message: Clone> overlay::Overlay<Message, Renderer> for Popup<'a, 'b, Message> {
+    fn layout(&self, renderer: &Renderer, bounds: Size, position: Point) -> layout::Node {
+        if let Some(anchor) = self.anchor {
+            let limits = layout::Limits::new(Size::ZERO, Size::new(self.width, bounds.height))
+                .width(Length::Units(self.width as u16));
+
+            let mut node = self.content.as_widget().layout(renderer, &limits);
+            let size = node.size();
+
+            let x = position.x.min(bounds.width - size.width).max(0.0);
+            let above = anchor.y - size.height;
+
+            let y = if position.y + size.height > bounds.height && above >= 0.0 {
+                above
+            } else {
+                position.y.min(bounds.height - size.height).max(0.0)
+            };
+
+            node.move_to(Point::new(x, y));
+            return node;
+        }
+
+        let limits = layout::Limits::new(
+            Size::ZERO,
+            Size::new(self.width, (bounds.height - position.y).max(0.0)),
+        )
+        .width(Length::Units(self.width as u16));
+
+        let mut node = self.content.as_widget().layout(renderer, &limits);
+        node.move_to(position);
+        node
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        match &event {
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+                if !layout.bounds().contains(cursor_position) =>
+            {
+                if let Some(message) = &self.on_dismiss {
+                    shell.publish(message.clone());
+                }
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Escape,
+                ..
+            }) => {
+                if let Some(message) = &self.on_dismiss {
+                    shell.publish(message.clone());
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) => {
+                if let Some(message) = self.on_key.and_then(|on_key| on_key(*key_code)) {
+                    shell.publish(message);
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        self.content.as_widget_mut().on_event(
+            self.tree,
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            self.tree,
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let bounds = layout.bounds();
+        let palette = theme.palette();
+
+        let mut border_color = palette.text;
+        border_color.a = 0.2;
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds,
+                border_radius: 8.0,
+                border_width: 1.0,
+                border_color,
+            },
+            palette.background,
+        );
+
+        self.content.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            &renderer::Style {
+                text_color: palette.text,
+            },
+            layout,
+            cursor_position,
+            &bounds,
+        );
+    }
+}
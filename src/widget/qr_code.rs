@@ -0,0 +1,95 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{widget::image, Length},
+    Element,
+};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Pixels drawn for each module of a code.
+const MODULE_SIZE: u32 = 8;
+
+/// Light modules around a code, which readers need to find it.
+const QUIET_ZONE: u32 = 4;
+
+/// Encodes text as a QR code with `qrencode`, drawn as dark modules on a light background.
+///
+/// The text is written to `qrencode` rather than passed as an argument, as it may be a secret
+/// which other processes should not see.
+///
+/// # Errors
+///
+/// Fails if `qrencode` could not be executed, or could not encode the text.
+pub fn encode(text: &str) -> Result<image::Handle, String> {
+    let mut child = Command::new("qrencode")
+        .args(["--type", "ASCII", "--margin", "0", "--output", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|why| format!("failed to execute qrencode: {why}"))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|why| why.to_string())?;
+    }
+
+    let output = child.wait_with_output().map_err(|why| why.to_string())?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(String::from(stderr.trim()));
+    }
+
+    // Each module is printed as two characters, which are `##` when it is dark.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<Vec<bool>> = stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.as_bytes()
+                .chunks(2)
+                .map(|module| module[0] == b'#')
+                .collect()
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return Err(String::from("qrencode printed no code"));
+    }
+
+    let size = (rows.len() as u32 + QUIET_ZONE * 2) * MODULE_SIZE;
+    let mut pixels = vec![u8::MAX; (size * size * 4) as usize];
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|(_, dark)| **dark) {
+            let left = (x as u32 + QUIET_ZONE) * MODULE_SIZE;
+            let top = (y as u32 + QUIET_ZONE) * MODULE_SIZE;
+
+            for py in top..top + MODULE_SIZE {
+                let start = ((py * size + left) * 4) as usize;
+                let end = start + (MODULE_SIZE * 4) as usize;
+
+                // Only the color is darkened, leaving every pixel opaque.
+                for pixel in pixels[start..end].chunks_mut(4) {
+                    pixel[..3].fill(0);
+                }
+            }
+        }
+    }
+
+    Ok(image::Handle::from_pixels(size, size, pixels))
+}
+
+/// Shows a QR code at a fixed size, so that it is never stretched out of shape.
+pub fn qr_code<'a, Message: 'a>(code: &image::Handle, size: u16) -> Element<'a, Message> {
+    image(code.clone())
+        .width(Length::Units(size))
+        .height(Length::Units(size))
+        .into()
+}
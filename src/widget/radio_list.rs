@@ -0,0 +1,325 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, container, Column, Row},
+        Alignment, Length,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{tree, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    theme,
+    widget::{icon, text},
+    Element, Renderer,
+};
+
+use super::{settings_row::disabled, Focus};
+
+/// A choice of a radio list.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Entry<Id> {
+    pub id: Id,
+    pub title: String,
+    pub description: String,
+    pub icon: Option<&'static str>,
+    /// Disabled entries are dimmed, and cannot be selected.
+    pub disabled: bool,
+    /// The icon of a button which trails the entry, pressed with [`radio_list_with_action`].
+    pub action: Option<&'static str>,
+}
+
+impl<Id> Entry<Id> {
+    pub fn new(id: Id, title: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            description: description.into(),
+            icon: None,
+            disabled: false,
+            action: None,
+        }
+    }
+
+    /// Places a symbolic icon between the radio indicator and the title.
+    pub fn icon(mut self, name: &'static str) -> Self {
+        self.icon = Some(name);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Trails the entry with a button of a symbolic icon, such as to configure the entry.
+    pub fn action(mut self, icon: &'static str) -> Self {
+        self.action = Some(icon);
+        self
+    }
+}
+
+/// Lists mutually exclusive choices as rows, with a radio indicator leading each.
+///
+/// Once focused by pressing it, the selection moves between the enabled entries with the
+/// Up and Down keys.
+pub fn radio_list<'a, Id: Clone + PartialEq, Message: Clone + 'static>(
+    entries: &'a [Entry<Id>],
+    selected: Option<&Id>,
+    on_select: impl Fn(Id) -> Message + 'a,
+) -> Element<'a, Message> {
+    list_view(entries, selected, &on_select, None)
+}
+
+/// A [`radio_list`] whose entries with an [`Entry::action`] trail their row with its button,
+/// which is pressed without selecting the entry.
+pub fn radio_list_with_action<'a, Id: Clone + PartialEq, Message: Clone + 'static>(
+    entries: &'a [Entry<Id>],
+    selected: Option<&Id>,
+    on_select: impl Fn(Id) -> Message + 'a,
+    on_action: impl Fn(Id) -> Message + 'a,
+) -> Element<'a, Message> {
+    list_view(entries, selected, &on_select, Some(&on_action))
+}
+
+fn list_view<'a, Id: Clone + PartialEq, Message: Clone + 'static>(
+    entries: &'a [Entry<Id>],
+    selected: Option<&Id>,
+    on_select: &dyn Fn(Id) -> Message,
+    on_action: Option<&dyn Fn(Id) -> Message>,
+) -> Element<'a, Message> {
+    let enabled: Vec<&Entry<Id>> = entries.iter().filter(|entry| !entry.disabled).collect();
+
+    let position = enabled.iter().position(|entry| Some(&entry.id) == selected);
+
+    let (previous, next) = match position {
+        Some(position) => (
+            position.checked_sub(1).map(|position| enabled[position]),
+            enabled.get(position + 1).copied(),
+        ),
+        None => (None, enabled.first().copied()),
+    };
+
+    let list = entries
+        .iter()
+        .fold(Column::new(), |list, entry| {
+            list.push(entry_view(
+                entry,
+                selected == Some(&entry.id),
+                on_select,
+                on_action,
+            ))
+        })
+        .spacing(4);
+
+    Element::new(Navigate {
+        content: list.into(),
+        previous: previous.map(|entry| on_select(entry.id.clone())),
+        next: next.map(|entry| on_select(entry.id.clone())),
+    })
+}
+
+fn entry_view<'a, Id: Clone, Message: Clone + 'static>(
+    entry: &'a Entry<Id>,
+    is_selected: bool,
+    on_select: &dyn Fn(Id) -> Message,
+    on_action: Option<&dyn Fn(Id) -> Message>,
+) -> Element<'a, Message> {
+    let indicator = if is_selected {
+        "radio-checked-symbolic"
+    } else {
+        "radio-symbolic"
+    };
+
+    let mut labels = column!(text(entry.title.as_str()))
+        .spacing(2)
+        .width(Length::Fill);
+
+    if !entry.description.is_empty() {
+        labels = labels.push(text(entry.description.as_str()).size(12));
+    }
+
+    let mut content = Row::new()
+        .spacing(16)
+        .align_items(Alignment::Center)
+        .push(icon(indicator, 16).style(theme::Svg::Symbolic));
+
+    if let Some(name) = entry.icon {
+        content = content.push(icon(name, 20).style(theme::Svg::Symbolic));
+    }
+
+    let content = content.push(labels).apply(container).padding([8, 0]);
+
+    let content: Element<'a, Message> = if entry.disabled {
+        content.style(theme::Container::Custom(disabled)).into()
+    } else {
+        content
+            .apply(button)
+            .padding(0)
+            .width(Length::Fill)
+            .style(theme::Button::Transparent)
+            .on_press(on_select(entry.id.clone()))
+            .into()
+    };
+
+    // The action sits beside the row rather than in it, so that pressing it selects nothing.
+    match (entry.action, on_action) {
+        (Some(name), Some(on_action)) => Row::new()
+            .spacing(8)
+            .align_items(Alignment::Center)
+            .push(content)
+            .push(
+                icon(name, 16)
+                    .style(theme::Svg::Symbolic)
+                    .apply(button)
+                    .style(theme::Button::Transparent)
+                    .on_press(on_action(entry.id.clone())),
+            )
+            .into(),
+        _ => content,
+    }
+}
+
+/// Selects the previous or next entry with the Up and Down keys, while the list is focused.
+struct Navigate<'a, Message> {
+    content: Element<'a, Message>,
+    previous: Option<Message>,
+    next: Option<Message>,
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Navigate<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Focus>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(Focus::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        operation.focusable(tree.state.downcast_mut::<Focus>(), None);
+
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let focus = tree.state.downcast_mut::<Focus>();
+
+        match &event {
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                focus.focused = layout.bounds().contains(cursor_position);
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) if focus.focused => {
+                let message = match key_code {
+                    KeyCode::Up => &self.previous,
+                    KeyCode::Down => &self.next,
+                    _ => &None,
+                };
+
+                if let Some(message) = message {
+                    shell.publish(message.clone());
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
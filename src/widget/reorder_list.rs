@@ -0,0 +1,397 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{
+        widget::{row, Column},
+        Alignment, Color, Length,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{operation, tree, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    theme,
+    widget::icon,
+    Element, Renderer,
+};
+use std::cell::Cell;
+
+/// Distance from the edges of the viewport within which dragging scrolls it.
+const SCROLL_EDGE: f32 = 48.0;
+
+/// Rows which are dragged into place by the handles leading them.
+///
+/// Once focused, the focused row moves with the Up and Down keys, and is itself moved along
+/// with them while Ctrl is held. Positions are only published once a row is dropped, and a
+/// drag is cancelled if the pointer leaves the window.
+#[must_use]
+pub struct ReorderList<'a, Message> {
+    rows: Vec<Element<'a, Message>>,
+    on_move: Box<dyn Fn(usize, usize) -> Message + 'a>,
+    on_autoscroll: Option<Box<dyn Fn(f32) -> Message + 'a>>,
+    spacing: u16,
+}
+
+/// Creates a list whose rows are moved with `on_move` from the first position to the second.
+pub fn reorder_list<'a, Message>(
+    rows: Vec<Element<'a, Message>>,
+    on_move: impl Fn(usize, usize) -> Message + 'a,
+) -> ReorderList<'a, Message> {
+    ReorderList {
+        rows,
+        on_move: Box::new(on_move),
+        on_autoscroll: None,
+        spacing: 4,
+    }
+}
+
+impl<'a, Message> ReorderList<'a, Message> {
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Emitted while a row is dragged near the top or bottom of the viewport, with a speed
+    /// from -1 to 1 at which the scrollable containing the list should scroll.
+    pub fn on_autoscroll(mut self, on_autoscroll: impl Fn(f32) -> Message + 'a) -> Self {
+        self.on_autoscroll = Some(Box::new(on_autoscroll));
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<ReorderList<'a, Message>> for Element<'a, Message> {
+    fn from(list: ReorderList<'a, Message>) -> Self {
+        let len = list.rows.len();
+
+        let rows =
+            list.rows
+                .into_iter()
+                .fold(Column::new().spacing(list.spacing), |rows, content| {
+                    rows.push(
+                        row!(
+                            icon("list-drag-handle-symbolic", 16).style(theme::Svg::Symbolic),
+                            content
+                        )
+                        .spacing(8)
+                        .align_items(Alignment::Center),
+                    )
+                });
+
+        Element::new(Reorder {
+            content: rows.into(),
+            len,
+            on_move: list.on_move,
+            on_autoscroll: list.on_autoscroll,
+        })
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// The row which is being dragged.
+    drag: Option<usize>,
+    focused: bool,
+    /// The row which is moved with the keyboard while the list is focused.
+    row: usize,
+    /// The viewport which the list was last drawn within, which is scrolled while dragging.
+    viewport: Cell<Option<Rectangle>>,
+}
+
+impl operation::Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn focus(&mut self) {
+        self.focused = true;
+    }
+
+    fn unfocus(&mut self) {
+        self.focused = false;
+    }
+}
+
+struct Reorder<'a, Message> {
+    content: Element<'a, Message>,
+    len: usize,
+    on_move: Box<dyn Fn(usize, usize) -> Message + 'a>,
+    on_autoscroll: Option<Box<dyn Fn(f32) -> Message + 'a>>,
+}
+
+/// The row beneath the cursor, or the nearest row if the cursor is above or below all rows.
+fn row_at(layout: Layout<'_>, cursor_position: Point) -> Option<usize> {
+    let rows: Vec<Rectangle> = layout.children().map(|row| row.bounds()).collect();
+    let last = rows.len().checked_sub(1)?;
+
+    Some(
+        rows.iter()
+            .position(|bounds| cursor_position.y < bounds.y + bounds.height)
+            .unwrap_or(last),
+    )
+}
+
+/// The handle is the first child of each row.
+fn handle_at(layout: Layout<'_>, cursor_position: Point) -> Option<usize> {
+    layout.children().position(|row| {
+        matches!(
+            row.children().next(),
+            Some(handle) if handle.bounds().contains(cursor_position)
+        )
+    })
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Reorder<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        operation.focusable(tree.state.downcast_mut::<State>(), None);
+
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+
+        // Rows may have been removed since the row was focused.
+        state.row = state.row.min(self.len.saturating_sub(1));
+
+        match &event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.focused = layout.bounds().contains(cursor_position);
+
+                if let Some(row) = row_at(layout, cursor_position).filter(|_| state.focused) {
+                    state.row = row;
+                }
+
+                if let Some(index) = handle_at(layout, cursor_position) {
+                    state.drag = Some(index);
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                state.focused = layout.bounds().contains(cursor_position);
+            }
+
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some(from) = state.drag.take() {
+                    if let Some(to) = row_at(layout, cursor_position) {
+                        if from != to {
+                            state.row = to;
+                            shell.publish((self.on_move)(from, to));
+                        }
+                    }
+
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Mouse(mouse::Event::CursorLeft) => state.drag = None,
+
+            Event::Mouse(mouse::Event::CursorMoved { position }) if state.drag.is_some() => {
+                let viewport = state.viewport.get();
+
+                if let (Some(on_autoscroll), Some(viewport)) = (&self.on_autoscroll, viewport) {
+                    let top = position.y - viewport.y;
+                    let bottom = viewport.y + viewport.height - position.y;
+
+                    let speed = if top < SCROLL_EDGE {
+                        -(SCROLL_EDGE - top.max(0.0)) / SCROLL_EDGE
+                    } else if bottom < SCROLL_EDGE {
+                        (SCROLL_EDGE - bottom.max(0.0)) / SCROLL_EDGE
+                    } else {
+                        0.0
+                    };
+
+                    if speed != 0.0 {
+                        shell.publish(on_autoscroll(speed));
+                    }
+                }
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) if state.focused && self.len > 0 => {
+                let to = match key_code {
+                    KeyCode::Up => state.row.checked_sub(1),
+                    KeyCode::Down => Some(state.row + 1).filter(|row| *row < self.len),
+                    _ => None,
+                };
+
+                if let Some(to) = to {
+                    if modifiers.control() {
+                        shell.publish((self.on_move)(state.row, to));
+                    }
+
+                    state.row = to;
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if tree.state.downcast_ref::<State>().drag.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+
+        if handle_at(layout, cursor_position).is_some() {
+            return mouse::Interaction::Grab;
+        }
+
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let state = tree.state.downcast_ref::<State>();
+        state.viewport.set(Some(*viewport));
+
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+
+        let primary = theme.palette().primary;
+
+        if state.focused && state.drag.is_none() {
+            if let Some(row) = layout.children().nth(state.row) {
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: row.bounds(),
+                        border_radius: 4.0,
+                        border_width: 2.0,
+                        border_color: primary,
+                    },
+                    Color::TRANSPARENT,
+                );
+            }
+        }
+
+        let Some(from) = state.drag else {
+            return;
+        };
+
+        let Some(to) = row_at(layout, cursor_position) else {
+            return;
+        };
+
+        let Some(target) = layout.children().nth(to).map(|row| row.bounds()) else {
+            return;
+        };
+
+        // Marks the edge of the row which the dragged row will be placed beside.
+        let y = if to > from {
+            target.y + target.height
+        } else {
+            target.y
+        };
+
+        renderer.fill_quad(
+            renderer::Quad {
+                bounds: Rectangle {
+                    x: target.x,
+                    y: y - 1.0,
+                    width: target.width,
+                    height: 2.0,
+                },
+                border_radius: 1.0,
+                border_width: 0.0,
+                border_color: primary,
+            },
+            primary,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
@@ -0,0 +1,296 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        self,
+        widget::{button, row, text_input},
+        Alignment, Command, Length, Subscription,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    theme,
+    widget::icon,
+    Element, Renderer,
+};
+use std::time::{Duration, Instant};
+
+/// How long typing must pause before the phrase is searched for.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Interval between checks of whether typing has paused.
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(50);
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    /// Empties the phrase, such as when the clear button or Escape is pressed.
+    Clear,
+    Input(String),
+    Submit,
+    Tick,
+}
+
+/// A search entry with a leading search icon, and a button which clears it.
+///
+/// Typed phrases are only emitted once typing pauses, or once they are submitted, so that
+/// they are not searched for on every keystroke.
+pub struct SearchInput {
+    id: text_input::Id,
+    phrase: String,
+    /// The phrase which was last emitted.
+    searched: String,
+    /// When the phrase was last typed, until it is emitted.
+    typed: Option<Instant>,
+}
+
+impl Default for SearchInput {
+    fn default() -> Self {
+        Self {
+            id: text_input::Id::unique(),
+            phrase: String::new(),
+            searched: String::new(),
+            typed: None,
+        }
+    }
+}
+
+impl SearchInput {
+    /// Whether a phrase is being searched for.
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        !self.searched.is_empty()
+    }
+
+    #[must_use]
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    /// Empties the phrase without emitting it, such as when a result is opened.
+    pub fn clear(&mut self) {
+        self.phrase.clear();
+        self.searched.clear();
+        self.typed = None;
+    }
+
+    /// Focuses the entry, such as from a keyboard shortcut, and selects the phrase in it so
+    /// that typing replaces it.
+    #[must_use]
+    pub fn focus<Message: 'static>(&self) -> Command<Message> {
+        Command::batch(vec![
+            text_input::focus(self.id.clone()),
+            text_input::select_all(self.id.clone()),
+        ])
+    }
+
+    /// Emits the phrase to search for whenever it changes.
+    pub fn update(&mut self, message: Message) -> Option<String> {
+        match message {
+            Message::Clear => {
+                if self.phrase.is_empty() && self.searched.is_empty() {
+                    return None;
+                }
+
+                self.clear();
+                Some(String::new())
+            }
+
+            // Emptying the entry ends the search at once, rather than after a pause.
+            Message::Input(phrase) if phrase.is_empty() => self.update(Message::Clear),
+
+            Message::Input(phrase) => {
+                self.phrase = phrase;
+                self.typed = Some(Instant::now());
+                None
+            }
+
+            Message::Submit => self.emit(),
+
+            Message::Tick => {
+                if self.typed?.elapsed() < DEBOUNCE {
+                    return None;
+                }
+
+                self.emit()
+            }
+        }
+    }
+
+    /// Checks whether typing has paused, while a typed phrase is yet to be emitted.
+    #[must_use]
+    pub fn subscription(&self) -> Subscription<Message> {
+        if self.typed.is_some() {
+            iced::time::every(DEBOUNCE_INTERVAL).map(|_| Message::Tick)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    #[must_use]
+    pub fn view(&self, placeholder: &str) -> Element<Message> {
+        let input = text_input(placeholder, &self.phrase, Message::Input)
+            .id(self.id.clone())
+            .on_submit(Message::Submit)
+            .padding(8)
+            .width(Length::Units(240));
+
+        // Escape clears the phrase while the entry is focused, which it then leaves.
+        let input = Element::new(EscapeClears {
+            content: input.into(),
+        });
+
+        let mut content = row!(
+            icon("system-search-symbolic", 16).style(theme::Svg::Symbolic),
+            input
+        )
+        .spacing(8)
+        .align_items(Alignment::Center);
+
+        if !self.phrase.is_empty() {
+            content = content.push(
+                icon("edit-clear-symbolic", 16)
+                    .style(theme::Svg::Symbolic)
+                    .apply(button)
+                    .style(theme::Button::Transparent)
+                    .on_press(Message::Clear),
+            );
+        }
+
+        content.into()
+    }
+
+    fn emit(&mut self) -> Option<String> {
+        self.typed = None;
+
+        if self.phrase == self.searched {
+            return None;
+        }
+
+        self.searched = self.phrase.clone();
+        Some(self.searched.clone())
+    }
+}
+
+/// Publishes [`Message::Clear`] when Escape is pressed while the entry is focused.
+///
+/// The entry itself takes Escape to leave focus, so that it would otherwise never reach the
+/// application. The press still reaches the entry, which returns focus to the page.
+struct EscapeClears<'a> {
+    content: Element<'a, Message>,
+}
+
+impl<'a> Widget<Message, Renderer> for EscapeClears<'a> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if let Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code: KeyCode::Escape,
+            ..
+        }) = event
+        {
+            let input = tree.children[0].state.downcast_ref::<text_input::State>();
+
+            if input.is_focused() {
+                shell.publish(Message::Clear);
+            }
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
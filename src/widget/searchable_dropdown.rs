@@ -0,0 +1,519 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        alignment,
+        widget::{button, column, container, horizontal_space, row, text_input, vertical_space},
+        Alignment, Command, Length,
+    },
+    iced_native::{
+        event,
+        keyboard::KeyCode,
+        layout, mouse, renderer,
+        widget::{Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    theme,
+    widget::{icon, text},
+    Element, Renderer,
+};
+
+use super::popover::popover;
+use crate::page::section::normalize;
+
+/// The height of every row of the list, including the headers of groups.
+const ROW_HEIGHT: u16 = 32;
+
+/// The number of rows which are built at once, as the list is scrolled through.
+const VISIBLE_ROWS: usize = 10;
+
+/// An item of a dropdown, identified by the value emitted when it is selected.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Item<Id> {
+    pub id: Id,
+    pub label: String,
+    /// Consecutive items of the same group are listed beneath its name.
+    pub group: Option<String>,
+    normalized: String,
+}
+
+impl<Id> Item<Id> {
+    pub fn new(id: Id, label: impl Into<String>) -> Self {
+        let label = label.into();
+
+        Self {
+            id,
+            normalized: normalize(&label),
+            label,
+            group: None,
+        }
+    }
+
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message<Id> {
+    Dismiss,
+    Filter(String),
+    /// Moves the highlight by a number of items.
+    Move(isize),
+    /// Scrolls the list by a number of rows.
+    Scroll(isize),
+    Select(Id),
+    Submit,
+    Toggle,
+}
+
+pub enum Output<Id> {
+    /// The filter should be focused with [`SearchableDropdown::focus`].
+    Opened,
+    Selected(Id),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Row {
+    /// The header of the group of an item.
+    Group(usize),
+    Item(usize),
+}
+
+/// A dropdown whose items are filtered by a search, the same way the settings are searched.
+///
+/// Only the rows which are scrolled into view are built, so that long lists stay cheap to
+/// open and to filter.
+pub struct SearchableDropdown<Id> {
+    items: Vec<Item<Id>>,
+    filter: String,
+    filter_id: text_input::Id,
+    rows: Vec<Row>,
+    /// The row which is selected by pressing Enter.
+    highlighted: Option<usize>,
+    /// The first row which is scrolled into view.
+    first: usize,
+    open: bool,
+}
+
+impl<Id> Default for SearchableDropdown<Id> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            filter: String::new(),
+            filter_id: text_input::Id::unique(),
+            rows: Vec::new(),
+            highlighted: None,
+            first: 0,
+            open: false,
+        }
+    }
+}
+
+impl<Id: Clone + PartialEq + std::fmt::Debug + Send + 'static> SearchableDropdown<Id> {
+    #[must_use]
+    pub fn new(items: Vec<Item<Id>>) -> Self {
+        let mut dropdown = Self::default();
+        dropdown.set_items(items);
+        dropdown
+    }
+
+    #[must_use]
+    pub fn items(&self) -> &[Item<Id>] {
+        &self.items
+    }
+
+    pub fn set_items(&mut self, items: Vec<Item<Id>>) {
+        self.items = items;
+        self.refresh();
+    }
+
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.filter.clear();
+        self.refresh();
+    }
+
+    /// Focuses the filter, so that typing filters the items as soon as the dropdown opens.
+    #[must_use]
+    pub fn focus<Message: 'static>(&self) -> Command<Message> {
+        text_input::focus(self.filter_id.clone())
+    }
+
+    pub fn update(&mut self, message: Message<Id>) -> Option<Output<Id>> {
+        match message {
+            Message::Toggle if self.open => self.close(),
+
+            Message::Toggle => {
+                self.open = true;
+                return Some(Output::Opened);
+            }
+
+            Message::Dismiss => self.close(),
+
+            Message::Filter(filter) => {
+                self.filter = filter;
+                self.refresh();
+            }
+
+            Message::Move(offset) => self.move_highlight(offset),
+
+            Message::Scroll(rows) => {
+                let last = self.rows.len().saturating_sub(VISIBLE_ROWS);
+                self.first = add_offset(self.first, rows).min(last);
+            }
+
+            Message::Select(id) => {
+                self.close();
+                return Some(Output::Selected(id));
+            }
+
+            Message::Submit => {
+                if let Some(Row::Item(item)) = self.highlighted.map(|row| self.rows[row]) {
+                    let id = self.items[item].id.clone();
+                    self.close();
+                    return Some(Output::Selected(id));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Filters the rows by the normalized filter, and highlights the first match.
+    fn refresh(&mut self) {
+        let filter = normalize(self.filter.trim());
+
+        self.rows.clear();
+        self.first = 0;
+
+        let mut group = None;
+
+        for (id, item) in self.items.iter().enumerate() {
+            if !item.normalized.contains(&filter) {
+                continue;
+            }
+
+            if item.group.is_some() && item.group != group {
+                group = item.group.clone();
+                self.rows.push(Row::Group(id));
+            }
+
+            self.rows.push(Row::Item(id));
+        }
+
+        self.highlighted = self.rows.iter().position(|row| matches!(row, Row::Item(_)));
+    }
+
+    fn move_highlight(&mut self, offset: isize) {
+        let items: Vec<usize> = (0..self.rows.len())
+            .filter(|&row| matches!(self.rows[row], Row::Item(_)))
+            .collect();
+
+        if items.is_empty() {
+            return;
+        }
+
+        let current = self
+            .highlighted
+            .and_then(|row| items.iter().position(|&item| item == row))
+            .unwrap_or(0);
+
+        let next = add_offset(current, offset).min(items.len() - 1);
+
+        let row = items[next];
+        self.highlighted = Some(row);
+
+        // Keeps the highlighted row in view, along with the header of its group.
+        if row < self.first {
+            self.first = match row.checked_sub(1) {
+                Some(header) if matches!(self.rows[header], Row::Group(_)) => header,
+                _ => row,
+            };
+        } else if row >= self.first + VISIBLE_ROWS {
+            self.first = row + 1 - VISIBLE_ROWS;
+        }
+    }
+
+    /// Displays the selected item, with the list of items beneath it while open.
+    #[must_use]
+    pub fn view(&self, selected: Option<&Id>) -> Element<Message<Id>> {
+        let label = selected
+            .and_then(|id| self.items.iter().find(|item| &item.id == id))
+            .map_or_else(
+                || fl!("searchable-dropdown", "none"),
+                |item| item.label.clone(),
+            );
+
+        let header = row!(
+            text(label),
+            horizontal_space(Length::Fill),
+            icon("go-down-symbolic", 16).style(theme::Svg::Symbolic),
+        )
+        .align_items(Alignment::Center)
+        .apply(button)
+        .width(Length::Units(240))
+        .style(theme::Button::Secondary)
+        .on_press(Message::Toggle);
+
+        let dropdown = popover(header)
+            .on_dismiss(Message::Dismiss)
+            .on_key(|key| match key {
+                KeyCode::Up => Some(Message::Move(-1)),
+                KeyCode::Down => Some(Message::Move(1)),
+                KeyCode::PageUp => Some(Message::Move(-(VISIBLE_ROWS as isize))),
+                KeyCode::PageDown => Some(Message::Move(VISIBLE_ROWS as isize)),
+                KeyCode::Enter | KeyCode::NumpadEnter => Some(Message::Submit),
+                _ => None,
+            });
+
+        if !self.open {
+            return dropdown.into();
+        }
+
+        let filter = text_input(
+            &fl!("searchable-dropdown", "filter"),
+            &self.filter,
+            Message::Filter,
+        )
+        .id(self.filter_id.clone())
+        .padding(8);
+
+        dropdown
+            .popup(
+                column!(filter, self.list_view(selected))
+                    .spacing(8)
+                    .padding(8),
+            )
+            .into()
+    }
+
+    /// Builds the rows which are scrolled into view, between spaces taking the place of the rest.
+    fn list_view(&self, selected: Option<&Id>) -> Element<Message<Id>> {
+        if self.rows.is_empty() {
+            return text(fl!("searchable-dropdown", "empty"))
+                .apply(container)
+                .padding(8)
+                .into();
+        }
+
+        let end = (self.first + VISIBLE_ROWS).min(self.rows.len());
+        let mut list = Vec::with_capacity(end - self.first + 2);
+
+        list.push(vertical_space(Length::Units(rows_height(self.first))).into());
+
+        for row in self.first..end {
+            list.push(match self.rows[row] {
+                Row::Group(item) => {
+                    let group = self.items[item].group.as_deref().unwrap_or_default();
+
+                    text(group)
+                        .size(12)
+                        .apply(container)
+                        .height(Length::Units(ROW_HEIGHT))
+                        .align_y(alignment::Vertical::Bottom)
+                        .padding([0, 8, 4, 8])
+                        .into()
+                }
+
+                Row::Item(item) => {
+                    let item = &self.items[item];
+
+                    let mut label = row!(text(item.label.as_str()), horizontal_space(Length::Fill))
+                        .align_items(Alignment::Center);
+
+                    if selected == Some(&item.id) {
+                        label = label
+                            .push(icon("object-select-symbolic", 16).style(theme::Svg::Symbolic));
+                    }
+
+                    let style = if self.highlighted == Some(row) {
+                        theme::Button::Primary
+                    } else {
+                        theme::Button::Transparent
+                    };
+
+                    label
+                        .apply(button)
+                        .width(Length::Fill)
+                        .height(Length::Units(ROW_HEIGHT))
+                        .style(style)
+                        .on_press(Message::Select(item.id.clone()))
+                        .into()
+                }
+            });
+        }
+
+        list.push(vertical_space(Length::Units(rows_height(self.rows.len() - end))).into());
+
+        Element::new(Wheel {
+            content: column(list).into(),
+            on_scroll: Message::Scroll,
+            offset: f32::from(rows_height(self.first)),
+            height: f32::from(rows_height(VISIBLE_ROWS.min(self.rows.len()))),
+        })
+    }
+}
+
+fn add_offset(index: usize, offset: isize) -> usize {
+    if offset < 0 {
+        index.saturating_sub(offset.unsigned_abs())
+    } else {
+        index.saturating_add(offset as usize)
+    }
+}
+
+fn rows_height(rows: usize) -> u16 {
+    (rows * ROW_HEIGHT as usize).min(u16::MAX as usize) as u16
+}
+
+/// Shows the rows of a list which are in view, scrolling them with the mouse wheel.
+///
+/// The spaces before and after the rows in view are laid out, so that the rows keep their
+/// place, but only the rows in view are shown.
+struct Wheel<'a, Message> {
+    content: Element<'a, Message>,
+    on_scroll: fn(isize) -> Message,
+    /// The height of the space before the rows in view.
+    offset: f32,
+    height: f32,
+}
+
+impl<'a, Message> Widget<Message, Renderer> for Wheel<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let max = limits.max();
+        let content_limits = layout::Limits::new(Size::ZERO, Size::new(max.width, f32::INFINITY))
+            .width(Length::Fill);
+
+        let mut content = self.content.as_widget().layout(renderer, &content_limits);
+        content.move_to(Point::new(0.0, -self.offset));
+
+        let size = Size::new(content.size().width, self.height.min(max.height));
+
+        layout::Node::with_children(size, vec![content])
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        if let Some(layout) = layout.children().next() {
+            self.content
+                .as_widget()
+                .operate(&mut tree.children[0], layout, operation);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let Some(content_layout) = layout.children().next() else {
+            return event::Status::Ignored;
+        };
+
+        if let Event::Mouse(mouse::Event::WheelScrolled { delta }) = event {
+            if !layout.bounds().contains(cursor_position) {
+                return event::Status::Ignored;
+            }
+
+            let rows = match delta {
+                mouse::ScrollDelta::Lines { y, .. } => -y.signum() * y.abs().ceil(),
+                mouse::ScrollDelta::Pixels { y, .. } => (-y / f32::from(ROW_HEIGHT)).round(),
+            };
+
+            if rows != 0.0 {
+                shell.publish((self.on_scroll)(rows as isize));
+            }
+
+            return event::Status::Captured;
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            content_layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        match layout.children().next() {
+            Some(content_layout) => self.content.as_widget().mouse_interaction(
+                &tree.children[0],
+                content_layout,
+                cursor_position,
+                viewport,
+                renderer,
+            ),
+            None => mouse::Interaction::default(),
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let Some(content_layout) = layout.children().next() else {
+            return;
+        };
+
+        renderer.with_layer(layout.bounds(), |renderer| {
+            self.content.as_widget().draw(
+                &tree.children[0],
+                renderer,
+                theme,
+                style,
+                content_layout,
+                cursor_position,
+                viewport,
+            );
+        });
+    }
+}
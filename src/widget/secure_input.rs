@@ -0,0 +1,498 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, progress_bar, row, text_input},
+        Alignment, Command, Length,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, overlay, renderer,
+        widget::{
+            operation::{self, Focusable},
+            tree, Operation, Tree,
+        },
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    theme,
+    widget::{icon, text},
+    Element, Renderer,
+};
+use std::fmt;
+
+/// Passwords which are guessed before any other, regardless of their length.
+const COMMON: [&str; 12] = [
+    "123456", "abc123", "admin", "dragon", "iloveyou", "letmein", "monkey", "password", "qwerty",
+    "sunshine", "welcome", "111111",
+];
+
+/// A value which is kept out of logs, as it is never printed by its `Debug` impl.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    #[must_use]
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    CapsLock(bool),
+    Input(Secret),
+    Reveal(bool),
+    Submit,
+    Unfocused,
+}
+
+/// What the owner of the input is told of, once the input has handled a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Output {
+    Input,
+    Submit,
+}
+
+/// An estimate of how long a password would take to be guessed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strength {
+    VeryWeak,
+    Weak,
+    Fair,
+    Strong,
+    VeryStrong,
+}
+
+impl Strength {
+    /// Estimates the strength of a password from the characters it is made of.
+    ///
+    /// Repeated and sequential characters, such as "aaa" or "123", add little to its strength,
+    /// and passwords that are commonly used are always very weak.
+    #[must_use]
+    pub fn of(password: &str) -> Self {
+        let lowercase = password.to_lowercase();
+
+        if password.is_empty() || COMMON.iter().any(|common| lowercase.contains(common)) {
+            return Strength::VeryWeak;
+        }
+
+        let classes = [
+            (password.chars().any(char::is_lowercase), 26.0),
+            (password.chars().any(char::is_uppercase), 26.0),
+            (password.chars().any(|c| c.is_ascii_digit()), 10.0),
+            (password.chars().any(|c| !c.is_alphanumeric()), 33.0),
+        ];
+
+        let pool: f64 = classes
+            .iter()
+            .filter(|(present, _)| *present)
+            .map(|(_, size)| size)
+            .sum();
+
+        let mut length = 0.0;
+        let mut previous: Option<char> = None;
+
+        for c in password.chars() {
+            let predictable = previous.map_or(false, |previous| {
+                let distance = (c as i64) - (previous as i64);
+                (-1..=1).contains(&distance)
+            });
+
+            length += if predictable { 0.25 } else { 1.0 };
+            previous = Some(c);
+        }
+
+        let bits = length * pool.max(10.0).log2();
+
+        match bits {
+            bits if bits < 28.0 => Strength::VeryWeak,
+            bits if bits < 36.0 => Strength::Weak,
+            bits if bits < 60.0 => Strength::Fair,
+            bits if bits < 80.0 => Strength::Strong,
+            _ => Strength::VeryStrong,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            Strength::VeryWeak => fl!("secure-input", "very-weak"),
+            Strength::Weak => fl!("secure-input", "weak"),
+            Strength::Fair => fl!("secure-input", "fair"),
+            Strength::Strong => fl!("secure-input", "strong"),
+            Strength::VeryStrong => fl!("secure-input", "very-strong"),
+        }
+    }
+}
+
+/// A masked text input, for passwords and other secrets, which may be revealed with a toggle.
+///
+/// The input is masked again whenever it loses focus.
+pub struct SecureInput {
+    id: text_input::Id,
+    placeholder: String,
+    value: Secret,
+    revealed: bool,
+    caps_lock: bool,
+    caps_lock_warning: bool,
+    strength_meter: bool,
+}
+
+/// Creates a masked input, which is empty.
+#[must_use]
+pub fn secure_input(placeholder: impl Into<String>) -> SecureInput {
+    SecureInput {
+        id: text_input::Id::unique(),
+        placeholder: placeholder.into(),
+        value: Secret::default(),
+        revealed: false,
+        caps_lock: false,
+        caps_lock_warning: false,
+        strength_meter: false,
+    }
+}
+
+impl SecureInput {
+    /// Warns that caps lock is on while typing into the input.
+    #[must_use]
+    pub fn caps_lock_warning(mut self, enabled: bool) -> Self {
+        self.caps_lock_warning = enabled;
+        self
+    }
+
+    /// Shows how strong the typed password is, for choosing a new password.
+    #[must_use]
+    pub fn strength_meter(mut self, enabled: bool) -> Self {
+        self.strength_meter = enabled;
+        self
+    }
+
+    #[must_use]
+    pub fn value(&self) -> &Secret {
+        &self.value
+    }
+
+    /// Replaces the value of the input, such as with a secret which was saved before.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = Secret(value.into());
+    }
+
+    /// Empties and masks the input, such as once its value was used.
+    pub fn clear(&mut self) {
+        self.value = Secret::default();
+        self.revealed = false;
+    }
+
+    /// Focuses the input, so that it may be typed into at once.
+    #[must_use]
+    pub fn focus<Message: 'static>(&self) -> Command<Message> {
+        text_input::focus(self.id.clone())
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Output> {
+        match message {
+            Message::CapsLock(caps_lock) => self.caps_lock = caps_lock,
+
+            Message::Input(value) => {
+                self.value = value;
+                return Some(Output::Input);
+            }
+
+            Message::Reveal(revealed) => self.revealed = revealed,
+
+            Message::Submit => return Some(Output::Submit),
+
+            Message::Unfocused => self.revealed = false,
+        }
+
+        None
+    }
+
+    #[must_use]
+    pub fn view(&self) -> Element<Message> {
+        let mut input = text_input(&self.placeholder, self.value.expose(), |value| {
+            Message::Input(Secret(value))
+        })
+        .id(self.id.clone())
+        .on_submit(Message::Submit)
+        .padding(8)
+        .width(Length::Fill);
+
+        if !self.revealed {
+            input = input.password();
+        }
+
+        let reveal_icon = if self.revealed {
+            "view-conceal-symbolic"
+        } else {
+            "view-reveal-symbolic"
+        };
+
+        // Pressed through the watch, so that the input is not unfocused by pressing it.
+        let reveal = icon(reveal_icon, 16)
+            .style(theme::Svg::Symbolic)
+            .apply(button)
+            .padding(8)
+            .style(theme::Button::Secondary)
+            .on_press(Message::Reveal(!self.revealed));
+
+        let field = Element::new(Watch {
+            content: row!(input, reveal)
+                .spacing(8)
+                .align_items(Alignment::Center)
+                .into(),
+            on_reveal: Message::Reveal(!self.revealed),
+        });
+
+        let mut content = column!(field).spacing(8);
+
+        if self.caps_lock_warning && self.caps_lock {
+            content = content.push(
+                row!(
+                    icon("dialog-warning-symbolic", 16).style(theme::Svg::Symbolic),
+                    text(fl!("secure-input", "caps-lock")).size(12),
+                )
+                .spacing(8)
+                .align_items(Alignment::Center),
+            );
+        }
+
+        if self.strength_meter && !self.value.is_empty() {
+            let strength = Strength::of(self.value.expose());
+
+            content = content.push(
+                row!(
+                    progress_bar(0.0..=5.0, f32::from(strength as u8 + 1))
+                        .height(Length::Units(4))
+                        .width(Length::Fill),
+                    text(strength.label()).size(12),
+                )
+                .spacing(8)
+                .align_items(Alignment::Center),
+            );
+        }
+
+        content.into()
+    }
+}
+
+#[derive(Default)]
+struct State {
+    focused: bool,
+    shift: bool,
+    /// Unknown until a letter is typed, or caps lock is pressed.
+    caps_lock: Option<bool>,
+}
+
+/// Tells when the input loses focus or caps lock is toggled, and reveals it without
+/// unfocusing it when the reveal button is pressed.
+struct Watch<'a> {
+    content: Element<'a, Message>,
+    on_reveal: Message,
+}
+
+impl<'a> Watch<'a> {
+    fn is_focused(&self, tree: &mut Tree, layout: Layout<'_>) -> bool {
+        let mut focused = IsFocused(false);
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, &mut focused);
+        focused.0
+    }
+}
+
+impl<'a> Widget<Message, Renderer> for Watch<'a> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let over_reveal = layout
+            .children()
+            .nth(1)
+            .map_or(false, |reveal| reveal.bounds().contains(cursor_position));
+
+        if over_reveal {
+            if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+                shell.publish(self.on_reveal.clone());
+                return event::Status::Captured;
+            }
+        }
+
+        let state = tree.state.downcast_mut::<State>();
+
+        match &event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.shift = modifiers.shift();
+            }
+
+            Event::Keyboard(keyboard::Event::CharacterReceived(c)) if state.focused => {
+                // Letters are only uppercase without shift, or lowercase with it, if caps lock is on.
+                if c.is_uppercase() != c.is_lowercase() {
+                    let caps_lock = c.is_uppercase() != state.shift;
+
+                    if state.caps_lock != Some(caps_lock) {
+                        state.caps_lock = Some(caps_lock);
+                        shell.publish(Message::CapsLock(caps_lock));
+                    }
+                }
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code: KeyCode::Capital,
+                ..
+            }) if state.focused => {
+                if let Some(caps_lock) = state.caps_lock.as_mut() {
+                    *caps_lock = !*caps_lock;
+                    shell.publish(Message::CapsLock(*caps_lock));
+                }
+            }
+
+            _ => (),
+        }
+
+        let status = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        );
+
+        let focused = self.is_focused(tree, layout);
+        let state = tree.state.downcast_mut::<State>();
+
+        if state.focused && !focused {
+            shell.publish(Message::Unfocused);
+        }
+
+        state.focused = focused;
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
+
+/// Finds whether any focusable widget within the content is focused.
+struct IsFocused(bool);
+
+impl<T> Operation<T> for IsFocused {
+    fn container(
+        &mut self,
+        _id: Option<&operation::Id>,
+        operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+    ) {
+        operate_on_children(self);
+    }
+
+    fn focusable(&mut self, state: &mut dyn Focusable, _id: Option<&operation::Id>) {
+        self.0 |= state.is_focused();
+    }
+}
@@ -0,0 +1,452 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        alignment::{Horizontal, Vertical},
+        widget::{container, Row},
+        Alignment, Color, Length,
+    },
+    iced_native::{
+        event,
+        keyboard::{self, KeyCode},
+        layout, mouse, renderer,
+        widget::{tree, Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Size, Widget,
+    },
+    theme,
+    widget::{icon, text},
+    Element, Renderer,
+};
+
+use super::{settings_row::disabled, Focus};
+
+/// Radius of the corners at the ends of the group.
+const RADIUS: f32 = 8.0;
+
+/// A choice of a segmented control.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Segment<Id> {
+    pub id: Id,
+    pub label: String,
+    pub icon: Option<&'static str>,
+    /// Disabled segments are dimmed, and cannot be selected, such as when the hardware
+    /// does not support them.
+    pub disabled: bool,
+}
+
+impl<Id> Segment<Id> {
+    pub fn new(id: Id, label: impl Into<String>) -> Self {
+        Self {
+            id,
+            label: label.into(),
+            icon: None,
+            disabled: false,
+        }
+    }
+
+    /// Places a symbolic icon before the label.
+    pub fn icon(mut self, name: &'static str) -> Self {
+        self.icon = Some(name);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// A group of joined buttons of which one is selected, for choices of only a few options.
+///
+/// Once focused by pressing it, the selection moves between the enabled segments with the
+/// Left and Right keys.
+#[must_use]
+pub struct Segmented<'a, Id, Message> {
+    segments: &'a [Segment<Id>],
+    selected: Option<&'a Id>,
+    on_select: Box<dyn Fn(Id) -> Message + 'a>,
+    icons_only: bool,
+}
+
+/// Creates a segmented control, which emits the id of a segment when it is selected.
+pub fn segmented<'a, Id, Message>(
+    segments: &'a [Segment<Id>],
+    selected: Option<&'a Id>,
+    on_select: impl Fn(Id) -> Message + 'a,
+) -> Segmented<'a, Id, Message> {
+    Segmented {
+        segments,
+        selected,
+        on_select: Box::new(on_select),
+        icons_only: false,
+    }
+}
+
+impl<'a, Id, Message> Segmented<'a, Id, Message> {
+    /// Shows only the icons of segments which have them, for narrow layouts.
+    pub fn icons_only(mut self, icons_only: bool) -> Self {
+        self.icons_only = icons_only;
+        self
+    }
+}
+
+impl<'a, Id: Clone + PartialEq, Message: Clone + 'static> From<Segmented<'a, Id, Message>>
+    for Element<'a, Message>
+{
+    fn from(control: Segmented<'a, Id, Message>) -> Self {
+        let selected = control
+            .segments
+            .iter()
+            .position(|segment| Some(&segment.id) == control.selected);
+
+        let on_press = control
+            .segments
+            .iter()
+            .map(|segment| (!segment.disabled).then(|| (control.on_select)(segment.id.clone())))
+            .collect();
+
+        let contents = control
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let mut content = Row::new().spacing(8).align_items(Alignment::Center);
+
+                if let Some(name) = segment.icon {
+                    content = content.push(icon(name, 16).style(theme::Svg::Symbolic));
+                }
+
+                if !control.icons_only || segment.icon.is_none() {
+                    content = content.push(text(segment.label.as_str()));
+                }
+
+                let style: fn(&cosmic::Theme) -> container::Appearance = if segment.disabled {
+                    disabled
+                } else if selected == Some(index) {
+                    on_selected
+                } else {
+                    enabled
+                };
+
+                content
+                    .apply(container)
+                    .padding([6, 16])
+                    .align_x(Horizontal::Center)
+                    .align_y(Vertical::Center)
+                    .style(theme::Container::Custom(style))
+                    .into()
+            })
+            .collect();
+
+        Element::new(Segments {
+            contents,
+            on_press,
+            selected,
+        })
+    }
+}
+
+fn enabled(_theme: &cosmic::Theme) -> container::Appearance {
+    container::Appearance::default()
+}
+
+fn on_selected(theme: &cosmic::Theme) -> container::Appearance {
+    container::Appearance {
+        text_color: Some(theme.palette().background),
+        ..container::Appearance::default()
+    }
+}
+
+/// Mixes two opaque colors, so that quads drawn over each other do not blend.
+fn mix(a: Color, b: Color, amount: f32) -> Color {
+    Color::from_rgb(
+        a.r + (b.r - a.r) * amount,
+        a.g + (b.g - a.g) * amount,
+        a.b + (b.b - a.b) * amount,
+    )
+}
+
+fn fill(renderer: &mut Renderer, bounds: Rectangle, radius: f32, color: Color) {
+    use cosmic::iced_native::Renderer as _;
+
+    renderer.fill_quad(
+        renderer::Quad {
+            bounds,
+            border_radius: radius,
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        },
+        color,
+    );
+}
+
+/// Lays the segments out in a row, and draws their backgrounds joined together.
+struct Segments<'a, Message> {
+    contents: Vec<Element<'a, Message>>,
+    /// The message of each segment, unless it is disabled.
+    on_press: Vec<Option<Message>>,
+    selected: Option<usize>,
+}
+
+impl<'a, Message> Segments<'a, Message> {
+    /// The message of the nearest enabled segment before or after the selected one.
+    fn step(&self, forward: bool) -> Option<&Message> {
+        let len = self.on_press.len();
+
+        let mut indices: Box<dyn Iterator<Item = usize>> = match (self.selected, forward) {
+            (Some(selected), true) => Box::new(selected + 1..len),
+            (Some(selected), false) => Box::new((0..selected).rev()),
+            (None, _) => Box::new(0..len),
+        };
+
+        indices.find_map(|index| self.on_press[index].as_ref())
+    }
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Segments<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Focus>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(Focus::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.contents.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(&self.contents);
+    }
+
+    fn width(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.loose();
+
+        let mut nodes: Vec<layout::Node> = self
+            .contents
+            .iter()
+            .map(|content| content.as_widget().layout(renderer, &limits))
+            .collect();
+
+        let height = nodes
+            .iter()
+            .map(|node| node.size().height)
+            .fold(0.0, f32::max);
+
+        let mut x = 0.0;
+
+        for node in &mut nodes {
+            node.move_to(Point::new(x, (height - node.size().height) / 2.0));
+            x += node.size().width;
+        }
+
+        layout::Node::with_children(Size::new(x, height), nodes)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        operation.focusable(tree.state.downcast_mut::<Focus>(), None);
+
+        for ((content, state), layout) in self
+            .contents
+            .iter()
+            .zip(&mut tree.children)
+            .zip(layout.children())
+        {
+            content.as_widget().operate(state, layout, operation);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let focus = tree.state.downcast_mut::<Focus>();
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                focus.focused = layout.bounds().contains(cursor_position);
+
+                let pressed = layout
+                    .children()
+                    .position(|segment| segment.bounds().contains(cursor_position));
+
+                if let Some(index) = pressed {
+                    if self.selected != Some(index) {
+                        if let Some(message) = &self.on_press[index] {
+                            shell.publish(message.clone());
+                        }
+                    }
+
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Mouse(mouse::Event::ButtonPressed(_)) => {
+                focus.focused = layout.bounds().contains(cursor_position);
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) if focus.focused => {
+                let message = match key_code {
+                    KeyCode::Left => self.step(false),
+                    KeyCode::Right => self.step(true),
+                    _ => None,
+                };
+
+                if let Some(message) = message {
+                    shell.publish(message.clone());
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let pressable = layout
+            .children()
+            .zip(&self.on_press)
+            .any(|(segment, on_press)| {
+                on_press.is_some() && segment.bounds().contains(cursor_position)
+            });
+
+        if pressable {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let palette = theme.palette();
+        let bounds = layout.bounds();
+        let last = self.contents.len().saturating_sub(1);
+
+        fill(
+            renderer,
+            bounds,
+            RADIUS,
+            mix(palette.background, palette.text, 0.08),
+        );
+
+        for (index, segment) in layout.children().enumerate() {
+            let segment = segment.bounds();
+            let hovered = self.on_press[index].is_some() && segment.contains(cursor_position);
+
+            let color = if self.selected == Some(index) {
+                palette.primary
+            } else if hovered {
+                mix(palette.background, palette.text, 0.16)
+            } else {
+                // Divides the segment from the previous one, unless either is highlighted.
+                if index > 0 && self.selected != Some(index - 1) {
+                    fill(
+                        renderer,
+                        Rectangle {
+                            x: segment.x,
+                            y: segment.y + 6.0,
+                            width: 1.0,
+                            height: segment.height - 12.0,
+                        },
+                        0.0,
+                        mix(palette.background, palette.text, 0.24),
+                    );
+                }
+
+                continue;
+            };
+
+            // Only the outer corners of the segments at the ends are rounded, so the corners
+            // facing their neighbors are squared off by a quad over that half of them.
+            let (first, end) = (index == 0, index == last);
+            fill(
+                renderer,
+                segment,
+                if first || end { RADIUS } else { 0.0 },
+                color,
+            );
+
+            if first != end {
+                let half = segment.width / 2.0;
+                let x = if first { segment.x + half } else { segment.x };
+                fill(
+                    renderer,
+                    Rectangle {
+                        x,
+                        width: half,
+                        ..segment
+                    },
+                    0.0,
+                    color,
+                );
+            }
+        }
+
+        for ((content, state), layout) in self
+            .contents
+            .iter()
+            .zip(&tree.children)
+            .zip(layout.children())
+        {
+            content.as_widget().draw(
+                state,
+                renderer,
+                theme,
+                style,
+                layout,
+                cursor_position,
+                viewport,
+            );
+        }
+
+        if tree.state.downcast_ref::<Focus>().focused {
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds,
+                    border_radius: RADIUS,
+                    border_width: 2.0,
+                    border_color: palette.primary,
+                },
+                Color::TRANSPARENT,
+            );
+        }
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, column, container, Row},
+        Alignment, Background, Color, Length,
+    },
+    iced_native::{layout, renderer, widget::Tree, Layout, Point, Rectangle, Widget},
+    theme,
+    widget::{icon, text, toggler},
+    Element, Renderer,
+};
+
+/// A row of a settings section, with a title and description leading a control.
+#[must_use]
+pub struct SettingsRow<'a, Message> {
+    title: &'a str,
+    description: &'a str,
+    icon: Option<&'a str>,
+    control: Option<Element<'a, Message>>,
+    toggle: Option<(bool, Box<dyn Fn(bool) -> Message + 'a>)>,
+    on_press: Option<Message>,
+    disabled: bool,
+}
+
+/// Creates a settings row, which omits its description if it is empty.
+pub fn settings_row<'a, Message>(title: &'a str, description: &'a str) -> SettingsRow<'a, Message> {
+    SettingsRow {
+        title,
+        description,
+        icon: None,
+        control: None,
+        toggle: None,
+        on_press: None,
+        disabled: false,
+    }
+}
+
+impl<'a, Message: Clone + 'static> SettingsRow<'a, Message> {
+    /// Places a symbolic icon before the title.
+    pub fn icon(mut self, name: &'a str) -> Self {
+        self.icon = Some(name);
+        self
+    }
+
+    /// Places a control, such as a dropdown or button, at the end of the row.
+    pub fn control(mut self, control: impl Into<Element<'a, Message>>) -> Self {
+        self.control = Some(control.into());
+        self
+    }
+
+    /// Places a toggler at the end of the row, which is also toggled by pressing the row.
+    pub fn toggler(mut self, is_checked: bool, on_toggle: impl Fn(bool) -> Message + 'a) -> Self {
+        self.on_press = Some(on_toggle(!is_checked));
+        self.toggle = Some((is_checked, Box::new(on_toggle)));
+        self
+    }
+
+    /// Emits a message when the row is pressed.
+    pub fn on_press(mut self, message: Message) -> Self {
+        self.on_press = Some(message);
+        self
+    }
+
+    /// Dims the row and ignores presses. Togglers of disabled rows show their state, but
+    /// cannot be toggled.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<SettingsRow<'a, Message>> for Element<'a, Message> {
+    fn from(row: SettingsRow<'a, Message>) -> Self {
+        let mut labels = column!(text(row.title)).spacing(2).width(Length::Fill);
+
+        if !row.description.is_empty() {
+            labels = labels.push(text(row.description).size(12));
+        }
+
+        let mut content = Row::new()
+            .spacing(16)
+            .padding([8, 12])
+            .align_items(Alignment::Center);
+
+        if let Some(name) = row.icon {
+            content = content.push(icon(name, 20).style(theme::Svg::Symbolic));
+        }
+
+        content = content.push(labels);
+
+        if let Some((is_checked, on_toggle)) = row.toggle {
+            let toggler = toggler(None, is_checked, on_toggle);
+
+            content = if row.disabled {
+                content.push(Element::new(Inert {
+                    content: toggler.into(),
+                }))
+            } else {
+                content.push(toggler)
+            };
+        }
+
+        if let Some(control) = row.control {
+            content = content.push(control);
+        }
+
+        if row.disabled {
+            return content
+                .apply(container)
+                .style(theme::Container::Custom(disabled))
+                .into();
+        }
+
+        match row.on_press {
+            Some(message) => content
+                .apply(button)
+                .padding(0)
+                .style(theme::Button::Custom {
+                    active: row_active,
+                    hover: row_hover,
+                })
+                .on_press(message)
+                .into(),
+            None => content.into(),
+        }
+    }
+}
+
+/// A pressable row at rest, which looks as a row which cannot be pressed does.
+fn row_active(theme: &cosmic::Theme) -> button::Appearance {
+    button::Appearance {
+        border_radius: 8.0,
+        text_color: theme.palette().text,
+        ..button::Appearance::default()
+    }
+}
+
+/// Shades a pressable row while it is hovered, so that it shows that it can be pressed.
+fn row_hover(theme: &cosmic::Theme) -> button::Appearance {
+    let text = theme.palette().text;
+
+    button::Appearance {
+        background: Some(Background::Color(Color { a: 0.08, ..text })),
+        ..row_active(theme)
+    }
+}
+
+/// Shows content without passing any events to it, for controls of disabled rows, as
+/// events are ignored by the default methods of a widget.
+struct Inert<'a, Message> {
+    content: Element<'a, Message>,
+}
+
+impl<'a, Message> Widget<Message, Renderer> for Inert<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+}
+
+/// Dims the text of disabled content.
+pub(super) fn disabled(theme: &cosmic::Theme) -> container::Appearance {
+    let mut text_color = theme.palette().text;
+    text_color.a = 0.5;
+
+    container::Appearance {
+        text_color: Some(text_color),
+        ..container::Appearance::default()
+    }
+}
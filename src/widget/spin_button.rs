@@ -0,0 +1,404 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        self,
+        widget::{button, container, row, text_input},
+        Alignment, Length, Subscription,
+    },
+    iced_native::{
+        event, layout, mouse, overlay, renderer,
+        widget::{Operation, Tree},
+        Clipboard, Event, Layout, Point, Rectangle, Shell, Widget,
+    },
+    theme,
+    widget::{icon, text},
+    Element, Renderer,
+};
+use std::{
+    fmt::Display,
+    ops::{Add, RangeInclusive, Sub},
+    str::FromStr,
+    time::Duration,
+};
+
+/// Interval between the steps repeated while a button is held.
+const REPEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Intervals which pass before a held button begins to repeat, about 400 ms.
+const REPEAT_DELAY: u32 = 8;
+
+/// A value which can be stepped through by a spin button.
+pub trait Number:
+    Copy + PartialOrd + Display + FromStr + Add<Output = Self> + Sub<Output = Self> + Send + 'static
+{
+}
+
+impl<T> Number for T where
+    T: Copy + PartialOrd + Display + FromStr + Add<Output = T> + Sub<Output = T> + Send + 'static
+{
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Down,
+    Up,
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    /// A button was pressed, and is held until it is released.
+    Hold(Step),
+    Input(String),
+    Release,
+    Repeat,
+    Scroll(Step),
+    Submit,
+}
+
+/// A numeric entry clamped to a range, which is stepped with buttons, the scroll wheel, or
+/// by typing a value.
+///
+/// Typed values are only emitted once they are submitted and valid. Invalid values are kept
+/// as typed, and styled as errors until they are corrected.
+#[derive(Debug)]
+pub struct SpinButton<T> {
+    value: T,
+    range: RangeInclusive<T>,
+    step: T,
+    suffix: String,
+    text: String,
+    invalid: bool,
+    held: Option<(Step, u32)>,
+}
+
+/// Creates a spin button, which clamps its value to the range.
+#[must_use]
+pub fn spin_button<T: Number>(value: T, range: RangeInclusive<T>, step: T) -> SpinButton<T> {
+    let mut spin_button = SpinButton {
+        value,
+        range,
+        step,
+        suffix: String::new(),
+        text: String::new(),
+        invalid: false,
+        held: None,
+    };
+
+    spin_button.set_value(value);
+    spin_button
+}
+
+impl<T: Number> SpinButton<T> {
+    /// A unit shown after the value, such as "px" or "ms", which is not part of the value.
+    #[must_use]
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    #[must_use]
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// Sets the value, such as when it is loaded, discarding what was typed.
+    pub fn set_value(&mut self, value: T) {
+        self.value = self.clamp(value);
+        self.text = self.value.to_string();
+        self.invalid = false;
+    }
+
+    /// Emits the new value whenever it changes.
+    pub fn update(&mut self, message: Message) -> Option<T> {
+        match message {
+            Message::Hold(step) => {
+                self.held = Some((step, 0));
+                self.step(step)
+            }
+
+            Message::Release => {
+                self.held = None;
+                None
+            }
+
+            Message::Repeat => {
+                let (step, intervals) = self.held.as_mut()?;
+                *intervals += 1;
+
+                if *intervals < REPEAT_DELAY {
+                    return None;
+                }
+
+                let step = *step;
+                self.step(step)
+            }
+
+            Message::Scroll(step) => self.step(step),
+
+            Message::Input(text) => {
+                self.invalid = self.parse(&text).is_none();
+                self.text = text;
+                None
+            }
+
+            Message::Submit => {
+                let value = self.parse(&self.text)?;
+                let changed = value != self.value;
+                self.set_value(value);
+                changed.then_some(value)
+            }
+        }
+    }
+
+    /// Repeats the step of a held button.
+    #[must_use]
+    pub fn subscription(&self) -> Subscription<Message> {
+        if self.held.is_some() {
+            iced::time::every(REPEAT_INTERVAL).map(|_| Message::Repeat)
+        } else {
+            Subscription::none()
+        }
+    }
+
+    #[must_use]
+    pub fn view(&self) -> Element<Message> {
+        let style: fn(&cosmic::Theme) -> container::Appearance =
+            if self.invalid { invalid } else { valid };
+
+        let input = text_input("", &self.text, Message::Input)
+            .on_submit(Message::Submit)
+            .padding(8)
+            .width(Length::Units(80))
+            .apply(container)
+            .style(theme::Container::Custom(style));
+
+        let mut content = row!(step_button("list-remove-symbolic", Step::Down), input)
+            .spacing(8)
+            .align_items(Alignment::Center);
+
+        if !self.suffix.is_empty() {
+            content = content.push(text(self.suffix.as_str()));
+        }
+
+        Element::new(Pointer {
+            content: content
+                .push(step_button("list-add-symbolic", Step::Up))
+                .into(),
+            on_press: None,
+            on_release: None,
+            on_scroll: Some(Message::Scroll),
+        })
+    }
+
+    fn clamp(&self, value: T) -> T {
+        if value < *self.range.start() {
+            *self.range.start()
+        } else if value > *self.range.end() {
+            *self.range.end()
+        } else {
+            value
+        }
+    }
+
+    /// Parses a typed value, which is only valid within the range.
+    fn parse(&self, text: &str) -> Option<T> {
+        let text = text.trim();
+        let text = text.strip_suffix(self.suffix.as_str()).unwrap_or(text);
+        let value = text.trim_end().parse::<T>().ok()?;
+        self.range.contains(&value).then_some(value)
+    }
+
+    fn step(&mut self, step: Step) -> Option<T> {
+        let (min, max) = (*self.range.start(), *self.range.end());
+
+        // Compared before stepping, so that unsigned values do not underflow.
+        let value = match step {
+            Step::Down if self.value <= min + self.step => min,
+            Step::Down => self.value - self.step,
+            Step::Up if self.value >= max - self.step => max,
+            Step::Up => self.value + self.step,
+        };
+
+        let changed = value != self.value;
+        self.set_value(value);
+        changed.then_some(value)
+    }
+}
+
+/// A button which steps once when pressed, and repeats the step while it is held.
+fn step_button(name: &'static str, step: Step) -> Element<'static, Message> {
+    // The hold also ends when the button itself is released.
+    let button = icon(name, 16)
+        .style(theme::Svg::Symbolic)
+        .apply(button)
+        .padding(8)
+        .style(theme::Button::Secondary)
+        .on_press(Message::Release);
+
+    Element::new(Pointer {
+        content: button.into(),
+        on_press: Some(Message::Hold(step)),
+        on_release: Some(Message::Release),
+        on_scroll: None,
+    })
+}
+
+fn valid(_theme: &cosmic::Theme) -> container::Appearance {
+    container::Appearance::default()
+}
+
+fn invalid(theme: &cosmic::Theme) -> container::Appearance {
+    container::Appearance {
+        border_color: theme.palette().danger,
+        border_radius: 4.0,
+        border_width: 2.0,
+        ..container::Appearance::default()
+    }
+}
+
+/// Publishes messages when the left button is pressed over the content and released, and
+/// when the wheel is scrolled over it.
+///
+/// Presses are published as soon as the button is pressed, rather than once it is released,
+/// so that holding the button repeats them. Presses still reach the content.
+struct Pointer<'a, Message> {
+    content: Element<'a, Message>,
+    on_press: Option<Message>,
+    on_release: Option<Message>,
+    on_scroll: Option<fn(Step) -> Message>,
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Pointer<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(&self, tree: &mut Tree, layout: Layout<'_>, operation: &mut dyn Operation<Message>) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let is_over = layout.bounds().contains(cursor_position);
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) if is_over => {
+                if let Some(message) = &self.on_press {
+                    shell.publish(message.clone());
+                }
+            }
+
+            // Released anywhere, as the pointer may have left the content while held.
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if let Some(message) = &self.on_release {
+                    shell.publish(message.clone());
+                }
+            }
+
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) if is_over => {
+                if let Some(on_scroll) = self.on_scroll {
+                    let y = match delta {
+                        mouse::ScrollDelta::Lines { y, .. }
+                        | mouse::ScrollDelta::Pixels { y, .. } => y,
+                    };
+
+                    if y != 0.0 {
+                        shell.publish(on_scroll(if y > 0.0 { Step::Up } else { Step::Down }));
+                    }
+
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer)
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmic::{
+    iced::{Color, Length},
+    iced_native::{layout, renderer, widget::Tree, Layout, Point, Rectangle, Size, Widget},
+    Element, Renderer,
+};
+use std::{f32::consts::TAU, time::Duration};
+
+/// Interval between the steps of a spinner.
+pub const STEP: Duration = Duration::from_millis(100);
+
+/// Dots which are arranged in a circle, of which one is lit at each step.
+const DOTS: usize = 8;
+
+/// The steps of a spinner as text, for places which only show text, such as the nav bar.
+const TEXT_STEPS: [&str; 3] = ["•··", "·•·", "··•"];
+
+/// A spinner which shows that an operation is in progress, as a circle of dots of which the
+/// lit dot goes around with each step.
+///
+/// The step is advanced by the app while any page is busy, rather than by the spinner, so
+/// that every spinner turns together.
+#[must_use]
+pub struct Spinner {
+    step: usize,
+    size: u16,
+}
+
+pub fn spinner(step: usize) -> Spinner {
+    Spinner { step, size: 16 }
+}
+
+/// The spinner at a step as text.
+#[must_use]
+pub fn text_step(step: usize) -> &'static str {
+    TEXT_STEPS[step % TEXT_STEPS.len()]
+}
+
+impl<Message> Widget<Message, Renderer> for Spinner {
+    fn width(&self) -> Length {
+        Length::Units(self.size)
+    }
+
+    fn height(&self) -> Length {
+        Length::Units(self.size)
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits
+            .width(Length::Units(self.size))
+            .height(Length::Units(self.size));
+
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &cosmic::Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        _viewport: &Rectangle,
+    ) {
+        use cosmic::iced_native::Renderer as _;
+
+        let bounds = layout.bounds();
+        let center = bounds.center();
+        let dot = bounds.width.min(bounds.height) / 5.0;
+        let radius = (bounds.width.min(bounds.height) - dot) / 2.0;
+        let lit = self.step % DOTS;
+
+        for index in 0..DOTS {
+            let angle = TAU * index as f32 / DOTS as f32;
+
+            // Dots fade behind the lit dot, as a trail of where it has been.
+            let behind = (lit + DOTS - index) % DOTS;
+            let mut color = theme.palette().text;
+            color.a = 1.0 - behind as f32 / DOTS as f32 * 0.85;
+
+            renderer.fill_quad(
+                renderer::Quad {
+                    bounds: Rectangle {
+                        x: center.x + radius * angle.sin() - dot / 2.0,
+                        y: center.y - radius * angle.cos() - dot / 2.0,
+                        width: dot,
+                        height: dot,
+                    },
+                    border_radius: dot / 2.0,
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                },
+                color,
+            );
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<Spinner> for Element<'a, Message> {
+    fn from(spinner: Spinner) -> Self {
+        Element::new(spinner)
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use chrono::{
+    DateTime, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike,
+};
+use cosmic::{
+    iced::{
+        widget::{button, row},
+        Alignment, Subscription,
+    },
+    theme,
+    widget::text,
+    Element,
+};
+
+use super::{spin_button, SpinButton};
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Hour(spin_button::Message),
+    Minute(spin_button::Message),
+    /// Whether the time is after noon, in 12-hour time.
+    Meridiem(bool),
+}
+
+/// Hour and minute fields, in 24-hour time or in 12-hour time with a toggle for AM and PM.
+pub struct TimePicker {
+    time: NaiveTime,
+    military_time: bool,
+    hour: SpinButton<u32>,
+    minute: SpinButton<u32>,
+}
+
+/// Creates a time picker, in 24-hour time unless it is set to 12-hour time.
+#[must_use]
+pub fn time_picker(time: NaiveTime) -> TimePicker {
+    let mut picker = TimePicker {
+        time,
+        military_time: true,
+        hour: spin_button(0, 0..=23, 1),
+        minute: spin_button(0, 0..=59, 1),
+    };
+
+    picker.set_value(time);
+    picker
+}
+
+impl TimePicker {
+    /// Whether the time is shown in 24-hour time, or in 12-hour time.
+    #[must_use]
+    pub fn military_time(mut self, military_time: bool) -> Self {
+        self.set_military_time(military_time);
+        self
+    }
+
+    /// Switches between 24-hour and 12-hour time, such as when the preference changes.
+    pub fn set_military_time(&mut self, military_time: bool) {
+        self.military_time = military_time;
+        self.set_value(self.time);
+    }
+
+    #[must_use]
+    pub fn value(&self) -> NaiveTime {
+        self.time
+    }
+
+    /// Sets the time, such as when it is loaded, discarding what was typed.
+    pub fn set_value(&mut self, time: NaiveTime) {
+        self.time = time.with_second(0).unwrap_or(time);
+
+        self.hour = if self.military_time {
+            spin_button(self.time.hour(), 0..=23, 1)
+        } else {
+            spin_button(self.time.hour12().1, 1..=12, 1)
+        };
+
+        self.minute.set_value(self.time.minute());
+    }
+
+    /// Emits the new time whenever it changes.
+    pub fn update(&mut self, message: Message) -> Option<NaiveTime> {
+        let (pm, _) = self.time.hour12();
+
+        let hour = match message {
+            Message::Hour(message) => {
+                let hour = self.hour.update(message)?;
+
+                if self.military_time {
+                    hour
+                } else {
+                    to_military(hour, pm)
+                }
+            }
+
+            Message::Minute(message) => {
+                let minute = self.minute.update(message)?;
+                return self.set_time(self.time.hour(), minute);
+            }
+
+            Message::Meridiem(after_noon) => to_military(self.time.hour12().1, after_noon),
+        };
+
+        self.set_time(hour, self.time.minute())
+    }
+
+    fn set_time(&mut self, hour: u32, minute: u32) -> Option<NaiveTime> {
+        let time = NaiveTime::from_hms_opt(hour, minute, 0)?;
+        let changed = time != self.time;
+        self.time = time;
+        changed.then_some(time)
+    }
+
+    /// Repeats the steps of held buttons.
+    #[must_use]
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch(vec![
+            self.hour.subscription().map(Message::Hour),
+            self.minute.subscription().map(Message::Minute),
+        ])
+    }
+
+    #[must_use]
+    pub fn view(&self) -> Element<Message> {
+        let mut content = row!(
+            self.hour.view().map(Message::Hour),
+            text(":"),
+            self.minute.view().map(Message::Minute),
+        )
+        .spacing(8)
+        .align_items(Alignment::Center);
+
+        if !self.military_time {
+            let (pm, _) = self.time.hour12();
+
+            content = content.push(
+                row!(
+                    meridiem_button(fl!("time-picker", "am"), !pm, false),
+                    meridiem_button(fl!("time-picker", "pm"), pm, true),
+                )
+                .spacing(4),
+            );
+        }
+
+        content.into()
+    }
+}
+
+fn meridiem_button(label: String, selected: bool, after_noon: bool) -> Element<'static, Message> {
+    let style = if selected {
+        theme::Button::Primary
+    } else {
+        theme::Button::Secondary
+    };
+
+    button(text(label))
+        .style(style)
+        .on_press(Message::Meridiem(after_noon))
+        .into()
+}
+
+/// Converts an hour of 12-hour time, from 1 to 12, to 24-hour time.
+fn to_military(hour: u32, after_noon: bool) -> u32 {
+    hour % 12 + if after_noon { 12 } else { 0 }
+}
+
+/// The instant of a date and time in the local time zone.
+///
+/// Times which are skipped when daylight saving time begins are nudged forward to the first
+/// valid instant after the gap, and times which are repeated when it ends are resolved to
+/// their earlier instant.
+#[must_use]
+pub fn local_date_time(date: NaiveDate, time: NaiveTime) -> Option<DateTime<Local>> {
+    let mut local = NaiveDateTime::new(date, time);
+
+    // Gaps are rarely longer than an hour, and never longer than a day.
+    for _ in 0..=24 * 60 {
+        match Local.from_local_datetime(&local) {
+            LocalResult::Single(instant) => return Some(instant),
+            LocalResult::Ambiguous(earlier, _) => return Some(earlier),
+            LocalResult::None => local = local.checked_add_signed(Duration::minutes(1))?,
+        }
+    }
+
+    None
+}
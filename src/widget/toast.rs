@@ -0,0 +1,135 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use apply::Apply;
+use cosmic::{
+    iced::{
+        widget::{button, container, Row},
+        Alignment, Length,
+    },
+    theme,
+    widget::{icon, text},
+    Element,
+};
+use std::time::Duration;
+
+/// Time for which a toast is shown, unless it is dismissed sooner.
+pub const DURATION: Duration = Duration::from_secs(5);
+
+/// Time for which a toast with an action is shown, so that there is time to press it.
+pub const ACTION_DURATION: Duration = Duration::from_secs(10);
+
+/// How many toasts are stacked at most, beyond which the oldest are dismissed.
+pub const MAX_SHOWN: usize = 3;
+
+/// How a toast is to be taken, which its icon tells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Severity {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn icon(self) -> &'static str {
+        match self {
+            Severity::Info => "dialog-information-symbolic",
+            Severity::Success => "object-select-symbolic",
+            Severity::Warning => "dialog-warning-symbolic",
+            Severity::Error => "dialog-error-symbolic",
+        }
+    }
+}
+
+/// A brief message shown at the bottom of the window, such as when an action failed.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Toast<Message = crate::Message> {
+    pub text: String,
+    pub severity: Severity,
+    /// The label of a button, such as to undo what was done, and the message it emits.
+    pub action: Option<(String, Box<Message>)>,
+}
+
+impl<Message> Toast<Message> {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            severity: Severity::Info,
+            action: None,
+        }
+    }
+
+    pub fn success(text: impl Into<String>) -> Self {
+        Self::new(text).severity(Severity::Success)
+    }
+
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self::new(text).severity(Severity::Warning)
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(text).severity(Severity::Error)
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Adds a button which emits a message, and then dismisses the toast.
+    pub fn action(mut self, label: impl Into<String>, message: Message) -> Self {
+        self.action = Some((label.into(), Box::new(message)));
+        self
+    }
+
+    /// Time for which the toast is shown, unless it is dismissed sooner.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        if self.action.is_some() {
+            ACTION_DURATION
+        } else {
+            DURATION
+        }
+    }
+}
+
+/// Displays a toast, with the button of its action if it has one, and a button which
+/// dismisses it.
+#[must_use]
+pub fn toast<'a, Action, Message: Clone + 'static>(
+    toast: &'a Toast<Action>,
+    on_action: Message,
+    on_dismiss: Message,
+) -> Element<'a, Message> {
+    let mut content = Row::new()
+        .spacing(16)
+        .align_items(Alignment::Center)
+        .push(icon(toast.severity.icon(), 16).style(theme::Svg::Symbolic))
+        .push(text(toast.text.as_str()).width(Length::Fill));
+
+    if let Some((label, _)) = &toast.action {
+        content = content.push(
+            button(text(label.as_str()))
+                .style(theme::Button::Secondary)
+                .on_press(on_action),
+        );
+    }
+
+    content
+        .push(
+            icon("window-close-symbolic", 16)
+                .style(theme::Svg::Symbolic)
+                .apply(button)
+                .padding(4)
+                .style(theme::Button::Transparent)
+                .on_press(on_dismiss),
+        )
+        .apply(container)
+        .padding([8, 16])
+        .width(Length::Units(480))
+        .style(theme::Container::Box)
+        .into()
+}